@@ -21,6 +21,13 @@ impl<S: KVQBinaryStoreReader, K: KVQSerializable, V: KVQSerializable> KVQStoreAd
         Ok(V::from_bytes(&r)?)
     }
 
+    fn get_exact_if_exists(s: &S, key: &K) -> anyhow::Result<Option<V>> {
+        match s.get_exact_if_exists(&key.to_bytes()?)? {
+            Some(r) => Ok(Some(V::from_bytes(&r)?)),
+            None => Ok(None),
+        }
+    }
+
     fn get_leq_kv(s: &S, key: &K, fuzzy_bytes: usize) -> anyhow::Result<Option<KVQPair<K, V>>> {
         let r = s.get_leq_kv(&key.to_bytes()?, fuzzy_bytes)?;
         match r {
@@ -0,0 +1,321 @@
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::hash_types::HashOut;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Compact values are framed as [magic, version, tag, payload..]. Values written
+// before the compact codec existed are JSON objects and always start with '{',
+// so the magic byte is enough to tell the two formats apart.
+pub const KVQ_COMPACT_MAGIC: u8 = 0xb7;
+// Bumped whenever an encoding gains a field, decoders branch on the version a
+// value was written with. 2: L2O-A deploys declare their public inputs.
+// 3: L2O-A deploys list their sequencer committee.
+pub const KVQ_COMPACT_VERSION: u8 = 3;
+
+pub const KVQ_COMPACT_TAG_MERKLE_PROOF: u8 = 1;
+pub const KVQ_COMPACT_TAG_L2OA_DEPLOY: u8 = 2;
+pub const KVQ_COMPACT_TAG_L2OA_BLOCK: u8 = 3;
+pub const KVQ_COMPACT_TAG_L2_DEPOSIT: u8 = 4;
+pub const KVQ_COMPACT_TAG_DELTA_MERKLE_PROOF: u8 = 5;
+
+pub trait KVQCompactCodec: Sized {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()>;
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self>;
+}
+
+#[derive(Default)]
+pub struct KVQCompactWriter {
+    bytes: Vec<u8>,
+}
+
+impl KVQCompactWriter {
+    pub fn new() -> Self {
+        Self { bytes: Vec::new() }
+    }
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+    pub fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+    pub fn write_var_bytes(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        self.write_len(bytes.len())?;
+        self.write_bytes(bytes);
+        Ok(())
+    }
+    pub fn write_len(&mut self, len: usize) -> anyhow::Result<()> {
+        let len = u32::try_from(len)
+            .map_err(|_| anyhow::anyhow!("Length {} does not fit in a compact value", len))?;
+        self.write_u32(len);
+        Ok(())
+    }
+    pub fn write<T: KVQCompactCodec>(&mut self, value: &T) -> anyhow::Result<()> {
+        value.encode_compact(self)
+    }
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub struct KVQCompactReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    version: u8,
+}
+
+impl<'a> KVQCompactReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            version: KVQ_COMPACT_VERSION,
+        }
+    }
+    // the version the value being read was written with
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+    pub fn read_bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.remaining() < len {
+            anyhow::bail!(
+                "Unexpected end of compact value: wanted {} bytes, {} left",
+                len,
+                self.remaining()
+            );
+        }
+        let result = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(result)
+    }
+    pub fn read_array<const SIZE: usize>(&mut self) -> anyhow::Result<[u8; SIZE]> {
+        let mut result = [0u8; SIZE];
+        result.copy_from_slice(self.read_bytes(SIZE)?);
+        Ok(result)
+    }
+    pub fn read_u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+    pub fn read_u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+    pub fn read_u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+    pub fn read_len(&mut self) -> anyhow::Result<usize> {
+        Ok(self.read_u32()? as usize)
+    }
+    pub fn read_var_bytes(&mut self) -> anyhow::Result<&'a [u8]> {
+        let len = self.read_len()?;
+        self.read_bytes(len)
+    }
+    pub fn read<T: KVQCompactCodec>(&mut self) -> anyhow::Result<T> {
+        T::decode_compact(self)
+    }
+    pub fn finish(&self) -> anyhow::Result<()> {
+        if self.remaining() != 0 {
+            anyhow::bail!("Compact value has {} trailing bytes", self.remaining());
+        }
+        Ok(())
+    }
+}
+
+pub fn is_compact_bytes(bytes: &[u8]) -> bool {
+    bytes.first() == Some(&KVQ_COMPACT_MAGIC)
+}
+
+pub fn to_compact_bytes<T: KVQCompactCodec>(tag: u8, value: &T) -> anyhow::Result<Vec<u8>> {
+    let mut writer = KVQCompactWriter::new();
+    writer.write_u8(KVQ_COMPACT_MAGIC);
+    writer.write_u8(KVQ_COMPACT_VERSION);
+    writer.write_u8(tag);
+    value.encode_compact(&mut writer)?;
+    Ok(writer.into_bytes())
+}
+
+pub fn from_compact_bytes<T: KVQCompactCodec>(tag: u8, bytes: &[u8]) -> anyhow::Result<T> {
+    let mut reader = KVQCompactReader::new(bytes);
+    if reader.read_u8()? != KVQ_COMPACT_MAGIC {
+        anyhow::bail!("Value is not compact encoded");
+    }
+    let version = reader.read_u8()?;
+    if version == 0 || version > KVQ_COMPACT_VERSION {
+        anyhow::bail!("Unsupported compact encoding version {}", version);
+    }
+    reader.version = version;
+    let found_tag = reader.read_u8()?;
+    if found_tag != tag {
+        anyhow::bail!(
+            "Compact value tag mismatch: expected {}, found {}",
+            tag,
+            found_tag
+        );
+    }
+    let value = T::decode_compact(&mut reader)?;
+    reader.finish()?;
+    Ok(value)
+}
+
+// Entries written before the compact codec was introduced are still stored as
+// JSON, so readers fall back to serde_json when the magic byte is missing.
+pub fn from_compact_or_json_bytes<T: KVQCompactCodec + DeserializeOwned>(
+    tag: u8,
+    bytes: &[u8],
+) -> anyhow::Result<T> {
+    if is_compact_bytes(bytes) {
+        from_compact_bytes(tag, bytes)
+    } else {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+pub fn to_json_bytes<T: Serialize>(value: &T) -> anyhow::Result<Vec<u8>> {
+    Ok(serde_json::to_vec(value)?)
+}
+
+pub fn from_json_bytes<T: DeserializeOwned>(bytes: &[u8]) -> anyhow::Result<T> {
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+impl KVQCompactCodec for u8 {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_u8(*self);
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        reader.read_u8()
+    }
+}
+
+impl KVQCompactCodec for u32 {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_u32(*self);
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        reader.read_u32()
+    }
+}
+
+impl KVQCompactCodec for u64 {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_u64(*self);
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        reader.read_u64()
+    }
+}
+
+impl<const SIZE: usize> KVQCompactCodec for [u8; SIZE] {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_bytes(self);
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        reader.read_array()
+    }
+}
+
+impl KVQCompactCodec for String {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_var_bytes(self.as_bytes())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(std::str::from_utf8(reader.read_var_bytes()?)?.to_string())
+    }
+}
+
+impl<T: KVQCompactCodec> KVQCompactCodec for Vec<T> {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_len(self.len())?;
+        for item in self.iter() {
+            item.encode_compact(writer)?;
+        }
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        let len = reader.read_len()?;
+        // a corrupted length must not trigger a huge allocation
+        let mut result = Vec::with_capacity(len.min(reader.remaining()));
+        for _ in 0..len {
+            result.push(T::decode_compact(reader)?);
+        }
+        Ok(result)
+    }
+}
+
+impl KVQCompactCodec for HashOut<GoldilocksField> {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        for element in self.elements.iter() {
+            writer.write_u64(element.to_canonical_u64());
+        }
+        Ok(())
+    }
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        let mut elements = [GoldilocksField::ZERO; 4];
+        for element in elements.iter_mut() {
+            *element = GoldilocksField::from_canonical_u64(reader.read_u64()?);
+        }
+        Ok(HashOut { elements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Example {
+        id: u64,
+        name: String,
+        hashes: Vec<[u8; 32]>,
+    }
+
+    impl KVQCompactCodec for Example {
+        fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+            writer.write(&self.id)?;
+            writer.write(&self.name)?;
+            writer.write(&self.hashes)
+        }
+        fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+            Ok(Example {
+                id: reader.read()?,
+                name: reader.read()?,
+                hashes: reader.read()?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_compact_roundtrip() {
+        let example = Example {
+            id: 7,
+            name: "l2o".to_string(),
+            hashes: vec![[1u8; 32], [2u8; 32]],
+        };
+        let bytes = to_compact_bytes(KVQ_COMPACT_TAG_L2_DEPOSIT, &example).unwrap();
+        assert!(is_compact_bytes(&bytes));
+        assert_eq!(bytes.len(), 3 + 8 + 4 + 3 + 4 + 64);
+
+        let decoded: Example = from_compact_bytes(KVQ_COMPACT_TAG_L2_DEPOSIT, &bytes).unwrap();
+        assert_eq!(decoded, example);
+
+        assert!(from_compact_bytes::<Example>(KVQ_COMPACT_TAG_L2OA_BLOCK, &bytes).is_err());
+        assert!(from_compact_bytes::<Example>(KVQ_COMPACT_TAG_L2_DEPOSIT, &bytes[..20]).is_err());
+
+        let mut newer = bytes.clone();
+        newer[1] = KVQ_COMPACT_VERSION + 1;
+        assert!(from_compact_bytes::<Example>(KVQ_COMPACT_TAG_L2_DEPOSIT, &newer).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::codec::KVQCompactReader;
+
+// Key parts are written big-endian so that the byte order of an encoded key
+// matches the order of its fields, which the store's range lookups
+// (get_leq/fuzzy bytes) rely on.
+pub trait KVQKeyPart: Sized {
+    // Tail parts (strings, byte vectors) have no length prefix and consume the
+    // rest of the key, so they can only be the last field of a key.
+    const IS_TAIL: bool = false;
+
+    fn write_key_part(&self, result: &mut Vec<u8>);
+    fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self>;
+}
+
+macro_rules! impl_kvq_key_part_unsigned {
+    ($($typ:ty),+ $(,)?) => {
+        $(
+            impl KVQKeyPart for $typ {
+                fn write_key_part(&self, result: &mut Vec<u8>) {
+                    result.extend_from_slice(&self.to_be_bytes());
+                }
+                fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+                    Ok(<$typ>::from_be_bytes(reader.read_array()?))
+                }
+            }
+        )+
+    };
+}
+
+// flipping the sign bit makes negative values sort before positive ones
+macro_rules! impl_kvq_key_part_signed {
+    ($($typ:ty => $unsigned:ty),+ $(,)?) => {
+        $(
+            impl KVQKeyPart for $typ {
+                fn write_key_part(&self, result: &mut Vec<u8>) {
+                    let flipped = (*self as $unsigned) ^ (1 << (<$unsigned>::BITS - 1));
+                    result.extend_from_slice(&flipped.to_be_bytes());
+                }
+                fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+                    let flipped = <$unsigned>::from_be_bytes(reader.read_array()?);
+                    Ok((flipped ^ (1 << (<$unsigned>::BITS - 1))) as $typ)
+                }
+            }
+        )+
+    };
+}
+
+impl_kvq_key_part_unsigned!(u8, u16, u32, u64, u128);
+impl_kvq_key_part_signed!(i32 => u32, i64 => u64);
+
+impl KVQKeyPart for bool {
+    fn write_key_part(&self, result: &mut Vec<u8>) {
+        result.push(*self as u8);
+    }
+    fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        match reader.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => anyhow::bail!("Invalid bool key part {}", value),
+        }
+    }
+}
+
+impl<const SIZE: usize> KVQKeyPart for [u8; SIZE] {
+    fn write_key_part(&self, result: &mut Vec<u8>) {
+        result.extend_from_slice(self);
+    }
+    fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        reader.read_array()
+    }
+}
+
+impl KVQKeyPart for String {
+    const IS_TAIL: bool = true;
+
+    fn write_key_part(&self, result: &mut Vec<u8>) {
+        result.extend_from_slice(self.as_bytes());
+    }
+    fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(String::from_utf8(
+            reader.read_bytes(reader.remaining())?.to_vec(),
+        )?)
+    }
+}
+
+impl KVQKeyPart for Vec<u8> {
+    const IS_TAIL: bool = true;
+
+    fn write_key_part(&self, result: &mut Vec<u8>) {
+        result.extend_from_slice(self);
+    }
+    fn read_key_part(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(reader.read_bytes(reader.remaining())?.to_vec())
+    }
+}
+
+pub fn write_table_type(table_type: u16, result: &mut Vec<u8>) {
+    table_type.write_key_part(result);
+}
+
+pub fn read_table_type(table_type: u16, reader: &mut KVQCompactReader) -> anyhow::Result<()> {
+    let found = u16::read_key_part(reader)?;
+    if found != table_type {
+        anyhow::bail!(
+            "Key table type mismatch: expected {}, found {}",
+            table_type,
+            found
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_bytes<T: KVQKeyPart>(value: T) -> Vec<u8> {
+        let mut result = Vec::new();
+        value.write_key_part(&mut result);
+        result
+    }
+
+    #[test]
+    fn test_key_parts_preserve_order() {
+        let values = [i64::MIN, -2, -1, 0, 1, i64::MAX];
+        for window in values.windows(2) {
+            assert!(key_bytes(window[0]) < key_bytes(window[1]));
+        }
+        assert!(key_bytes(255u64) < key_bytes(256u64));
+
+        for value in values {
+            let bytes = key_bytes(value);
+            let mut reader = KVQCompactReader::new(&bytes);
+            assert_eq!(i64::read_key_part(&mut reader).unwrap(), value);
+        }
+    }
+}
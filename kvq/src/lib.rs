@@ -1,4 +1,6 @@
 pub mod adapters;
 pub mod base_types;
+pub mod codec;
+pub mod key;
 pub mod memory;
 pub mod traits;
@@ -23,6 +23,10 @@ impl KVQBinaryStoreReader for KVQSimpleMemoryBackingStore {
         }
     }
 
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.map.get(key).cloned())
+    }
+
     fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
         let mut result = Vec::new();
         for key in keys {
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use crate::traits::KVQBinaryStore;
+use crate::traits::KVQBinaryStoreReader;
+use crate::traits::KVQPair;
+
+// A copy-on-write overlay over another store. Reads fall through to the base
+// unless the key was written or deleted in the snapshot, writes are buffered
+// until commit(). The base can be a shared borrow (dry runs, discarded on
+// drop), a mutable borrow (committable) or another snapshot.
+pub struct KVQSnapshotStore<S> {
+    base: S,
+    // None marks a key deleted in the snapshot
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<S: KVQBinaryStoreReader> KVQSnapshotStore<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            writes: BTreeMap::new(),
+        }
+    }
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+    // drops the buffered writes and hands back the untouched base
+    pub fn discard(self) -> S {
+        self.base
+    }
+}
+
+impl<S: KVQBinaryStore> KVQSnapshotStore<S> {
+    pub fn commit(mut self) -> anyhow::Result<S> {
+        let mut sets = Vec::new();
+        let mut deletes = Vec::new();
+        for (key, value) in std::mem::take(&mut self.writes) {
+            match value {
+                Some(value) => sets.push(KVQPair { key, value }),
+                None => deletes.push(key),
+            }
+        }
+        self.base.set_many_vec(sets)?;
+        self.base.delete_many(&deletes)?;
+        Ok(self.base)
+    }
+}
+
+fn fuzzy_base_key(key: &[u8], fuzzy_bytes: usize) -> anyhow::Result<Vec<u8>> {
+    let mut base_key = key.to_vec();
+    let key_len = base_key.len();
+    if fuzzy_bytes > key_len {
+        return Err(anyhow::anyhow!(
+            "Fuzzy bytes must be less than or equal to key length"
+        ));
+    }
+    for i in 0..fuzzy_bytes {
+        base_key[key_len - i - 1] = 0;
+    }
+    Ok(base_key)
+}
+
+impl<S: KVQBinaryStoreReader> KVQBinaryStoreReader for KVQSnapshotStore<S> {
+    fn get_exact(&self, key: &Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        match self.writes.get(key) {
+            Some(Some(v)) => Ok(v.to_owned()),
+            Some(None) => anyhow::bail!("Key not found"),
+            None => self.base.get_exact(key),
+        }
+    }
+
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.writes.get(key) {
+            Some(value) => Ok(value.to_owned()),
+            None => self.base.get_exact_if_exists(key),
+        }
+    }
+
+    fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut result = Vec::with_capacity(keys.len());
+        for key in keys {
+            result.push(self.get_exact(key)?);
+        }
+        Ok(result)
+    }
+
+    fn get_leq(&self, key: &Vec<u8>, fuzzy_bytes: usize) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.get_leq_kv(key, fuzzy_bytes)?.map(|pair| pair.value))
+    }
+
+    fn get_leq_kv(
+        &self,
+        key: &Vec<u8>,
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Option<KVQPair<Vec<u8>, Vec<u8>>>> {
+        // same range as the backing stores: [key with zeroed fuzzy bytes, key)
+        let base_key = fuzzy_base_key(key, fuzzy_bytes)?;
+
+        let overlay = self
+            .writes
+            .range(base_key.clone()..key.to_vec())
+            .rev()
+            .find_map(|(k, v)| v.as_ref().map(|v| (k, v)));
+
+        // skip base entries shadowed by the snapshot, searching below them
+        let mut upper = key.to_vec();
+        let base = loop {
+            match self.base.get_leq_kv(&upper, fuzzy_bytes.min(upper.len()))? {
+                Some(pair) if pair.key >= base_key => {
+                    if self.writes.contains_key(&pair.key) {
+                        upper = pair.key;
+                    } else {
+                        break Some(pair);
+                    }
+                }
+                _ => break None,
+            }
+        };
+
+        Ok(match (overlay, base) {
+            (Some((k, _)), Some(pair)) if pair.key > *k => Some(pair),
+            (Some((k, v)), _) => Some(KVQPair {
+                key: k.to_owned(),
+                value: v.to_owned(),
+            }),
+            (None, base) => base,
+        })
+    }
+
+    fn get_many_leq(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        let mut results = Vec::with_capacity(keys.len());
+        for k in keys {
+            results.push(self.get_leq(k, fuzzy_bytes)?);
+        }
+        Ok(results)
+    }
+
+    fn get_many_leq_kv(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<KVQPair<Vec<u8>, Vec<u8>>>>> {
+        let mut results = Vec::with_capacity(keys.len());
+        for k in keys {
+            results.push(self.get_leq_kv(k, fuzzy_bytes)?);
+        }
+        Ok(results)
+    }
+}
+
+impl<S: KVQBinaryStoreReader> KVQBinaryStore for KVQSnapshotStore<S> {
+    fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> anyhow::Result<()> {
+        self.writes.insert(key, Some(value));
+        Ok(())
+    }
+
+    fn set_ref(&mut self, key: &Vec<u8>, value: &Vec<u8>) -> anyhow::Result<()> {
+        self.writes.insert(key.clone(), Some(value.clone()));
+        Ok(())
+    }
+
+    fn set_many_ref<'a>(
+        &mut self,
+        items: &[KVQPair<&'a Vec<u8>, &'a Vec<u8>>],
+    ) -> anyhow::Result<()> {
+        for item in items {
+            self.writes
+                .insert(item.key.clone(), Some(item.value.clone()));
+        }
+        Ok(())
+    }
+
+    fn set_many_vec(&mut self, items: Vec<KVQPair<Vec<u8>, Vec<u8>>>) -> anyhow::Result<()> {
+        for item in items {
+            self.writes.insert(item.key, Some(item.value));
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &Vec<u8>) -> anyhow::Result<bool> {
+        let existed = match self.writes.get(key) {
+            Some(value) => value.is_some(),
+            None => self.base.get_exact_if_exists(key)?.is_some(),
+        };
+        self.writes.insert(key.clone(), None);
+        Ok(existed)
+    }
+
+    fn delete_many(&mut self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<bool>> {
+        let mut result = Vec::with_capacity(keys.len());
+        for key in keys {
+            result.push(self.delete(key)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::simple::KVQSimpleMemoryBackingStore;
+
+    #[test]
+    fn test_snapshot_overlay() {
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        store.set(vec![1, 1], vec![11]).unwrap();
+        store.set(vec![1, 2], vec![12]).unwrap();
+        store.set(vec![1, 3], vec![13]).unwrap();
+
+        {
+            let mut snapshot = KVQSnapshotStore::new(&store);
+            snapshot.set(vec![1, 1], vec![21]).unwrap();
+            assert!(snapshot.delete(&vec![1, 2]).unwrap());
+            assert!(!snapshot.delete(&vec![1, 2]).unwrap());
+
+            assert_eq!(snapshot.get_exact(&vec![1, 1]).unwrap(), vec![21]);
+            assert!(snapshot.get_exact(&vec![1, 2]).is_err());
+            assert_eq!(snapshot.get_exact(&vec![1, 3]).unwrap(), vec![13]);
+            // [1, 2] is deleted, so the lookup falls back to the overlay's [1, 1]
+            assert_eq!(snapshot.get_leq(&vec![1, 3], 1).unwrap(), Some(vec![21]));
+            assert_eq!(snapshot.get_leq(&vec![1, 4], 1).unwrap(), Some(vec![13]));
+        }
+        assert_eq!(store.get_exact(&vec![1, 1]).unwrap(), vec![11]);
+
+        let mut outer = KVQSnapshotStore::new(&mut store);
+        outer.set(vec![1, 4], vec![14]).unwrap();
+        let mut inner = KVQSnapshotStore::new(&mut outer);
+        inner.delete(&vec![1, 3]).unwrap();
+        inner.set(vec![1, 5], vec![15]).unwrap();
+        assert_eq!(inner.get_leq(&vec![1, 5], 1).unwrap(), Some(vec![14]));
+        inner.commit().unwrap();
+        assert!(outer.get_exact(&vec![1, 3]).is_err());
+        outer.commit().unwrap();
+
+        assert!(store.get_exact(&vec![1, 3]).is_err());
+        assert_eq!(store.get_exact(&vec![1, 4]).unwrap(), vec![14]);
+        assert_eq!(store.get_exact(&vec![1, 5]).unwrap(), vec![15]);
+        assert_eq!(store.get_exact(&vec![1, 1]).unwrap(), vec![11]);
+    }
+}
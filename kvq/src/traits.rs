@@ -65,6 +65,7 @@ pub fn unwrap_kv_result<T>(item_opt: Option<T>) -> anyhow::Result<T> {
 
 pub trait KVQStoreAdapterReader<S, K: KVQSerializable, V: KVQSerializable> {
     fn get_exact(s: &S, key: &K) -> anyhow::Result<V>;
+    fn get_exact_if_exists(s: &S, key: &K) -> anyhow::Result<Option<V>>;
     fn get_many_exact(s: &S, keys: &[K]) -> anyhow::Result<Vec<V>>;
 
     fn get_leq(s: &S, key: &K, fuzzy_bytes: usize) -> anyhow::Result<Option<V>>;
@@ -134,6 +135,8 @@ pub trait KVQStoreAdapterWithHelpers<S, K: KVQSerializable, V: KVQSerializable>:
 
 pub trait KVQBinaryStoreReader {
     fn get_exact(&self, key: &Vec<u8>) -> anyhow::Result<Vec<u8>>;
+    // none when the key is missing, unlike get_exact
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>>;
     fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>>;
 
     fn get_leq(&self, key: &Vec<u8>, fuzzy_bytes: usize) -> anyhow::Result<Option<Vec<u8>>>;
@@ -189,3 +192,101 @@ pub trait KVQBinaryStore: KVQBinaryStoreReader {
     fn delete(&mut self, key: &Vec<u8>) -> anyhow::Result<bool>;
     fn delete_many(&mut self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<bool>>;
 }
+
+// Lets wrappers such as the snapshot store hold either a shared or a mutable
+// borrow of another store.
+impl<T: KVQBinaryStoreReader + ?Sized> KVQBinaryStoreReader for &T {
+    fn get_exact(&self, key: &Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        (**self).get_exact(key)
+    }
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        (**self).get_exact_if_exists(key)
+    }
+    fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
+        (**self).get_many_exact(keys)
+    }
+    fn get_leq(&self, key: &Vec<u8>, fuzzy_bytes: usize) -> anyhow::Result<Option<Vec<u8>>> {
+        (**self).get_leq(key, fuzzy_bytes)
+    }
+    fn get_leq_kv(
+        &self,
+        key: &Vec<u8>,
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Option<KVQPair<Vec<u8>, Vec<u8>>>> {
+        (**self).get_leq_kv(key, fuzzy_bytes)
+    }
+    fn get_many_leq(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        (**self).get_many_leq(keys, fuzzy_bytes)
+    }
+    fn get_many_leq_kv(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<KVQPair<Vec<u8>, Vec<u8>>>>> {
+        (**self).get_many_leq_kv(keys, fuzzy_bytes)
+    }
+}
+
+impl<T: KVQBinaryStoreReader + ?Sized> KVQBinaryStoreReader for &mut T {
+    fn get_exact(&self, key: &Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        (**self).get_exact(key)
+    }
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        (**self).get_exact_if_exists(key)
+    }
+    fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
+        (**self).get_many_exact(keys)
+    }
+    fn get_leq(&self, key: &Vec<u8>, fuzzy_bytes: usize) -> anyhow::Result<Option<Vec<u8>>> {
+        (**self).get_leq(key, fuzzy_bytes)
+    }
+    fn get_leq_kv(
+        &self,
+        key: &Vec<u8>,
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Option<KVQPair<Vec<u8>, Vec<u8>>>> {
+        (**self).get_leq_kv(key, fuzzy_bytes)
+    }
+    fn get_many_leq(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        (**self).get_many_leq(keys, fuzzy_bytes)
+    }
+    fn get_many_leq_kv(
+        &self,
+        keys: &[Vec<u8>],
+        fuzzy_bytes: usize,
+    ) -> anyhow::Result<Vec<Option<KVQPair<Vec<u8>, Vec<u8>>>>> {
+        (**self).get_many_leq_kv(keys, fuzzy_bytes)
+    }
+}
+
+impl<T: KVQBinaryStore + ?Sized> KVQBinaryStore for &mut T {
+    fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> anyhow::Result<()> {
+        (**self).set(key, value)
+    }
+    fn set_ref(&mut self, key: &Vec<u8>, value: &Vec<u8>) -> anyhow::Result<()> {
+        (**self).set_ref(key, value)
+    }
+    fn set_many_ref<'a>(
+        &mut self,
+        items: &[KVQPair<&'a Vec<u8>, &'a Vec<u8>>],
+    ) -> anyhow::Result<()> {
+        (**self).set_many_ref(items)
+    }
+    fn set_many_vec(&mut self, items: Vec<KVQPair<Vec<u8>, Vec<u8>>>) -> anyhow::Result<()> {
+        (**self).set_many_vec(items)
+    }
+    fn delete(&mut self, key: &Vec<u8>) -> anyhow::Result<bool> {
+        (**self).delete(key)
+    }
+    fn delete_many(&mut self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<bool>> {
+        (**self).delete_many(keys)
+    }
+}
@@ -0,0 +1,261 @@
+use l2o_common::common::data::hash::Hash256;
+use l2o_ord::operation::l2o_a::block::Block;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::HashOutTarget;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::WitnessWrite;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::brc21::L2TransferTarget;
+use super::hash::two_to_one;
+use super::hash::Hash256Target;
+use super::merkle::MerkleProofTarget;
+
+// Every field of an L2O-A block except the proof. Numbers are single field
+// elements, reduced like the from_noncanonical_u64 calls of the native hasher.
+#[derive(Clone, Copy, Debug)]
+pub struct L2OABlockTarget {
+    pub l2id: Target,
+    pub l2_block_number: Target,
+
+    pub bitcoin_block_number: Target,
+    pub bitcoin_block_hash: Hash256Target,
+
+    pub public_key: Hash256Target,
+
+    pub start_state_root: Hash256Target,
+    pub end_state_root: Hash256Target,
+
+    pub deposit_state_root: Hash256Target,
+
+    pub start_withdrawal_state_root: Hash256Target,
+    pub end_withdrawal_state_root: Hash256Target,
+
+    pub superchain_root: Hash256Target,
+}
+
+impl L2OABlockTarget {
+    pub fn add_virtual<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> Self {
+        Self {
+            l2id: builder.add_virtual_target(),
+            l2_block_number: builder.add_virtual_target(),
+            bitcoin_block_number: builder.add_virtual_target(),
+            bitcoin_block_hash: Hash256Target::add_virtual(builder),
+            public_key: Hash256Target::add_virtual(builder),
+            start_state_root: Hash256Target::add_virtual(builder),
+            end_state_root: Hash256Target::add_virtual(builder),
+            deposit_state_root: Hash256Target::add_virtual(builder),
+            start_withdrawal_state_root: Hash256Target::add_virtual(builder),
+            end_withdrawal_state_root: Hash256Target::add_virtual(builder),
+            superchain_root: Hash256Target::add_virtual(builder),
+        }
+    }
+    pub fn set_witness<F, W, Proof>(&self, witness: &mut W, block: &Block<Proof>)
+    where
+        F: RichField,
+        W: WitnessWrite<F>,
+        Proof: Serialize,
+        for<'de2> Proof: Deserialize<'de2>,
+    {
+        witness.set_target(self.l2id, F::from_canonical_u64(block.l2id));
+        witness.set_target(
+            self.l2_block_number,
+            F::from_noncanonical_u64(block.l2_block_number),
+        );
+        witness.set_target(
+            self.bitcoin_block_number,
+            F::from_noncanonical_u64(block.bitcoin_block_number),
+        );
+        for (target, hash) in [
+            (&self.bitcoin_block_hash, block.bitcoin_block_hash),
+            (&self.public_key, Hash256(block.public_key.0)),
+            (&self.start_state_root, block.start_state_root),
+            (&self.end_state_root, block.end_state_root),
+            (&self.deposit_state_root, block.deposit_state_root),
+            (
+                &self.start_withdrawal_state_root,
+                block.start_withdrawal_state_root,
+            ),
+            (
+                &self.end_withdrawal_state_root,
+                block.end_withdrawal_state_root,
+            ),
+            (&self.superchain_root, block.superchain_root),
+        ] {
+            target.set_witness(witness, &hash);
+        }
+    }
+
+    // In-circuit PoseidonHasher::get_l2_block_hash.
+    pub fn get_l2_block_hash<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> HashOutTarget {
+        let zero = builder.zero();
+        let payload_a = HashOutTarget {
+            elements: [
+                self.l2id,
+                self.l2_block_number,
+                self.bitcoin_block_number,
+                zero,
+            ],
+        };
+        let payload_b =
+            builder.hash_n_to_hash_no_pad::<PoseidonHash>(self.bitcoin_block_hash.0.to_vec());
+        let payload_c = builder.hash_n_to_hash_no_pad::<PoseidonHash>(self.public_key.0.to_vec());
+
+        let start_state_root = self.start_state_root.to_hash_out(builder);
+        let end_state_root = self.end_state_root.to_hash_out(builder);
+        let start_withdrawal_state_root = self.start_withdrawal_state_root.to_hash_out(builder);
+        let end_withdrawal_state_root = self.end_withdrawal_state_root.to_hash_out(builder);
+        let deposit_state_root = self.deposit_state_root.to_hash_out(builder);
+        let superchain_root = self.superchain_root.to_hash_out(builder);
+
+        let state_roots = two_to_one(builder, start_state_root, end_state_root);
+        let withdrawal_state_roots = two_to_one(
+            builder,
+            start_withdrawal_state_root,
+            end_withdrawal_state_root,
+        );
+        let roots = two_to_one(builder, state_roots, withdrawal_state_roots);
+        let deposit_superchain_roots = two_to_one(builder, deposit_state_root, superchain_root);
+        let payload_d = two_to_one(builder, roots, deposit_superchain_roots);
+
+        let left = two_to_one(builder, payload_a, payload_b);
+        let right = two_to_one(builder, payload_c, payload_d);
+        two_to_one(builder, left, right)
+    }
+
+    // The superchain root commits to the state root of every L2 at the leaf
+    // with its l2id. State root trees do not mark their leaves.
+    pub fn enforce_superchain_inclusion<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        l2id: Target,
+        state_root: &Hash256Target,
+        proof: &MerkleProofTarget,
+    ) {
+        let superchain_root = self.superchain_root.to_hash_out(builder);
+        let state_root = state_root.to_hash_out(builder);
+        builder.connect_hashes(superchain_root, proof.root);
+        builder.connect_hashes(state_root, proof.value);
+        builder.connect(l2id, proof.index);
+        proof.enforce_valid_marked_if(builder, false);
+    }
+
+    pub fn enforce_deposit_inclusion<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        deposit: &L2TransferTarget,
+        proof: &MerkleProofTarget,
+    ) {
+        let deposit_root = self.deposit_state_root.to_hash_out(builder);
+        deposit.enforce_deposit_inclusion(builder, deposit_root, proof);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_groth16::Proof;
+    use l2o_common::common::data::signature::L2OCompactPublicKey;
+    use l2o_common::common::data::signature::L2OSignature512;
+    use l2o_crypto::fields::goldilocks::hash::hash256_to_goldilocks_hash;
+    use l2o_crypto::fields::goldilocks::hash::GHashOut;
+    use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+    use l2o_crypto::hash::merkle::core::calc_merkle_root_marked_if;
+    use l2o_crypto::hash::merkle::core::MerkleProofCore;
+    use l2o_crypto::hash::traits::L2OHash;
+    use l2o_crypto::proof::groth16::bn128::proof_data::Groth16BN128ProofData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAProofData;
+    use l2o_ord::hasher::L2OBlockHasher;
+    use l2o_ord::operation::l2o_a::L2OABlockV1;
+    use plonky2::field::types::Field;
+    use plonky2::iop::witness::PartialWitness;
+
+    use super::*;
+    use crate::goldilocks::hash::tests::hash_out_inputs;
+    use crate::goldilocks::hash::tests::new_builder;
+    use crate::goldilocks::hash::tests::prove_and_verify;
+    use crate::goldilocks::hash::tests::F;
+
+    fn test_block() -> L2OABlockV1 {
+        L2OABlockV1 {
+            l2id: 7,
+            l2_block_number: u64::MAX,
+            bitcoin_block_number: 830000,
+            bitcoin_block_hash: Hash256([1u8; 32]),
+            public_key: L2OCompactPublicKey([2u8; 32]),
+            start_state_root: Hash256([3u8; 32]),
+            end_state_root: Hash256([4u8; 32]),
+            deposit_state_root: Hash256([5u8; 32]),
+            start_withdrawal_state_root: Hash256([6u8; 32]),
+            end_withdrawal_state_root: Hash256([7u8; 32]),
+            proof: L2OAProofData::Groth16BN128(Groth16BN128ProofData {
+                proof: Proof::default(),
+                public_inputs: vec![],
+            }),
+            superchain_root: Hash256([0xfe; 32]),
+            signature: L2OSignature512([0u8; 64]),
+        }
+    }
+
+    #[test]
+    fn test_block_hash_target() {
+        let block = test_block();
+
+        let mut builder = new_builder();
+        let block_target = L2OABlockTarget::add_virtual(&mut builder);
+        let hash = block_target.get_l2_block_hash(&mut builder);
+        builder.register_public_inputs(&hash.elements);
+
+        let mut pw = PartialWitness::new();
+        block_target.set_witness(&mut pw, &block);
+        assert_eq!(
+            prove_and_verify(builder, pw),
+            hash_out_inputs(&GHashOut::from_hash_256(
+                &PoseidonHasher::get_l2_block_hash(&block)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_superchain_inclusion_target() {
+        let mut block = test_block();
+        let other_l2id = 12;
+        let other_state_root = Hash256([0x42; 32]);
+        let leaf = hash256_to_goldilocks_hash(&other_state_root);
+        let siblings = (0..8u8)
+            .map(|i| hash256_to_goldilocks_hash(&Hash256([i; 32])))
+            .collect::<Vec<_>>();
+        let root = calc_merkle_root_marked_if::<GHashOut, PoseidonHasher>(
+            leaf, &siblings, other_l2id, false,
+        );
+        block.superchain_root = root.to_hash_256();
+        let proof = MerkleProofCore {
+            root,
+            value: leaf,
+            index: other_l2id,
+            siblings,
+        };
+
+        let mut builder = new_builder();
+        let block_target = L2OABlockTarget::add_virtual(&mut builder);
+        let l2id = builder.add_virtual_target();
+        let state_root = Hash256Target::add_virtual(&mut builder);
+        let proof_target = MerkleProofTarget::add_virtual(&mut builder, proof.siblings.len());
+        block_target.enforce_superchain_inclusion(&mut builder, l2id, &state_root, &proof_target);
+
+        let mut pw = PartialWitness::new();
+        block_target.set_witness(&mut pw, &block);
+        pw.set_target(l2id, F::from_canonical_u64(other_l2id));
+        state_root.set_witness(&mut pw, &other_state_root);
+        proof_target.set_witness(&mut pw, &proof);
+        prove_and_verify(builder, pw);
+    }
+}
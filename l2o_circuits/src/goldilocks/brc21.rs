@@ -0,0 +1,262 @@
+use l2o_ord::operation::brc21::l2deposit::L2Deposit;
+use l2o_ord::operation::brc21::l2withdraw::L2Withdraw;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::HashOutTarget;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::WitnessWrite;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::merkle::MerkleProofTarget;
+
+// A string of a fixed byte length packed like bytes_to_goldilocks_u32. The
+// zero padding of the last limb is enforced by its range check.
+#[derive(Clone, Debug)]
+pub struct BytesTarget {
+    pub len: usize,
+    pub limbs: Vec<Target>,
+}
+
+impl BytesTarget {
+    pub fn add_virtual<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        len: usize,
+    ) -> Self {
+        let limbs = (0..len.div_ceil(4))
+            .map(|i| {
+                let limb = builder.add_virtual_target();
+                builder.range_check(limb, 8 * (len - 4 * i).min(4));
+                limb
+            })
+            .collect();
+        Self { len, limbs }
+    }
+    pub fn set_witness<F: RichField, W: WitnessWrite<F>>(&self, witness: &mut W, bytes: &[u8]) {
+        assert_eq!(
+            bytes.len(),
+            self.len,
+            "byte length does not match the circuit"
+        );
+        for (target, chunk) in self.limbs.iter().zip(bytes.chunks(4)) {
+            let mut limb = [0u8; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            witness.set_target(*target, F::from_canonical_u32(u32::from_le_bytes(limb)));
+        }
+    }
+    pub fn payload<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> Vec<Target> {
+        let mut payload = vec![builder.constant(F::from_canonical_usize(self.len))];
+        payload.extend_from_slice(&self.limbs);
+        payload
+    }
+}
+
+// A deposit or withdrawal leaf. Circuits are built for fixed string lengths,
+// see BytesTarget.
+#[derive(Clone, Debug)]
+pub struct L2TransferTarget {
+    pub l2id: Target,
+    pub tick: BytesTarget,
+    pub to: BytesTarget,
+    pub amount: BytesTarget,
+}
+
+impl L2TransferTarget {
+    pub fn add_virtual<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        tick_len: usize,
+        to_len: usize,
+        amount_len: usize,
+    ) -> Self {
+        Self {
+            l2id: builder.add_virtual_target(),
+            tick: BytesTarget::add_virtual(builder, tick_len),
+            to: BytesTarget::add_virtual(builder, to_len),
+            amount: BytesTarget::add_virtual(builder, amount_len),
+        }
+    }
+    fn set_witness_from_fields<F: RichField, W: WitnessWrite<F>>(
+        &self,
+        witness: &mut W,
+        fields: (u64, &str, &str, &str),
+    ) {
+        let (l2id, tick, to, amount) = fields;
+        witness.set_target(self.l2id, F::from_noncanonical_u64(l2id));
+        self.tick.set_witness(witness, tick.as_bytes());
+        self.to.set_witness(witness, to.as_bytes());
+        self.amount.set_witness(witness, amount.as_bytes());
+    }
+    pub fn set_deposit_witness<F: RichField, W: WitnessWrite<F>>(
+        &self,
+        witness: &mut W,
+        deposit: &L2Deposit,
+    ) {
+        self.set_witness_from_fields(
+            witness,
+            (deposit.l2id, &deposit.tick, &deposit.to, &deposit.amount),
+        );
+    }
+    pub fn set_withdraw_witness<F, W, Proof>(&self, witness: &mut W, withdraw: &L2Withdraw<Proof>)
+    where
+        F: RichField,
+        W: WitnessWrite<F>,
+        Proof: Serialize,
+        for<'de2> Proof: Deserialize<'de2>,
+    {
+        self.set_witness_from_fields(
+            witness,
+            (
+                withdraw.l2id,
+                &withdraw.tick,
+                &withdraw.to,
+                &withdraw.amount,
+            ),
+        );
+    }
+
+    // same layout as get_l2_deposit_payload_goldilocks_u32_mode and
+    // get_l2_withdraw_payload_goldilocks_u32_mode
+    pub fn payload<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> Vec<Target> {
+        let mut payload = vec![self.l2id];
+        payload.extend(self.tick.payload(builder));
+        payload.extend(self.to.payload(builder));
+        payload.extend(self.amount.payload(builder));
+        payload
+    }
+    // In-circuit PoseidonHasher::get_l2_deposit_hash/get_l2_withdraw_hash.
+    pub fn get_l2_transfer_hash<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> HashOutTarget {
+        let payload = self.payload(builder);
+        builder.hash_n_to_hash_no_pad::<PoseidonHash>(payload)
+    }
+    // Deposit trees are append only trees without marked leaves.
+    pub fn enforce_deposit_inclusion<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        deposit_root: HashOutTarget,
+        proof: &MerkleProofTarget,
+    ) {
+        let leaf = self.get_l2_transfer_hash(builder);
+        builder.connect_hashes(leaf, proof.value);
+        builder.connect_hashes(deposit_root, proof.root);
+        proof.enforce_valid_marked_if(builder, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use l2o_common::common::data::hash::Hash256;
+    use l2o_crypto::fields::goldilocks::hash::GHashOut;
+    use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+    use l2o_crypto::hash::merkle::core::calc_merkle_root_marked_if;
+    use l2o_crypto::hash::merkle::core::MerkleProofCore;
+    use l2o_crypto::hash::traits::L2OHash;
+    use l2o_ord::hasher::L2ODepositHasher;
+    use l2o_ord::hasher::L2OWithdrawHasher;
+    use l2o_ord::operation::brc21::L2WithdrawV1;
+    use plonky2::iop::witness::PartialWitness;
+
+    use super::*;
+    use crate::goldilocks::hash::tests::hash_out_inputs;
+    use crate::goldilocks::hash::tests::new_builder;
+    use crate::goldilocks::hash::tests::prove_and_verify;
+    use crate::goldilocks::hash::tests::D;
+    use crate::goldilocks::hash::tests::F;
+
+    fn test_deposit() -> L2Deposit {
+        L2Deposit {
+            l2id: 3,
+            tick: "l2o".to_string(),
+            to: "bc1qexampleaddress".to_string(),
+            amount: "1000.5".to_string(),
+        }
+    }
+
+    fn add_transfer_target(
+        builder: &mut CircuitBuilder<F, D>,
+        deposit: &L2Deposit,
+    ) -> L2TransferTarget {
+        L2TransferTarget::add_virtual(
+            builder,
+            deposit.tick.len(),
+            deposit.to.len(),
+            deposit.amount.len(),
+        )
+    }
+
+    #[test]
+    fn test_transfer_hash_targets() {
+        let deposit = test_deposit();
+        let withdraw = L2WithdrawV1 {
+            l2id: deposit.l2id,
+            tick: deposit.tick.clone(),
+            to: deposit.to.clone(),
+            amount: deposit.amount.clone(),
+            proof: MerkleProofCore {
+                root: Hash256([0u8; 32]),
+                value: Hash256([0u8; 32]),
+                index: 0,
+                siblings: vec![],
+            },
+        };
+
+        let mut builder = new_builder();
+        let deposit_target = add_transfer_target(&mut builder, &deposit);
+        let withdraw_target = add_transfer_target(&mut builder, &deposit);
+        let deposit_hash = deposit_target.get_l2_transfer_hash(&mut builder);
+        let withdraw_hash = withdraw_target.get_l2_transfer_hash(&mut builder);
+        builder.register_public_inputs(&deposit_hash.elements);
+        builder.register_public_inputs(&withdraw_hash.elements);
+
+        let mut pw = PartialWitness::new();
+        deposit_target.set_deposit_witness(&mut pw, &deposit);
+        withdraw_target.set_withdraw_witness(&mut pw, &withdraw);
+
+        let mut expected = hash_out_inputs(&GHashOut::from_hash_256(
+            &PoseidonHasher::get_l2_deposit_hash(&deposit),
+        ));
+        expected.extend(hash_out_inputs(&GHashOut::from_hash_256(
+            &PoseidonHasher::get_l2_withdraw_hash(&withdraw),
+        )));
+        assert_eq!(prove_and_verify(builder, pw), expected);
+    }
+
+    #[test]
+    fn test_deposit_inclusion_target() {
+        let deposit = test_deposit();
+        let leaf = GHashOut::from_hash_256(&PoseidonHasher::get_l2_deposit_hash(&deposit));
+        let siblings = vec![GHashOut::from_hash_256(&Hash256([5u8; 32])); 8];
+        let index = 37;
+        let proof = MerkleProofCore {
+            root: calc_merkle_root_marked_if::<GHashOut, PoseidonHasher>(
+                leaf, &siblings, index, false,
+            ),
+            value: leaf,
+            index,
+            siblings,
+        };
+
+        let mut builder = new_builder();
+        let deposit_target = add_transfer_target(&mut builder, &deposit);
+        let deposit_root = builder.add_virtual_hash();
+        let proof_target = MerkleProofTarget::add_virtual(&mut builder, proof.siblings.len());
+        deposit_target.enforce_deposit_inclusion(&mut builder, deposit_root, &proof_target);
+        builder.register_public_inputs(&deposit_root.elements);
+
+        let mut pw = PartialWitness::new();
+        deposit_target.set_deposit_witness(&mut pw, &deposit);
+        pw.set_hash_target(deposit_root, proof.root);
+        proof_target.set_witness(&mut pw, &proof);
+        assert_eq!(prove_and_verify(builder, pw), hash_out_inputs(&proof.root));
+    }
+}
@@ -0,0 +1,162 @@
+use l2o_common::common::data::hash::Hash256;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::HashOutTarget;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::BoolTarget;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::WitnessWrite;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+// A Hash256 as eight range checked u32 limbs, in the order of
+// hash256_to_goldilocks_u32.
+#[derive(Clone, Copy, Debug)]
+pub struct Hash256Target(pub [Target; 8]);
+
+impl Hash256Target {
+    pub fn add_virtual<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> Self {
+        Self(core::array::from_fn(|_| {
+            let limb = builder.add_virtual_target();
+            builder.range_check(limb, 32);
+            limb
+        }))
+    }
+    pub fn set_witness<F: RichField, W: WitnessWrite<F>>(&self, witness: &mut W, hash: &Hash256) {
+        let limbs: [u32; 8] = hash.into();
+        for (target, limb) in self.0.iter().zip(limbs) {
+            witness.set_target(*target, F::from_canonical_u32(limb));
+        }
+    }
+    // hash256_to_goldilocks_hash: every element packs two limbs into a u64,
+    // which the field reduces like from_noncanonical_u64
+    pub fn to_hash_out<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+    ) -> HashOutTarget {
+        HashOutTarget {
+            elements: core::array::from_fn(|i| {
+                builder.mul_const_add(
+                    F::from_canonical_u64(1 << 32),
+                    self.0[2 * i + 1],
+                    self.0[2 * i],
+                )
+            }),
+        }
+    }
+}
+
+pub fn select_hash<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    b: BoolTarget,
+    x: HashOutTarget,
+    y: HashOutTarget,
+) -> HashOutTarget {
+    HashOutTarget {
+        elements: core::array::from_fn(|i| builder.select(b, x.elements[i], y.elements[i])),
+    }
+}
+
+// PoseidonHash::two_to_one is a single permutation of [left, right], the
+// same as hash_no_pad over the eight elements.
+pub fn two_to_one<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    left: HashOutTarget,
+    right: HashOutTarget,
+) -> HashOutTarget {
+    builder.hash_n_to_hash_no_pad::<PoseidonHash>([left.elements, right.elements].concat())
+}
+
+// In-circuit PoseidonHasher::two_to_one_marked_leaf.
+pub fn two_to_one_marked_leaf<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    left: HashOutTarget,
+    right: HashOutTarget,
+) -> HashOutTarget {
+    let mut inputs = [left.elements, right.elements].concat();
+    inputs.push(builder.one());
+    builder.hash_n_to_hash_no_pad::<PoseidonHash>(inputs)
+}
+
+pub fn two_to_one_marked_if<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    left: HashOutTarget,
+    right: HashOutTarget,
+    marked: bool,
+) -> HashOutTarget {
+    if marked {
+        two_to_one_marked_leaf(builder, left, right)
+    } else {
+        two_to_one(builder, left, right)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use l2o_crypto::fields::goldilocks::hash::hash256_to_goldilocks_hash;
+    use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+    use l2o_crypto::hash::merkle::traits::MerkleHasher;
+    use l2o_crypto::hash::merkle::traits::MerkleHasherWithMarkedLeaf;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Field;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+    use super::*;
+
+    pub(crate) const D: usize = 2;
+    pub(crate) type C = PoseidonGoldilocksConfig;
+    pub(crate) type F = GoldilocksField;
+
+    pub(crate) fn new_builder() -> CircuitBuilder<F, D> {
+        CircuitBuilder::new(CircuitConfig::standard_recursion_config())
+    }
+
+    // proves the circuit and returns its public inputs
+    pub(crate) fn prove_and_verify(builder: CircuitBuilder<F, D>, pw: PartialWitness<F>) -> Vec<F> {
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        let public_inputs = proof.public_inputs.clone();
+        data.verify(proof).unwrap();
+        public_inputs
+    }
+
+    pub(crate) fn hash_out_inputs(hash: &HashOut<F>) -> Vec<F> {
+        hash.elements.to_vec()
+    }
+
+    #[test]
+    fn test_hash_targets() {
+        let hash = Hash256([0xf3; 32]);
+        let left = hash256_to_goldilocks_hash(&Hash256([1u8; 32]));
+        let right = HashOut {
+            elements: [F::ONE, F::TWO, F::ZERO, F::NEG_ONE],
+        };
+
+        let mut builder = new_builder();
+        let hash_target = Hash256Target::add_virtual(&mut builder);
+        let hash_out = hash_target.to_hash_out(&mut builder);
+        let left_target = builder.add_virtual_hash();
+        let right_target = builder.add_virtual_hash();
+        let plain = two_to_one(&mut builder, left_target, right_target);
+        let marked = two_to_one_marked_leaf(&mut builder, left_target, right_target);
+        builder.register_public_inputs(&hash_out.elements);
+        builder.register_public_inputs(&plain.elements);
+        builder.register_public_inputs(&marked.elements);
+
+        let mut pw = PartialWitness::new();
+        hash_target.set_witness(&mut pw, &hash);
+        pw.set_hash_target(left_target, left);
+        pw.set_hash_target(right_target, right);
+
+        let mut expected = hash_out_inputs(&hash256_to_goldilocks_hash(&hash));
+        expected.extend(hash_out_inputs(&PoseidonHasher::two_to_one(&left, &right)));
+        expected.extend(hash_out_inputs(&PoseidonHasher::two_to_one_marked_leaf(
+            &left, &right,
+        )));
+        assert_eq!(prove_and_verify(builder, pw), expected);
+    }
+}
@@ -0,0 +1,144 @@
+use l2o_crypto::hash::merkle::core::MerkleProofCore;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::HashOut;
+use plonky2::hash::hash_types::HashOutTarget;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::BoolTarget;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::WitnessWrite;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use super::hash::select_hash;
+use super::hash::two_to_one_marked_if;
+
+// In-circuit calc_merkle_root_marked_if: index bits are little endian, one
+// per level, and only the leaf level uses the marked leaf hash.
+pub fn calc_merkle_root_marked_if_target<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    value: HashOutTarget,
+    siblings: &[HashOutTarget],
+    index_bits: &[BoolTarget],
+    marked: bool,
+) -> HashOutTarget {
+    assert_eq!(
+        siblings.len(),
+        index_bits.len(),
+        "one index bit per merkle level"
+    );
+    let mut current = value;
+    for (i, (sibling, bit)) in siblings.iter().zip(index_bits.iter()).enumerate() {
+        let left = select_hash(builder, *bit, *sibling, current);
+        let right = select_hash(builder, *bit, current, *sibling);
+        current = two_to_one_marked_if(builder, left, right, i == 0 && marked);
+    }
+    current
+}
+
+// A MerkleProofCore<GHashOut> of a fixed height. The index is split into
+// bits in-circuit, which also range checks it against the height.
+#[derive(Clone, Debug)]
+pub struct MerkleProofTarget {
+    pub root: HashOutTarget,
+    pub value: HashOutTarget,
+    pub index: Target,
+    pub siblings: Vec<HashOutTarget>,
+}
+
+impl MerkleProofTarget {
+    pub fn add_virtual<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        height: usize,
+    ) -> Self {
+        Self {
+            root: builder.add_virtual_hash(),
+            value: builder.add_virtual_hash(),
+            index: builder.add_virtual_target(),
+            siblings: (0..height).map(|_| builder.add_virtual_hash()).collect(),
+        }
+    }
+    pub fn set_witness<F: RichField, W: WitnessWrite<F>>(
+        &self,
+        witness: &mut W,
+        proof: &MerkleProofCore<HashOut<F>>,
+    ) {
+        assert_eq!(
+            proof.siblings.len(),
+            self.siblings.len(),
+            "merkle proof height does not match the circuit"
+        );
+        witness.set_hash_target(self.root, proof.root);
+        witness.set_hash_target(self.value, proof.value);
+        witness.set_target(self.index, F::from_canonical_u64(proof.index));
+        for (target, sibling) in self.siblings.iter().zip(proof.siblings.iter()) {
+            witness.set_hash_target(*target, *sibling);
+        }
+    }
+    pub fn calc_root_marked_if<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        marked: bool,
+    ) -> HashOutTarget {
+        let index_bits = builder.split_le(self.index, self.siblings.len());
+        calc_merkle_root_marked_if_target(builder, self.value, &self.siblings, &index_bits, marked)
+    }
+    pub fn enforce_valid_marked_if<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        marked: bool,
+    ) {
+        let root = self.calc_root_marked_if(builder, marked);
+        builder.connect_hashes(root, self.root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use l2o_crypto::fields::goldilocks::hash::GHashOut;
+    use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+    use l2o_crypto::hash::merkle::core::calc_merkle_root_marked_if;
+    use plonky2::field::types::Field;
+    use plonky2::iop::witness::PartialWitness;
+
+    use super::*;
+    use crate::goldilocks::hash::tests::hash_out_inputs;
+    use crate::goldilocks::hash::tests::new_builder;
+    use crate::goldilocks::hash::tests::prove_and_verify;
+    use crate::goldilocks::hash::tests::F;
+
+    fn test_proof(marked: bool) -> MerkleProofCore<GHashOut> {
+        let value = GHashOut {
+            elements: [F::from_canonical_u64(9); 4],
+        };
+        let siblings = (0..4u64)
+            .map(|i| GHashOut {
+                elements: [F::from_canonical_u64(i), F::ONE, F::TWO, F::NEG_ONE],
+            })
+            .collect::<Vec<_>>();
+        let index = 0b1010;
+        MerkleProofCore {
+            root: calc_merkle_root_marked_if::<GHashOut, PoseidonHasher>(
+                value, &siblings, index, marked,
+            ),
+            value,
+            index,
+            siblings,
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof_target() {
+        for marked in [false, true] {
+            let proof = test_proof(marked);
+
+            let mut builder = new_builder();
+            let proof_target = MerkleProofTarget::add_virtual(&mut builder, proof.siblings.len());
+            let root = proof_target.calc_root_marked_if(&mut builder, marked);
+            proof_target.enforce_valid_marked_if(&mut builder, marked);
+            builder.register_public_inputs(&root.elements);
+
+            let mut pw = PartialWitness::new();
+            proof_target.set_witness(&mut pw, &proof);
+            assert_eq!(prove_and_verify(builder, pw), hash_out_inputs(&proof.root));
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod goldilocks;
+pub mod r1cs;
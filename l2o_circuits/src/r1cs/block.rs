@@ -0,0 +1,264 @@
+use std::borrow::Borrow;
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::alloc::AllocationMode;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::uint64::UInt64;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::ToBytesGadget;
+use ark_relations::r1cs::Namespace;
+use ark_relations::r1cs::SynthesisError;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
+use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+use l2o_ord::operation::l2o_a::block::Block;
+use l2o_ord::operation::l2o_a::L2OAPublicInput;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::hash::poseidon_bn254::bn254_to_hash256_var;
+use super::hash::poseidon_bn254::hash_no_pad;
+use super::hash::BytesHasherGadget;
+use super::hash::Hash256Var;
+
+// Every field of an L2O-A block except the proof.
+#[derive(Clone, Debug)]
+pub struct L2OABlockVar<F: PrimeField> {
+    pub l2id: UInt64<F>,
+    pub l2_block_number: UInt64<F>,
+
+    pub bitcoin_block_number: UInt64<F>,
+    pub bitcoin_block_hash: Hash256Var<F>,
+
+    pub public_key: Hash256Var<F>,
+
+    pub start_state_root: Hash256Var<F>,
+    pub end_state_root: Hash256Var<F>,
+
+    pub deposit_state_root: Hash256Var<F>,
+
+    pub start_withdrawal_state_root: Hash256Var<F>,
+    pub end_withdrawal_state_root: Hash256Var<F>,
+
+    pub superchain_root: Hash256Var<F>,
+}
+
+fn u64_to_fp_var<F: PrimeField>(value: &UInt64<F>) -> Result<FpVar<F>, SynthesisError> {
+    Boolean::le_bits_to_fp_var(&value.to_bits_le())
+}
+
+impl<F: PrimeField> L2OABlockVar<F> {
+    fn hashes(&self) -> [&Hash256Var<F>; 8] {
+        [
+            &self.bitcoin_block_hash,
+            &self.public_key,
+            &self.start_state_root,
+            &self.end_state_root,
+            &self.deposit_state_root,
+            &self.start_withdrawal_state_root,
+            &self.end_withdrawal_state_root,
+            &self.superchain_root,
+        ]
+    }
+
+    // same layout as get_block_payload_bytes
+    pub fn payload_bytes(&self) -> Result<Vec<UInt8<F>>, SynthesisError> {
+        let mut payload = Vec::new();
+        payload.extend(self.l2id.to_bytes()?);
+        payload.extend(self.l2_block_number.to_bytes()?);
+        payload.extend(self.bitcoin_block_number.to_bytes()?);
+        for hash in self.hashes() {
+            payload.extend_from_slice(&hash.0);
+        }
+        Ok(payload)
+    }
+
+    // same layout and order as Block::get_public_inputs
+    pub fn get_public_inputs(
+        &self,
+        layout: &[L2OAPublicInput],
+        block_hash: &Hash256Var<F>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let mut result = Vec::with_capacity(layout.iter().map(|p| p.field_count()).sum());
+        for public_input in layout.iter() {
+            let hash = match public_input {
+                L2OAPublicInput::L2Id => {
+                    result.push(u64_to_fp_var(&self.l2id)?);
+                    continue;
+                }
+                L2OAPublicInput::L2BlockNumber => {
+                    result.push(u64_to_fp_var(&self.l2_block_number)?);
+                    continue;
+                }
+                L2OAPublicInput::BitcoinBlockNumber => {
+                    result.push(u64_to_fp_var(&self.bitcoin_block_number)?);
+                    continue;
+                }
+                L2OAPublicInput::BlockHash => block_hash,
+                L2OAPublicInput::BitcoinBlockHash => &self.bitcoin_block_hash,
+                L2OAPublicInput::PublicKey => &self.public_key,
+                L2OAPublicInput::StartStateRoot => &self.start_state_root,
+                L2OAPublicInput::EndStateRoot => &self.end_state_root,
+                L2OAPublicInput::DepositStateRoot => &self.deposit_state_root,
+                L2OAPublicInput::StartWithdrawalStateRoot => &self.start_withdrawal_state_root,
+                L2OAPublicInput::EndWithdrawalStateRoot => &self.end_withdrawal_state_root,
+                L2OAPublicInput::SuperchainRoot => &self.superchain_root,
+            };
+            result.extend(hash.to_fp_pair()?);
+        }
+        Ok(result)
+    }
+}
+
+impl L2OABlockVar<Fr> {
+    // same layout as get_block_payload_bn254
+    pub fn payload_bn254(&self) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+        let mut payload = vec![
+            u64_to_fp_var(&self.l2id)?,
+            u64_to_fp_var(&self.l2_block_number)?,
+            u64_to_fp_var(&self.bitcoin_block_number)?,
+        ];
+        for hash in self.hashes() {
+            payload.extend(hash.to_fp_pair()?);
+        }
+        Ok(payload)
+    }
+}
+
+impl<F, Proof> AllocVar<Block<Proof>, F> for L2OABlockVar<F>
+where
+    F: PrimeField,
+    Proof: Serialize,
+    for<'de2> Proof: Deserialize<'de2>,
+{
+    fn new_variable<T: Borrow<Block<Proof>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let cs = cs.into().cs();
+        let block = f();
+        let block = block.as_ref().map(|b| b.borrow()).map_err(|e| *e);
+        let number = |get: fn(&Block<Proof>) -> u64| {
+            UInt64::new_variable(cs.clone(), || block.map(get), mode)
+        };
+        let hash = |get: fn(&Block<Proof>) -> Hash256| {
+            Hash256Var::new_variable(cs.clone(), || block.map(get), mode)
+        };
+        Ok(Self {
+            l2id: number(|b| b.l2id)?,
+            l2_block_number: number(|b| b.l2_block_number)?,
+            bitcoin_block_number: number(|b| b.bitcoin_block_number)?,
+            bitcoin_block_hash: hash(|b| b.bitcoin_block_hash)?,
+            public_key: hash(|b| Hash256(b.public_key.0))?,
+            start_state_root: hash(|b| b.start_state_root)?,
+            end_state_root: hash(|b| b.end_state_root)?,
+            deposit_state_root: hash(|b| b.deposit_state_root)?,
+            start_withdrawal_state_root: hash(|b| b.start_withdrawal_state_root)?,
+            end_withdrawal_state_root: hash(|b| b.end_withdrawal_state_root)?,
+            superchain_root: hash(|b| b.superchain_root)?,
+        })
+    }
+}
+
+// In-circuit L2OBlockHasher.
+pub trait L2OBlockHasherGadget<F: PrimeField> {
+    fn get_l2_block_hash(block: &L2OABlockVar<F>) -> Result<Hash256Var<F>, SynthesisError>;
+}
+
+macro_rules! impl_bytes_block_hasher_gadget {
+    ($($hasher:ty),+ $(,)?) => {
+        $(
+            impl<F: PrimeField> L2OBlockHasherGadget<F> for $hasher {
+                fn get_l2_block_hash(
+                    block: &L2OABlockVar<F>,
+                ) -> Result<Hash256Var<F>, SynthesisError> {
+                    <$hasher as BytesHasherGadget<F>>::hash_bytes(&block.payload_bytes()?)
+                }
+            }
+        )+
+    };
+}
+
+impl_bytes_block_hasher_gadget!(Blake3Hasher, Keccak256Hasher, Sha256Hasher);
+
+impl L2OBlockHasherGadget<Fr> for PoseidonBN254Hasher {
+    fn get_l2_block_hash(block: &L2OABlockVar<Fr>) -> Result<Hash256Var<Fr>, SynthesisError> {
+        bn254_to_hash256_var(&hash_no_pad(&block.payload_bn254()?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_groth16::Proof;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_common::common::data::signature::L2OCompactPublicKey;
+    use l2o_common::common::data::signature::L2OSignature512;
+    use l2o_crypto::proof::groth16::bn128::proof_data::Groth16BN128ProofData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAProofData;
+    use l2o_ord::hasher::L2OBlockHasher;
+    use l2o_ord::operation::l2o_a::L2OABlockV1;
+
+    use super::*;
+
+    fn test_block() -> L2OABlockV1 {
+        L2OABlockV1 {
+            l2id: 7,
+            l2_block_number: 1 << 40,
+            bitcoin_block_number: 830000,
+            bitcoin_block_hash: Hash256([1u8; 32]),
+            public_key: L2OCompactPublicKey([2u8; 32]),
+            start_state_root: Hash256([3u8; 32]),
+            end_state_root: Hash256([4u8; 32]),
+            deposit_state_root: Hash256([5u8; 32]),
+            start_withdrawal_state_root: Hash256([6u8; 32]),
+            end_withdrawal_state_root: Hash256([7u8; 32]),
+            proof: L2OAProofData::Groth16BN128(Groth16BN128ProofData {
+                proof: Proof::default(),
+                public_inputs: vec![],
+            }),
+            superchain_root: Hash256([0xfe; 32]),
+            signature: L2OSignature512([0u8; 64]),
+        }
+    }
+
+    fn check_block_hasher_gadget<Hasher>()
+    where
+        Hasher: L2OBlockHasherGadget<Fr> + L2OBlockHasher,
+    {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let block = test_block();
+        let block_var = L2OABlockVar::new_witness(cs.clone(), || Ok(&block)).unwrap();
+        let hash = <Hasher as L2OBlockHasherGadget<Fr>>::get_l2_block_hash(&block_var).unwrap();
+        let expected = <Hasher as L2OBlockHasher>::get_l2_block_hash(&block);
+        assert_eq!(hash.value().unwrap(), expected);
+
+        let layout = vec![
+            L2OAPublicInput::BlockHash,
+            L2OAPublicInput::L2Id,
+            L2OAPublicInput::L2BlockNumber,
+            L2OAPublicInput::PublicKey,
+            L2OAPublicInput::SuperchainRoot,
+        ];
+        let public_inputs = block_var.get_public_inputs(&layout, &hash).unwrap();
+        assert_eq!(
+            public_inputs.value().unwrap(),
+            block.get_public_inputs(&layout, expected)
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_block_hasher_gadgets() {
+        check_block_hasher_gadget::<Sha256Hasher>();
+        check_block_hasher_gadget::<Blake3Hasher>();
+        check_block_hasher_gadget::<Keccak256Hasher>();
+        check_block_hasher_gadget::<PoseidonBN254Hasher>();
+    }
+}
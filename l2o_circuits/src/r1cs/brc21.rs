@@ -0,0 +1,297 @@
+use std::borrow::Borrow;
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::alloc::AllocationMode;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::uint64::UInt64;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::ToBytesGadget;
+use ark_relations::r1cs::Namespace;
+use ark_relations::r1cs::SynthesisError;
+use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
+use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+use l2o_ord::operation::brc21::l2deposit::L2Deposit;
+use l2o_ord::operation::brc21::l2withdraw::L2Withdraw;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::hash::poseidon_bn254::bn254_to_hash256_var;
+use super::hash::poseidon_bn254::bytes_to_bn254_var;
+use super::hash::poseidon_bn254::hash_no_pad;
+use super::hash::BytesHasherGadget;
+use super::hash::Hash256Var;
+
+// The fields deposits and withdrawals commit to. Both leaves share the same
+// payload layout, so one gadget covers the deposit and the withdrawal trees.
+// String lengths are fixed by the allocated value, or by the shape given to
+// new_variable_with_shape.
+#[derive(Clone, Debug)]
+pub struct L2TransferVar<F: PrimeField> {
+    pub l2id: UInt64<F>,
+    pub tick: Vec<UInt8<F>>,
+    pub to: Vec<UInt8<F>>,
+    pub amount: Vec<UInt8<F>>,
+}
+
+// The byte lengths of the tick, to and amount of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L2TransferShape {
+    pub tick: usize,
+    pub to: usize,
+    pub amount: usize,
+}
+
+pub trait L2TransferFields {
+    fn transfer_fields(&self) -> (u64, &str, &str, &str);
+
+    fn transfer_shape(&self) -> L2TransferShape {
+        let (_, tick, to, amount) = self.transfer_fields();
+        L2TransferShape {
+            tick: tick.len(),
+            to: to.len(),
+            amount: amount.len(),
+        }
+    }
+}
+
+impl L2TransferFields for L2Deposit {
+    fn transfer_fields(&self) -> (u64, &str, &str, &str) {
+        (self.l2id, &self.tick, &self.to, &self.amount)
+    }
+}
+
+impl<Proof> L2TransferFields for L2Withdraw<Proof>
+where
+    Proof: Serialize,
+    for<'de2> Proof: Deserialize<'de2>,
+{
+    fn transfer_fields(&self) -> (u64, &str, &str, &str) {
+        (self.l2id, &self.tick, &self.to, &self.amount)
+    }
+}
+
+impl<F: PrimeField> L2TransferVar<F> {
+    pub fn new_variable_with_shape<V: L2TransferFields, T: Borrow<V>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        shape: L2TransferShape,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let cs = cs.into().cs();
+        let transfer = f();
+        let transfer = transfer.as_ref().map(|t| t.borrow()).map_err(|e| *e);
+        if transfer.is_ok_and(|t| t.transfer_shape() != shape) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let fields = transfer.map(|t| t.transfer_fields());
+        let bytes = |len: usize, get: for<'a> fn((u64, &'a str, &'a str, &'a str)) -> &'a str| {
+            (0..len)
+                .map(|i| {
+                    UInt8::new_variable(
+                        cs.clone(),
+                        || fields.map(|fields| get(fields).as_bytes()[i]),
+                        mode,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            l2id: UInt64::new_variable(cs.clone(), || fields.map(|fields| fields.0), mode)?,
+            tick: bytes(shape.tick, |fields| fields.1)?,
+            to: bytes(shape.to, |fields| fields.2)?,
+            amount: bytes(shape.amount, |fields| fields.3)?,
+        })
+    }
+
+    // same layout as get_l2_deposit_payload_bytes/get_l2_withdraw_payload_bytes
+    pub fn payload_bytes(&self) -> Result<Vec<UInt8<F>>, SynthesisError> {
+        let mut payload = self.l2id.to_bytes()?;
+        payload.extend_from_slice(&self.tick);
+        payload.extend_from_slice(&self.to);
+        payload.extend_from_slice(&self.amount);
+        Ok(payload)
+    }
+}
+
+impl L2TransferVar<Fr> {
+    // same layout as get_l2_deposit_payload_bn254/get_l2_withdraw_payload_bn254
+    pub fn payload_bn254(&self) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+        let mut payload = vec![Boolean::le_bits_to_fp_var(&self.l2id.to_bits_le())?];
+        payload.extend(bytes_to_bn254_var(&self.tick)?);
+        payload.extend(bytes_to_bn254_var(&self.to)?);
+        payload.extend(bytes_to_bn254_var(&self.amount)?);
+        Ok(payload)
+    }
+}
+
+impl<F: PrimeField> AllocVar<L2Deposit, F> for L2TransferVar<F> {
+    fn new_variable<T: Borrow<L2Deposit>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the shape comes from the deposit, see new_variable_with_shape
+        let deposit = f();
+        let shape = deposit
+            .as_ref()
+            .map(|d| d.borrow().transfer_shape())
+            .map_err(|e| *e)?;
+        Self::new_variable_with_shape::<L2Deposit, T>(cs, || deposit, shape, mode)
+    }
+}
+
+impl<F, Proof> AllocVar<L2Withdraw<Proof>, F> for L2TransferVar<F>
+where
+    F: PrimeField,
+    Proof: Serialize,
+    for<'de2> Proof: Deserialize<'de2>,
+{
+    fn new_variable<T: Borrow<L2Withdraw<Proof>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let withdraw = f();
+        let shape = withdraw
+            .as_ref()
+            .map(|w| w.borrow().transfer_shape())
+            .map_err(|e| *e)?;
+        Self::new_variable_with_shape::<L2Withdraw<Proof>, T>(cs, || withdraw, shape, mode)
+    }
+}
+
+// In-circuit L2ODepositHasher/L2OWithdrawHasher.
+pub trait L2OTransferHasherGadget<F: PrimeField> {
+    fn get_l2_transfer_hash(transfer: &L2TransferVar<F>) -> Result<Hash256Var<F>, SynthesisError>;
+}
+
+macro_rules! impl_bytes_transfer_hasher_gadget {
+    ($($hasher:ty),+ $(,)?) => {
+        $(
+            impl<F: PrimeField> L2OTransferHasherGadget<F> for $hasher {
+                fn get_l2_transfer_hash(
+                    transfer: &L2TransferVar<F>,
+                ) -> Result<Hash256Var<F>, SynthesisError> {
+                    <$hasher as BytesHasherGadget<F>>::hash_bytes(&transfer.payload_bytes()?)
+                }
+            }
+        )+
+    };
+}
+
+impl_bytes_transfer_hasher_gadget!(Blake3Hasher, Keccak256Hasher, Sha256Hasher);
+
+impl L2OTransferHasherGadget<Fr> for PoseidonBN254Hasher {
+    fn get_l2_transfer_hash(
+        transfer: &L2TransferVar<Fr>,
+    ) -> Result<Hash256Var<Fr>, SynthesisError> {
+        bn254_to_hash256_var(&hash_no_pad(&transfer.payload_bn254()?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_relations::r1cs::SynthesisMode;
+    use l2o_common::common::data::hash::Hash256;
+    use l2o_crypto::hash::merkle::core::MerkleProofCore;
+    use l2o_ord::hasher::L2ODepositHasher;
+    use l2o_ord::hasher::L2OWithdrawHasher;
+    use l2o_ord::operation::brc21::L2WithdrawV1;
+
+    use super::*;
+
+    fn check_transfer_hasher_gadget<Hasher>()
+    where
+        Hasher: L2OTransferHasherGadget<Fr> + L2ODepositHasher + L2OWithdrawHasher,
+    {
+        let deposit = L2Deposit {
+            l2id: 3,
+            tick: "ordi".to_string(),
+            to: "bc1qexampleexampleexampleexampleexample".to_string(),
+            amount: "1000.5".to_string(),
+        };
+        let withdraw = L2WithdrawV1 {
+            l2id: deposit.l2id,
+            tick: deposit.tick.clone(),
+            to: deposit.to.clone(),
+            amount: deposit.amount.clone(),
+            proof: MerkleProofCore {
+                root: Hash256([0u8; 32]),
+                value: Hash256([0u8; 32]),
+                index: 0,
+                siblings: vec![],
+            },
+        };
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let deposit_var: L2TransferVar<Fr> =
+            AllocVar::<L2Deposit, Fr>::new_witness(cs.clone(), || Ok(&deposit)).unwrap();
+        assert_eq!(
+            Hasher::get_l2_transfer_hash(&deposit_var)
+                .unwrap()
+                .value()
+                .unwrap(),
+            Hasher::get_l2_deposit_hash(&deposit)
+        );
+        let withdraw_var: L2TransferVar<Fr> =
+            AllocVar::<L2WithdrawV1, Fr>::new_witness(cs.clone(), || Ok(&withdraw)).unwrap();
+        assert_eq!(
+            Hasher::get_l2_transfer_hash(&withdraw_var)
+                .unwrap()
+                .value()
+                .unwrap(),
+            Hasher::get_l2_withdraw_hash(&withdraw)
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_transfer_hasher_gadgets() {
+        check_transfer_hasher_gadget::<Sha256Hasher>();
+        check_transfer_hasher_gadget::<Blake3Hasher>();
+        check_transfer_hasher_gadget::<Keccak256Hasher>();
+        check_transfer_hasher_gadget::<PoseidonBN254Hasher>();
+    }
+
+    #[test]
+    fn test_transfer_setup_without_a_transfer() {
+        let shape = L2TransferShape {
+            tick: 4,
+            to: 42,
+            amount: 6,
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        let transfer_var = L2TransferVar::new_variable_with_shape::<L2Deposit, L2Deposit>(
+            cs.clone(),
+            || Err(SynthesisError::AssignmentMissing),
+            shape,
+            AllocationMode::Witness,
+        )
+        .unwrap();
+        assert_eq!(transfer_var.to.len(), 42);
+        Sha256Hasher::get_l2_transfer_hash(&transfer_var).unwrap();
+
+        let deposit = L2Deposit {
+            l2id: 3,
+            tick: "ordi".to_string(),
+            to: "bc1q".to_string(),
+            amount: "1000.5".to_string(),
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        assert!(L2TransferVar::new_variable_with_shape::<L2Deposit, _>(
+            cs,
+            || Ok(&deposit),
+            shape,
+            AllocationMode::Witness
+        )
+        .is_err());
+    }
+}
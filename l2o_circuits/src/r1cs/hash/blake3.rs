@@ -0,0 +1,145 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::uint32::UInt32;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::ToBitsGadget;
+use ark_r1cs_std::ToBytesGadget;
+use ark_relations::r1cs::SynthesisError;
+use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
+
+use super::BytesHasherGadget;
+use super::Hash256Var;
+
+// Inputs are hashed as a single BLAKE3 chunk, which covers every payload the
+// indexer hashes (blocks, deposits, withdrawals and merkle nodes).
+pub const BLAKE3_MAX_INPUT_LEN: usize = 1024;
+
+const BLAKE3_BLOCK_LEN: usize = 64;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const CHUNK_START: u32 = 1;
+const CHUNK_END: u32 = 2;
+const ROOT: u32 = 8;
+
+#[allow(clippy::too_many_arguments)]
+fn g<F: PrimeField>(
+    state: &mut [UInt32<F>],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    mx: &UInt32<F>,
+    my: &UInt32<F>,
+) -> Result<(), SynthesisError> {
+    state[a] = UInt32::addmany(&[state[a].clone(), state[b].clone(), mx.clone()])?;
+    state[d] = state[d].xor(&state[a])?.rotr(16);
+    state[c] = UInt32::addmany(&[state[c].clone(), state[d].clone()])?;
+    state[b] = state[b].xor(&state[c])?.rotr(12);
+    state[a] = UInt32::addmany(&[state[a].clone(), state[b].clone(), my.clone()])?;
+    state[d] = state[d].xor(&state[a])?.rotr(8);
+    state[c] = UInt32::addmany(&[state[c].clone(), state[d].clone()])?;
+    state[b] = state[b].xor(&state[c])?.rotr(7);
+    Ok(())
+}
+
+// returns the first 8 words of the compression output, the chaining value
+fn compress<F: PrimeField>(
+    cv: &[UInt32<F>],
+    block: &[UInt32<F>],
+    block_len: u32,
+    flags: u32,
+) -> Result<Vec<UInt32<F>>, SynthesisError> {
+    let mut state = cv.to_vec();
+    state.extend(IV[0..4].iter().map(|word| UInt32::constant(*word)));
+    // the chunk counter is always 0 for a single chunk
+    state.push(UInt32::constant(0));
+    state.push(UInt32::constant(0));
+    state.push(UInt32::constant(block_len));
+    state.push(UInt32::constant(flags));
+
+    let mut m = block.to_vec();
+    for round in 0..7 {
+        g(&mut state, 0, 4, 8, 12, &m[0], &m[1])?;
+        g(&mut state, 1, 5, 9, 13, &m[2], &m[3])?;
+        g(&mut state, 2, 6, 10, 14, &m[4], &m[5])?;
+        g(&mut state, 3, 7, 11, 15, &m[6], &m[7])?;
+        g(&mut state, 0, 5, 10, 15, &m[8], &m[9])?;
+        g(&mut state, 1, 6, 11, 12, &m[10], &m[11])?;
+        g(&mut state, 2, 7, 8, 13, &m[12], &m[13])?;
+        g(&mut state, 3, 4, 9, 14, &m[14], &m[15])?;
+        if round < 6 {
+            m = MSG_PERMUTATION.iter().map(|i| m[*i].clone()).collect();
+        }
+    }
+
+    (0..8).map(|i| state[i].xor(&state[i + 8])).collect()
+}
+
+impl<F: PrimeField> BytesHasherGadget<F> for Blake3Hasher {
+    fn hash_bytes(bytes: &[UInt8<F>]) -> Result<Hash256Var<F>, SynthesisError> {
+        assert!(
+            bytes.len() <= BLAKE3_MAX_INPUT_LEN,
+            "the BLAKE3 gadget hashes at most one chunk"
+        );
+        let block_count = bytes.len().div_ceil(BLAKE3_BLOCK_LEN).max(1);
+
+        let mut cv = IV
+            .iter()
+            .map(|word| UInt32::constant(*word))
+            .collect::<Vec<_>>();
+        for i in 0..block_count {
+            let start = i * BLAKE3_BLOCK_LEN;
+            let end = bytes.len().min(start + BLAKE3_BLOCK_LEN);
+            let mut block = bytes[start..end].to_vec();
+            block.resize(BLAKE3_BLOCK_LEN, UInt8::constant(0));
+            let words = block
+                .chunks(4)
+                .map(|word| Ok(UInt32::from_bits_le(&word.to_bits_le()?)))
+                .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+            let mut flags = 0;
+            if i == 0 {
+                flags |= CHUNK_START;
+            }
+            if i == block_count - 1 {
+                flags |= CHUNK_END | ROOT;
+            }
+            cv = compress(&cv, &words, (end - start) as u32, flags)?;
+        }
+
+        let mut digest = Vec::with_capacity(32);
+        for word in cv.iter() {
+            digest.extend(word.to_bytes()?);
+        }
+        Ok(Hash256Var::from_bytes(digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_crypto::hash::hash_functions::blake3;
+
+    use super::*;
+    use crate::r1cs::hash::tests::check_merkle_hasher_gadget;
+
+    #[test]
+    fn test_blake3_gadget() {
+        for len in [0usize, 64, 150] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let data_var = UInt8::new_witness_vec(cs.clone(), &data).unwrap();
+            let hash = Blake3Hasher::hash_bytes(&data_var).unwrap();
+            assert_eq!(hash.value().unwrap(), blake3::hash(&data));
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        check_merkle_hasher_gadget::<Blake3Hasher>();
+    }
+}
@@ -0,0 +1,166 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::ToBitsGadget;
+use ark_relations::r1cs::SynthesisError;
+use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+
+use super::BytesHasherGadget;
+use super::Hash256Var;
+
+// Keccak-256 with the original keccak padding (0x01 ... 0x80), as used by
+// Ethereum and the sha3 crate's Keccak256.
+const KECCAK256_RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// rotation offsets indexed by [x][y]
+const ROTATIONS: [[usize; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+// lanes are 64 little endian bits, indexed by x + 5 * y
+type Lane<F> = Vec<Boolean<F>>;
+
+fn rotl<F: PrimeField>(lane: &Lane<F>, by: usize) -> Lane<F> {
+    (0..64).map(|i| lane[(i + 64 - by) % 64].clone()).collect()
+}
+
+fn xor<F: PrimeField>(a: &Lane<F>, b: &Lane<F>) -> Result<Lane<F>, SynthesisError> {
+    a.iter().zip(b.iter()).map(|(a, b)| a.xor(b)).collect()
+}
+
+fn keccak_f<F: PrimeField>(state: &mut [Lane<F>]) -> Result<(), SynthesisError> {
+    for round_constant in ROUND_CONSTANTS.iter() {
+        // theta
+        let mut c = Vec::with_capacity(5);
+        for x in 0..5 {
+            let mut column = state[x].clone();
+            for y in 1..5 {
+                column = xor(&column, &state[x + 5 * y])?;
+            }
+            c.push(column);
+        }
+        for x in 0..5 {
+            let d = xor(&c[(x + 4) % 5], &rotl(&c[(x + 1) % 5], 1))?;
+            for y in 0..5 {
+                state[x + 5 * y] = xor(&state[x + 5 * y], &d)?;
+            }
+        }
+
+        // rho and pi
+        let mut b = vec![Vec::new(); 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = rotl(&state[x + 5 * y], ROTATIONS[x][y]);
+            }
+        }
+
+        // chi
+        for x in 0..5 {
+            for y in 0..5 {
+                let next = &b[(x + 1) % 5 + 5 * y];
+                let after = &b[(x + 2) % 5 + 5 * y];
+                state[x + 5 * y] = b[x + 5 * y]
+                    .iter()
+                    .zip(next.iter().zip(after.iter()))
+                    .map(|(a, (n, m))| a.xor(&n.not().and(m)?))
+                    .collect::<Result<Vec<_>, _>>()?;
+            }
+        }
+
+        // iota
+        state[0] = state[0]
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| {
+                if round_constant >> i & 1 == 1 {
+                    bit.not()
+                } else {
+                    bit.clone()
+                }
+            })
+            .collect();
+    }
+    Ok(())
+}
+
+impl<F: PrimeField> BytesHasherGadget<F> for Keccak256Hasher {
+    fn hash_bytes(bytes: &[UInt8<F>]) -> Result<Hash256Var<F>, SynthesisError> {
+        let mut padded = bytes.to_vec();
+        padded.push(UInt8::constant(0x01));
+        let padded_len = padded.len().div_ceil(KECCAK256_RATE) * KECCAK256_RATE;
+        padded.resize(padded_len, UInt8::constant(0));
+        let last = padded.len() - 1;
+        padded[last] = padded[last].xor(&UInt8::constant(0x80))?;
+
+        let mut state: Vec<Lane<F>> = vec![vec![Boolean::FALSE; 64]; 25];
+        for block in padded.chunks(KECCAK256_RATE) {
+            for (i, lane_bytes) in block.chunks(8).enumerate() {
+                state[i] = xor(&state[i], &lane_bytes.to_bits_le()?)?;
+            }
+            keccak_f(&mut state)?;
+        }
+
+        let output = state[0..4]
+            .iter()
+            .flat_map(|lane| lane.chunks(8).map(UInt8::from_bits_le))
+            .collect::<Vec<_>>();
+        Ok(Hash256Var::from_bytes(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_crypto::hash::hash_functions::keccak256;
+
+    use super::*;
+    use crate::r1cs::hash::tests::check_merkle_hasher_gadget;
+
+    #[test]
+    fn test_keccak256_gadget() {
+        // the empty input and an input spanning two blocks
+        for len in [0usize, 150] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let data_var = UInt8::new_witness_vec(cs.clone(), &data).unwrap();
+            let hash = Keccak256Hasher::hash_bytes(&data_var).unwrap();
+            assert_eq!(hash.value().unwrap(), keccak256::hash(&data));
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        check_merkle_hasher_gadget::<Keccak256Hasher>();
+    }
+}
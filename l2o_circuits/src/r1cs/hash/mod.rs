@@ -0,0 +1,215 @@
+use std::borrow::Borrow;
+
+use ark_ff::PrimeField;
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::alloc::AllocationMode;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::eq::EqGadget;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::select::CondSelectGadget;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::R1CSVar;
+use ark_r1cs_std::ToBitsGadget;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::Namespace;
+use ark_relations::r1cs::SynthesisError;
+use l2o_common::common::data::hash::Hash256;
+
+pub mod blake3;
+pub mod keccak256;
+pub mod poseidon_bn254;
+pub mod sha256;
+
+// A Hash256 as 32 byte variables, in the byte order of the native value.
+#[derive(Clone, Debug)]
+pub struct Hash256Var<F: PrimeField>(pub Vec<UInt8<F>>);
+
+impl<F: PrimeField> Hash256Var<F> {
+    pub fn constant(hash: &Hash256) -> Self {
+        Self(UInt8::constant_vec(&hash.0))
+    }
+    pub fn from_bytes(bytes: Vec<UInt8<F>>) -> Self {
+        assert_eq!(bytes.len(), 32, "Hash256Var must be 32 bytes");
+        Self(bytes)
+    }
+    // two little endian 16 byte halves, like `From<Hash256> for [Fr; 2]`
+    pub fn to_fp_pair(&self) -> Result<[FpVar<F>; 2], SynthesisError> {
+        Ok([
+            Boolean::le_bits_to_fp_var(&self.0[0..16].to_bits_le()?)?,
+            Boolean::le_bits_to_fp_var(&self.0[16..32].to_bits_le()?)?,
+        ])
+    }
+}
+
+impl<F: PrimeField> AllocVar<Hash256, F> for Hash256Var<F> {
+    fn new_variable<T: Borrow<Hash256>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let cs = cs.into().cs();
+        let value = f().map(|hash| hash.borrow().0);
+        let bytes = (0..32)
+            .map(|i| UInt8::new_variable(cs.clone(), || value.map(|v| v[i]), mode))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<F: PrimeField> R1CSVar<F> for Hash256Var<F> {
+    type Value = Hash256;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.0.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        let mut result = [0u8; 32];
+        for (byte, var) in result.iter_mut().zip(self.0.iter()) {
+            *byte = var.value()?;
+        }
+        Ok(Hash256(result))
+    }
+}
+
+impl<F: PrimeField> EqGadget<F> for Hash256Var<F> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.0.is_eq(&other.0)
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for Hash256Var<F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let bytes = true_value
+            .0
+            .iter()
+            .zip(false_value.0.iter())
+            .map(|(t, f)| UInt8::conditionally_select(cond, t, f))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(bytes))
+    }
+}
+
+// In-circuit counterparts of the native hashers, implemented on the same
+// marker types (Sha256Hasher, PoseidonBN254Hasher, ...).
+pub trait MerkleHasherGadget<F: PrimeField> {
+    fn two_to_one(
+        left: &Hash256Var<F>,
+        right: &Hash256Var<F>,
+    ) -> Result<Hash256Var<F>, SynthesisError>;
+    fn two_to_one_marked_leaf(
+        left: &Hash256Var<F>,
+        right: &Hash256Var<F>,
+    ) -> Result<Hash256Var<F>, SynthesisError>;
+
+    fn two_to_one_marked_if(
+        left: &Hash256Var<F>,
+        right: &Hash256Var<F>,
+        marked: bool,
+    ) -> Result<Hash256Var<F>, SynthesisError> {
+        if marked {
+            Self::two_to_one_marked_leaf(left, right)
+        } else {
+            Self::two_to_one(left, right)
+        }
+    }
+}
+
+pub trait BytesHasherGadget<F: PrimeField> {
+    fn hash_bytes(bytes: &[UInt8<F>]) -> Result<Hash256Var<F>, SynthesisError>;
+}
+
+// The byte oriented hashers hash the concatenation of both children, with a
+// trailing 1 byte for marked leaves.
+macro_rules! impl_bytes_merkle_hasher_gadget {
+    ($($hasher:ty),+ $(,)?) => {
+        $(
+            impl<F: PrimeField> MerkleHasherGadget<F> for $hasher {
+                fn two_to_one(
+                    left: &Hash256Var<F>,
+                    right: &Hash256Var<F>,
+                ) -> Result<Hash256Var<F>, SynthesisError> {
+                    let mut bytes = left.0.clone();
+                    bytes.extend_from_slice(&right.0);
+                    <$hasher as BytesHasherGadget<F>>::hash_bytes(&bytes)
+                }
+                fn two_to_one_marked_leaf(
+                    left: &Hash256Var<F>,
+                    right: &Hash256Var<F>,
+                ) -> Result<Hash256Var<F>, SynthesisError> {
+                    let mut bytes = left.0.clone();
+                    bytes.extend_from_slice(&right.0);
+                    bytes.push(UInt8::constant(1));
+                    <$hasher as BytesHasherGadget<F>>::hash_bytes(&bytes)
+                }
+            }
+        )+
+    };
+}
+
+impl_bytes_merkle_hasher_gadget!(
+    l2o_crypto::hash::hash_functions::blake3::Blake3Hasher,
+    l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher,
+    l2o_crypto::hash::hash_functions::sha256::Sha256Hasher,
+);
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::alloc::AllocVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_crypto::hash::merkle::traits::MerkleHasher;
+    use l2o_crypto::hash::merkle::traits::MerkleHasherWithMarkedLeaf;
+
+    use super::*;
+
+    pub(crate) fn test_hashes() -> (Hash256, Hash256) {
+        let mut left = [0u8; 32];
+        let mut right = [0u8; 32];
+        for i in 0..32 {
+            left[i] = i as u8;
+            right[i] = 0xff - 3 * i as u8;
+        }
+        (Hash256(left), Hash256(right))
+    }
+
+    // checks a merkle hasher gadget against its native hasher
+    pub(crate) fn check_merkle_hasher_gadget<H>()
+    where
+        H: MerkleHasherGadget<Fr> + MerkleHasherWithMarkedLeaf<Hash256>,
+    {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let (left, right) = test_hashes();
+        let left_var = Hash256Var::new_witness(cs.clone(), || Ok(left)).unwrap();
+        let right_var = Hash256Var::new_witness(cs.clone(), || Ok(right)).unwrap();
+
+        let hash = <H as MerkleHasherGadget<Fr>>::two_to_one(&left_var, &right_var).unwrap();
+        assert_eq!(
+            hash.value().unwrap(),
+            <H as MerkleHasher<Hash256>>::two_to_one(&left, &right)
+        );
+        let marked =
+            <H as MerkleHasherGadget<Fr>>::two_to_one_marked_leaf(&left_var, &right_var).unwrap();
+        assert_eq!(
+            marked.value().unwrap(),
+            <H as MerkleHasherWithMarkedLeaf<Hash256>>::two_to_one_marked_leaf(&left, &right)
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_hash256_var_fp_pair() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let (hash, _) = test_hashes();
+        let var = Hash256Var::new_input(cs.clone(), || Ok(hash)).unwrap();
+        let pair = var.to_fp_pair().unwrap();
+        let expected: [Fr; 2] = hash.into();
+        assert_eq!(pair[0].value().unwrap(), expected[0]);
+        assert_eq!(pair[1].value().unwrap(), expected[1]);
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,118 @@
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_ff::Field;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::fields::FieldVar;
+use ark_r1cs_std::uint8::UInt8;
+use ark_r1cs_std::R1CSVar;
+use ark_r1cs_std::ToBitsGadget;
+use ark_r1cs_std::ToBytesGadget;
+use ark_relations::r1cs::SynthesisError;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::POSEIDON_BN254_CONFIG;
+
+use super::Hash256Var;
+use super::MerkleHasherGadget;
+
+pub fn hash_no_pad(inputs: &[FpVar<Fr>]) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut sponge = PoseidonSpongeVar::new(inputs.cs(), &POSEIDON_BN254_CONFIG);
+    sponge.absorb(&inputs)?;
+    Ok(sponge.squeeze_field_elements(1)?.remove(0))
+}
+
+// Reduces the big-endian bytes modulo r like hash256_to_bn254. Both 128 bit
+// halves fit below the modulus, so only their sum wraps around.
+pub fn hash256_var_to_bn254(hash: &Hash256Var<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut bytes = hash.0.clone();
+    bytes.reverse();
+    let bits = bytes.to_bits_le()?;
+    let low = Boolean::le_bits_to_fp_var(&bits[0..128])?;
+    let high = Boolean::le_bits_to_fp_var(&bits[128..256])?;
+    Ok(low + high * Fr::from(2u8).pow([128u64]))
+}
+
+// the canonical big-endian bytes of the element, like bn254_to_hash256
+pub fn bn254_to_hash256_var(value: &FpVar<Fr>) -> Result<Hash256Var<Fr>, SynthesisError> {
+    let mut bytes = value.to_bytes()?;
+    bytes.reverse();
+    Ok(Hash256Var::from_bytes(bytes))
+}
+
+// length prefixed 31 byte little endian limbs, like bytes_to_bn254
+pub fn bytes_to_bn254_var(bytes: &[UInt8<Fr>]) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+    let mut result = Vec::with_capacity(1 + bytes.len().div_ceil(31));
+    result.push(FpVar::constant(Fr::from(bytes.len() as u64)));
+    for chunk in bytes.chunks(31) {
+        result.push(Boolean::le_bits_to_fp_var(&chunk.to_bits_le()?)?);
+    }
+    Ok(result)
+}
+
+impl MerkleHasherGadget<Fr> for PoseidonBN254Hasher {
+    fn two_to_one(
+        left: &Hash256Var<Fr>,
+        right: &Hash256Var<Fr>,
+    ) -> Result<Hash256Var<Fr>, SynthesisError> {
+        bn254_to_hash256_var(&hash_no_pad(&[
+            hash256_var_to_bn254(left)?,
+            hash256_var_to_bn254(right)?,
+        ])?)
+    }
+    fn two_to_one_marked_leaf(
+        left: &Hash256Var<Fr>,
+        right: &Hash256Var<Fr>,
+    ) -> Result<Hash256Var<Fr>, SynthesisError> {
+        bn254_to_hash256_var(&hash_no_pad(&[
+            hash256_var_to_bn254(left)?,
+            hash256_var_to_bn254(right)?,
+            FpVar::constant(Fr::ONE),
+        ])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_r1cs_std::alloc::AllocVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_common::common::data::hash::Hash256;
+    use l2o_crypto::fields::bn254::hash::bytes_to_bn254;
+    use l2o_crypto::fields::bn254::hash::hash256_to_bn254;
+    use l2o_crypto::hash::hash_functions::poseidon_bn254;
+
+    use super::*;
+    use crate::r1cs::hash::tests::check_merkle_hasher_gadget;
+
+    #[test]
+    fn test_poseidon_bn254_gadget() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let inputs = [Fr::from(1u8), Fr::from(2u8), Fr::from(3u8)];
+        let input_vars = inputs
+            .iter()
+            .map(|input| FpVar::new_witness(cs.clone(), || Ok(*input)).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hash_no_pad(&input_vars).unwrap().value().unwrap(),
+            poseidon_bn254::hash_no_pad(&inputs)
+        );
+
+        // above the modulus, so the conversion has to reduce
+        let hash = Hash256([0xee; 32]);
+        let hash_var = Hash256Var::new_witness(cs.clone(), || Ok(hash)).unwrap();
+        assert_eq!(
+            hash256_var_to_bn254(&hash_var).unwrap().value().unwrap(),
+            hash256_to_bn254(&hash)
+        );
+
+        let data = (0..40u8).collect::<Vec<_>>();
+        let data_var = UInt8::new_witness_vec(cs.clone(), &data).unwrap();
+        assert_eq!(
+            bytes_to_bn254_var(&data_var).unwrap().value().unwrap(),
+            bytes_to_bn254(&data)
+        );
+        assert!(cs.is_satisfied().unwrap());
+
+        check_merkle_hasher_gadget::<PoseidonBN254Hasher>();
+    }
+}
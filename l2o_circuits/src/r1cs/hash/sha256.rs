@@ -0,0 +1,37 @@
+use ark_crypto_primitives::crh::sha256::constraints::Sha256Gadget;
+use ark_ff::PrimeField;
+use ark_r1cs_std::uint8::UInt8;
+use ark_relations::r1cs::SynthesisError;
+use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+
+use super::BytesHasherGadget;
+use super::Hash256Var;
+
+impl<F: PrimeField> BytesHasherGadget<F> for Sha256Hasher {
+    fn hash_bytes(bytes: &[UInt8<F>]) -> Result<Hash256Var<F>, SynthesisError> {
+        Ok(Hash256Var::from_bytes(Sha256Gadget::digest(bytes)?.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use l2o_crypto::hash::hash_functions::sha256;
+
+    use super::*;
+    use crate::r1cs::hash::tests::check_merkle_hasher_gadget;
+
+    #[test]
+    fn test_sha256_gadget() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let data = (0..100u8).collect::<Vec<_>>();
+        let data_var = UInt8::new_witness_vec(cs.clone(), &data).unwrap();
+        let hash = Sha256Hasher::hash_bytes(&data_var).unwrap();
+        assert_eq!(hash.value().unwrap(), sha256::hash(&data));
+        assert!(cs.is_satisfied().unwrap());
+
+        check_merkle_hasher_gadget::<Sha256Hasher>();
+    }
+}
@@ -0,0 +1,236 @@
+use std::borrow::Borrow;
+
+use ark_ff::PrimeField;
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::alloc::AllocationMode;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::eq::EqGadget;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::select::CondSelectGadget;
+use ark_relations::ns;
+use ark_relations::r1cs::Namespace;
+use ark_relations::r1cs::SynthesisError;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::merkle::core::MerkleProofCore;
+
+use super::hash::Hash256Var;
+use super::hash::MerkleHasherGadget;
+
+// In-circuit calc_merkle_root_marked_if: index bits are little endian, one
+// per level, and only the leaf level uses the marked leaf hash.
+pub fn calc_merkle_root_marked_if_var<F: PrimeField, Hasher: MerkleHasherGadget<F>>(
+    value: &Hash256Var<F>,
+    siblings: &[Hash256Var<F>],
+    index_bits: &[Boolean<F>],
+    marked: bool,
+) -> Result<Hash256Var<F>, SynthesisError> {
+    assert_eq!(
+        siblings.len(),
+        index_bits.len(),
+        "one index bit per merkle level"
+    );
+    let mut current = value.clone();
+    for (i, (sibling, bit)) in siblings.iter().zip(index_bits.iter()).enumerate() {
+        let left = Hash256Var::conditionally_select(bit, sibling, &current)?;
+        let right = Hash256Var::conditionally_select(bit, &current, sibling)?;
+        current = Hasher::two_to_one_marked_if(&left, &right, i == 0 && marked)?;
+    }
+    Ok(current)
+}
+
+// The height is taken from the number of siblings of the allocated proof, or
+// given to new_variable_with_height.
+#[derive(Clone, Debug)]
+pub struct MerkleProofVar<F: PrimeField> {
+    pub root: Hash256Var<F>,
+    pub value: Hash256Var<F>,
+    pub index_bits: Vec<Boolean<F>>,
+    pub siblings: Vec<Hash256Var<F>>,
+}
+
+impl<F: PrimeField> MerkleProofVar<F> {
+    pub fn index(&self) -> Result<FpVar<F>, SynthesisError> {
+        Boolean::le_bits_to_fp_var(&self.index_bits)
+    }
+    pub fn calc_root_marked_if<Hasher: MerkleHasherGadget<F>>(
+        &self,
+        marked: bool,
+    ) -> Result<Hash256Var<F>, SynthesisError> {
+        calc_merkle_root_marked_if_var::<F, Hasher>(
+            &self.value,
+            &self.siblings,
+            &self.index_bits,
+            marked,
+        )
+    }
+    pub fn verify_marked_if<Hasher: MerkleHasherGadget<F>>(
+        &self,
+        marked: bool,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        self.calc_root_marked_if::<Hasher>(marked)?
+            .is_eq(&self.root)
+    }
+    pub fn enforce_valid_marked_if<Hasher: MerkleHasherGadget<F>>(
+        &self,
+        marked: bool,
+    ) -> Result<(), SynthesisError> {
+        self.calc_root_marked_if::<Hasher>(marked)?
+            .enforce_equal(&self.root)
+    }
+}
+
+impl<F: PrimeField> MerkleProofVar<F> {
+    pub fn new_variable_with_height<T: Borrow<MerkleProofCore<Hash256>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        height: usize,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let cs = cs.into().cs();
+        let proof = f();
+        let proof = proof.as_ref().map(|p| p.borrow()).map_err(|e| *e);
+        if proof.is_ok_and(|p| p.siblings.len() != height) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let index_bits = (0..height)
+            .map(|i| {
+                Boolean::new_variable(
+                    ns!(cs, "index bit"),
+                    || proof.map(|p| p.index >> i & 1 == 1),
+                    mode,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = Hash256Var::new_variable(ns!(cs, "root"), || proof.map(|p| p.root), mode)?;
+        let value = Hash256Var::new_variable(ns!(cs, "value"), || proof.map(|p| p.value), mode)?;
+        let siblings = (0..height)
+            .map(|i| {
+                Hash256Var::new_variable(ns!(cs, "sibling"), || proof.map(|p| p.siblings[i]), mode)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            root,
+            value,
+            index_bits,
+            siblings,
+        })
+    }
+}
+
+impl<F: PrimeField> AllocVar<MerkleProofCore<Hash256>, F> for MerkleProofVar<F> {
+    fn new_variable<T: Borrow<MerkleProofCore<Hash256>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the height comes from the proof, see new_variable_with_height
+        let proof = f();
+        let height = proof
+            .as_ref()
+            .map(|p| p.borrow().siblings.len())
+            .map_err(|e| *e)?;
+        Self::new_variable_with_height(cs, || proof, height, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_relations::r1cs::SynthesisMode;
+    use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
+    use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+    use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+    use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+    use l2o_crypto::hash::merkle::core::calc_merkle_root_marked_if;
+    use l2o_crypto::hash::merkle::traits::GeneralMerkleZeroHasher;
+
+    use super::*;
+
+    fn check_merkle_proof_gadget<Hasher>()
+    where
+        Hasher: MerkleHasherGadget<Fr> + GeneralMerkleZeroHasher<Hash256>,
+    {
+        let siblings = (0..4u8).map(|i| Hash256([i + 1; 32])).collect::<Vec<_>>();
+        for marked in [false, true] {
+            let value = Hash256([9u8; 32]);
+            let index = 0b1010;
+            let proof = MerkleProofCore {
+                root: calc_merkle_root_marked_if::<Hash256, Hasher>(
+                    value, &siblings, index, marked,
+                ),
+                value,
+                index,
+                siblings: siblings.clone(),
+            };
+            assert!(proof.verify_marked_if::<Hasher>(marked));
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let proof_var = MerkleProofVar::new_witness(cs.clone(), || Ok(&proof)).unwrap();
+            assert_eq!(proof_var.index().unwrap().value().unwrap(), Fr::from(index));
+            assert!(proof_var
+                .verify_marked_if::<Hasher>(marked)
+                .unwrap()
+                .value()
+                .unwrap());
+            assert!(!proof_var
+                .verify_marked_if::<Hasher>(!marked)
+                .unwrap()
+                .value()
+                .unwrap());
+            proof_var.enforce_valid_marked_if::<Hasher>(marked).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+
+            // a proof for another index does not satisfy the circuit
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let wrong = MerkleProofCore {
+                index: index + 1,
+                ..proof.clone()
+            };
+            let wrong_var = MerkleProofVar::new_witness(cs.clone(), || Ok(&wrong)).unwrap();
+            wrong_var.enforce_valid_marked_if::<Hasher>(marked).unwrap();
+            assert!(!cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof_setup_without_a_proof() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        let proof_var = MerkleProofVar::new_variable_with_height(
+            cs.clone(),
+            || Err::<MerkleProofCore<Hash256>, _>(SynthesisError::AssignmentMissing),
+            4,
+            AllocationMode::Witness,
+        )
+        .unwrap();
+        assert_eq!(proof_var.siblings.len(), 4);
+        proof_var
+            .enforce_valid_marked_if::<Sha256Hasher>(false)
+            .unwrap();
+
+        let proof = MerkleProofCore {
+            root: Hash256([0u8; 32]),
+            value: Hash256([0u8; 32]),
+            index: 0,
+            siblings: vec![Hash256([0u8; 32]); 3],
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        assert!(MerkleProofVar::new_variable_with_height(
+            cs,
+            || Ok(&proof),
+            4,
+            AllocationMode::Witness
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_merkle_proof_gadget() {
+        check_merkle_proof_gadget::<Sha256Hasher>();
+        check_merkle_proof_gadget::<Blake3Hasher>();
+        check_merkle_proof_gadget::<Keccak256Hasher>();
+        check_merkle_proof_gadget::<PoseidonBN254Hasher>();
+    }
+}
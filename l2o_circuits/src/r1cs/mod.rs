@@ -0,0 +1,4 @@
+pub mod block;
+pub mod brc21;
+pub mod hash;
+pub mod merkle;
@@ -41,7 +41,20 @@ pub async fn run(
     StdRng,
     SigningKey,
     Arc<Client>,
-    Arc<Provider>,
+)> {
+    let rpc = Arc::new(Provider::new(args.indexer_url.clone())?);
+    run_with_provider(args, rpc).await
+}
+
+pub async fn run_with_provider(
+    args: &InitializerArgs,
+    rpc: Arc<dyn L2OAProvider + Send + Sync>,
+) -> anyhow::Result<(
+    ProvingKey<Bn254>,
+    VerifyingKey<Bn254>,
+    StdRng,
+    SigningKey,
+    Arc<Client>,
 )> {
     let deploy_json = include_str!("../../../static/deploy.json");
     let block_json = include_str!("../../../static/block.json");
@@ -71,7 +84,6 @@ pub async fn run(
     block.bitcoin_block_number = bitcoin_block_number;
     block.bitcoin_block_hash = Hash256::from_hex(&bitcoin_block_hash.to_string())?;
 
-    let rpc = Arc::new(Provider::new(args.indexer_url.clone()));
     let superchain_root = rpc
         .get_superchainroot_at_block(bitcoin_block_number, L2OAHashFunction::Sha256)
         .await?;
@@ -147,5 +159,5 @@ pub async fn run(
         .status()
         .is_ok());
 
-    Ok((pk, vk, rng, signing_key, bitcoin_rpc, rpc))
+    Ok((pk, vk, rng, signing_key, bitcoin_rpc))
 }
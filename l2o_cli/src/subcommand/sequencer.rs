@@ -23,8 +23,10 @@ use l2o_ord::hasher::get_block_payload_bytes;
 use l2o_ord::hasher::L2OBlockHasher;
 use l2o_ord::operation::l2o_a::L2OABlockV1;
 use l2o_ord::operation::l2o_a::L2OAHashFunction;
+use l2o_rpc_provider::quorum::QuorumProvider;
 use l2o_rpc_provider::L2OAProvider;
 use l2o_rpc_provider::Provider;
+use l2o_rpc_provider::ProviderConfig;
 use serde_json::json;
 
 use crate::circuits::BlockCircuit;
@@ -37,7 +39,7 @@ async fn execute_single(
     rng: &mut StdRng,
     signing_key: &SigningKey,
     bitcoincore_rpc: Arc<bitcoincore_rpc::Client>,
-    rpc: Arc<Provider>,
+    rpc: Arc<dyn L2OAProvider + Send + Sync>,
 ) -> anyhow::Result<()> {
     let prev_block = rpc.get_last_block_inscription(args.l2id).await?;
     let bitcoin_block_number = prev_block.bitcoin_block_number + 1;
@@ -100,14 +102,42 @@ async fn execute_single(
     Ok::<_, anyhow::Error>(())
 }
 
+fn indexer_provider(args: &SequencerArgs) -> anyhow::Result<Arc<dyn L2OAProvider + Send + Sync>> {
+    let config = ProviderConfig {
+        timeout: Duration::from_secs(args.indexer_timeout_secs),
+        retries: args.indexer_retries,
+        ..Default::default()
+    };
+    let mut urls = vec![args.indexer_url.to_string()];
+    urls.extend(args.fallback_indexer_urls.iter().cloned());
+    let provider = Provider::with_config(urls, config.clone())?;
+    if args.quorum_indexer_urls.is_empty() {
+        return Ok(Arc::new(provider));
+    }
+
+    let mut providers = vec![provider];
+    for url in args.quorum_indexer_urls.iter() {
+        providers.push(Provider::with_config(
+            vec![url.to_string()],
+            config.clone(),
+        )?);
+    }
+    let quorum = args.indexer_quorum.unwrap_or(providers.len() / 2 + 1);
+    Ok(Arc::new(QuorumProvider::new(providers, quorum)?))
+}
+
 pub async fn run(args: &SequencerArgs) -> anyhow::Result<()> {
-    let (pk, vk, mut rng, signing_key, bitcoincore_rpc, rpc) = initializer::run(&InitializerArgs {
-        indexer_url: args.indexer_url.to_string(),
-        bitcoin_rpc: args.bitcoin_rpc.to_string(),
-        bitcoin_rpcuser: args.bitcoin_rpcuser.to_string(),
-        bitcoin_rpcpassword: args.bitcoin_rpcpassword.to_string(),
-        l2id: args.l2id,
-    })
+    let rpc = indexer_provider(args)?;
+    let (pk, vk, mut rng, signing_key, bitcoincore_rpc) = initializer::run_with_provider(
+        &InitializerArgs {
+            indexer_url: args.indexer_url.to_string(),
+            bitcoin_rpc: args.bitcoin_rpc.to_string(),
+            bitcoin_rpcuser: args.bitcoin_rpcuser.to_string(),
+            bitcoin_rpcpassword: args.bitcoin_rpcpassword.to_string(),
+            l2id: args.l2id,
+        },
+        rpc.clone(),
+    )
     .await?;
 
     loop {
@@ -14,6 +14,15 @@ pub struct IndexerArgs {
     pub bitcoin_rpcpassword: String,
     #[clap(short, env, long, default_value = "db", env)]
     pub db_path: String,
+    // store inscription bodies and serve them on /content/{inscription_id}
+    #[clap(env, long, env)]
+    pub index_content: bool,
+    // a JSON file of the webhooks to POST the receipts they match to
+    #[clap(env, long, env)]
+    pub webhooks: Option<String>,
+    // the outbox of the webhooks, kept apart from db_path
+    #[clap(env, long, default_value = "webhooks", env)]
+    pub webhooks_db_path: String,
 }
 
 #[derive(Clone, Args)]
@@ -21,6 +30,20 @@ pub struct IndexerArgs {
 pub struct SequencerArgs {
     #[clap(short, env, long, default_value = "http://localhost:3000", env)]
     pub indexer_url: String,
+    // tried in order when indexer_url fails
+    #[clap(env, long, value_delimiter = ',', env)]
+    pub fallback_indexer_urls: Vec<String>,
+    // independent indexers that must agree with indexer_url on the last block
+    // and the superchain root before a block is built
+    #[clap(env, long, value_delimiter = ',', env)]
+    pub quorum_indexer_urls: Vec<String>,
+    // how many of the indexers have to answer, a majority of them by default
+    #[clap(env, long, env)]
+    pub indexer_quorum: Option<usize>,
+    #[clap(env, long, default_value = "10", env)]
+    pub indexer_timeout_secs: u64,
+    #[clap(env, long, default_value = "3", env)]
+    pub indexer_retries: u32,
     #[clap(env, long, default_value = "http://localhost:18443", env)]
     pub bitcoin_rpc: String,
     #[clap(env, long, default_value = "devnet", env)]
@@ -2,6 +2,9 @@ use std::fmt::Display;
 
 use ark_bn254::Fr;
 use ark_ff::PrimeField;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use kvq::traits::KVQSerializable;
 use num_traits::Zero;
 use rand::RngCore;
@@ -114,3 +117,14 @@ impl KVQSerializable for Hash256 {
         Ok(Hash256(result))
     }
 }
+
+impl KVQCompactCodec for Hash256 {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_bytes(&self.0);
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(Hash256(reader.read_array()?))
+    }
+}
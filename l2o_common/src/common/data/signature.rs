@@ -1,9 +1,12 @@
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 use serde::Serializer;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct L2OCompactPublicKey(pub [u8; 32]);
 
 impl L2OCompactPublicKey {
@@ -58,7 +61,47 @@ impl<'de> Deserialize<'de> for L2OCompactPublicKey {
     }
 }
 
+// A SEC1 compressed secp256k1 key, as MuSig2 key aggregation takes them.
 #[derive(Debug, Clone, PartialEq, Hash)]
+pub struct L2OCompressedPublicKey(pub [u8; 33]);
+
+impl L2OCompressedPublicKey {
+    pub fn from_hex(s: &str) -> crate::Result<Self> {
+        let mut array = [0u8; 33];
+        hex::decode_to_slice(s, &mut array)?;
+        Ok(Self(array))
+    }
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl Serialize for L2OCompressedPublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for L2OCompressedPublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        String::deserialize(deserializer).and_then(|string| {
+            let mut bytes = [0u8; 33];
+            if hex::decode_to_slice(&string, &mut bytes).is_err() {
+                return Err(Error::custom("Invalid public key"));
+            }
+            Ok(L2OCompressedPublicKey(bytes))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct L2OSignature512(pub [u8; 64]);
 
 impl L2OSignature512 {
@@ -112,3 +155,53 @@ impl<'de> Deserialize<'de> for L2OSignature512 {
         })
     }
 }
+
+impl KVQCompactCodec for L2OCompactPublicKey {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_bytes(&self.0);
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(L2OCompactPublicKey(reader.read_array()?))
+    }
+}
+
+impl KVQCompactCodec for L2OSignature512 {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_bytes(&self.0);
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(L2OSignature512(reader.read_array()?))
+    }
+}
+
+impl KVQCompactCodec for L2OCompressedPublicKey {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_bytes(&self.0);
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(L2OCompressedPublicKey(reader.read_array()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressed_public_key_from_hex() {
+        let hex = format!("02{}", "11".repeat(32));
+        assert_eq!(
+            L2OCompressedPublicKey::from_hex(&hex).unwrap().to_hex(),
+            hex
+        );
+        assert!(L2OCompressedPublicKey::from_hex(&"11".repeat(32)).is_err());
+        assert!(L2OCompressedPublicKey::from_hex(&"11".repeat(34)).is_err());
+        assert!(L2OCompressedPublicKey::from_hex("zz").is_err());
+    }
+}
@@ -2,6 +2,7 @@ pub mod cli;
 pub mod common;
 pub mod error;
 pub mod logger;
+pub mod metrics;
 
 use std::str::FromStr;
 
@@ -0,0 +1,127 @@
+use once_cell::sync::Lazy;
+use prometheus::exponential_buckets;
+use prometheus::register_histogram;
+use prometheus::register_histogram_vec;
+use prometheus::register_int_counter;
+use prometheus::register_int_counter_vec;
+use prometheus::register_int_gauge;
+use prometheus::Encoder;
+use prometheus::Histogram;
+use prometheus::HistogramVec;
+use prometheus::IntCounter;
+use prometheus::IntCounterVec;
+use prometheus::IntGauge;
+use prometheus::TextEncoder;
+
+pub static INDEXED_HEIGHT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("l2o_indexed_height", "Height of the last indexed block").unwrap()
+});
+
+pub static CHAIN_TIP: Lazy<IntGauge> =
+    Lazy::new(|| register_int_gauge!("l2o_chain_tip", "Block count of the bitcoin node").unwrap());
+
+// stage is fetch, index_envelopes or execution
+pub static BLOCK_INDEX_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "l2o_block_index_seconds",
+        "Time spent on a block by indexing stage",
+        &["stage"],
+        exponential_buckets(0.005, 2.0, 16).unwrap()
+    )
+    .unwrap()
+});
+
+pub static FETCHER_BATCH_SIZE: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "l2o_fetcher_batch_size",
+        "Outpoints fetched from the bitcoin node in a batch",
+        exponential_buckets(1.0, 2.0, 12).unwrap()
+    )
+    .unwrap()
+});
+
+pub static FETCHER_RETRIES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "l2o_fetcher_retries_total",
+        "Requests to the bitcoin node that were retried"
+    )
+    .unwrap()
+});
+
+pub static REORGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!("l2o_reorgs_total", "Recovered reorgs by depth", &["depth"]).unwrap()
+});
+
+// outcome is ok or error
+pub static RECEIPTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "l2o_receipts_total",
+        "Receipts of executed operations",
+        &["op", "outcome"]
+    )
+    .unwrap()
+});
+
+pub static L2OA_PROOF_VERIFICATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "l2o_l2oa_proof_verification_seconds",
+        "Time spent verifying the proof of an L2O-A block",
+        exponential_buckets(0.001, 2.0, 14).unwrap()
+    )
+    .unwrap()
+});
+
+// method is unknown for requests that do not parse, outcome is ok or error
+pub static RPC_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "l2o_rpc_requests_total",
+        "RPC requests by method",
+        &["method", "outcome"]
+    )
+    .unwrap()
+});
+
+pub static RPC_REQUEST_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "l2o_rpc_request_seconds",
+        "Time spent answering RPC requests by method",
+        &["method"],
+        exponential_buckets(0.0005, 2.0, 16).unwrap()
+    )
+    .unwrap()
+});
+
+pub static DB_SIZE_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("l2o_db_size_bytes", "Size of the index database file").unwrap()
+});
+
+// every metric in the text format, those without labels even before they are
+// set
+pub fn encode() -> anyhow::Result<Vec<u8>> {
+    Lazy::force(&INDEXED_HEIGHT);
+    Lazy::force(&CHAIN_TIP);
+    Lazy::force(&FETCHER_BATCH_SIZE);
+    Lazy::force(&FETCHER_RETRIES);
+    Lazy::force(&L2OA_PROOF_VERIFICATION_SECONDS);
+    Lazy::force(&DB_SIZE_BYTES);
+
+    let mut buffer = vec![];
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        INDEXED_HEIGHT.set(840_000);
+        RECEIPTS.with_label_values(&["BRC20Mint", "ok"]).inc();
+        let metrics = String::from_utf8(encode().unwrap()).unwrap();
+        assert!(metrics.contains("l2o_indexed_height 840000"));
+        assert!(metrics.contains(r#"l2o_receipts_total{op="BRC20Mint",outcome="ok"} 1"#));
+        assert!(metrics.contains("l2o_chain_tip 0"));
+        assert!(metrics.contains("l2o_fetcher_retries_total 0"));
+    }
+}
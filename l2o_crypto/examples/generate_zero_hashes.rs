@@ -1,6 +1,7 @@
 use l2o_common::common::data::hash::Hash256;
 use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
 use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
 use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
 use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
 use l2o_crypto::hash::merkle::traits::MerkleHasher;
@@ -97,6 +98,11 @@ impl NamedMerkleHasher for Keccak256Hasher {
         "Keccak256Hasher"
     }
 }
+impl NamedMerkleHasher for PoseidonBN254Hasher {
+    fn get_zh_hasher_name() -> &'static str {
+        "PoseidonBN254Hasher"
+    }
+}
 impl NamedMerkleHasher for PoseidonHasher {
     fn get_zh_hasher_name() -> &'static str {
         "PoseidonHasher"
@@ -177,6 +183,13 @@ pub fn print_hash256_zero_hashes() {
     result.push_str(
         &get_zero_hashes_for_leaf_hash_str::<Hash256, Keccak256Hasher>(ZERO_HASH_CACHE_SIZE),
     );
+    result.push_str(
+        &get_zero_hashes_for_hash_str::<Hash256, PoseidonBN254Hasher>(ZERO_HASH_CACHE_SIZE),
+    );
+    result.push_str(&get_zero_hashes_for_leaf_hash_str::<
+        Hash256,
+        PoseidonBN254Hasher,
+    >(ZERO_HASH_CACHE_SIZE));
     tracing::info!("{}", result);
 }
 
@@ -0,0 +1,59 @@
+use ark_bn254::Fr;
+use ark_ff::BigInteger;
+use ark_ff::PrimeField;
+use l2o_common::common::data::hash::Hash256;
+
+// BN254 hashes are stored as Hash256 holding the big-endian encoding of a
+// scalar field element, so they share the stores and proof types of the byte
+// oriented hashes. Values above the modulus are reduced.
+pub fn hash256_to_bn254(hash: &Hash256) -> Fr {
+    Fr::from_be_bytes_mod_order(&hash.0)
+}
+
+pub fn bn254_to_hash256(value: &Fr) -> Hash256 {
+    let bytes = value.into_bigint().to_bytes_be();
+    let mut result = [0u8; 32];
+    result[32 - bytes.len()..].copy_from_slice(&bytes);
+    Hash256(result)
+}
+
+// canonical form of a hash used as a BN254 leaf
+pub fn hash256_to_bn254_hash(hash: &Hash256) -> Hash256 {
+    bn254_to_hash256(&hash256_to_bn254(hash))
+}
+
+// Byte strings are length prefixed and packed into 31 byte little endian
+// limbs, which always fit below the modulus.
+pub fn bytes_to_bn254(bytes: &[u8]) -> Vec<Fr> {
+    let mut result = Vec::with_capacity(1 + bytes.len().div_ceil(31));
+    result.push(Fr::from(bytes.len() as u64));
+    for chunk in bytes.chunks(31) {
+        result.push(Fr::from_le_bytes_mod_order(chunk));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::Field;
+
+    use super::*;
+
+    #[test]
+    fn test_bn254_hash256_roundtrip() {
+        let value = -Fr::ONE;
+        let hash = bn254_to_hash256(&value);
+        assert_eq!(hash256_to_bn254(&hash), value);
+        assert_eq!(hash256_to_bn254_hash(&hash), hash);
+
+        // 2^256 - 1 is above the modulus and gets reduced
+        let reduced = hash256_to_bn254_hash(&Hash256([0xff; 32]));
+        assert_ne!(reduced, Hash256([0xff; 32]));
+        assert_eq!(hash256_to_bn254_hash(&reduced), reduced);
+
+        let packed = bytes_to_bn254(&[7u8; 32]);
+        assert_eq!(packed.len(), 3);
+        assert_eq!(packed[0], Fr::from(32u64));
+        assert_eq!(packed[2], Fr::from(7u64));
+    }
+}
@@ -1 +1,2 @@
+pub mod bn254;
 pub mod goldilocks;
@@ -1,5 +1,6 @@
 pub mod blake3;
 pub mod keccak256;
+pub mod poseidon_bn254;
 pub mod poseidon_goldilocks;
 pub mod sha256;
 pub mod zero_hashes;
@@ -0,0 +1,105 @@
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::poseidon::find_poseidon_ark_and_mds;
+use ark_crypto_primitives::sponge::poseidon::PoseidonConfig;
+use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::Field;
+use ark_ff::PrimeField;
+use l2o_common::common::data::hash::Hash256;
+use once_cell::sync::Lazy;
+
+use crate::fields::bn254::hash::bn254_to_hash256;
+use crate::fields::bn254::hash::hash256_to_bn254;
+use crate::hash::merkle::traits::MerkleHasher;
+use crate::hash::merkle::traits::MerkleHasherWithMarkedLeaf;
+
+// Width 3 Poseidon (rate 2, capacity 1) with the x^5 s-box, 8 full and 57
+// partial rounds. The round constants and MDS matrix come from the Grain LFSR,
+// so circuits get the same permutation by building a PoseidonSpongeVar from
+// POSEIDON_BN254_CONFIG.
+pub const POSEIDON_BN254_RATE: usize = 2;
+pub const POSEIDON_BN254_CAPACITY: usize = 1;
+pub const POSEIDON_BN254_ALPHA: u64 = 5;
+pub const POSEIDON_BN254_FULL_ROUNDS: usize = 8;
+pub const POSEIDON_BN254_PARTIAL_ROUNDS: usize = 57;
+
+pub static POSEIDON_BN254_CONFIG: Lazy<PoseidonConfig<Fr>> = Lazy::new(|| {
+    let (ark, mds) = find_poseidon_ark_and_mds::<Fr>(
+        Fr::MODULUS_BIT_SIZE as u64,
+        POSEIDON_BN254_RATE,
+        POSEIDON_BN254_FULL_ROUNDS as u64,
+        POSEIDON_BN254_PARTIAL_ROUNDS as u64,
+        0,
+    );
+    PoseidonConfig::new(
+        POSEIDON_BN254_FULL_ROUNDS,
+        POSEIDON_BN254_PARTIAL_ROUNDS,
+        POSEIDON_BN254_ALPHA,
+        mds,
+        ark,
+        POSEIDON_BN254_RATE,
+        POSEIDON_BN254_CAPACITY,
+    )
+});
+
+pub fn hash_no_pad(inputs: &[Fr]) -> Fr {
+    let mut sponge = PoseidonSponge::new(&POSEIDON_BN254_CONFIG);
+    sponge.absorb(&inputs);
+    sponge.squeeze_field_elements::<Fr>(1)[0]
+}
+
+pub fn two_to_one(left: &Fr, right: &Fr) -> Fr {
+    hash_no_pad(&[*left, *right])
+}
+
+pub struct PoseidonBN254Hasher;
+
+impl MerkleHasher<Hash256> for PoseidonBN254Hasher {
+    fn two_to_one(left: &Hash256, right: &Hash256) -> Hash256 {
+        bn254_to_hash256(&two_to_one(
+            &hash256_to_bn254(left),
+            &hash256_to_bn254(right),
+        ))
+    }
+}
+
+impl MerkleHasherWithMarkedLeaf<Hash256> for PoseidonBN254Hasher {
+    fn two_to_one_marked_leaf(left: &Hash256, right: &Hash256) -> Hash256 {
+        bn254_to_hash256(&hash_no_pad(&[
+            hash256_to_bn254(left),
+            hash256_to_bn254(right),
+            Fr::ONE,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::merkle::traits::MerkleZeroHasher;
+    use crate::hash::merkle::traits::MerkleZeroHasherWithMarkedLeaf;
+    use crate::hash::merkle::traits::ZERO_HASH_CACHE_SIZE;
+
+    #[test]
+    fn test_poseidon_bn254_zero_hashes() {
+        let zero = Hash256([0u8; 32]);
+        assert_eq!(
+            PoseidonBN254Hasher::get_zero_hash_marked(1),
+            PoseidonBN254Hasher::two_to_one_marked_leaf(&zero, &zero)
+        );
+        assert_ne!(
+            PoseidonBN254Hasher::get_zero_hash_marked(1),
+            PoseidonBN254Hasher::get_zero_hash(1)
+        );
+        for level in 1..ZERO_HASH_CACHE_SIZE + 2 {
+            let below = PoseidonBN254Hasher::get_zero_hash(level - 1);
+            assert_eq!(
+                PoseidonBN254Hasher::get_zero_hash(level),
+                PoseidonBN254Hasher::two_to_one(&below, &below)
+            );
+        }
+        // outputs are canonical field elements
+        let hash = PoseidonBN254Hasher::get_zero_hash(20);
+        assert_eq!(bn254_to_hash256(&hash256_to_bn254(&hash)), hash);
+    }
+}
@@ -4,6 +4,7 @@ use plonky2::hash::hash_types::HashOut;
 
 use super::blake3::Blake3Hasher;
 use super::keccak256::Keccak256Hasher;
+use super::poseidon_bn254::PoseidonBN254Hasher;
 use super::poseidon_goldilocks::PoseidonHasher;
 use super::sha256::Sha256Hasher;
 use crate::hash::merkle::traits::MerkleZeroHasherWithCache;
@@ -3111,6 +3112,1040 @@ impl MerkleZeroHasherWithCacheMarkedLeaf<Hash256> for Keccak256Hasher {
     ];
 }
 
+impl MerkleZeroHasherWithCache<Hash256> for PoseidonBN254Hasher {
+    const CACHED_ZERO_HASHES: [Hash256; 128] = [
+        Hash256([
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ]),
+        Hash256([
+            19, 165, 69, 161, 63, 29, 145, 221, 219, 135, 244, 102, 121, 223, 174, 192, 144, 12,
+            226, 71, 145, 169, 36, 190, 231, 250, 77, 105, 169, 86, 157, 133,
+        ]),
+        Hash256([
+            23, 162, 154, 238, 105, 147, 155, 174, 150, 33, 133, 189, 91, 91, 195, 24, 178, 18, 82,
+            118, 125, 87, 99, 129, 24, 164, 163, 125, 72, 205, 159, 94,
+        ]),
+        Hash256([
+            35, 84, 86, 189, 221, 222, 237, 65, 192, 174, 205, 62, 196, 197, 3, 7, 70, 224, 100,
+            26, 53, 147, 195, 7, 250, 81, 165, 43, 28, 73, 242, 75,
+        ]),
+        Hash256([
+            9, 98, 90, 12, 63, 255, 25, 183, 128, 119, 225, 55, 187, 137, 128, 170, 122, 89, 206,
+            54, 177, 51, 250, 16, 222, 102, 40, 243, 1, 204, 14, 226,
+        ]),
+        Hash256([
+            25, 23, 10, 255, 104, 138, 183, 178, 18, 134, 43, 37, 48, 4, 152, 77, 131, 38, 204,
+            129, 151, 87, 122, 83, 27, 154, 208, 207, 246, 145, 159, 126,
+        ]),
+        Hash256([
+            29, 251, 63, 161, 216, 44, 68, 110, 227, 226, 82, 171, 245, 135, 28, 115, 228, 198,
+            199, 64, 96, 84, 27, 203, 184, 121, 242, 6, 120, 66, 117, 209,
+        ]),
+        Hash256([
+            7, 19, 152, 214, 246, 67, 165, 235, 229, 45, 80, 148, 52, 196, 131, 4, 39, 65, 170, 45,
+            64, 239, 22, 204, 164, 215, 242, 225, 246, 217, 95, 76,
+        ]),
+        Hash256([
+            24, 185, 150, 143, 218, 148, 92, 125, 211, 20, 93, 46, 73, 177, 6, 18, 84, 137, 226,
+            236, 199, 225, 210, 222, 252, 153, 173, 79, 244, 135, 136, 82,
+        ]),
+        Hash256([
+            17, 39, 133, 35, 191, 186, 144, 57, 11, 23, 112, 26, 50, 208, 70, 62, 68, 13, 241, 120,
+            167, 105, 235, 238, 158, 64, 39, 227, 97, 183, 235, 248,
+        ]),
+        Hash256([
+            26, 43, 129, 103, 218, 48, 176, 94, 2, 247, 139, 87, 132, 108, 215, 125, 248, 168, 192,
+            227, 228, 20, 119, 204, 27, 175, 248, 71, 155, 11, 24, 94,
+        ]),
+        Hash256([
+            26, 220, 199, 89, 26, 91, 232, 67, 221, 58, 139, 159, 124, 112, 138, 49, 151, 113, 33,
+            215, 201, 177, 10, 86, 90, 195, 110, 77, 110, 165, 240, 156,
+        ]),
+        Hash256([
+            43, 42, 142, 141, 29, 101, 74, 42, 204, 176, 12, 94, 44, 159, 30, 48, 84, 111, 43, 147,
+            160, 59, 27, 159, 157, 60, 112, 72, 239, 16, 143, 153,
+        ]),
+        Hash256([
+            1, 80, 224, 129, 1, 40, 205, 121, 166, 107, 28, 239, 251, 13, 251, 168, 28, 237, 225,
+            185, 195, 171, 200, 2, 86, 252, 3, 86, 225, 245, 217, 43,
+        ]),
+        Hash256([
+            46, 22, 26, 95, 247, 185, 218, 199, 63, 220, 30, 95, 64, 193, 226, 148, 18, 244, 159,
+            138, 60, 157, 84, 235, 169, 208, 237, 153, 238, 120, 159, 69,
+        ]),
+        Hash256([
+            35, 225, 91, 131, 107, 196, 151, 114, 235, 91, 32, 162, 82, 168, 240, 181, 134, 42, 9,
+            166, 16, 147, 129, 251, 35, 82, 185, 119, 233, 32, 216, 184,
+        ]),
+        Hash256([
+            46, 89, 226, 179, 213, 146, 11, 112, 187, 7, 22, 194, 114, 217, 148, 123, 204, 251,
+            102, 20, 141, 54, 233, 183, 88, 238, 201, 112, 101, 143, 173, 250,
+        ]),
+        Hash256([
+            29, 194, 149, 221, 178, 133, 170, 11, 97, 189, 66, 67, 143, 190, 152, 194, 113, 55, 28,
+            158, 142, 16, 245, 229, 211, 104, 191, 15, 170, 10, 14, 85,
+        ]),
+        Hash256([
+            47, 211, 114, 167, 86, 185, 109, 58, 1, 166, 228, 133, 31, 113, 70, 25, 75, 241, 51,
+            235, 64, 0, 59, 98, 63, 184, 119, 43, 126, 21, 201, 251,
+        ]),
+        Hash256([
+            28, 17, 141, 70, 196, 96, 218, 245, 153, 247, 233, 186, 169, 152, 120, 74, 75, 186,
+            246, 140, 102, 12, 239, 196, 133, 242, 45, 105, 145, 253, 31, 168,
+        ]),
+        Hash256([
+            14, 166, 20, 235, 138, 128, 82, 213, 51, 185, 163, 81, 214, 97, 186, 6, 247, 123, 204,
+            225, 59, 143, 237, 191, 199, 239, 4, 168, 250, 43, 63, 111,
+        ]),
+        Hash256([
+            4, 51, 27, 154, 84, 131, 82, 107, 119, 236, 168, 214, 182, 255, 249, 160, 40, 225, 254,
+            45, 226, 11, 147, 126, 240, 238, 105, 249, 9, 216, 215, 250,
+        ]),
+        Hash256([
+            7, 248, 44, 134, 134, 199, 195, 222, 204, 12, 201, 91, 107, 213, 131, 173, 211, 172,
+            43, 226, 245, 132, 75, 165, 208, 136, 108, 140, 46, 244, 79, 134,
+        ]),
+        Hash256([
+            6, 202, 115, 217, 10, 34, 207, 18, 72, 254, 10, 116, 240, 167, 6, 151, 151, 162, 174,
+            120, 64, 165, 68, 97, 30, 14, 177, 131, 151, 44, 230, 89,
+        ]),
+        Hash256([
+            4, 118, 47, 137, 247, 12, 147, 20, 58, 196, 154, 41, 248, 38, 111, 84, 189, 143, 2,
+            114, 28, 238, 107, 20, 141, 97, 246, 33, 175, 114, 186, 5,
+        ]),
+        Hash256([
+            42, 151, 62, 219, 149, 99, 227, 20, 188, 239, 25, 228, 207, 203, 149, 26, 176, 218, 18,
+            105, 16, 239, 53, 28, 130, 169, 218, 251, 61, 113, 138, 238,
+        ]),
+        Hash256([
+            28, 109, 66, 158, 11, 170, 29, 54, 154, 251, 154, 54, 175, 9, 161, 172, 160, 239, 213,
+            246, 216, 216, 147, 160, 224, 19, 212, 94, 164, 220, 130, 194,
+        ]),
+        Hash256([
+            4, 101, 92, 239, 244, 226, 215, 213, 43, 182, 126, 192, 66, 45, 114, 151, 217, 195,
+            161, 81, 154, 230, 194, 110, 9, 255, 150, 197, 103, 4, 193, 176,
+        ]),
+        Hash256([
+            21, 77, 94, 164, 171, 170, 201, 194, 163, 231, 90, 238, 165, 177, 176, 51, 129, 54,
+            171, 97, 179, 120, 50, 207, 140, 3, 82, 229, 193, 237, 10, 202,
+        ]),
+        Hash256([
+            35, 97, 5, 168, 119, 144, 123, 169, 221, 2, 36, 152, 68, 146, 52, 39, 162, 84, 159, 64,
+            160, 29, 145, 23, 222, 252, 121, 97, 241, 234, 51, 96,
+        ]),
+        Hash256([
+            23, 214, 58, 237, 63, 233, 208, 19, 97, 92, 41, 44, 253, 79, 106, 67, 94, 154, 43, 48,
+            36, 71, 200, 201, 196, 18, 59, 125, 53, 241, 71, 10,
+        ]),
+        Hash256([
+            21, 14, 170, 119, 250, 216, 209, 23, 9, 30, 246, 20, 187, 234, 105, 114, 187, 13, 88,
+            28, 169, 174, 188, 138, 237, 199, 241, 209, 156, 140, 150, 166,
+        ]),
+        Hash256([
+            38, 227, 92, 65, 210, 95, 58, 79, 169, 208, 231, 118, 218, 160, 112, 205, 16, 188, 220,
+            227, 133, 108, 161, 255, 140, 129, 120, 188, 128, 50, 147, 126,
+        ]),
+        Hash256([
+            7, 154, 159, 234, 106, 150, 2, 142, 221, 191, 68, 159, 76, 135, 59, 229, 206, 91, 251,
+            245, 171, 151, 101, 227, 163, 152, 226, 39, 255, 40, 170, 6,
+        ]),
+        Hash256([
+            35, 78, 89, 139, 157, 183, 78, 53, 174, 191, 82, 112, 246, 160, 155, 87, 88, 254, 90,
+            235, 135, 28, 54, 42, 35, 144, 228, 208, 106, 250, 147, 158,
+        ]),
+        Hash256([
+            26, 199, 55, 206, 227, 62, 50, 251, 217, 182, 237, 9, 178, 134, 56, 172, 161, 95, 241,
+            11, 69, 172, 6, 58, 123, 137, 26, 131, 26, 14, 110, 70,
+        ]),
+        Hash256([
+            36, 84, 234, 37, 211, 194, 182, 188, 51, 2, 240, 53, 67, 39, 250, 43, 37, 84, 172, 205,
+            7, 248, 123, 94, 246, 134, 41, 211, 178, 13, 22, 49,
+        ]),
+        Hash256([
+            1, 210, 17, 21, 135, 54, 39, 7, 207, 50, 55, 222, 116, 2, 81, 209, 165, 158, 4, 0, 88,
+            148, 249, 132, 65, 236, 125, 174, 17, 231, 159, 108,
+        ]),
+        Hash256([
+            18, 112, 176, 131, 24, 156, 53, 163, 116, 168, 35, 3, 240, 40, 36, 200, 212, 186, 205,
+            55, 17, 92, 114, 46, 64, 122, 150, 142, 12, 228, 180, 26,
+        ]),
+        Hash256([
+            15, 213, 47, 152, 128, 201, 159, 176, 215, 146, 140, 122, 126, 201, 59, 185, 148, 1,
+            112, 167, 93, 247, 205, 14, 170, 154, 119, 135, 68, 173, 9, 169,
+        ]),
+        Hash256([
+            16, 223, 29, 137, 74, 29, 95, 151, 133, 142, 16, 254, 236, 111, 87, 57, 255, 55, 89,
+            230, 232, 224, 70, 185, 159, 12, 156, 58, 48, 32, 218, 208,
+        ]),
+        Hash256([
+            39, 204, 152, 162, 77, 162, 235, 60, 81, 8, 210, 182, 64, 152, 68, 13, 227, 199, 221,
+            253, 181, 249, 203, 135, 133, 234, 200, 3, 109, 200, 132, 120,
+        ]),
+        Hash256([
+            26, 67, 42, 203, 188, 16, 150, 103, 142, 11, 193, 47, 121, 167, 154, 229, 228, 150,
+            111, 33, 204, 26, 43, 36, 98, 39, 18, 61, 192, 146, 207, 67,
+        ]),
+        Hash256([
+            38, 185, 167, 219, 156, 236, 43, 163, 228, 89, 3, 131, 255, 133, 69, 248, 118, 230,
+            179, 3, 17, 143, 117, 34, 166, 19, 133, 163, 133, 255, 222, 78,
+        ]),
+        Hash256([
+            19, 249, 162, 20, 75, 185, 125, 224, 95, 223, 112, 114, 136, 20, 32, 20, 86, 175, 55,
+            18, 69, 136, 252, 232, 208, 219, 202, 193, 5, 48, 114, 208,
+        ]),
+        Hash256([
+            3, 167, 82, 190, 78, 87, 222, 208, 142, 47, 122, 48, 96, 78, 72, 133, 108, 187, 207,
+            242, 140, 99, 239, 241, 148, 2, 184, 52, 91, 127, 226, 35,
+        ]),
+        Hash256([
+            10, 80, 88, 0, 92, 35, 221, 146, 254, 23, 108, 134, 76, 67, 133, 140, 231, 60, 212, 63,
+            202, 69, 213, 8, 170, 161, 50, 221, 5, 206, 160, 165,
+        ]),
+        Hash256([
+            25, 82, 128, 94, 88, 253, 66, 106, 238, 203, 196, 124, 186, 126, 52, 96, 184, 201, 153,
+            115, 150, 116, 60, 205, 33, 104, 213, 64, 70, 175, 168, 244,
+        ]),
+        Hash256([
+            20, 124, 30, 155, 64, 169, 178, 113, 27, 68, 247, 100, 47, 40, 208, 101, 21, 24, 60,
+            30, 253, 133, 219, 37, 82, 184, 240, 213, 133, 233, 140, 232,
+        ]),
+        Hash256([
+            19, 228, 169, 203, 250, 129, 177, 169, 84, 28, 247, 168, 233, 60, 17, 65, 205, 196,
+            181, 137, 164, 19, 252, 56, 207, 88, 81, 192, 181, 150, 148, 240,
+        ]),
+        Hash256([
+            16, 126, 152, 45, 28, 143, 227, 142, 83, 119, 215, 205, 170, 157, 41, 235, 190, 209,
+            166, 201, 34, 187, 76, 33, 181, 43, 163, 236, 78, 227, 47, 19,
+        ]),
+        Hash256([
+            5, 235, 187, 89, 12, 26, 154, 209, 247, 220, 51, 185, 38, 166, 188, 106, 65, 75, 45,
+            239, 245, 22, 74, 253, 94, 166, 75, 149, 3, 23, 14, 175,
+        ]),
+        Hash256([
+            23, 247, 180, 168, 118, 20, 106, 184, 118, 249, 106, 154, 91, 242, 102, 164, 66, 18,
+            56, 1, 42, 133, 199, 114, 40, 170, 13, 223, 58, 131, 245, 49,
+        ]),
+        Hash256([
+            47, 162, 86, 205, 220, 194, 75, 201, 199, 237, 49, 206, 205, 164, 86, 34, 191, 155, 54,
+            232, 8, 157, 66, 179, 173, 81, 36, 149, 51, 190, 113, 232,
+        ]),
+        Hash256([
+            28, 217, 117, 221, 82, 112, 200, 83, 192, 163, 118, 131, 53, 40, 62, 225, 121, 143,
+            109, 145, 226, 229, 85, 176, 252, 125, 25, 119, 120, 156, 209, 28,
+        ]),
+        Hash256([
+            13, 163, 8, 198, 184, 133, 103, 189, 12, 32, 207, 115, 161, 15, 131, 130, 126, 3, 169,
+            176, 248, 210, 54, 245, 143, 225, 105, 122, 69, 41, 224, 107,
+        ]),
+        Hash256([
+            16, 155, 251, 83, 59, 196, 225, 68, 208, 108, 187, 183, 36, 142, 115, 149, 153, 19,
+            107, 137, 236, 35, 245, 239, 178, 31, 95, 61, 23, 98, 11, 17,
+        ]),
+        Hash256([
+            1, 0, 1, 42, 1, 150, 144, 128, 189, 2, 85, 241, 193, 171, 38, 156, 165, 13, 167, 228,
+            6, 1, 215, 89, 234, 112, 207, 204, 50, 152, 113, 233,
+        ]),
+        Hash256([
+            1, 87, 178, 120, 151, 93, 13, 178, 83, 60, 52, 123, 250, 104, 238, 72, 124, 245, 37, 8,
+            124, 25, 113, 176, 25, 5, 234, 168, 227, 244, 225, 13,
+        ]),
+        Hash256([
+            19, 68, 253, 98, 48, 219, 41, 35, 35, 241, 229, 156, 86, 147, 243, 142, 16, 205, 18,
+            50, 26, 165, 231, 201, 223, 105, 195, 170, 164, 55, 125, 204,
+        ]),
+        Hash256([
+            14, 30, 108, 131, 69, 99, 146, 30, 192, 205, 132, 251, 51, 104, 41, 134, 227, 201, 190,
+            22, 59, 25, 9, 209, 229, 237, 15, 206, 108, 191, 198, 119,
+        ]),
+        Hash256([
+            45, 88, 57, 216, 102, 124, 7, 53, 103, 156, 75, 159, 240, 230, 194, 171, 120, 231, 198,
+            197, 66, 253, 46, 1, 98, 9, 237, 158, 165, 209, 164, 116,
+        ]),
+        Hash256([
+            22, 152, 3, 239, 233, 26, 192, 99, 36, 209, 235, 39, 146, 249, 184, 136, 172, 152, 115,
+            238, 88, 94, 199, 220, 142, 129, 217, 235, 35, 55, 164, 5,
+        ]),
+        Hash256([
+            29, 191, 44, 203, 180, 36, 37, 206, 10, 254, 55, 18, 4, 71, 247, 154, 230, 148, 244,
+            43, 61, 84, 109, 123, 191, 232, 62, 43, 171, 88, 207, 44,
+        ]),
+        Hash256([
+            36, 79, 51, 168, 201, 224, 114, 222, 177, 203, 158, 209, 97, 175, 255, 93, 138, 245,
+            40, 233, 216, 187, 254, 214, 42, 99, 237, 180, 148, 16, 194, 65,
+        ]),
+        Hash256([
+            28, 24, 66, 91, 89, 68, 175, 66, 231, 204, 16, 106, 35, 220, 60, 128, 147, 156, 15,
+            171, 55, 159, 183, 210, 134, 101, 216, 237, 248, 44, 75, 17,
+        ]),
+        Hash256([
+            18, 140, 34, 19, 2, 113, 252, 136, 134, 139, 25, 227, 132, 32, 182, 177, 28, 94, 93,
+            31, 51, 34, 193, 30, 112, 40, 33, 11, 76, 111, 153, 15,
+        ]),
+        Hash256([
+            34, 248, 46, 100, 92, 151, 84, 249, 45, 4, 16, 185, 130, 124, 41, 161, 75, 167, 161,
+            80, 244, 38, 55, 243, 216, 66, 155, 0, 30, 130, 240, 118,
+        ]),
+        Hash256([
+            8, 116, 76, 236, 91, 171, 142, 53, 128, 237, 5, 46, 175, 188, 87, 68, 22, 118, 73, 155,
+            42, 26, 133, 164, 158, 237, 91, 244, 141, 55, 97, 216,
+        ]),
+        Hash256([
+            27, 20, 136, 147, 210, 36, 234, 193, 208, 144, 64, 85, 34, 178, 223, 235, 192, 31, 149,
+            134, 23, 196, 116, 123, 146, 18, 107, 193, 216, 30, 65, 153,
+        ]),
+        Hash256([
+            7, 101, 52, 94, 119, 50, 124, 36, 163, 180, 23, 246, 31, 208, 33, 26, 164, 154, 212,
+            216, 111, 231, 23, 86, 155, 196, 217, 39, 62, 127, 73, 123,
+        ]),
+        Hash256([
+            46, 154, 164, 64, 57, 184, 129, 132, 177, 104, 181, 191, 87, 44, 138, 136, 193, 155, 2,
+            244, 183, 151, 70, 57, 166, 251, 244, 106, 80, 233, 195, 69,
+        ]),
+        Hash256([
+            33, 121, 66, 69, 102, 140, 193, 200, 228, 41, 8, 169, 51, 122, 138, 10, 146, 209, 225,
+            168, 170, 201, 52, 29, 111, 2, 21, 174, 234, 255, 126, 12,
+        ]),
+        Hash256([
+            0, 65, 241, 138, 40, 246, 245, 221, 101, 169, 183, 193, 134, 142, 84, 165, 94, 60, 19,
+            187, 135, 151, 36, 162, 93, 9, 78, 120, 139, 213, 245, 168,
+        ]),
+        Hash256([
+            37, 2, 15, 156, 166, 146, 168, 219, 125, 142, 164, 33, 45, 118, 249, 138, 166, 131,
+            109, 128, 211, 57, 228, 84, 10, 30, 149, 217, 57, 52, 83, 226,
+        ]),
+        Hash256([
+            11, 23, 139, 41, 59, 175, 245, 3, 206, 111, 60, 91, 39, 131, 72, 91, 248, 1, 147, 111,
+            68, 184, 105, 193, 209, 238, 54, 236, 118, 51, 196, 57,
+        ]),
+        Hash256([
+            38, 174, 112, 176, 135, 93, 128, 31, 239, 129, 199, 243, 55, 50, 216, 19, 241, 49, 191,
+            211, 73, 37, 124, 123, 97, 114, 48, 23, 232, 35, 170, 71,
+        ]),
+        Hash256([
+            10, 240, 169, 5, 37, 235, 155, 210, 124, 238, 109, 59, 7, 98, 28, 95, 194, 223, 236, 3,
+            52, 118, 231, 59, 63, 250, 80, 105, 229, 45, 93, 15,
+        ]),
+        Hash256([
+            24, 233, 17, 39, 201, 12, 35, 219, 199, 124, 109, 162, 232, 255, 96, 147, 10, 70, 221,
+            239, 230, 144, 164, 139, 40, 10, 172, 33, 63, 42, 128, 69,
+        ]),
+        Hash256([
+            14, 237, 76, 231, 202, 5, 207, 149, 128, 127, 112, 182, 107, 61, 120, 122, 125, 220,
+            68, 116, 146, 201, 206, 168, 217, 89, 161, 183, 4, 52, 173, 132,
+        ]),
+        Hash256([
+            13, 208, 203, 239, 230, 141, 161, 192, 31, 20, 181, 2, 20, 105, 216, 245, 15, 159, 110,
+            213, 199, 21, 230, 232, 196, 143, 34, 99, 95, 127, 251, 38,
+        ]),
+        Hash256([
+            46, 39, 143, 15, 99, 122, 39, 52, 204, 24, 52, 7, 251, 65, 90, 131, 138, 27, 163, 8,
+            38, 231, 154, 163, 0, 232, 118, 180, 239, 10, 17, 120,
+        ]),
+        Hash256([
+            3, 168, 95, 110, 206, 45, 147, 133, 206, 194, 126, 35, 5, 194, 0, 213, 224, 219, 106,
+            234, 81, 251, 153, 242, 195, 26, 57, 223, 38, 104, 11, 195,
+        ]),
+        Hash256([
+            20, 158, 165, 81, 123, 159, 200, 115, 51, 4, 155, 131, 181, 206, 65, 13, 158, 16, 106,
+            143, 73, 242, 116, 113, 189, 6, 71, 31, 4, 170, 221, 43,
+        ]),
+        Hash256([
+            2, 103, 179, 121, 5, 163, 9, 227, 6, 225, 169, 216, 47, 51, 152, 178, 47, 203, 54, 163,
+            9, 3, 81, 219, 88, 127, 28, 250, 100, 189, 7, 235,
+        ]),
+        Hash256([
+            0, 161, 68, 216, 248, 87, 129, 250, 102, 96, 234, 223, 5, 224, 103, 247, 151, 106, 173,
+            148, 200, 152, 202, 152, 138, 73, 203, 123, 197, 132, 165, 34,
+        ]),
+        Hash256([
+            14, 113, 150, 246, 13, 118, 242, 140, 35, 56, 85, 191, 18, 122, 162, 34, 194, 137, 243,
+            24, 25, 151, 82, 115, 94, 11, 113, 51, 138, 160, 225, 144,
+        ]),
+        Hash256([
+            28, 112, 96, 240, 159, 95, 194, 58, 197, 253, 67, 26, 47, 41, 168, 25, 108, 22, 136,
+            10, 145, 83, 252, 239, 8, 113, 23, 139, 251, 126, 174, 4,
+        ]),
+        Hash256([
+            17, 191, 100, 31, 135, 141, 6, 24, 111, 211, 43, 137, 200, 192, 104, 53, 113, 149, 92,
+            98, 120, 64, 32, 100, 218, 186, 152, 45, 89, 33, 161, 77,
+        ]),
+        Hash256([
+            36, 141, 95, 166, 178, 250, 75, 179, 129, 46, 156, 87, 146, 92, 143, 85, 128, 94, 102,
+            27, 156, 24, 136, 75, 123, 150, 42, 139, 195, 52, 68, 185,
+        ]),
+        Hash256([
+            8, 246, 49, 124, 174, 14, 79, 150, 58, 146, 84, 79, 53, 193, 43, 175, 231, 205, 93,
+            128, 125, 60, 237, 232, 141, 216, 249, 166, 171, 204, 6, 137,
+        ]),
+        Hash256([
+            44, 81, 7, 195, 118, 208, 204, 200, 247, 38, 247, 149, 110, 251, 209, 95, 11, 253, 208,
+            239, 41, 90, 49, 221, 232, 238, 105, 167, 156, 41, 97, 33,
+        ]),
+        Hash256([
+            15, 184, 228, 46, 205, 194, 160, 183, 144, 180, 191, 183, 180, 86, 172, 8, 222, 115,
+            109, 224, 186, 145, 249, 26, 72, 1, 95, 192, 215, 192, 22, 15,
+        ]),
+        Hash256([
+            40, 118, 81, 237, 12, 90, 71, 142, 187, 52, 238, 137, 120, 66, 211, 30, 28, 113, 175,
+            173, 1, 233, 124, 167, 232, 243, 3, 91, 116, 74, 113, 236,
+        ]),
+        Hash256([
+            5, 89, 3, 111, 243, 44, 36, 75, 17, 3, 199, 230, 103, 21, 87, 9, 32, 109, 174, 225, 84,
+            135, 117, 45, 65, 138, 8, 201, 196, 25, 198, 87,
+        ]),
+        Hash256([
+            35, 222, 176, 206, 170, 187, 157, 94, 190, 131, 210, 214, 140, 53, 6, 153, 134, 76, 29,
+            53, 80, 136, 25, 79, 78, 71, 236, 158, 212, 162, 18, 135,
+        ]),
+        Hash256([
+            19, 40, 55, 154, 236, 34, 111, 162, 118, 28, 90, 160, 161, 132, 110, 173, 87, 12, 106,
+            29, 196, 24, 201, 114, 136, 33, 154, 88, 151, 26, 177, 250,
+        ]),
+        Hash256([
+            36, 133, 220, 133, 151, 137, 199, 164, 232, 250, 196, 182, 96, 3, 56, 10, 156, 208, 65,
+            123, 1, 51, 178, 192, 7, 135, 119, 172, 134, 26, 215, 61,
+        ]),
+        Hash256([
+            10, 13, 98, 72, 39, 216, 121, 218, 148, 247, 55, 55, 1, 242, 15, 194, 181, 38, 182,
+            159, 57, 25, 157, 110, 58, 164, 141, 41, 164, 194, 203, 98,
+        ]),
+        Hash256([
+            17, 117, 10, 243, 82, 24, 18, 222, 157, 173, 53, 230, 124, 108, 248, 221, 122, 4, 134,
+            30, 85, 13, 128, 65, 61, 42, 57, 173, 120, 205, 83, 250,
+        ]),
+        Hash256([
+            38, 223, 189, 121, 14, 100, 152, 61, 226, 171, 111, 239, 65, 249, 108, 14, 16, 122,
+            123, 115, 247, 0, 107, 20, 189, 20, 146, 162, 175, 79, 192, 91,
+        ]),
+        Hash256([
+            44, 81, 168, 241, 186, 250, 67, 54, 76, 221, 39, 153, 35, 55, 105, 95, 233, 184, 161,
+            53, 139, 138, 139, 171, 188, 252, 161, 14, 45, 75, 15, 44,
+        ]),
+        Hash256([
+            43, 38, 215, 236, 115, 136, 169, 89, 3, 161, 69, 194, 24, 17, 255, 145, 135, 213, 37,
+            46, 230, 222, 38, 45, 137, 34, 61, 103, 83, 100, 99, 185,
+        ]),
+        Hash256([
+            2, 130, 62, 155, 16, 31, 140, 230, 57, 22, 81, 95, 125, 20, 121, 105, 58, 19, 89, 241,
+            155, 130, 12, 143, 40, 141, 171, 189, 254, 82, 70, 246,
+        ]),
+        Hash256([
+            4, 235, 117, 57, 36, 128, 128, 235, 227, 6, 255, 120, 140, 14, 95, 128, 109, 250, 223,
+            155, 121, 215, 136, 182, 246, 41, 161, 238, 218, 125, 73, 69,
+        ]),
+        Hash256([
+            39, 107, 99, 96, 148, 98, 106, 233, 151, 207, 159, 250, 171, 64, 86, 249, 185, 246,
+            168, 183, 140, 198, 193, 24, 15, 36, 221, 114, 106, 107, 104, 131,
+        ]),
+        Hash256([
+            1, 67, 151, 128, 53, 173, 242, 117, 162, 92, 251, 143, 172, 188, 165, 127, 36, 168,
+            154, 140, 159, 150, 174, 234, 213, 0, 44, 209, 55, 169, 122, 134,
+        ]),
+        Hash256([
+            29, 28, 102, 0, 57, 123, 75, 212, 234, 152, 3, 126, 237, 146, 94, 188, 162, 118, 159,
+            72, 72, 171, 20, 153, 69, 166, 181, 161, 142, 191, 236, 108,
+        ]),
+        Hash256([
+            18, 140, 143, 182, 175, 37, 76, 126, 137, 92, 82, 85, 50, 14, 146, 151, 68, 202, 174,
+            109, 12, 67, 150, 145, 226, 43, 193, 149, 103, 141, 94, 241,
+        ]),
+        Hash256([
+            17, 193, 19, 194, 193, 201, 109, 216, 59, 97, 165, 251, 247, 102, 208, 166, 53, 34, 92,
+            191, 158, 55, 99, 149, 194, 85, 175, 202, 238, 125, 231, 26,
+        ]),
+        Hash256([
+            24, 248, 106, 163, 98, 110, 234, 154, 210, 250, 156, 35, 56, 96, 88, 10, 181, 117, 243,
+            91, 185, 164, 252, 119, 94, 173, 16, 45, 191, 92, 173, 154,
+        ]),
+        Hash256([
+            35, 187, 103, 146, 176, 156, 111, 78, 194, 132, 250, 177, 110, 119, 240, 159, 122, 132,
+            75, 231, 17, 179, 252, 194, 221, 0, 214, 236, 8, 28, 153, 75,
+        ]),
+        Hash256([
+            24, 6, 229, 84, 217, 248, 201, 137, 208, 198, 183, 251, 127, 105, 239, 164, 148, 135,
+            205, 139, 203, 70, 52, 242, 252, 151, 111, 94, 60, 31, 226, 48,
+        ]),
+        Hash256([
+            36, 138, 238, 25, 177, 144, 88, 98, 27, 106, 32, 105, 253, 7, 51, 160, 133, 101, 77,
+            193, 218, 238, 106, 255, 176, 45, 165, 198, 168, 177, 144, 3,
+        ]),
+        Hash256([
+            30, 150, 192, 12, 249, 69, 76, 163, 139, 30, 125, 200, 230, 176, 176, 11, 116, 135, 81,
+            103, 255, 179, 197, 142, 102, 187, 34, 92, 100, 176, 39, 66,
+        ]),
+        Hash256([
+            27, 2, 37, 13, 87, 70, 228, 189, 112, 144, 88, 192, 4, 195, 135, 200, 56, 234, 240,
+            115, 100, 35, 206, 168, 39, 165, 128, 188, 238, 128, 163, 159,
+        ]),
+        Hash256([
+            7, 218, 214, 68, 188, 113, 10, 147, 97, 94, 94, 181, 85, 200, 46, 29, 161, 118, 146,
+            179, 45, 7, 3, 153, 229, 168, 212, 209, 133, 54, 222, 55,
+        ]),
+        Hash256([
+            11, 43, 28, 87, 224, 183, 62, 31, 55, 6, 20, 185, 106, 38, 216, 228, 53, 150, 186, 148,
+            151, 210, 34, 67, 55, 108, 48, 176, 120, 32, 225, 167,
+        ]),
+        Hash256([
+            33, 101, 161, 150, 53, 41, 162, 183, 246, 96, 4, 114, 157, 28, 213, 0, 255, 90, 142,
+            211, 153, 159, 68, 63, 250, 208, 158, 255, 40, 121, 166, 118,
+        ]),
+        Hash256([
+            9, 212, 191, 127, 163, 67, 30, 139, 30, 151, 163, 146, 159, 82, 240, 121, 30, 114, 224,
+            78, 157, 55, 3, 154, 69, 120, 10, 217, 227, 157, 171, 72,
+        ]),
+        Hash256([
+            42, 136, 184, 169, 84, 120, 18, 205, 88, 207, 115, 16, 65, 125, 253, 182, 251, 235,
+            159, 65, 242, 104, 71, 218, 245, 120, 88, 175, 233, 78, 36, 232,
+        ]),
+        Hash256([
+            31, 164, 186, 128, 236, 244, 241, 229, 59, 123, 154, 62, 118, 248, 141, 101, 6, 96,
+            125, 106, 101, 125, 119, 116, 241, 125, 1, 58, 162, 218, 126, 146,
+        ]),
+        Hash256([
+            12, 77, 98, 149, 36, 45, 65, 211, 122, 170, 244, 7, 163, 174, 214, 74, 88, 157, 230,
+            207, 234, 78, 86, 6, 223, 88, 50, 124, 228, 215, 211, 201,
+        ]),
+        Hash256([
+            45, 230, 8, 53, 189, 139, 173, 194, 109, 11, 176, 190, 246, 42, 72, 148, 106, 254, 190,
+            142, 137, 145, 170, 16, 248, 49, 183, 173, 97, 222, 25, 21,
+        ]),
+        Hash256([
+            36, 25, 224, 55, 2, 86, 219, 98, 150, 239, 48, 186, 164, 128, 35, 59, 116, 53, 237, 25,
+            228, 55, 69, 18, 103, 61, 137, 120, 150, 146, 41, 3,
+        ]),
+        Hash256([
+            33, 48, 13, 140, 39, 22, 8, 42, 62, 133, 178, 98, 170, 29, 236, 5, 1, 195, 120, 243,
+            96, 80, 160, 205, 191, 193, 122, 89, 74, 28, 146, 168,
+        ]),
+        Hash256([
+            32, 11, 226, 92, 150, 47, 217, 147, 6, 203, 224, 178, 22, 87, 213, 189, 123, 199, 230,
+            47, 216, 27, 136, 109, 206, 195, 89, 27, 224, 76, 107, 42,
+        ]),
+        Hash256([
+            43, 42, 179, 150, 98, 155, 226, 14, 59, 124, 166, 87, 162, 74, 187, 243, 191, 150, 135,
+            89, 90, 242, 214, 254, 39, 166, 159, 186, 103, 26, 131, 107,
+        ]),
+    ];
+}
+
+impl MerkleZeroHasherWithCacheMarkedLeaf<Hash256> for PoseidonBN254Hasher {
+    const CACHED_MARKED_LEAF_ZERO_HASHES: [Hash256; 128] = [
+        Hash256([
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ]),
+        Hash256([
+            34, 14, 232, 28, 6, 121, 50, 214, 154, 181, 184, 29, 189, 3, 106, 100, 97, 64, 142,
+            149, 208, 74, 112, 28, 95, 95, 199, 137, 135, 37, 207, 64,
+        ]),
+        Hash256([
+            3, 113, 249, 33, 115, 206, 207, 167, 109, 30, 15, 29, 169, 116, 72, 248, 178, 7, 221,
+            108, 22, 0, 222, 181, 249, 106, 201, 124, 126, 160, 99, 162,
+        ]),
+        Hash256([
+            41, 144, 160, 72, 172, 218, 34, 151, 169, 219, 117, 182, 38, 32, 83, 46, 44, 246, 2,
+            54, 157, 125, 35, 168, 33, 184, 155, 245, 171, 176, 203, 246,
+        ]),
+        Hash256([
+            25, 170, 121, 136, 194, 158, 109, 186, 2, 49, 112, 51, 189, 13, 40, 71, 2, 163, 134,
+            93, 31, 7, 174, 66, 31, 93, 93, 221, 18, 226, 178, 220,
+        ]),
+        Hash256([
+            15, 104, 17, 217, 13, 71, 136, 135, 132, 184, 196, 193, 155, 200, 140, 108, 101, 22,
+            133, 159, 81, 251, 168, 4, 29, 253, 189, 38, 155, 216, 236, 235,
+        ]),
+        Hash256([
+            23, 91, 229, 124, 33, 33, 242, 50, 153, 81, 134, 220, 240, 212, 45, 108, 82, 43, 200,
+            45, 79, 174, 234, 144, 77, 86, 151, 30, 233, 130, 19, 84,
+        ]),
+        Hash256([
+            6, 69, 158, 224, 123, 84, 130, 119, 37, 163, 59, 202, 153, 214, 38, 112, 45, 118, 32,
+            147, 146, 36, 253, 6, 0, 209, 205, 140, 33, 40, 135, 45,
+        ]),
+        Hash256([
+            27, 225, 121, 252, 246, 146, 173, 104, 253, 238, 90, 140, 171, 173, 210, 253, 216, 233,
+            118, 147, 97, 209, 133, 21, 160, 49, 249, 27, 158, 176, 49, 5,
+        ]),
+        Hash256([
+            20, 52, 118, 172, 214, 125, 14, 124, 50, 226, 14, 106, 58, 244, 62, 33, 225, 224, 37,
+            170, 247, 207, 39, 194, 78, 158, 61, 169, 165, 22, 238, 190,
+        ]),
+        Hash256([
+            22, 144, 116, 69, 230, 118, 58, 124, 50, 146, 0, 10, 106, 166, 100, 89, 217, 124, 38,
+            175, 127, 77, 127, 76, 235, 60, 72, 230, 177, 29, 79, 180,
+        ]),
+        Hash256([
+            36, 96, 222, 200, 186, 191, 58, 59, 93, 206, 143, 76, 236, 85, 124, 90, 157, 193, 44,
+            202, 22, 28, 133, 233, 21, 134, 23, 31, 113, 134, 151, 54,
+        ]),
+        Hash256([
+            14, 65, 223, 237, 83, 179, 86, 39, 233, 223, 210, 217, 228, 161, 250, 209, 155, 62,
+            143, 233, 130, 24, 220, 33, 9, 92, 65, 31, 73, 254, 176, 147,
+        ]),
+        Hash256([
+            26, 206, 146, 113, 252, 90, 80, 30, 203, 151, 55, 166, 53, 19, 128, 233, 123, 241, 238,
+            182, 33, 122, 6, 192, 120, 192, 12, 128, 183, 201, 239, 211,
+        ]),
+        Hash256([
+            39, 96, 214, 108, 29, 52, 148, 50, 230, 141, 154, 250, 167, 220, 38, 123, 94, 59, 212,
+            62, 80, 132, 179, 196, 105, 226, 4, 19, 155, 47, 254, 104,
+        ]),
+        Hash256([
+            27, 77, 243, 35, 8, 27, 165, 224, 16, 71, 231, 164, 80, 131, 130, 78, 106, 4, 171, 104,
+            75, 208, 246, 205, 238, 165, 206, 214, 244, 212, 252, 125,
+        ]),
+        Hash256([
+            25, 150, 23, 232, 39, 170, 174, 217, 26, 136, 88, 25, 245, 99, 165, 45, 181, 50, 177,
+            133, 239, 220, 135, 4, 51, 54, 137, 120, 9, 227, 145, 120,
+        ]),
+        Hash256([
+            23, 214, 200, 174, 194, 104, 82, 159, 236, 25, 39, 189, 82, 252, 193, 148, 185, 40,
+            204, 188, 187, 171, 140, 205, 210, 72, 134, 42, 40, 134, 52, 135,
+        ]),
+        Hash256([
+            2, 82, 117, 214, 192, 198, 214, 45, 73, 162, 111, 123, 181, 161, 223, 195, 8, 109, 181,
+            67, 90, 70, 175, 55, 67, 83, 30, 172, 142, 45, 132, 233,
+        ]),
+        Hash256([
+            42, 118, 64, 75, 150, 47, 23, 38, 81, 160, 164, 121, 42, 223, 129, 222, 154, 203, 97,
+            150, 214, 246, 251, 169, 203, 200, 11, 81, 169, 211, 224, 150,
+        ]),
+        Hash256([
+            16, 83, 248, 221, 190, 188, 194, 36, 27, 246, 168, 252, 89, 87, 226, 218, 250, 111, 37,
+            107, 65, 115, 13, 79, 244, 132, 200, 136, 154, 144, 221, 108,
+        ]),
+        Hash256([
+            2, 168, 171, 228, 37, 81, 41, 105, 114, 218, 102, 137, 183, 243, 97, 6, 145, 52, 67,
+            190, 80, 187, 157, 30, 116, 9, 94, 187, 99, 223, 24, 78,
+        ]),
+        Hash256([
+            8, 203, 153, 227, 81, 74, 140, 100, 218, 116, 70, 196, 93, 235, 193, 57, 16, 49, 44,
+            142, 197, 149, 164, 201, 126, 231, 181, 105, 9, 147, 221, 101,
+        ]),
+        Hash256([
+            7, 62, 78, 214, 36, 136, 35, 7, 249, 128, 239, 195, 107, 253, 212, 169, 14, 200, 181,
+            163, 74, 206, 185, 211, 248, 187, 146, 198, 20, 79, 157, 20,
+        ]),
+        Hash256([
+            40, 68, 149, 140, 201, 199, 103, 136, 32, 252, 122, 44, 122, 103, 161, 186, 62, 12,
+            103, 209, 180, 200, 162, 45, 209, 60, 224, 20, 192, 229, 90, 27,
+        ]),
+        Hash256([
+            2, 28, 35, 165, 74, 238, 57, 223, 23, 31, 72, 49, 35, 32, 163, 38, 178, 187, 253, 201,
+            7, 154, 165, 104, 151, 6, 229, 201, 142, 19, 127, 172,
+        ]),
+        Hash256([
+            9, 46, 249, 35, 22, 22, 94, 22, 83, 153, 58, 165, 69, 119, 80, 27, 200, 109, 28, 150,
+            153, 34, 46, 97, 78, 7, 242, 59, 118, 124, 9, 205,
+        ]),
+        Hash256([
+            0, 245, 45, 217, 81, 246, 72, 207, 65, 172, 153, 68, 106, 42, 110, 141, 120, 150, 209,
+            137, 72, 182, 4, 7, 110, 245, 52, 249, 230, 167, 195, 62,
+        ]),
+        Hash256([
+            47, 116, 134, 245, 214, 39, 170, 225, 246, 162, 0, 24, 122, 52, 144, 80, 58, 62, 118,
+            31, 72, 169, 66, 246, 144, 216, 77, 183, 29, 96, 92, 91,
+        ]),
+        Hash256([
+            40, 239, 181, 37, 212, 54, 39, 183, 117, 20, 81, 108, 68, 200, 19, 214, 48, 81, 233,
+            85, 247, 216, 3, 41, 173, 209, 74, 152, 150, 14, 201, 180,
+        ]),
+        Hash256([
+            31, 139, 154, 232, 132, 23, 17, 187, 58, 232, 173, 29, 47, 109, 160, 47, 166, 45, 59,
+            215, 242, 80, 125, 85, 209, 99, 89, 77, 130, 142, 207, 254,
+        ]),
+        Hash256([
+            12, 216, 186, 69, 123, 236, 142, 19, 30, 193, 220, 24, 115, 225, 242, 234, 138, 116,
+            216, 20, 181, 62, 206, 116, 3, 127, 4, 70, 146, 185, 139, 57,
+        ]),
+        Hash256([
+            13, 10, 30, 43, 198, 88, 136, 72, 10, 125, 208, 113, 208, 19, 203, 16, 194, 192, 25,
+            240, 196, 113, 58, 161, 126, 198, 234, 127, 185, 137, 20, 165,
+        ]),
+        Hash256([
+            4, 56, 18, 94, 243, 251, 110, 216, 214, 164, 123, 208, 69, 91, 192, 57, 22, 172, 184,
+            200, 253, 184, 241, 237, 181, 232, 170, 191, 233, 142, 32, 206,
+        ]),
+        Hash256([
+            37, 80, 117, 108, 243, 87, 155, 84, 188, 200, 11, 2, 110, 62, 198, 199, 200, 12, 136,
+            194, 51, 75, 143, 130, 39, 102, 168, 123, 244, 165, 249, 115,
+        ]),
+        Hash256([
+            46, 59, 15, 246, 178, 179, 254, 223, 60, 77, 143, 24, 64, 33, 18, 171, 164, 78, 199,
+            60, 120, 168, 113, 32, 213, 167, 232, 138, 253, 116, 57, 202,
+        ]),
+        Hash256([
+            46, 173, 157, 116, 230, 66, 190, 106, 154, 182, 230, 57, 204, 232, 212, 220, 141, 10,
+            157, 111, 79, 162, 112, 248, 62, 88, 162, 162, 11, 195, 90, 214,
+        ]),
+        Hash256([
+            25, 251, 87, 153, 233, 126, 26, 152, 84, 198, 15, 135, 137, 171, 219, 93, 183, 187,
+            195, 173, 86, 135, 127, 137, 77, 239, 163, 14, 165, 254, 24, 106,
+        ]),
+        Hash256([
+            16, 66, 53, 67, 143, 88, 19, 136, 127, 30, 189, 121, 124, 13, 68, 208, 227, 140, 77,
+            148, 145, 77, 59, 105, 114, 240, 30, 57, 60, 47, 4, 221,
+        ]),
+        Hash256([
+            17, 233, 43, 242, 152, 63, 227, 133, 80, 83, 203, 196, 249, 98, 188, 63, 32, 101, 103,
+            137, 60, 38, 148, 229, 47, 139, 52, 212, 98, 84, 221, 69,
+        ]),
+        Hash256([
+            43, 164, 198, 0, 142, 7, 213, 214, 247, 149, 132, 55, 189, 65, 111, 139, 116, 216, 235,
+            198, 197, 137, 14, 83, 233, 22, 57, 38, 142, 54, 24, 242,
+        ]),
+        Hash256([
+            11, 143, 252, 136, 45, 248, 195, 7, 89, 116, 145, 62, 188, 192, 199, 21, 12, 45, 107,
+            244, 216, 125, 194, 223, 105, 3, 51, 139, 236, 59, 121, 204,
+        ]),
+        Hash256([
+            12, 205, 244, 216, 33, 233, 130, 112, 81, 194, 139, 126, 127, 37, 83, 53, 209, 38, 81,
+            204, 0, 226, 110, 0, 175, 63, 227, 13, 239, 250, 85, 48,
+        ]),
+        Hash256([
+            15, 228, 142, 118, 243, 10, 143, 139, 36, 147, 48, 85, 68, 69, 162, 153, 193, 213, 132,
+            198, 113, 234, 82, 80, 140, 161, 208, 22, 45, 239, 105, 79,
+        ]),
+        Hash256([
+            16, 32, 43, 205, 18, 161, 95, 18, 6, 227, 68, 90, 137, 107, 132, 202, 107, 62, 152, 94,
+            28, 9, 67, 54, 127, 178, 161, 189, 11, 170, 87, 78,
+        ]),
+        Hash256([
+            25, 36, 134, 232, 194, 7, 12, 34, 0, 6, 249, 184, 118, 124, 121, 37, 46, 243, 107, 38,
+            138, 225, 197, 89, 109, 209, 142, 220, 10, 163, 146, 116,
+        ]),
+        Hash256([
+            32, 112, 171, 92, 203, 0, 79, 17, 208, 205, 118, 126, 231, 165, 62, 150, 54, 141, 213,
+            208, 150, 123, 217, 53, 133, 63, 122, 246, 8, 100, 223, 58,
+        ]),
+        Hash256([
+            18, 255, 183, 99, 90, 216, 1, 233, 70, 81, 182, 250, 123, 55, 36, 103, 134, 132, 169,
+            244, 168, 118, 233, 205, 143, 218, 204, 119, 67, 238, 177, 1,
+        ]),
+        Hash256([
+            35, 27, 135, 241, 45, 210, 162, 82, 194, 122, 248, 243, 240, 139, 146, 120, 115, 13,
+            79, 171, 237, 25, 239, 27, 113, 131, 193, 219, 239, 28, 105, 245,
+        ]),
+        Hash256([
+            19, 203, 34, 239, 208, 137, 145, 21, 97, 108, 1, 34, 121, 191, 117, 252, 175, 188, 111,
+            84, 97, 234, 64, 2, 153, 36, 191, 86, 59, 167, 145, 140,
+        ]),
+        Hash256([
+            37, 112, 144, 228, 120, 252, 63, 93, 93, 238, 29, 84, 61, 197, 134, 102, 208, 138, 33,
+            213, 3, 245, 45, 76, 253, 194, 44, 0, 232, 63, 227, 32,
+        ]),
+        Hash256([
+            24, 50, 139, 184, 219, 193, 70, 203, 187, 130, 53, 161, 188, 250, 246, 80, 207, 252,
+            80, 2, 30, 63, 63, 242, 161, 106, 31, 243, 249, 20, 4, 17,
+        ]),
+        Hash256([
+            44, 186, 163, 176, 94, 254, 184, 167, 126, 114, 80, 147, 120, 62, 12, 82, 122, 223, 40,
+            58, 75, 131, 46, 145, 69, 58, 95, 17, 57, 162, 25, 53,
+        ]),
+        Hash256([
+            40, 213, 136, 195, 197, 11, 63, 235, 103, 193, 129, 216, 109, 127, 86, 168, 253, 2, 91,
+            239, 18, 131, 110, 127, 205, 29, 78, 56, 196, 225, 83, 40,
+        ]),
+        Hash256([
+            44, 109, 79, 207, 145, 159, 196, 35, 0, 165, 22, 173, 0, 214, 228, 120, 46, 59, 100,
+            50, 168, 165, 208, 67, 217, 121, 240, 86, 220, 121, 103, 231,
+        ]),
+        Hash256([
+            14, 170, 85, 40, 3, 194, 248, 0, 109, 40, 63, 89, 85, 110, 29, 28, 14, 100, 222, 127,
+            221, 145, 254, 70, 28, 40, 163, 227, 141, 111, 50, 184,
+        ]),
+        Hash256([
+            1, 73, 14, 66, 229, 154, 24, 98, 132, 67, 169, 168, 167, 42, 229, 44, 91, 65, 106, 181,
+            2, 104, 23, 205, 163, 78, 81, 170, 86, 220, 191, 91,
+        ]),
+        Hash256([
+            28, 251, 168, 218, 95, 182, 196, 47, 179, 172, 105, 50, 37, 199, 250, 161, 118, 162,
+            112, 222, 233, 196, 158, 53, 16, 105, 61, 109, 84, 250, 78, 38,
+        ]),
+        Hash256([
+            15, 244, 58, 247, 226, 206, 168, 179, 248, 170, 226, 154, 14, 65, 204, 41, 226, 62, 90,
+            254, 172, 91, 231, 3, 21, 38, 147, 16, 159, 68, 28, 138,
+        ]),
+        Hash256([
+            26, 87, 52, 219, 60, 16, 85, 155, 55, 23, 168, 184, 94, 84, 90, 115, 46, 135, 109, 124,
+            82, 27, 51, 183, 207, 218, 129, 78, 105, 147, 240, 84,
+        ]),
+        Hash256([
+            38, 72, 199, 192, 219, 89, 234, 195, 120, 209, 78, 111, 65, 40, 82, 250, 230, 87, 171,
+            56, 147, 231, 108, 40, 5, 70, 54, 9, 147, 75, 34, 193,
+        ]),
+        Hash256([
+            46, 224, 10, 42, 7, 37, 168, 167, 231, 129, 43, 55, 80, 162, 174, 218, 44, 254, 191,
+            193, 178, 74, 127, 183, 65, 191, 60, 155, 218, 49, 216, 166,
+        ]),
+        Hash256([
+            38, 211, 32, 111, 248, 245, 0, 129, 83, 208, 31, 231, 57, 51, 15, 227, 75, 117, 84, 29,
+            104, 16, 23, 218, 203, 41, 209, 22, 105, 142, 156, 162,
+        ]),
+        Hash256([
+            7, 251, 4, 233, 98, 143, 246, 144, 185, 12, 254, 92, 50, 98, 72, 32, 220, 185, 240, 53,
+            92, 255, 43, 180, 6, 10, 176, 255, 55, 139, 172, 115,
+        ]),
+        Hash256([
+            46, 158, 47, 38, 40, 65, 46, 24, 100, 19, 246, 159, 78, 76, 217, 126, 25, 31, 153, 124,
+            208, 148, 220, 109, 68, 55, 212, 21, 120, 83, 230, 33,
+        ]),
+        Hash256([
+            46, 152, 211, 225, 62, 123, 8, 245, 116, 125, 148, 150, 158, 84, 77, 179, 201, 187, 58,
+            2, 110, 149, 223, 96, 234, 243, 158, 228, 143, 22, 128, 92,
+        ]),
+        Hash256([
+            45, 82, 90, 85, 167, 29, 131, 35, 89, 97, 42, 113, 154, 128, 24, 121, 154, 16, 60, 211,
+            116, 32, 40, 88, 109, 208, 35, 13, 10, 30, 91, 223,
+        ]),
+        Hash256([
+            42, 151, 189, 215, 190, 189, 193, 55, 84, 54, 184, 30, 226, 120, 250, 58, 186, 188, 60,
+            230, 167, 48, 72, 177, 138, 139, 74, 36, 244, 36, 165, 144,
+        ]),
+        Hash256([
+            7, 172, 119, 45, 21, 240, 3, 199, 114, 59, 70, 5, 176, 74, 184, 233, 171, 140, 190,
+            246, 116, 16, 233, 16, 122, 206, 242, 88, 157, 97, 80, 102,
+        ]),
+        Hash256([
+            46, 153, 72, 229, 240, 132, 155, 149, 48, 120, 70, 245, 203, 215, 99, 130, 224, 110,
+            205, 89, 252, 145, 179, 192, 72, 11, 100, 180, 49, 102, 2, 176,
+        ]),
+        Hash256([
+            10, 84, 54, 47, 151, 214, 139, 106, 170, 144, 193, 71, 4, 191, 197, 166, 248, 135, 248,
+            144, 159, 217, 70, 84, 141, 145, 246, 164, 244, 97, 78, 33,
+        ]),
+        Hash256([
+            28, 67, 148, 216, 48, 108, 217, 207, 214, 91, 183, 132, 167, 175, 219, 84, 159, 151,
+            146, 70, 3, 50, 49, 181, 166, 242, 159, 215, 52, 168, 223, 184,
+        ]),
+        Hash256([
+            30, 33, 196, 163, 44, 12, 172, 173, 250, 243, 29, 224, 166, 158, 228, 90, 116, 199,
+            111, 99, 43, 245, 20, 87, 89, 158, 134, 195, 158, 128, 190, 146,
+        ]),
+        Hash256([
+            37, 197, 202, 101, 80, 135, 78, 189, 79, 25, 70, 87, 106, 94, 248, 2, 120, 75, 63, 244,
+            21, 165, 210, 216, 3, 191, 95, 184, 27, 110, 125, 75,
+        ]),
+        Hash256([
+            31, 228, 29, 5, 26, 255, 82, 61, 252, 66, 200, 144, 116, 140, 122, 150, 243, 162, 169,
+            63, 42, 243, 122, 217, 219, 77, 88, 240, 64, 118, 19, 146,
+        ]),
+        Hash256([
+            25, 243, 160, 230, 197, 127, 171, 94, 118, 223, 127, 96, 134, 109, 1, 187, 86, 29, 69,
+            191, 194, 10, 65, 28, 16, 161, 37, 66, 26, 245, 18, 124,
+        ]),
+        Hash256([
+            46, 217, 71, 5, 94, 5, 168, 151, 91, 188, 128, 252, 19, 220, 116, 42, 167, 17, 62, 167,
+            124, 183, 61, 1, 89, 220, 182, 240, 15, 108, 65, 147,
+        ]),
+        Hash256([
+            13, 45, 208, 6, 126, 215, 28, 148, 135, 173, 12, 228, 231, 138, 226, 150, 127, 231, 61,
+            10, 134, 208, 251, 74, 215, 176, 72, 148, 69, 29, 30, 152,
+        ]),
+        Hash256([
+            23, 22, 174, 220, 63, 155, 241, 84, 250, 50, 230, 194, 192, 247, 232, 78, 106, 135,
+            117, 194, 34, 64, 176, 254, 80, 223, 55, 79, 237, 143, 202, 28,
+        ]),
+        Hash256([
+            47, 134, 50, 175, 125, 167, 103, 157, 96, 89, 153, 23, 156, 191, 33, 203, 166, 210, 93,
+            139, 238, 34, 119, 67, 26, 136, 208, 233, 47, 0, 129, 104,
+        ]),
+        Hash256([
+            36, 140, 42, 228, 239, 37, 219, 220, 225, 189, 75, 203, 95, 91, 21, 3, 18, 75, 78, 35,
+            188, 111, 228, 131, 217, 250, 128, 96, 0, 73, 225, 65,
+        ]),
+        Hash256([
+            6, 74, 39, 150, 46, 65, 155, 162, 66, 179, 96, 98, 154, 118, 22, 49, 70, 191, 11, 211,
+            63, 57, 86, 127, 214, 130, 160, 92, 245, 172, 119, 234,
+        ]),
+        Hash256([
+            27, 253, 31, 81, 132, 213, 191, 250, 7, 217, 14, 100, 247, 135, 167, 242, 147, 83, 100,
+            45, 95, 27, 102, 110, 138, 2, 148, 153, 77, 243, 97, 66,
+        ]),
+        Hash256([
+            23, 81, 223, 221, 53, 99, 10, 191, 9, 164, 88, 40, 245, 68, 201, 0, 156, 192, 61, 195,
+            177, 40, 219, 89, 75, 169, 192, 24, 252, 117, 140, 48,
+        ]),
+        Hash256([
+            8, 45, 188, 175, 128, 30, 18, 62, 40, 225, 218, 59, 30, 88, 51, 132, 97, 137, 35, 47,
+            139, 250, 6, 62, 13, 100, 221, 44, 130, 77, 123, 150,
+        ]),
+        Hash256([
+            16, 78, 45, 39, 204, 247, 195, 107, 147, 250, 161, 96, 246, 221, 6, 4, 88, 192, 103,
+            213, 79, 36, 55, 246, 117, 98, 160, 199, 160, 64, 218, 36,
+        ]),
+        Hash256([
+            18, 135, 71, 167, 218, 69, 5, 129, 88, 6, 36, 184, 68, 84, 248, 4, 244, 156, 139, 204,
+            211, 101, 80, 241, 210, 180, 182, 60, 202, 77, 188, 232,
+        ]),
+        Hash256([
+            45, 231, 150, 210, 28, 47, 248, 242, 35, 70, 204, 110, 60, 87, 72, 204, 218, 131, 36,
+            9, 162, 161, 99, 128, 200, 90, 112, 143, 123, 193, 198, 78,
+        ]),
+        Hash256([
+            12, 40, 161, 215, 193, 51, 239, 51, 61, 248, 8, 214, 222, 127, 63, 44, 89, 181, 185,
+            117, 247, 199, 141, 150, 206, 235, 113, 236, 14, 77, 207, 169,
+        ]),
+        Hash256([
+            43, 77, 248, 204, 210, 110, 181, 1, 194, 17, 89, 214, 142, 7, 193, 15, 64, 251, 19, 97,
+            146, 3, 232, 201, 83, 245, 95, 158, 77, 225, 241, 243,
+        ]),
+        Hash256([
+            33, 64, 250, 27, 121, 254, 35, 255, 29, 39, 51, 155, 23, 170, 186, 102, 224, 22, 52,
+            76, 161, 87, 242, 47, 127, 42, 28, 131, 197, 135, 229, 70,
+        ]),
+        Hash256([
+            30, 133, 54, 37, 174, 34, 103, 16, 231, 13, 129, 78, 227, 164, 233, 225, 64, 26, 180,
+            189, 227, 143, 82, 67, 29, 83, 208, 182, 52, 116, 112, 244,
+        ]),
+        Hash256([
+            9, 236, 177, 60, 138, 56, 80, 115, 52, 69, 172, 67, 196, 153, 6, 12, 201, 10, 75, 77,
+            200, 181, 44, 57, 21, 11, 233, 218, 41, 141, 161, 205,
+        ]),
+        Hash256([
+            29, 56, 48, 139, 95, 1, 158, 35, 226, 129, 217, 92, 191, 246, 216, 165, 239, 113, 221,
+            151, 170, 53, 133, 129, 83, 15, 151, 202, 107, 167, 179, 109,
+        ]),
+        Hash256([
+            43, 188, 80, 3, 135, 73, 193, 6, 214, 230, 201, 174, 19, 23, 240, 169, 207, 159, 44,
+            131, 68, 225, 194, 5, 5, 10, 36, 150, 84, 22, 29, 229,
+        ]),
+        Hash256([
+            31, 24, 108, 73, 193, 246, 251, 250, 90, 224, 76, 173, 214, 165, 131, 65, 205, 212,
+            170, 154, 161, 137, 92, 10, 46, 29, 140, 0, 194, 150, 236, 124,
+        ]),
+        Hash256([
+            12, 95, 169, 185, 138, 108, 21, 10, 246, 18, 247, 228, 30, 7, 72, 245, 205, 184, 248,
+            148, 242, 90, 6, 54, 179, 147, 236, 27, 194, 45, 146, 222,
+        ]),
+        Hash256([
+            15, 83, 240, 240, 195, 76, 55, 63, 76, 126, 183, 89, 202, 122, 104, 107, 107, 167, 129,
+            122, 122, 15, 122, 217, 41, 101, 171, 198, 208, 151, 103, 43,
+        ]),
+        Hash256([
+            31, 198, 86, 17, 154, 234, 245, 255, 202, 143, 12, 217, 180, 87, 153, 145, 37, 173, 2,
+            205, 145, 149, 94, 197, 165, 208, 37, 171, 88, 90, 165, 52,
+        ]),
+        Hash256([
+            2, 204, 122, 190, 132, 133, 176, 82, 145, 186, 66, 191, 89, 90, 147, 68, 122, 129, 116,
+            40, 19, 86, 28, 186, 102, 39, 176, 138, 62, 91, 121, 254,
+        ]),
+        Hash256([
+            27, 194, 176, 196, 74, 173, 197, 58, 199, 133, 241, 148, 133, 127, 82, 206, 195, 118,
+            213, 85, 172, 7, 70, 64, 206, 240, 77, 70, 119, 201, 40, 213,
+        ]),
+        Hash256([
+            16, 88, 185, 27, 132, 167, 253, 208, 106, 152, 201, 48, 30, 195, 173, 233, 187, 237,
+            181, 221, 176, 166, 93, 16, 183, 38, 127, 73, 73, 103, 21, 86,
+        ]),
+        Hash256([
+            48, 33, 92, 170, 6, 192, 79, 12, 31, 195, 176, 103, 146, 179, 28, 96, 71, 252, 46, 89,
+            32, 250, 82, 251, 230, 91, 17, 231, 231, 77, 161, 164,
+        ]),
+        Hash256([
+            28, 237, 28, 72, 135, 137, 67, 134, 26, 193, 112, 149, 15, 23, 100, 145, 149, 125, 156,
+            141, 97, 125, 159, 145, 9, 90, 102, 17, 189, 20, 35, 165,
+        ]),
+        Hash256([
+            25, 2, 187, 200, 54, 162, 250, 125, 97, 166, 234, 13, 83, 148, 83, 244, 57, 115, 224,
+            118, 240, 161, 96, 21, 41, 45, 171, 27, 250, 229, 23, 249,
+        ]),
+        Hash256([
+            36, 231, 217, 139, 34, 219, 72, 97, 224, 12, 200, 251, 240, 91, 134, 167, 200, 154, 19,
+            34, 140, 214, 87, 41, 74, 137, 67, 187, 253, 36, 255, 3,
+        ]),
+        Hash256([
+            10, 143, 68, 79, 202, 127, 40, 238, 192, 32, 70, 229, 60, 10, 63, 206, 104, 186, 33,
+            111, 126, 169, 29, 4, 150, 15, 230, 167, 162, 193, 179, 131,
+        ]),
+        Hash256([
+            29, 96, 142, 36, 165, 73, 252, 154, 64, 134, 237, 66, 186, 181, 111, 26, 33, 35, 156,
+            168, 62, 179, 220, 135, 4, 92, 69, 10, 111, 125, 34, 210,
+        ]),
+        Hash256([
+            26, 20, 112, 0, 241, 96, 239, 112, 150, 242, 116, 13, 216, 239, 171, 253, 203, 103,
+            218, 237, 162, 190, 62, 2, 89, 52, 28, 144, 64, 16, 12, 128,
+        ]),
+        Hash256([
+            7, 38, 138, 211, 199, 254, 14, 255, 57, 238, 72, 165, 18, 40, 158, 145, 174, 133, 179,
+            223, 89, 23, 215, 119, 42, 248, 175, 233, 165, 171, 106, 114,
+        ]),
+        Hash256([
+            24, 171, 235, 63, 95, 223, 63, 142, 143, 110, 50, 131, 26, 0, 111, 123, 32, 217, 181,
+            86, 28, 10, 123, 125, 26, 215, 217, 144, 199, 30, 199, 160,
+        ]),
+        Hash256([
+            10, 140, 57, 11, 83, 114, 220, 209, 68, 42, 109, 126, 235, 253, 38, 65, 83, 126, 250,
+            151, 144, 181, 250, 181, 49, 244, 106, 74, 77, 34, 52, 200,
+        ]),
+        Hash256([
+            29, 34, 112, 82, 6, 83, 246, 174, 229, 156, 219, 139, 46, 111, 53, 15, 142, 107, 89,
+            223, 55, 254, 188, 110, 164, 88, 126, 86, 90, 37, 226, 248,
+        ]),
+        Hash256([
+            27, 81, 14, 67, 146, 27, 0, 50, 195, 225, 55, 76, 86, 238, 92, 54, 85, 227, 11, 43, 62,
+            5, 236, 144, 8, 28, 40, 129, 125, 175, 82, 72,
+        ]),
+        Hash256([
+            11, 205, 163, 123, 239, 66, 159, 4, 170, 33, 237, 13, 91, 179, 97, 190, 35, 65, 120,
+            124, 254, 167, 175, 40, 48, 213, 141, 224, 122, 98, 202, 236,
+        ]),
+        Hash256([
+            15, 123, 204, 82, 86, 247, 16, 24, 81, 112, 223, 189, 236, 139, 245, 21, 243, 11, 55,
+            47, 21, 125, 81, 168, 42, 120, 172, 223, 87, 80, 242, 210,
+        ]),
+        Hash256([
+            26, 129, 70, 15, 156, 124, 58, 21, 96, 103, 122, 46, 58, 185, 45, 120, 240, 88, 225,
+            163, 148, 228, 196, 29, 87, 71, 73, 85, 52, 101, 17, 207,
+        ]),
+        Hash256([
+            9, 18, 97, 178, 3, 234, 105, 189, 248, 129, 228, 107, 138, 6, 99, 62, 228, 214, 110,
+            208, 17, 128, 0, 202, 59, 159, 94, 156, 213, 150, 83, 28,
+        ]),
+        Hash256([
+            24, 160, 214, 133, 80, 182, 150, 87, 117, 187, 149, 203, 142, 86, 164, 81, 154, 172,
+            182, 113, 97, 117, 127, 160, 117, 67, 235, 32, 53, 11, 58, 8,
+        ]),
+        Hash256([
+            2, 28, 118, 210, 12, 36, 238, 35, 195, 202, 18, 37, 88, 136, 122, 43, 250, 29, 187,
+            247, 131, 234, 142, 166, 86, 224, 136, 134, 192, 124, 166, 54,
+        ]),
+        Hash256([
+            22, 217, 82, 59, 113, 123, 238, 217, 163, 102, 14, 73, 225, 28, 253, 49, 49, 100, 242,
+            131, 74, 136, 147, 139, 207, 79, 70, 28, 228, 112, 191, 251,
+        ]),
+        Hash256([
+            1, 178, 223, 231, 110, 27, 55, 86, 107, 12, 174, 146, 212, 193, 209, 225, 16, 177, 50,
+            197, 45, 62, 21, 80, 7, 149, 70, 231, 78, 157, 112, 124,
+        ]),
+        Hash256([
+            1, 85, 109, 51, 100, 144, 48, 139, 191, 16, 103, 153, 94, 226, 205, 49, 66, 236, 130,
+            67, 8, 122, 247, 43, 200, 200, 37, 110, 180, 238, 3, 81,
+        ]),
+        Hash256([
+            37, 143, 175, 203, 12, 26, 246, 114, 112, 118, 216, 250, 201, 112, 191, 238, 103, 190,
+            215, 229, 184, 244, 163, 80, 199, 191, 117, 162, 255, 16, 227, 63,
+        ]),
+        Hash256([
+            29, 202, 135, 213, 173, 77, 29, 81, 247, 216, 123, 199, 231, 108, 68, 207, 88, 111, 86,
+            107, 74, 58, 70, 217, 205, 61, 107, 131, 51, 153, 123, 187,
+        ]),
+        Hash256([
+            27, 174, 211, 41, 124, 42, 66, 237, 1, 182, 133, 234, 147, 81, 152, 100, 238, 100, 30,
+            199, 28, 157, 21, 172, 70, 13, 47, 104, 1, 96, 1, 39,
+        ]),
+        Hash256([
+            1, 216, 9, 217, 62, 144, 52, 220, 158, 67, 19, 22, 81, 71, 172, 23, 149, 185, 195, 198,
+            215, 119, 12, 199, 10, 226, 15, 193, 9, 239, 175, 34,
+        ]),
+        Hash256([
+            34, 233, 35, 85, 24, 182, 14, 94, 19, 242, 150, 103, 83, 185, 165, 87, 106, 79, 180,
+            220, 203, 216, 254, 164, 169, 234, 114, 127, 40, 215, 156, 36,
+        ]),
+    ];
+}
+
 impl MerkleZeroHasherWithCache<HashOut<GoldilocksField>> for PoseidonHasher {
     const CACHED_ZERO_HASHES: [HashOut<GoldilocksField>; 128] = [
         HashOut {
@@ -1,3 +1,11 @@
+use kvq::codec::from_compact_bytes;
+use kvq::codec::from_compact_or_json_bytes;
+use kvq::codec::to_compact_bytes;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
+use kvq::codec::KVQ_COMPACT_TAG_DELTA_MERKLE_PROOF;
+use kvq::codec::KVQ_COMPACT_TAG_MERKLE_PROOF;
 use kvq::traits::KVQSerializable;
 use l2o_common::common::data::hash::Hash256;
 use plonky2::field::goldilocks_field::GoldilocksField;
@@ -59,6 +67,21 @@ where
     }
 }
 
+impl<Hash> MerkleProofCore<Hash>
+where
+    Hash: L2OHash + PartialEq + Copy + Serialize,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    pub fn to_hash_256(&self) -> MerkleProofCore<Hash256> {
+        MerkleProofCore {
+            root: self.root.to_hash_256(),
+            value: self.value.to_hash_256(),
+            index: self.index,
+            siblings: self.siblings.iter().map(|x| x.to_hash_256()).collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DeltaMerkleProofCorePartial<Hash: PartialEq + Copy> {
     pub old_value: Hash,
@@ -84,6 +107,18 @@ impl<Hash: PartialEq + Copy> DeltaMerkleProofCore<Hash> {
         verify_delta_merkle_proof_core_marked_if::<Hash, Hasher>(&self, marked)
     }
 }
+impl<Hash: L2OHash + PartialEq + Copy> DeltaMerkleProofCore<Hash> {
+    pub fn to_hash_256(&self) -> DeltaMerkleProofCore<Hash256> {
+        DeltaMerkleProofCore {
+            old_root: self.old_root.to_hash_256(),
+            old_value: self.old_value.to_hash_256(),
+            new_root: self.new_root.to_hash_256(),
+            new_value: self.new_value.to_hash_256(),
+            index: self.index,
+            siblings: self.siblings.iter().map(|x| x.to_hash_256()).collect(),
+        }
+    }
+}
 
 impl From<&DeltaMerkleProofCore<HashOut<GoldilocksField>>> for DeltaMerkleProofCore<Hash256> {
     fn from(proof: &DeltaMerkleProofCore<HashOut<GoldilocksField>>) -> Self {
@@ -189,16 +224,195 @@ pub fn calc_merkle_root_marked_if<Hash: PartialEq + Copy, Hasher: GeneralMerkleZ
     current
 }
 
-impl<Hash> KVQSerializable for MerkleProofCore<Hash>
+// Proves several leaves of the same tree at once. Siblings shared between the
+// leaf paths or computable from the proven leaves are only included once, in
+// the order returned by calc_multiproof_sibling_positions.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(bound = "Hash: Serialize, for<'de2> Hash: Deserialize<'de2>")]
+pub struct MerkleMultiProofCore<Hash>
+where
+    Hash: PartialEq + Copy + Serialize,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    pub root: Hash,
+    pub height: u8,
+
+    pub indices: Vec<u64>,
+    pub values: Vec<Hash>,
+    pub siblings: Vec<Hash>,
+}
+
+impl<Hash> MerkleMultiProofCore<Hash>
+where
+    Hash: PartialEq + Copy + Serialize,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    pub fn verify_marked_if<Hasher: GeneralMerkleZeroHasher<Hash>>(&self, marked: bool) -> bool {
+        verify_merkle_multiproof_core_marked_if::<Hash, Hasher>(self, marked)
+    }
+}
+
+// Returns the (level, index) of every node a multiproof for the given leaf
+// indices has to include, leaves first. Levels count from the root like
+// KVQMerkleNodeKey, so leaves are at `height`. The indices must be sorted and
+// unique.
+pub fn calc_multiproof_sibling_positions(height: u8, indices: &[u64]) -> Vec<(u8, u64)> {
+    let mut positions = Vec::new();
+    let mut current = indices.to_vec();
+    for level in (1..=height).rev() {
+        let mut parents = Vec::with_capacity(current.len());
+        let mut i = 0;
+        while i < current.len() {
+            let index = current[i];
+            if index & 1 == 0 && current.get(i + 1) == Some(&(index + 1)) {
+                i += 2;
+            } else {
+                positions.push((level, index ^ 1));
+                i += 1;
+            }
+            parents.push(index >> 1);
+        }
+        current = parents;
+    }
+    positions
+}
+
+pub fn calc_merkle_multiproof_root_marked_if<
+    Hash: PartialEq + Copy,
+    Hasher: GeneralMerkleZeroHasher<Hash>,
+>(
+    height: u8,
+    indices: &[u64],
+    values: &[Hash],
+    siblings: &[Hash],
+    marked: bool,
+) -> Option<Hash> {
+    if indices.is_empty()
+        || indices.len() != values.len()
+        || indices.windows(2).any(|w| w[0] >= w[1])
+        || (height < 64 && indices[indices.len() - 1] >> height != 0)
+    {
+        return None;
+    }
+    let mut current: Vec<(u64, Hash)> = indices
+        .iter()
+        .copied()
+        .zip(values.iter().copied())
+        .collect();
+    let mut siblings = siblings.iter();
+    for level in 0..(height as usize) {
+        let marked = level == 0 && marked;
+        let mut parents = Vec::with_capacity(current.len());
+        let mut i = 0;
+        while i < current.len() {
+            let (index, value) = current[i];
+            let (left, right) = if index & 1 == 0 {
+                match current.get(i + 1) {
+                    Some((next, next_value)) if *next == index + 1 => {
+                        i += 1;
+                        (value, *next_value)
+                    }
+                    _ => (value, *siblings.next()?),
+                }
+            } else {
+                (*siblings.next()?, value)
+            };
+            parents.push((
+                index >> 1,
+                Hasher::two_to_one_marked_if(&left, &right, marked),
+            ));
+            i += 1;
+        }
+        current = parents;
+    }
+    if siblings.next().is_some() {
+        return None;
+    }
+    Some(current[0].1)
+}
+
+pub fn verify_merkle_multiproof_core_marked_if<Hash, Hasher: GeneralMerkleZeroHasher<Hash>>(
+    proof: &MerkleMultiProofCore<Hash>,
+    marked: bool,
+) -> bool
 where
     Hash: PartialEq + Copy + Serialize,
     for<'de2> Hash: Deserialize<'de2>,
 {
+    calc_merkle_multiproof_root_marked_if::<Hash, Hasher>(
+        proof.height,
+        &proof.indices,
+        &proof.values,
+        &proof.siblings,
+        marked,
+    ) == Some(proof.root)
+}
+
+impl<Hash> KVQCompactCodec for MerkleProofCore<Hash>
+where
+    Hash: PartialEq + Copy + Serialize + KVQCompactCodec,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write(&self.root)?;
+        writer.write(&self.value)?;
+        writer.write(&self.index)?;
+        writer.write(&self.siblings)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(MerkleProofCore {
+            root: reader.read()?,
+            value: reader.read()?,
+            index: reader.read()?,
+            siblings: reader.read()?,
+        })
+    }
+}
+
+impl<Hash> KVQSerializable for MerkleProofCore<Hash>
+where
+    Hash: PartialEq + Copy + Serialize + KVQCompactCodec,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        to_compact_bytes(KVQ_COMPACT_TAG_MERKLE_PROOF, self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        from_compact_or_json_bytes(KVQ_COMPACT_TAG_MERKLE_PROOF, bytes)
+    }
+}
+
+impl<Hash: PartialEq + Copy + KVQCompactCodec> KVQCompactCodec for DeltaMerkleProofCore<Hash> {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write(&self.old_root)?;
+        writer.write(&self.old_value)?;
+        writer.write(&self.new_root)?;
+        writer.write(&self.new_value)?;
+        writer.write(&self.index)?;
+        writer.write(&self.siblings)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(DeltaMerkleProofCore {
+            old_root: reader.read()?,
+            old_value: reader.read()?,
+            new_root: reader.read()?,
+            new_value: reader.read()?,
+            index: reader.read()?,
+            siblings: reader.read()?,
+        })
+    }
+}
+
+// delta proofs were never stored as JSON, so there is nothing to fall back to
+impl<Hash: PartialEq + Copy + KVQCompactCodec> KVQSerializable for DeltaMerkleProofCore<Hash> {
     fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        Ok(serde_json::to_vec(self)?)
+        to_compact_bytes(KVQ_COMPACT_TAG_DELTA_MERKLE_PROOF, self)
     }
 
     fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(serde_json::from_slice(bytes)?)
+        from_compact_bytes(KVQ_COMPACT_TAG_DELTA_MERKLE_PROOF, bytes)
     }
 }
@@ -1,3 +1,4 @@
 pub mod core;
 pub mod store;
 pub mod traits;
+pub mod tree_hasher;
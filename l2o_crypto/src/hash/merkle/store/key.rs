@@ -1,4 +1,4 @@
-use kvq::traits::KVQSerializable;
+use l2o_derive::KVQSerializable;
 use plonky2::field::types::PrimeField64;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -51,7 +51,8 @@ impl KVQTreeIdentifier {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
 pub struct KVQMerkleNodeKey<const TABLE_TYPE: u16> {
     pub tree_id: u8,
     pub primary_id: u64,
@@ -107,31 +108,6 @@ impl<const TABLE_TYPE: u16> KVQMerkleNodeKey<TABLE_TYPE> {
         }
     }
 }
-impl<const TABLE_TYPE: u16> KVQSerializable for KVQMerkleNodeKey<TABLE_TYPE> {
-    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        let mut result: Vec<u8> = Vec::with_capacity(32);
-        result.push(((TABLE_TYPE & 0xFF00) >> 8) as u8); // 1
-        result.push((TABLE_TYPE & 0xFF) as u8); // 2
-        result.push(self.tree_id); // 3
-        result.extend_from_slice(&self.primary_id.to_be_bytes()); // 11
-        result.extend_from_slice(&self.secondary_id.to_be_bytes()); // 15
-        result.push(self.level); // 16
-        result.extend_from_slice(&self.index.to_be_bytes()); // 24
-        result.extend_from_slice(&self.checkpoint_id.to_be_bytes()); // 32
-        Ok(result)
-    }
-
-    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(Self {
-            tree_id: bytes[2],
-            primary_id: u64::from_be_bytes(bytes[3..11].try_into()?),
-            secondary_id: u32::from_be_bytes(bytes[11..15].try_into()?),
-            level: bytes[15],
-            index: u64::from_be_bytes(bytes[16..24].try_into()?),
-            checkpoint_id: u64::from_be_bytes(bytes[24..32].try_into()?),
-        })
-    }
-}
 impl<const TABLE_TYPE: u16> KVQMerkleNodeKey<TABLE_TYPE> {
     #[inline]
     pub const fn new(
@@ -210,7 +186,8 @@ impl<const TABLE_TYPE: u16> KVQMerkleNodeKey<TABLE_TYPE> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
 pub struct KVQAppendOnlyMerkleKey<const TABLE_TYPE: u16> {
     pub tree_id: u8,
     pub primary_id: u64,
@@ -218,30 +195,6 @@ pub struct KVQAppendOnlyMerkleKey<const TABLE_TYPE: u16> {
     pub checkpoint_id: u64,
     pub tick: String,
 }
-impl<const TABLE_TYPE: u16> KVQSerializable for KVQAppendOnlyMerkleKey<TABLE_TYPE> {
-    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        let mut result: Vec<u8> = Vec::with_capacity(32);
-        result.push(((TABLE_TYPE & 0xFF00) >> 8) as u8); // 1
-        result.push((TABLE_TYPE & 0xFF) as u8); // 2
-        result.push(self.tree_id); // 3
-        result.extend_from_slice(&self.primary_id.to_be_bytes()); // 11
-        result.extend_from_slice(&self.secondary_id.to_be_bytes()); // 15
-        result.extend_from_slice(&self.checkpoint_id.to_be_bytes()); // 23
-        result.extend_from_slice(self.tick.as_bytes());
-        Ok(result)
-    }
-
-    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(Self {
-            tree_id: bytes[2],
-            primary_id: u64::from_be_bytes(bytes[3..11].try_into()?),
-            secondary_id: u32::from_be_bytes(bytes[11..15].try_into()?),
-            checkpoint_id: u64::from_be_bytes(bytes[15..23].try_into()?),
-            tick: String::from_utf8(bytes[23..].to_vec())?,
-        })
-    }
-}
-
 impl<const TABLE_TYPE: u16> KVQAppendOnlyMerkleKey<TABLE_TYPE> {
     #[inline]
     pub const fn new(
@@ -274,3 +227,37 @@ impl<const TABLE_TYPE: u16> KVQAppendOnlyMerkleKey<TABLE_TYPE> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use kvq::traits::KVQSerializable;
+
+    use super::*;
+
+    #[test]
+    fn test_merkle_node_key_layout() {
+        let key = KVQMerkleNodeKey::<0x0102>::new(3, 4, 5, 6, 7, 8);
+        let bytes = key.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[0..3], &[1, 2, 3]);
+        assert_eq!(&bytes[3..11], &4u64.to_be_bytes());
+        assert_eq!(&bytes[11..15], &5u32.to_be_bytes());
+        assert_eq!(bytes[15], 6);
+        assert_eq!(&bytes[16..24], &7u64.to_be_bytes());
+        assert_eq!(&bytes[24..32], &8u64.to_be_bytes());
+        assert_eq!(KVQMerkleNodeKey::<0x0102>::from_bytes(&bytes).unwrap(), key);
+        assert!(KVQMerkleNodeKey::<0x0103>::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_append_only_key_layout() {
+        let key = KVQAppendOnlyMerkleKey::<1>::new(2, 3, 4, 5, "ordi".to_string());
+        let bytes = key.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 23 + 4);
+        assert_eq!(&bytes[23..], b"ordi");
+        assert_eq!(
+            KVQAppendOnlyMerkleKey::<1>::from_bytes(&bytes).unwrap(),
+            key
+        );
+    }
+}
@@ -1,2 +1,3 @@
 pub mod key;
 pub mod model;
+pub mod sparse;
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use kvq::codec::KVQCompactCodec;
 use kvq::traits::KVQBinaryStore;
 use kvq::traits::KVQBinaryStoreReader;
 use kvq::traits::KVQPair;
@@ -12,13 +14,15 @@ use serde::Serialize;
 use super::key::KVQMerkleNodeKey;
 use crate::hash::merkle::core::calc_merkle_path;
 use crate::hash::merkle::core::calc_merkle_root_marked_if;
+use crate::hash::merkle::core::calc_multiproof_sibling_positions;
 use crate::hash::merkle::core::DeltaMerkleProofCore;
+use crate::hash::merkle::core::MerkleMultiProofCore;
 use crate::hash::merkle::core::MerkleProofCore;
 use crate::hash::merkle::store::key::KVQAppendOnlyMerkleKey;
 use crate::hash::merkle::traits::GeneralMerkleZeroHasher;
 use crate::hash::traits::ZeroableHash;
 
-const CHECKPOINT_SIZE: usize = 8;
+pub(crate) const CHECKPOINT_SIZE: usize = 8;
 
 pub struct KVQMerkleTreeModel<
     const TABLE_TYPE: u16,
@@ -92,6 +96,59 @@ where
             index: key.index,
         })
     }
+    fn check_same_tree(keys: &[KVQMerkleNodeKey<TABLE_TYPE>]) -> anyhow::Result<()> {
+        let first = match keys.first() {
+            Some(first) => first,
+            None => anyhow::bail!("Expected at least one leaf"),
+        };
+        for key in keys.iter() {
+            if key.tree_id != first.tree_id
+                || key.primary_id != first.primary_id
+                || key.secondary_id != first.secondary_id
+                || key.checkpoint_id != first.checkpoint_id
+                || key.level != first.level
+            {
+                anyhow::bail!("All leaves must be in the same tree, checkpoint and level");
+            }
+        }
+        Ok(())
+    }
+    pub fn get_multiproof(
+        store: &S,
+        keys: &[KVQMerkleNodeKey<TABLE_TYPE>],
+    ) -> anyhow::Result<MerkleMultiProofCore<Hash>> {
+        Self::check_same_tree(keys)?;
+        let first = keys[0];
+        let height = first.level;
+
+        let mut indices = keys.iter().map(|key| key.index).collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        let positions = calc_multiproof_sibling_positions(height, &indices);
+
+        let node_keys = indices
+            .iter()
+            .map(|index| (height, *index))
+            .chain(positions.iter().copied())
+            .map(|(level, index)| KVQMerkleNodeKey {
+                level,
+                index,
+                ..first
+            })
+            .chain(std::iter::once(first.root()))
+            .collect::<Vec<_>>();
+        let mut nodes = Self::get_nodes(store, &node_keys)?;
+
+        let root = nodes.pop().unwrap();
+        let siblings = nodes.split_off(indices.len());
+        Ok(MerkleMultiProofCore {
+            root,
+            height,
+            indices,
+            values: nodes,
+            siblings,
+        })
+    }
 }
 
 impl<
@@ -156,13 +213,105 @@ where
             index: key.index,
         })
     }
+    // Applies the leaves in order, like repeated calls to set_leaf at the same
+    // checkpoint, but reads the affected paths once and writes every touched
+    // node once. The returned delta proofs chain, each old_root being the
+    // previous new_root.
+    pub fn set_leaves(
+        store: &mut S,
+        leaves: &[KVQPair<KVQMerkleNodeKey<TABLE_TYPE>, Hash>],
+    ) -> anyhow::Result<Vec<DeltaMerkleProofCore<Hash>>> {
+        if leaves.is_empty() {
+            return Ok(Vec::new());
+        }
+        let keys = leaves.iter().map(|leaf| leaf.key).collect::<Vec<_>>();
+        let proof = Self::get_multiproof(store, &keys)?;
+        let first = keys[0];
+        let height = first.level;
+
+        let mut nodes: HashMap<(u8, u64), Hash> = HashMap::new();
+        for (index, value) in proof.indices.iter().zip(proof.values.iter()) {
+            nodes.insert((height, *index), *value);
+        }
+        let positions = calc_multiproof_sibling_positions(height, &proof.indices);
+        for (position, sibling) in positions.into_iter().zip(proof.siblings) {
+            nodes.insert(position, sibling);
+        }
+
+        // rebuild the current state of every node on the updated paths
+        let mut path_positions = proof
+            .indices
+            .iter()
+            .map(|index| (height, *index))
+            .collect::<Vec<_>>();
+        let mut current = proof.indices;
+        for level in (1..=height).rev() {
+            let mark_leaves = level == height && MARK_LEAVES;
+            let mut parents = current.iter().map(|index| index >> 1).collect::<Vec<_>>();
+            parents.dedup();
+            for parent in parents.iter() {
+                let left = nodes[&(level, parent << 1)];
+                let right = nodes[&(level, (parent << 1) | 1)];
+                nodes.insert(
+                    (level - 1, *parent),
+                    Hasher::two_to_one_marked_if(&left, &right, mark_leaves),
+                );
+                path_positions.push((level - 1, *parent));
+            }
+            current = parents;
+        }
+
+        let mut deltas = Vec::with_capacity(leaves.len());
+        for leaf in leaves.iter() {
+            let old_root = nodes[&(0, 0)];
+            let old_value = nodes[&(height, leaf.key.index)];
+            let mut siblings = Vec::with_capacity(height as usize);
+            let mut index = leaf.key.index;
+            let mut current_value = leaf.value;
+            nodes.insert((height, index), current_value);
+            for level in (1..=height).rev() {
+                let mark_leaves = level == height && MARK_LEAVES;
+                let sibling = nodes[&(level, index ^ 1)];
+                current_value = if index & 1 == 0 {
+                    Hasher::two_to_one_marked_if(&current_value, &sibling, mark_leaves)
+                } else {
+                    Hasher::two_to_one_marked_if(&sibling, &current_value, mark_leaves)
+                };
+                siblings.push(sibling);
+                index >>= 1;
+                nodes.insert((level - 1, index), current_value);
+            }
+            deltas.push(DeltaMerkleProofCore {
+                old_root,
+                old_value,
+                new_root: current_value,
+                new_value: leaf.value,
+                siblings,
+                index: leaf.key.index,
+            });
+        }
+
+        let updates = path_positions
+            .into_iter()
+            .map(|(level, index)| KVQPair {
+                key: KVQMerkleNodeKey {
+                    level,
+                    index,
+                    ..first
+                },
+                value: nodes[&(level, index)],
+            })
+            .collect::<Vec<_>>();
+        Self::set_nodes(store, &updates)?;
+        Ok(deltas)
+    }
 }
 
 pub struct KVQAppendOnlyMerkleTreeModel<
     const TABLE_TYPE: u16,
     const TREE_HEIGHT: u8,
     S: KVQBinaryStoreReader,
-    Hash: Copy + PartialEq + KVQSerializable + Serialize + ZeroableHash,
+    Hash: Copy + PartialEq + KVQSerializable + KVQCompactCodec + Serialize + ZeroableHash,
     Hasher: GeneralMerkleZeroHasher<Hash>,
     KVA: KVQStoreAdapterReader<S, KVQAppendOnlyMerkleKey<TABLE_TYPE>, MerkleProofCore<Hash>>,
 > where
@@ -177,18 +326,19 @@ impl<
         const TABLE_TYPE: u16,
         const TREE_HEIGHT: u8,
         S: KVQBinaryStoreReader,
-        Hash: PartialEq + KVQSerializable + Copy + Serialize + ZeroableHash,
+        Hash: PartialEq + KVQSerializable + KVQCompactCodec + Copy + Serialize + ZeroableHash,
         Hasher: GeneralMerkleZeroHasher<Hash>,
         KVA: KVQStoreAdapterReader<S, KVQAppendOnlyMerkleKey<TABLE_TYPE>, MerkleProofCore<Hash>>,
     > KVQAppendOnlyMerkleTreeModel<TABLE_TYPE, TREE_HEIGHT, S, Hash, Hasher, KVA>
 where
     for<'de2> Hash: Deserialize<'de2>,
 {
+    // none when nothing has been appended yet
     pub fn get_last_proof(
         store: &S,
         key: &KVQAppendOnlyMerkleKey<TABLE_TYPE>,
-    ) -> anyhow::Result<MerkleProofCore<Hash>> {
-        KVA::get_exact(&store, &key)
+    ) -> anyhow::Result<Option<MerkleProofCore<Hash>>> {
+        KVA::get_exact_if_exists(&store, &key)
     }
 }
 
@@ -196,7 +346,7 @@ impl<
         const TABLE_TYPE: u16,
         const TREE_HEIGHT: u8,
         S: KVQBinaryStore,
-        Hash: PartialEq + KVQSerializable + Copy + Serialize + ZeroableHash,
+        Hash: PartialEq + KVQSerializable + KVQCompactCodec + Copy + Serialize + ZeroableHash,
         Hasher: GeneralMerkleZeroHasher<Hash>,
         KVA: KVQStoreAdapter<S, KVQAppendOnlyMerkleKey<TABLE_TYPE>, MerkleProofCore<Hash>>,
     > KVQAppendOnlyMerkleTreeModel<TABLE_TYPE, TREE_HEIGHT, S, Hash, Hasher, KVA>
@@ -208,42 +358,49 @@ where
         key: &KVQAppendOnlyMerkleKey<TABLE_TYPE>,
         value: Hash,
     ) -> anyhow::Result<DeltaMerkleProofCore<Hash>> {
-        let mut last_proof = Self::get_last_proof(store, key)?;
-
-        let old_merkle_path = calc_merkle_path::<Hash, Hasher>(
-            last_proof.value,
-            &last_proof.siblings,
-            last_proof.index,
-        );
-        let old_root = last_proof.root;
         let old_value = Hash::get_zero_value();
-        let prev_index = last_proof.index;
-        let new_index = prev_index + 1;
-        let mut siblings = Vec::new();
-        let mut multiplier = 1;
+        let (old_root, new_index, siblings) = match Self::get_last_proof(store, key)? {
+            Some(last_proof) => {
+                let old_merkle_path = calc_merkle_path::<Hash, Hasher>(
+                    last_proof.value,
+                    &last_proof.siblings,
+                    last_proof.index,
+                );
+                let prev_index = last_proof.index;
+                let new_index = prev_index + 1;
+                let mut siblings = Vec::new();
+                let mut multiplier = 1;
 
-        for level in 0..(TREE_HEIGHT as usize) {
-            let prev_level_index = prev_index / multiplier;
-            let new_level_index = new_index / multiplier;
+                for level in 0..(TREE_HEIGHT as usize) {
+                    let prev_level_index = prev_index / multiplier;
+                    let new_level_index = new_index / multiplier;
 
-            if new_level_index == prev_level_index {
-                siblings.push(last_proof.siblings[level]);
-            } else {
-                if new_level_index & 1 == 0 {
-                    siblings.push(Hasher::get_zero_hash_marked_if(
-                        TREE_HEIGHT as usize - level,
-                        false,
-                    ));
-                } else {
-                    siblings.push(old_merkle_path[level]);
+                    if new_level_index == prev_level_index {
+                        siblings.push(last_proof.siblings[level]);
+                    } else {
+                        if new_level_index & 1 == 0 {
+                            siblings.push(Hasher::get_zero_hash_marked_if(level, false));
+                        } else {
+                            siblings.push(old_merkle_path[level]);
+                        }
+                    }
+                    multiplier *= 2;
                 }
+                (last_proof.root, new_index, siblings)
             }
-            multiplier *= 2;
-        }
+            // nothing has been appended yet, the first leaf goes into an empty tree
+            None => (
+                Hasher::get_zero_hash(TREE_HEIGHT as usize),
+                0,
+                (0..(TREE_HEIGHT as usize))
+                    .map(|level| Hasher::get_zero_hash(level))
+                    .collect::<Vec<_>>(),
+            ),
+        };
 
         let new_root =
             calc_merkle_root_marked_if::<Hash, Hasher>(value, &siblings, new_index, false);
-        last_proof = MerkleProofCore {
+        let last_proof = MerkleProofCore {
             index: new_index,
             siblings: siblings.clone(),
             root: new_root,
@@ -262,3 +419,129 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use kvq::adapters::standard::KVQStandardAdapter;
+    use kvq::memory::simple::KVQSimpleMemoryBackingStore;
+    use l2o_common::common::data::hash::Hash256;
+
+    use super::*;
+    use crate::hash::hash_functions::sha256::Sha256Hasher;
+    use crate::hash::merkle::traits::MerkleZeroHasher;
+
+    const TABLE_TYPE: u16 = 1;
+    const TREE_HEIGHT: u8 = 8;
+    type TestTree = KVQMerkleTreeModel<
+        TABLE_TYPE,
+        TREE_HEIGHT,
+        false,
+        KVQSimpleMemoryBackingStore,
+        Hash256,
+        Sha256Hasher,
+        KVQStandardAdapter<KVQSimpleMemoryBackingStore, KVQMerkleNodeKey<TABLE_TYPE>, Hash256>,
+    >;
+
+    fn leaf_key(index: u64, checkpoint_id: u64) -> KVQMerkleNodeKey<TABLE_TYPE> {
+        KVQMerkleNodeKey::new(0, 1, 2, TREE_HEIGHT, index, checkpoint_id)
+    }
+
+    #[test]
+    fn test_set_leaves_matches_set_leaf() {
+        let indices = [5u64, 4, 200, 5, 17];
+        let leaves = indices
+            .iter()
+            .enumerate()
+            .map(|(i, index)| KVQPair {
+                key: leaf_key(*index, 1),
+                value: Hash256([i as u8 + 1; 32]),
+            })
+            .collect::<Vec<_>>();
+
+        let mut batch_store = KVQSimpleMemoryBackingStore::new();
+        let batch_deltas = TestTree::set_leaves(&mut batch_store, &leaves).unwrap();
+
+        // reads only see earlier checkpoints, so apply the serial updates one
+        // checkpoint apart
+        let mut serial_store = KVQSimpleMemoryBackingStore::new();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let delta = TestTree::set_leaf(
+                &mut serial_store,
+                &leaf_key(leaf.key.index, i as u64 + 1),
+                leaf.value,
+            )
+            .unwrap();
+            assert!(batch_deltas[i].verify_marked_if::<Sha256Hasher>(false));
+            assert_eq!(batch_deltas[i], delta);
+        }
+
+        let keys = indices
+            .iter()
+            .map(|index| leaf_key(*index, 2))
+            .collect::<Vec<_>>();
+        let multiproof = TestTree::get_multiproof(&batch_store, &keys).unwrap();
+        assert_eq!(multiproof.root, batch_deltas[4].new_root);
+        assert_eq!(multiproof.indices, vec![4, 5, 17, 200]);
+        assert_eq!(multiproof.values[1], Hash256([4u8; 32]));
+        assert!(multiproof.siblings.len() < 4 * TREE_HEIGHT as usize);
+        assert!(multiproof.verify_marked_if::<Sha256Hasher>(false));
+
+        let mut tampered = multiproof.clone();
+        tampered.values[2] = Hash256([9u8; 32]);
+        assert!(!tampered.verify_marked_if::<Sha256Hasher>(false));
+        let mut tampered = multiproof;
+        tampered.siblings.pop();
+        assert!(!tampered.verify_marked_if::<Sha256Hasher>(false));
+    }
+
+    #[test]
+    fn test_append_leaf() {
+        type AppendOnlyTree = KVQAppendOnlyMerkleTreeModel<
+            TABLE_TYPE,
+            TREE_HEIGHT,
+            KVQSimpleMemoryBackingStore,
+            Hash256,
+            Sha256Hasher,
+            KVQStandardAdapter<
+                KVQSimpleMemoryBackingStore,
+                KVQAppendOnlyMerkleKey<TABLE_TYPE>,
+                MerkleProofCore<Hash256>,
+            >,
+        >;
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        let key = KVQAppendOnlyMerkleKey::new(0, 1, 2, 0, "ordi".to_string());
+
+        let mut fixed_store = KVQSimpleMemoryBackingStore::new();
+        let mut old_root = Sha256Hasher::get_zero_hash(TREE_HEIGHT as usize);
+        for i in 0..5u64 {
+            let value = Hash256([i as u8 + 1; 32]);
+            let delta = AppendOnlyTree::append_leaf(&mut store, &key, value).unwrap();
+            assert_eq!(delta.index, i);
+            assert_eq!(delta.old_root, old_root);
+            assert!(delta.verify_marked_if::<Sha256Hasher>(false));
+
+            // the append-only tree matches a fixed index tree with the same leaves
+            let fixed = TestTree::set_leaf(&mut fixed_store, &leaf_key(i, i + 1), value).unwrap();
+            assert_eq!(delta.new_root, fixed.new_root);
+            old_root = delta.new_root;
+        }
+        assert_eq!(
+            AppendOnlyTree::get_last_proof(&store, &key)
+                .unwrap()
+                .unwrap()
+                .index,
+            4
+        );
+
+        // a last proof that does not decode is an error, not an empty tree
+        let mut corrupt_store = KVQSimpleMemoryBackingStore::new();
+        corrupt_store
+            .set(key.to_bytes().unwrap(), vec![1, 2, 3])
+            .unwrap();
+        assert!(AppendOnlyTree::append_leaf(&mut corrupt_store, &key, Hash256([1u8; 32])).is_err());
+        assert_eq!(
+            corrupt_store.get_exact(&key.to_bytes().unwrap()).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+}
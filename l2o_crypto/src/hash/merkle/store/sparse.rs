@@ -0,0 +1,442 @@
+use std::marker::PhantomData;
+
+use kvq::traits::KVQBinaryStore;
+use kvq::traits::KVQBinaryStoreReader;
+use kvq::traits::KVQPair;
+use kvq::traits::KVQSerializable;
+use kvq::traits::KVQStoreAdapter;
+use kvq::traits::KVQStoreAdapterReader;
+use l2o_common::common::data::hash::Hash256;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::key::KVQMerkleNodeKey;
+use super::key::KVQTreeIdentifier;
+use super::key::KVQTreeNodePosition;
+use super::model::KVQMerkleTreeModel;
+use super::model::CHECKPOINT_SIZE;
+use crate::hash::hash_functions::sha256;
+use crate::hash::merkle::core::DeltaMerkleProofCore;
+use crate::hash::merkle::core::MerkleProofCore;
+use crate::hash::merkle::traits::GeneralMerkleZeroHasher;
+use crate::hash::traits::L2OHash;
+
+// Keys are hashed to their paths, so keys sharing a prefix are spread over the
+// tree. The tree is as deep as a node index can address, a leaf is shared only
+// by keys whose hashes agree on their top 64 bits.
+pub const SPARSE_MERKLE_TREE_HEIGHT: u8 = 64;
+
+pub fn get_sparse_merkle_path<Hash: L2OHash>(key: &Hash) -> Hash256 {
+    sha256::hash(&key.to_hash_256().0)
+}
+
+pub fn get_sparse_merkle_index<Hash: L2OHash>(key: &Hash) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&get_sparse_merkle_path(key).0[0..8]);
+    u64::from_be_bytes(buf)
+}
+
+// The key and value of a leaf are kept below the leaves, at levels no tree
+// reaches, so the key occupying a slot can be told apart from the other keys
+// sharing its path.
+const LEAF_KEY_LEVEL: u8 = u8::MAX;
+const LEAF_VALUE_LEVEL: u8 = u8::MAX - 1;
+
+// Leaves commit to the key as well as the value, so an occupied slot can never
+// be mistaken for another key that shares the same path.
+pub fn get_sparse_merkle_leaf<Hash: PartialEq + Copy, Hasher: GeneralMerkleZeroHasher<Hash>>(
+    key: &Hash,
+    value: &Hash,
+) -> Hash {
+    Hasher::two_to_one(key, value)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct SparseMerkleLeaf<Hash> {
+    pub key: Hash,
+    pub value: Hash,
+}
+
+// A key is excluded by an empty slot, or by a slot another key occupies.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(bound = "Hash: Serialize, for<'de2> Hash: Deserialize<'de2>")]
+pub struct SparseMerkleExclusionProof<Hash>
+where
+    Hash: PartialEq + Copy + Serialize,
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    pub proof: MerkleProofCore<Hash>,
+    pub occupant: Option<SparseMerkleLeaf<Hash>>,
+}
+
+pub fn verify_sparse_merkle_inclusion_proof<Hash, Hasher>(
+    proof: &MerkleProofCore<Hash>,
+    key: &Hash,
+    value: &Hash,
+    marked: bool,
+) -> bool
+where
+    Hash: PartialEq + Copy + Serialize + L2OHash,
+    for<'de2> Hash: Deserialize<'de2>,
+    Hasher: GeneralMerkleZeroHasher<Hash>,
+{
+    proof.siblings.len() == SPARSE_MERKLE_TREE_HEIGHT as usize
+        && proof.index == get_sparse_merkle_index(key)
+        && proof.value == get_sparse_merkle_leaf::<Hash, Hasher>(key, value)
+        && proof.verify_marked_if::<Hasher>(marked)
+}
+
+pub fn verify_sparse_merkle_exclusion_proof<Hash, Hasher>(
+    exclusion: &SparseMerkleExclusionProof<Hash>,
+    key: &Hash,
+    marked: bool,
+) -> bool
+where
+    Hash: PartialEq + Copy + Serialize + L2OHash,
+    for<'de2> Hash: Deserialize<'de2>,
+    Hasher: GeneralMerkleZeroHasher<Hash>,
+{
+    let proof = &exclusion.proof;
+    let slot_value = match exclusion.occupant {
+        None => Hasher::get_zero_hash(0),
+        Some(ref occupant) if occupant.key != *key => {
+            get_sparse_merkle_leaf::<Hash, Hasher>(&occupant.key, &occupant.value)
+        }
+        Some(_) => return false,
+    };
+    proof.siblings.len() == SPARSE_MERKLE_TREE_HEIGHT as usize
+        && proof.index == get_sparse_merkle_index(key)
+        && proof.value == slot_value
+        && proof.verify_marked_if::<Hasher>(marked)
+}
+
+pub struct KVQSparseMerkleTreeModel<
+    const TABLE_TYPE: u16,
+    const MARK_LEAVES: bool,
+    S: KVQBinaryStoreReader,
+    Hash: Copy + PartialEq + KVQSerializable + Serialize + L2OHash,
+    Hasher: GeneralMerkleZeroHasher<Hash>,
+    KVA: KVQStoreAdapterReader<S, KVQMerkleNodeKey<TABLE_TYPE>, Hash>,
+> where
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    _hasher: PhantomData<Hasher>,
+    _hash: PhantomData<Hash>,
+    _s: PhantomData<S>,
+    _kva: PhantomData<KVA>,
+}
+impl<
+        const TABLE_TYPE: u16,
+        const MARK_LEAVES: bool,
+        S: KVQBinaryStoreReader,
+        Hash: PartialEq + KVQSerializable + Copy + Serialize + L2OHash,
+        Hasher: GeneralMerkleZeroHasher<Hash>,
+        KVA: KVQStoreAdapterReader<S, KVQMerkleNodeKey<TABLE_TYPE>, Hash>,
+    > KVQSparseMerkleTreeModel<TABLE_TYPE, MARK_LEAVES, S, Hash, Hasher, KVA>
+where
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    pub fn get_node_key(
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+    ) -> KVQMerkleNodeKey<TABLE_TYPE> {
+        KVQMerkleNodeKey::from_identifier_position(
+            tree,
+            checkpoint_id,
+            KVQTreeNodePosition::new(SPARSE_MERKLE_TREE_HEIGHT, get_sparse_merkle_index(key)),
+        )
+    }
+    fn get_leaf_part_key(
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+        level: u8,
+    ) -> KVQMerkleNodeKey<TABLE_TYPE> {
+        KVQMerkleNodeKey::from_identifier_position(
+            tree,
+            checkpoint_id,
+            KVQTreeNodePosition::new(level, get_sparse_merkle_index(key)),
+        )
+    }
+    fn get_leaf_part(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+        level: u8,
+    ) -> anyhow::Result<Hash> {
+        KVA::get_leq(
+            store,
+            &Self::get_leaf_part_key(tree, checkpoint_id, key, level),
+            CHECKPOINT_SIZE,
+        )?
+        .ok_or_else(|| anyhow::anyhow!("sparse merkle leaf is missing its key or value"))
+    }
+    // the key and value in the slot of key, none if the slot is empty
+    pub fn get_occupant(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+    ) -> anyhow::Result<Option<SparseMerkleLeaf<Hash>>> {
+        let leaf = KVQMerkleTreeModel::<
+            TABLE_TYPE,
+            SPARSE_MERKLE_TREE_HEIGHT,
+            MARK_LEAVES,
+            S,
+            Hash,
+            Hasher,
+            KVA,
+        >::get_node(store, &Self::get_node_key(tree, checkpoint_id, key))?;
+        // untouched slots read as the marked zero leaf, deleted ones hold the zero hash
+        if leaf == Hasher::get_zero_hash_marked_if(0, MARK_LEAVES)
+            || leaf == Hasher::get_zero_hash(0)
+        {
+            return Ok(None);
+        }
+        Ok(Some(SparseMerkleLeaf {
+            key: Self::get_leaf_part(store, tree, checkpoint_id, key, LEAF_KEY_LEVEL)?,
+            value: Self::get_leaf_part(store, tree, checkpoint_id, key, LEAF_VALUE_LEVEL)?,
+        }))
+    }
+    pub fn get_root(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+    ) -> anyhow::Result<Hash> {
+        KVQMerkleTreeModel::<TABLE_TYPE, SPARSE_MERKLE_TREE_HEIGHT, MARK_LEAVES, S, Hash, Hasher, KVA>::get_node(
+            store,
+            &KVQMerkleNodeKey::from_identifier_position(
+                tree,
+                checkpoint_id,
+                KVQTreeNodePosition::root(),
+            ),
+        )
+    }
+    // proof of whatever currently occupies the key's slot
+    pub fn get_proof(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+    ) -> anyhow::Result<MerkleProofCore<Hash>> {
+        KVQMerkleTreeModel::<TABLE_TYPE, SPARSE_MERKLE_TREE_HEIGHT, MARK_LEAVES, S, Hash, Hasher, KVA>::get_leaf(
+            store,
+            &Self::get_node_key(tree, checkpoint_id, key),
+        )
+    }
+    pub fn get_inclusion_proof(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+        value: &Hash,
+    ) -> anyhow::Result<MerkleProofCore<Hash>> {
+        let proof = Self::get_proof(store, tree, checkpoint_id, key)?;
+        if proof.value != get_sparse_merkle_leaf::<Hash, Hasher>(key, value) {
+            anyhow::bail!("Key is not in the sparse merkle tree with the given value");
+        }
+        Ok(proof)
+    }
+    pub fn get_exclusion_proof(
+        store: &S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+    ) -> anyhow::Result<SparseMerkleExclusionProof<Hash>> {
+        let occupant = Self::get_occupant(store, tree, checkpoint_id, key)?;
+        if occupant.is_some_and(|occupant| occupant.key == *key) {
+            anyhow::bail!("Key is in the sparse merkle tree");
+        }
+        Ok(SparseMerkleExclusionProof {
+            proof: Self::get_proof(store, tree, checkpoint_id, key)?,
+            occupant,
+        })
+    }
+}
+
+impl<
+        const TABLE_TYPE: u16,
+        const MARK_LEAVES: bool,
+        S: KVQBinaryStore,
+        Hash: PartialEq + KVQSerializable + Copy + Serialize + L2OHash,
+        Hasher: GeneralMerkleZeroHasher<Hash>,
+        KVA: KVQStoreAdapter<S, KVQMerkleNodeKey<TABLE_TYPE>, Hash>,
+    > KVQSparseMerkleTreeModel<TABLE_TYPE, MARK_LEAVES, S, Hash, Hasher, KVA>
+where
+    for<'de2> Hash: Deserialize<'de2>,
+{
+    // The delta proof's old and new values are leaf hashes, see
+    // get_sparse_merkle_leaf. Setting a key whose slot is held by another key,
+    // one with the same top 64 bits of its hash, fails.
+    pub fn set(
+        store: &mut S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+        value: &Hash,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash>> {
+        if Self::get_occupant(store, tree, checkpoint_id, key)?
+            .is_some_and(|occupant| occupant.key != *key)
+        {
+            anyhow::bail!("Key hash collides with another key in the sparse merkle tree");
+        }
+        KVA::set_many(
+            store,
+            &[
+                KVQPair {
+                    key: Self::get_leaf_part_key(tree, checkpoint_id, key, LEAF_KEY_LEVEL),
+                    value: *key,
+                },
+                KVQPair {
+                    key: Self::get_leaf_part_key(tree, checkpoint_id, key, LEAF_VALUE_LEVEL),
+                    value: *value,
+                },
+            ],
+        )?;
+        KVQMerkleTreeModel::<TABLE_TYPE, SPARSE_MERKLE_TREE_HEIGHT, MARK_LEAVES, S, Hash, Hasher, KVA>::set_leaf(
+            store,
+            &Self::get_node_key(tree, checkpoint_id, key),
+            get_sparse_merkle_leaf::<Hash, Hasher>(key, value),
+        )
+    }
+    pub fn delete(
+        store: &mut S,
+        tree: &KVQTreeIdentifier,
+        checkpoint_id: u64,
+        key: &Hash,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash>> {
+        if !Self::get_occupant(store, tree, checkpoint_id, key)?
+            .is_some_and(|occupant| occupant.key == *key)
+        {
+            anyhow::bail!("Key is not in the sparse merkle tree");
+        }
+        KVQMerkleTreeModel::<TABLE_TYPE, SPARSE_MERKLE_TREE_HEIGHT, MARK_LEAVES, S, Hash, Hasher, KVA>::set_leaf(
+            store,
+            &Self::get_node_key(tree, checkpoint_id, key),
+            Hasher::get_zero_hash(0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kvq::adapters::standard::KVQStandardAdapter;
+    use kvq::memory::simple::KVQSimpleMemoryBackingStore;
+    use l2o_common::common::data::hash::Hash256;
+
+    use super::*;
+    use crate::hash::hash_functions::sha256::Sha256Hasher;
+
+    const TABLE_TYPE: u16 = 1;
+    type TestTree = KVQSparseMerkleTreeModel<
+        TABLE_TYPE,
+        false,
+        KVQSimpleMemoryBackingStore,
+        Hash256,
+        Sha256Hasher,
+        KVQStandardAdapter<KVQSimpleMemoryBackingStore, KVQMerkleNodeKey<TABLE_TYPE>, Hash256>,
+    >;
+
+    #[test]
+    fn test_sparse_merkle_proofs() {
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        let tree = KVQTreeIdentifier::new(0, 1, 2);
+        let key = Hash256([7u8; 32]);
+        let other = Hash256([9u8; 32]);
+        let value = Hash256([1u8; 32]);
+
+        let exclusion = TestTree::get_exclusion_proof(&store, &tree, 1, &key).unwrap();
+        assert!(
+            verify_sparse_merkle_exclusion_proof::<Hash256, Sha256Hasher>(&exclusion, &key, false)
+        );
+
+        let delta = TestTree::set(&mut store, &tree, 1, &key, &value).unwrap();
+        assert!(delta.verify_marked_if::<Sha256Hasher>(false));
+        assert_eq!(delta.old_root, exclusion.proof.root);
+
+        // nodes written at a checkpoint are visible to later checkpoints
+        let inclusion = TestTree::get_inclusion_proof(&store, &tree, 2, &key, &value).unwrap();
+        assert_eq!(inclusion.root, delta.new_root);
+        assert!(
+            verify_sparse_merkle_inclusion_proof::<Hash256, Sha256Hasher>(
+                &inclusion, &key, &value, false
+            )
+        );
+        assert!(
+            !verify_sparse_merkle_inclusion_proof::<Hash256, Sha256Hasher>(
+                &inclusion, &other, &value, false
+            )
+        );
+        assert!(TestTree::get_exclusion_proof(&store, &tree, 2, &key).is_err());
+
+        let other_exclusion = TestTree::get_exclusion_proof(&store, &tree, 2, &other).unwrap();
+        assert_eq!(other_exclusion.proof.root, delta.new_root);
+        assert!(
+            verify_sparse_merkle_exclusion_proof::<Hash256, Sha256Hasher>(
+                &other_exclusion,
+                &other,
+                false
+            )
+        );
+
+        let removed = TestTree::delete(&mut store, &tree, 2, &key).unwrap();
+        assert_eq!(removed.new_root, exclusion.proof.root);
+        assert!(TestTree::delete(&mut store, &tree, 3, &key).is_err());
+    }
+
+    #[test]
+    fn test_sparse_merkle_keys_sharing_a_prefix() {
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        let tree = KVQTreeIdentifier::new(0, 1, 2);
+        let key = Hash256([7u8; 32]);
+        // all but the last byte shared, the raw key bits would put both in one slot
+        let mut sharing = [7u8; 32];
+        sharing[31] = 8;
+        let sharing = Hash256(sharing);
+        let value = Hash256([1u8; 32]);
+        let other_value = Hash256([2u8; 32]);
+        assert_ne!(
+            get_sparse_merkle_index(&key),
+            get_sparse_merkle_index(&sharing)
+        );
+
+        let first = TestTree::set(&mut store, &tree, 1, &key, &value).unwrap();
+        let second = TestTree::set(&mut store, &tree, 2, &sharing, &other_value).unwrap();
+        assert_eq!(second.old_root, first.new_root);
+        assert!(second.verify_marked_if::<Sha256Hasher>(false));
+        assert_eq!(
+            TestTree::get_root(&store, &tree, 3).unwrap(),
+            second.new_root
+        );
+
+        for (key, value) in [(key, value), (sharing, other_value)] {
+            let inclusion = TestTree::get_inclusion_proof(&store, &tree, 3, &key, &value).unwrap();
+            assert_eq!(inclusion.root, second.new_root);
+            assert!(
+                verify_sparse_merkle_inclusion_proof::<Hash256, Sha256Hasher>(
+                    &inclusion, &key, &value, false
+                )
+            );
+            assert!(TestTree::get_exclusion_proof(&store, &tree, 3, &key).is_err());
+        }
+        let inclusion = TestTree::get_inclusion_proof(&store, &tree, 3, &key, &value).unwrap();
+        assert!(
+            !verify_sparse_merkle_inclusion_proof::<Hash256, Sha256Hasher>(
+                &inclusion, &sharing, &value, false
+            )
+        );
+
+        // deleting one key leaves the other in place
+        let removed = TestTree::delete(&mut store, &tree, 3, &key).unwrap();
+        let exclusion = TestTree::get_exclusion_proof(&store, &tree, 4, &key).unwrap();
+        assert_eq!(exclusion.occupant, None);
+        assert!(
+            verify_sparse_merkle_exclusion_proof::<Hash256, Sha256Hasher>(&exclusion, &key, false)
+        );
+        let inclusion =
+            TestTree::get_inclusion_proof(&store, &tree, 4, &sharing, &other_value).unwrap();
+        assert_eq!(inclusion.root, removed.new_root);
+    }
+}
@@ -0,0 +1,105 @@
+use kvq::codec::KVQCompactCodec;
+use kvq::traits::KVQSerializable;
+use l2o_common::common::data::hash::Hash256;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::fields::bn254::hash::hash256_to_bn254_hash;
+use crate::fields::goldilocks::hash::hash256_to_goldilocks_hash;
+use crate::fields::goldilocks::hash::GHashOut;
+use crate::hash::hash_functions::blake3::Blake3Hasher;
+use crate::hash::hash_functions::keccak256::Keccak256Hasher;
+use crate::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+use crate::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+use crate::hash::hash_functions::sha256::Sha256Hasher;
+use crate::hash::merkle::core::MerkleProofCore;
+use crate::hash::merkle::traits::GeneralMerkleZeroHasher;
+use crate::hash::traits::L2OHash;
+
+// How a Hash256 becomes a leaf of a tree whose nodes are not Hash256s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L2OLeafEncoding {
+    // hashes of other functions, like the state roots of L2s, are reduced with
+    // hash256_to_goldilocks_hash or hash256_to_bn254_hash
+    FieldElements,
+    // hashes made with the tree's own function, see L2OHash::from_hash_256
+    NativeHash,
+}
+
+// A hash function the L2O merkle trees are built with.
+pub trait L2OTreeHasher {
+    type Hash: L2OHash
+        + PartialEq
+        + Copy
+        + KVQSerializable
+        + KVQCompactCodec
+        + Serialize
+        + for<'de2> Deserialize<'de2>;
+    type Hasher: GeneralMerkleZeroHasher<Self::Hash>;
+
+    fn to_leaf(value: &Hash256, encoding: L2OLeafEncoding) -> Self::Hash;
+}
+
+impl L2OTreeHasher for Sha256Hasher {
+    type Hash = Hash256;
+    type Hasher = Self;
+
+    fn to_leaf(value: &Hash256, _encoding: L2OLeafEncoding) -> Hash256 {
+        *value
+    }
+}
+
+impl L2OTreeHasher for Blake3Hasher {
+    type Hash = Hash256;
+    type Hasher = Self;
+
+    fn to_leaf(value: &Hash256, _encoding: L2OLeafEncoding) -> Hash256 {
+        *value
+    }
+}
+
+impl L2OTreeHasher for Keccak256Hasher {
+    type Hash = Hash256;
+    type Hasher = Self;
+
+    fn to_leaf(value: &Hash256, _encoding: L2OLeafEncoding) -> Hash256 {
+        *value
+    }
+}
+
+impl L2OTreeHasher for PoseidonHasher {
+    type Hash = GHashOut;
+    type Hasher = Self;
+
+    fn to_leaf(value: &Hash256, encoding: L2OLeafEncoding) -> GHashOut {
+        match encoding {
+            L2OLeafEncoding::FieldElements => hash256_to_goldilocks_hash(value),
+            L2OLeafEncoding::NativeHash => GHashOut::from_hash_256(value),
+        }
+    }
+}
+
+// BN254 nodes are kept as Hash256, see hash256_to_bn254_hash
+impl L2OTreeHasher for PoseidonBN254Hasher {
+    type Hash = Hash256;
+    type Hasher = Self;
+
+    fn to_leaf(value: &Hash256, encoding: L2OLeafEncoding) -> Hash256 {
+        match encoding {
+            L2OLeafEncoding::FieldElements => hash256_to_bn254_hash(value),
+            L2OLeafEncoding::NativeHash => *value,
+        }
+    }
+}
+
+// proofs are served as Hash256s, see MerkleProofCore::to_hash_256
+pub fn proof_from_hash_256<H: L2OTreeHasher>(
+    proof: &MerkleProofCore<Hash256>,
+) -> MerkleProofCore<H::Hash> {
+    MerkleProofCore {
+        root: H::Hash::from_hash_256(&proof.root),
+        value: H::Hash::from_hash_256(&proof.value),
+        index: proof.index,
+        siblings: proof.siblings.iter().map(H::Hash::from_hash_256).collect(),
+    }
+}
@@ -0,0 +1,447 @@
+use ark_bn254::Bn254;
+use ark_bn254::Fq;
+use ark_bn254::Fq2;
+use ark_bn254::Fr;
+use ark_bn254::G1Affine;
+use ark_bn254::G2Affine;
+use ark_ec::short_weierstrass::Affine;
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::AffineRepr;
+use ark_ff::BigInt;
+use ark_ff::BigInteger;
+use ark_ff::PrimeField;
+use ark_groth16::Proof;
+use ark_groth16::VerifyingKey;
+
+use super::proof_data::Groth16BN128ProofData;
+
+// gnark-crypto stores the point type in the two top bits of the first byte,
+// bn254 coordinates never use them
+const FLAG_MASK: u8 = 0b11 << 6;
+const FLAG_UNCOMPRESSED: u8 = 0b00 << 6;
+const FLAG_INFINITY: u8 = 0b01 << 6;
+const FLAG_COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const FLAG_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+
+const FIELD_SIZE: usize = 32;
+
+// gnark's WriteTo writes compressed points, WriteRawTo uncompressed ones. The
+// point at infinity has the same flag in both, so the reader has to be told.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GnarkEncoding {
+    Compressed,
+    Raw,
+}
+
+impl GnarkEncoding {
+    fn g1_size(&self) -> usize {
+        match self {
+            Self::Compressed => FIELD_SIZE,
+            Self::Raw => 2 * FIELD_SIZE,
+        }
+    }
+    fn g2_size(&self) -> usize {
+        2 * self.g1_size()
+    }
+}
+
+fn field_from_be_bytes<F: PrimeField<BigInt = BigInt<4>>>(bytes: &[u8]) -> anyhow::Result<F> {
+    let limbs = core::array::from_fn(|i| {
+        let end = FIELD_SIZE - 8 * i;
+        u64::from_be_bytes(bytes[end - 8..end].try_into().unwrap())
+    });
+    F::from_bigint(BigInt::new(limbs)).ok_or_else(|| anyhow::anyhow!("non-canonical field element"))
+}
+
+fn field_to_be_bytes<F: PrimeField>(value: &F) -> Vec<u8> {
+    value.into_bigint().to_bytes_be()
+}
+
+// gnark compares E2 elements by A1 first, the same order as arkworks
+fn is_largest<F: Ord + core::ops::Neg<Output = F> + Copy>(y: &F) -> bool {
+    *y > -*y
+}
+
+fn check_point<P: SWCurveConfig>(point: Affine<P>) -> anyhow::Result<Affine<P>> {
+    if !point.is_on_curve() {
+        anyhow::bail!("point is not on the curve");
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        anyhow::bail!("point is not in the prime order subgroup");
+    }
+    Ok(point)
+}
+
+struct GnarkReader<'a> {
+    bytes: &'a [u8],
+    encoding: GnarkEncoding,
+}
+
+impl<'a> GnarkReader<'a> {
+    fn new(bytes: &'a [u8], encoding: GnarkEncoding) -> Self {
+        Self { bytes, encoding }
+    }
+    fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            anyhow::bail!("unexpected end of gnark data");
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    fn read_fr(&mut self) -> anyhow::Result<Fr> {
+        field_from_be_bytes(self.take(FIELD_SIZE)?)
+    }
+    // returns the flag and the point bytes with the flag cleared
+    fn take_point(&mut self, size: usize) -> anyhow::Result<(u8, Vec<u8>)> {
+        let mut bytes = self.take(size)?.to_vec();
+        let flag = bytes[0] & FLAG_MASK;
+        bytes[0] &= !FLAG_MASK;
+        if flag == FLAG_INFINITY && bytes.iter().any(|b| *b != 0) {
+            anyhow::bail!("invalid encoding of the point at infinity");
+        }
+        let expected_compressed = self.encoding == GnarkEncoding::Compressed;
+        let is_compressed = flag == FLAG_COMPRESSED_SMALLEST || flag == FLAG_COMPRESSED_LARGEST;
+        if flag != FLAG_INFINITY && is_compressed != expected_compressed {
+            anyhow::bail!("unexpected gnark point encoding {:#04x}", flag);
+        }
+        Ok((flag, bytes))
+    }
+    fn read_g1(&mut self) -> anyhow::Result<G1Affine> {
+        let (flag, bytes) = self.take_point(self.encoding.g1_size())?;
+        if flag == FLAG_INFINITY {
+            return Ok(G1Affine::zero());
+        }
+        let x: Fq = field_from_be_bytes(&bytes[..FIELD_SIZE])?;
+        let point = if flag == FLAG_UNCOMPRESSED {
+            G1Affine::new_unchecked(x, field_from_be_bytes(&bytes[FIELD_SIZE..])?)
+        } else {
+            G1Affine::get_point_from_x_unchecked(x, flag == FLAG_COMPRESSED_LARGEST)
+                .ok_or_else(|| anyhow::anyhow!("G1 x coordinate is not on the curve"))?
+        };
+        check_point(point)
+    }
+    // E2 elements are stored as A1 | A0
+    fn read_g2(&mut self) -> anyhow::Result<G2Affine> {
+        let (flag, bytes) = self.take_point(self.encoding.g2_size())?;
+        if flag == FLAG_INFINITY {
+            return Ok(G2Affine::zero());
+        }
+        let fq2 = |offset: usize| -> anyhow::Result<Fq2> {
+            let c1 = field_from_be_bytes(&bytes[offset..offset + FIELD_SIZE])?;
+            let c0 = field_from_be_bytes(&bytes[offset + FIELD_SIZE..offset + 2 * FIELD_SIZE])?;
+            Ok(Fq2::new(c0, c1))
+        };
+        let x = fq2(0)?;
+        let point = if flag == FLAG_UNCOMPRESSED {
+            G2Affine::new_unchecked(x, fq2(2 * FIELD_SIZE)?)
+        } else {
+            G2Affine::get_point_from_x_unchecked(x, flag == FLAG_COMPRESSED_LARGEST)
+                .ok_or_else(|| anyhow::anyhow!("G2 x coordinate is not on the curve"))?
+        };
+        check_point(point)
+    }
+    fn read_g1_slice(&mut self) -> anyhow::Result<Vec<G1Affine>> {
+        let len = self.read_u32()? as usize;
+        if len * self.encoding.g1_size() > self.remaining() {
+            anyhow::bail!("unexpected end of gnark data");
+        }
+        (0..len).map(|_| self.read_g1()).collect()
+    }
+    fn finish(&self) -> anyhow::Result<()> {
+        if self.remaining() != 0 {
+            anyhow::bail!("{} trailing bytes in gnark data", self.remaining());
+        }
+        Ok(())
+    }
+}
+
+struct GnarkWriter {
+    bytes: Vec<u8>,
+    encoding: GnarkEncoding,
+}
+
+impl GnarkWriter {
+    fn new(encoding: GnarkEncoding) -> Self {
+        Self {
+            bytes: Vec::new(),
+            encoding,
+        }
+    }
+    fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+    }
+    fn write_point(&mut self, coords: Option<(Vec<u8>, Vec<u8>, bool)>, size: usize) {
+        let start = self.bytes.len();
+        match coords {
+            None => {
+                self.bytes.resize(start + size, 0);
+                self.bytes[start] = FLAG_INFINITY;
+            }
+            Some((x, y, largest)) => {
+                self.bytes.extend_from_slice(&x);
+                match self.encoding {
+                    GnarkEncoding::Raw => self.bytes.extend_from_slice(&y),
+                    GnarkEncoding::Compressed => {
+                        self.bytes[start] |= if largest {
+                            FLAG_COMPRESSED_LARGEST
+                        } else {
+                            FLAG_COMPRESSED_SMALLEST
+                        }
+                    }
+                }
+            }
+        }
+    }
+    fn write_g1(&mut self, point: &G1Affine) {
+        let coords = point
+            .xy()
+            .map(|(x, y)| (field_to_be_bytes(x), field_to_be_bytes(y), is_largest(y)));
+        self.write_point(coords, self.encoding.g1_size());
+    }
+    fn write_g2(&mut self, point: &G2Affine) {
+        let fq2 = |v: &Fq2| [field_to_be_bytes(&v.c1), field_to_be_bytes(&v.c0)].concat();
+        let coords = point.xy().map(|(x, y)| (fq2(x), fq2(y), is_largest(y)));
+        self.write_point(coords, self.encoding.g2_size());
+    }
+    fn write_g1_slice(&mut self, points: &[G1Affine]) {
+        self.write_u32(points.len() as u32);
+        for point in points {
+            self.write_g1(point);
+        }
+    }
+}
+
+// gnark's groth16.Proof: Ar, Bs, Krs, then (since v0.9) the commitments and
+// their proof of knowledge. Commitments are not supported by arkworks.
+pub fn read_gnark_proof(bytes: &[u8], encoding: GnarkEncoding) -> anyhow::Result<Proof<Bn254>> {
+    let mut reader = GnarkReader::new(bytes, encoding);
+    let proof = Proof::<Bn254> {
+        a: reader.read_g1()?,
+        b: reader.read_g2()?,
+        c: reader.read_g1()?,
+    };
+    if reader.remaining() != 0 {
+        if reader.read_u32()? != 0 {
+            anyhow::bail!("gnark proofs with commitments are not supported");
+        }
+        let _commitment_pok = reader.read_g1()?;
+    }
+    reader.finish()?;
+    Ok(proof)
+}
+
+pub fn write_gnark_proof(proof: &Proof<Bn254>, encoding: GnarkEncoding) -> Vec<u8> {
+    let mut writer = GnarkWriter::new(encoding);
+    writer.write_g1(&proof.a);
+    writer.write_g2(&proof.b);
+    writer.write_g1(&proof.c);
+    writer.write_g1_slice(&[]);
+    writer.write_g1(&G1Affine::zero());
+    writer.bytes
+}
+
+// gnark's groth16.VerifyingKey: [α]1, [β]1, [β]2, [γ]2, [δ]1, [δ]2, K, then
+// (since v0.9) the commitment layout and keys, which must be empty
+pub fn read_gnark_vk(bytes: &[u8], encoding: GnarkEncoding) -> anyhow::Result<VerifyingKey<Bn254>> {
+    let mut reader = GnarkReader::new(bytes, encoding);
+    let alpha_g1 = reader.read_g1()?;
+    let _beta_g1 = reader.read_g1()?;
+    let beta_g2 = reader.read_g2()?;
+    let gamma_g2 = reader.read_g2()?;
+    let _delta_g1 = reader.read_g1()?;
+    let delta_g2 = reader.read_g2()?;
+    let gamma_abc_g1 = reader.read_g1_slice()?;
+    if gamma_abc_g1.is_empty() {
+        anyhow::bail!("verifier data must have at least one K point");
+    }
+    if reader.remaining() != 0 {
+        let committed = reader.read_u32()?;
+        let commitment_keys = reader.read_u32()?;
+        if committed != 0 || commitment_keys != 0 {
+            anyhow::bail!("gnark keys with commitments are not supported");
+        }
+    }
+    reader.finish()?;
+    Ok(VerifyingKey::<Bn254> {
+        alpha_g1,
+        beta_g2,
+        gamma_g2,
+        delta_g2,
+        gamma_abc_g1,
+    })
+}
+
+// [β]1 and [δ]1 are not part of an arkworks key. gnark only uses them to
+// export solidity verifiers, they are written as the point at infinity.
+pub fn write_gnark_vk(vk: &VerifyingKey<Bn254>, encoding: GnarkEncoding) -> Vec<u8> {
+    let mut writer = GnarkWriter::new(encoding);
+    writer.write_g1(&vk.alpha_g1);
+    writer.write_g1(&G1Affine::zero());
+    writer.write_g2(&vk.beta_g2);
+    writer.write_g2(&vk.gamma_g2);
+    writer.write_g1(&G1Affine::zero());
+    writer.write_g2(&vk.delta_g2);
+    writer.write_g1_slice(&vk.gamma_abc_g1);
+    writer.write_u32(0);
+    writer.write_u32(0);
+    writer.bytes
+}
+
+// a public witness.MarshalBinary: nbPublic | nbSecret | len | elements
+pub fn read_gnark_public_witness(bytes: &[u8]) -> anyhow::Result<Vec<Fr>> {
+    let mut reader = GnarkReader::new(bytes, GnarkEncoding::Raw);
+    let n_public = reader.read_u32()?;
+    let n_secret = reader.read_u32()?;
+    let len = reader.read_u32()?;
+    if n_secret != 0 {
+        anyhow::bail!("gnark witness has {} secret elements", n_secret);
+    }
+    if n_public != len {
+        anyhow::bail!(
+            "gnark witness declares {} public of {} elements",
+            n_public,
+            len
+        );
+    }
+    if len as usize * FIELD_SIZE != reader.remaining() {
+        anyhow::bail!("gnark witness length does not match its elements");
+    }
+    let public_inputs = (0..len)
+        .map(|_| reader.read_fr())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    reader.finish()?;
+    Ok(public_inputs)
+}
+
+pub fn write_gnark_public_witness(public_inputs: &[Fr]) -> Vec<u8> {
+    let mut writer = GnarkWriter::new(GnarkEncoding::Raw);
+    let len = public_inputs.len() as u32;
+    writer.write_u32(len);
+    writer.write_u32(0);
+    writer.write_u32(len);
+    for public_input in public_inputs {
+        writer.bytes.extend(field_to_be_bytes(public_input));
+    }
+    writer.bytes
+}
+
+pub fn read_gnark_proof_data(
+    proof: &[u8],
+    public_witness: &[u8],
+    encoding: GnarkEncoding,
+) -> anyhow::Result<Groth16BN128ProofData> {
+    Ok(Groth16BN128ProofData {
+        proof: read_gnark_proof(proof, encoding)?,
+        public_inputs: read_gnark_public_witness(public_witness)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fq;
+    use ark_groth16::Groth16;
+    use ark_snark::SNARK;
+
+    use super::*;
+    use crate::proof::groth16::bn128::snarkjs::SnarkjsProof;
+    use crate::proof::groth16::bn128::snarkjs::SnarkjsVerificationKey;
+
+    fn fixtures() -> (VerifyingKey<Bn254>, Groth16BN128ProofData) {
+        let vk = serde_json::from_str::<SnarkjsVerificationKey>(include_str!(
+            "../../../../../static/snarkjs/verification_key.json"
+        ))
+        .unwrap()
+        .to_vk()
+        .unwrap();
+        let public_signals = serde_json::from_str::<Vec<String>>(include_str!(
+            "../../../../../static/snarkjs/public.json"
+        ))
+        .unwrap();
+        let proof = serde_json::from_str::<SnarkjsProof>(include_str!(
+            "../../../../../static/snarkjs/proof.json"
+        ))
+        .unwrap()
+        .to_proof_data(&public_signals)
+        .unwrap();
+        (vk, proof)
+    }
+
+    #[test]
+    fn test_gnark_roundtrip_verify() {
+        let (vk, proof) = fixtures();
+        for encoding in [GnarkEncoding::Compressed, GnarkEncoding::Raw] {
+            let vk_bytes = write_gnark_vk(&vk, encoding);
+            let proof_bytes = write_gnark_proof(&proof.proof, encoding);
+            let witness_bytes = write_gnark_public_witness(&proof.public_inputs);
+            assert_eq!(
+                proof_bytes.len(),
+                3 * encoding.g1_size() + encoding.g2_size() + 4
+            );
+
+            let vk2 = read_gnark_vk(&vk_bytes, encoding).unwrap();
+            let proof2 = read_gnark_proof_data(&proof_bytes, &witness_bytes, encoding).unwrap();
+            assert_eq!(vk2, vk);
+            assert_eq!(proof2, proof);
+
+            // proofs written before commitments were added
+            let legacy = &proof_bytes[..2 * encoding.g1_size() + encoding.g2_size()];
+            assert_eq!(read_gnark_proof(legacy, encoding).unwrap(), proof.proof);
+
+            let pvk = Groth16::<Bn254>::process_vk(&vk2).unwrap();
+            assert!(
+                Groth16::<Bn254>::verify_proof(&pvk, &proof2.proof, &proof2.public_inputs).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gnark_rejects_invalid() {
+        let (vk, proof) = fixtures();
+        let proof_bytes = write_gnark_proof(&proof.proof, GnarkEncoding::Raw);
+
+        assert!(read_gnark_proof(&proof_bytes, GnarkEncoding::Compressed).is_err());
+        assert!(read_gnark_proof(&proof_bytes[..100], GnarkEncoding::Raw).is_err());
+        assert!(
+            read_gnark_proof(&[proof_bytes.clone(), vec![0]].concat(), GnarkEncoding::Raw).is_err()
+        );
+
+        // off the curve
+        let mut bad = proof_bytes.clone();
+        bad[63] ^= 1;
+        assert!(read_gnark_proof(&bad, GnarkEncoding::Raw).is_err());
+
+        // above the modulus
+        let mut bad = proof_bytes.clone();
+        bad[..FIELD_SIZE].copy_from_slice(&field_to_be_bytes(&-Fq::from(1u8)));
+        bad[0] |= 0x3f;
+        assert!(read_gnark_proof(&bad, GnarkEncoding::Raw).is_err());
+
+        // on the curve, outside of the G2 subgroup
+        let point = (1u64..)
+            .filter_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), false))
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut writer = GnarkWriter::new(GnarkEncoding::Compressed);
+        writer.write_g2(&point);
+        assert!(GnarkReader::new(&writer.bytes, GnarkEncoding::Compressed)
+            .read_g2()
+            .is_err());
+
+        // commitments
+        let mut bad = write_gnark_vk(&vk, GnarkEncoding::Raw);
+        let len = bad.len();
+        bad[len - 1] = 1;
+        assert!(read_gnark_vk(&bad, GnarkEncoding::Raw).is_err());
+
+        let mut witness = write_gnark_public_witness(&proof.public_inputs);
+        witness[7] = 1;
+        assert!(read_gnark_public_witness(&witness).is_err());
+    }
+}
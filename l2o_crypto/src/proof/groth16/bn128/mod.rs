@@ -1,2 +1,4 @@
+pub mod gnark;
 pub mod proof_data;
+pub mod snarkjs;
 pub mod verifier_data;
@@ -8,6 +8,9 @@ use ark_bn254::G2Projective;
 use ark_groth16::Proof;
 use ark_serialize::CanonicalDeserialize;
 use ark_serialize::CanonicalSerialize;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use l2o_common::str_to_fq;
 use l2o_common::str_to_fr;
 use serde::Deserialize;
@@ -48,6 +51,21 @@ impl<'de> Deserialize<'de> for Groth16BN128ProofData {
     }
 }
 
+impl KVQCompactCodec for Groth16BN128ProofData {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes)?;
+        writer.write_var_bytes(&bytes)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        // points were validated when the proof was first parsed, before it was stored
+        Ok(Self::deserialize_compressed_unchecked(
+            reader.read_var_bytes()?,
+        )?)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Groth16ProofSerializable {
     pub pi_a: [String; 3],
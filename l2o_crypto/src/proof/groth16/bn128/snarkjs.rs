@@ -0,0 +1,329 @@
+use ark_bn254::Bn254;
+use ark_bn254::Fq;
+use ark_bn254::Fq2;
+use ark_bn254::Fr;
+use ark_bn254::G1Affine;
+use ark_bn254::G2Affine;
+use ark_ec::short_weierstrass::Affine;
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_groth16::Proof;
+use ark_groth16::VerifyingKey;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::proof_data::Groth16BN128ProofData;
+
+const SNARKJS_PROTOCOL: &str = "groth16";
+const SNARKJS_CURVE: &str = "bn128";
+
+// verification_key.json as written by `snarkjs zkey export verificationkey`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SnarkjsVerificationKey {
+    pub protocol: String,
+    pub curve: String,
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub vk_alpha_1: [String; 3],
+    pub vk_beta_2: [[String; 2]; 3],
+    pub vk_gamma_2: [[String; 2]; 3],
+    pub vk_delta_2: [[String; 2]; 3],
+    // e(alpha, beta) is recomputed by every verifier, it is neither checked nor
+    // exported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vk_alphabeta_12: Option<serde_json::Value>,
+    #[serde(rename = "IC")]
+    pub ic: Vec<[String; 3]>,
+}
+
+// proof.json as written by `snarkjs groth16 prove`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SnarkjsProof {
+    pub pi_a: [String; 3],
+    pub pi_b: [[String; 2]; 3],
+    pub pi_c: [String; 3],
+    pub protocol: String,
+    pub curve: String,
+}
+
+fn check_header(protocol: &str, curve: &str) -> anyhow::Result<()> {
+    if protocol != SNARKJS_PROTOCOL {
+        anyhow::bail!("unsupported snarkjs protocol {}", protocol);
+    }
+    if curve != SNARKJS_CURVE {
+        anyhow::bail!("unsupported snarkjs curve {}", curve);
+    }
+    Ok(())
+}
+
+// snarkjs writes canonical decimals, anything else (blank strings, leading
+// zeros, values above the modulus) is rejected instead of reduced
+fn str_to_field_strict<F: PrimeField>(s: &str) -> anyhow::Result<F> {
+    let value = F::from_str(s).map_err(|_| anyhow::anyhow!("invalid field element {:?}", s))?;
+    if value.into_bigint().to_string() != s {
+        anyhow::bail!("non-canonical field element {:?}", s);
+    }
+    Ok(value)
+}
+
+fn field_to_string<F: PrimeField>(value: &F) -> String {
+    value.into_bigint().to_string()
+}
+
+fn str_to_fq2_strict(c: &[String; 2]) -> anyhow::Result<Fq2> {
+    Ok(Fq2::new(
+        str_to_field_strict(&c[0])?,
+        str_to_field_strict(&c[1])?,
+    ))
+}
+
+fn fq2_to_strings(value: &Fq2) -> [String; 2] {
+    [field_to_string(&value.c0), field_to_string(&value.c1)]
+}
+
+fn check_point<P: SWCurveConfig>(point: Affine<P>) -> anyhow::Result<Affine<P>> {
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        anyhow::bail!("point is not in the prime order subgroup");
+    }
+    Ok(point)
+}
+
+// snarkjs points are affine with z = 1, or (0, 1, 0) for the point at infinity
+pub fn str_to_g1_strict(coords: &[String; 3]) -> anyhow::Result<G1Affine> {
+    let x: Fq = str_to_field_strict(&coords[0])?;
+    let y: Fq = str_to_field_strict(&coords[1])?;
+    let z: Fq = str_to_field_strict(&coords[2])?;
+    if z == Fq::from(0u8) {
+        if x != Fq::from(0u8) || y != Fq::from(1u8) {
+            anyhow::bail!("invalid encoding of the G1 point at infinity");
+        }
+        return Ok(G1Affine::zero());
+    }
+    if z != Fq::from(1u8) {
+        anyhow::bail!("G1 point is not in affine form");
+    }
+    let point = G1Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        anyhow::bail!("G1 point is not on the curve");
+    }
+    check_point(point)
+}
+
+pub fn str_to_g2_strict(coords: &[[String; 2]; 3]) -> anyhow::Result<G2Affine> {
+    let x = str_to_fq2_strict(&coords[0])?;
+    let y = str_to_fq2_strict(&coords[1])?;
+    let z = str_to_fq2_strict(&coords[2])?;
+    if z == Fq2::from(0u8) {
+        if x != Fq2::from(0u8) || y != Fq2::from(1u8) {
+            anyhow::bail!("invalid encoding of the G2 point at infinity");
+        }
+        return Ok(G2Affine::zero());
+    }
+    if z != Fq2::from(1u8) {
+        anyhow::bail!("G2 point is not in affine form");
+    }
+    let point = G2Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        anyhow::bail!("G2 point is not on the curve");
+    }
+    check_point(point)
+}
+
+pub fn g1_to_strings(point: &G1Affine) -> [String; 3] {
+    match point.xy() {
+        Some((x, y)) => [field_to_string(x), field_to_string(y), "1".to_string()],
+        None => ["0".to_string(), "1".to_string(), "0".to_string()],
+    }
+}
+
+pub fn g2_to_strings(point: &G2Affine) -> [[String; 2]; 3] {
+    match point.xy() {
+        Some((x, y)) => [
+            fq2_to_strings(x),
+            fq2_to_strings(y),
+            ["1".to_string(), "0".to_string()],
+        ],
+        None => [
+            ["0".to_string(), "0".to_string()],
+            ["1".to_string(), "0".to_string()],
+            ["0".to_string(), "0".to_string()],
+        ],
+    }
+}
+
+impl SnarkjsVerificationKey {
+    pub fn to_vk(&self) -> anyhow::Result<VerifyingKey<Bn254>> {
+        check_header(&self.protocol, &self.curve)?;
+        if self.ic.len() != self.n_public + 1 {
+            anyhow::bail!(
+                "nPublic is {} but the key has {} IC points",
+                self.n_public,
+                self.ic.len()
+            );
+        }
+        Ok(VerifyingKey::<Bn254> {
+            alpha_g1: str_to_g1_strict(&self.vk_alpha_1)?,
+            beta_g2: str_to_g2_strict(&self.vk_beta_2)?,
+            gamma_g2: str_to_g2_strict(&self.vk_gamma_2)?,
+            delta_g2: str_to_g2_strict(&self.vk_delta_2)?,
+            gamma_abc_g1: self
+                .ic
+                .iter()
+                .map(str_to_g1_strict)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        })
+    }
+
+    pub fn from_vk(vk: &VerifyingKey<Bn254>) -> Self {
+        Self {
+            protocol: SNARKJS_PROTOCOL.to_string(),
+            curve: SNARKJS_CURVE.to_string(),
+            n_public: vk.gamma_abc_g1.len().saturating_sub(1),
+            vk_alpha_1: g1_to_strings(&vk.alpha_g1),
+            vk_beta_2: g2_to_strings(&vk.beta_g2),
+            vk_gamma_2: g2_to_strings(&vk.gamma_g2),
+            vk_delta_2: g2_to_strings(&vk.delta_g2),
+            vk_alphabeta_12: None,
+            ic: vk.gamma_abc_g1.iter().map(g1_to_strings).collect(),
+        }
+    }
+}
+
+impl SnarkjsProof {
+    pub fn to_proof(&self) -> anyhow::Result<Proof<Bn254>> {
+        check_header(&self.protocol, &self.curve)?;
+        Ok(Proof::<Bn254> {
+            a: str_to_g1_strict(&self.pi_a)?,
+            b: str_to_g2_strict(&self.pi_b)?,
+            c: str_to_g1_strict(&self.pi_c)?,
+        })
+    }
+
+    pub fn from_proof(proof: &Proof<Bn254>) -> Self {
+        Self {
+            pi_a: g1_to_strings(&proof.a),
+            pi_b: g2_to_strings(&proof.b),
+            pi_c: g1_to_strings(&proof.c),
+            protocol: SNARKJS_PROTOCOL.to_string(),
+            curve: SNARKJS_CURVE.to_string(),
+        }
+    }
+
+    // proof.json and public.json together make up the proof of an L2O-A block
+    pub fn to_proof_data(
+        &self,
+        public_signals: &[String],
+    ) -> anyhow::Result<Groth16BN128ProofData> {
+        Ok(Groth16BN128ProofData {
+            proof: self.to_proof()?,
+            public_inputs: parse_public_signals(public_signals)?,
+        })
+    }
+}
+
+// public.json is a list of decimal Fr elements
+pub fn parse_public_signals(public_signals: &[String]) -> anyhow::Result<Vec<Fr>> {
+    public_signals
+        .iter()
+        .map(|s| str_to_field_strict(s))
+        .collect()
+}
+
+pub fn public_signals_to_strings(public_inputs: &[Fr]) -> Vec<String> {
+    public_inputs.iter().map(field_to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::BigInteger;
+    use ark_groth16::Groth16;
+    use ark_snark::SNARK;
+
+    use super::*;
+
+    const VK_JSON: &str = include_str!("../../../../../static/snarkjs/verification_key.json");
+    const PROOF_JSON: &str = include_str!("../../../../../static/snarkjs/proof.json");
+    const PUBLIC_JSON: &str = include_str!("../../../../../static/snarkjs/public.json");
+
+    fn fixtures() -> (SnarkjsVerificationKey, SnarkjsProof, Vec<String>) {
+        (
+            serde_json::from_str(VK_JSON).unwrap(),
+            serde_json::from_str(PROOF_JSON).unwrap(),
+            serde_json::from_str(PUBLIC_JSON).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_snarkjs_import_verify() {
+        let (vk_json, proof_json, public_json) = fixtures();
+        let vk = vk_json.to_vk().unwrap();
+        let proof = proof_json.to_proof_data(&public_json).unwrap();
+        assert_eq!(vk.gamma_abc_g1.len(), proof.public_inputs.len() + 1);
+
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+        assert!(
+            Groth16::<Bn254>::verify_proof(&pvk, &proof.proof, &proof.public_inputs).unwrap(),
+            "verify proof"
+        );
+    }
+
+    #[test]
+    fn test_snarkjs_export_roundtrip() {
+        let (mut vk_json, proof_json, public_json) = fixtures();
+        let vk = vk_json.to_vk().unwrap();
+        let proof = proof_json.to_proof_data(&public_json).unwrap();
+
+        vk_json.vk_alphabeta_12 = None;
+        assert_eq!(SnarkjsVerificationKey::from_vk(&vk), vk_json);
+        assert_eq!(SnarkjsProof::from_proof(&proof.proof), proof_json);
+        assert_eq!(public_signals_to_strings(&proof.public_inputs), public_json);
+
+        let infinity = G2Affine::zero();
+        assert_eq!(
+            str_to_g2_strict(&g2_to_strings(&infinity)).unwrap(),
+            infinity
+        );
+    }
+
+    #[test]
+    fn test_snarkjs_rejects_invalid() {
+        let (vk_json, proof_json, public_json) = fixtures();
+
+        let mut bad = vk_json.clone();
+        bad.n_public += 1;
+        assert!(bad.to_vk().is_err());
+
+        let mut bad = proof_json.clone();
+        bad.protocol = "plonk".to_string();
+        assert!(bad.to_proof().is_err());
+
+        // projective coordinates
+        let mut bad = proof_json.clone();
+        bad.pi_a[2] = "2".to_string();
+        assert!(bad.to_proof().is_err());
+
+        // off the curve
+        let mut bad = proof_json.clone();
+        bad.pi_c[1] = "1".to_string();
+        assert!(bad.to_proof().is_err());
+
+        // blank strings and values above the modulus
+        let mut bad = public_json.clone();
+        bad[0] = "".to_string();
+        assert!(parse_public_signals(&bad).is_err());
+        let mut above_modulus = Fr::MODULUS;
+        above_modulus.add_with_carry(&1u64.into());
+        bad[0] = above_modulus.to_string();
+        assert!(parse_public_signals(&bad).is_err());
+
+        // on the curve, outside of the G2 subgroup
+        let point = (1u64..)
+            .filter_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), false))
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut bad = proof_json.clone();
+        bad.pi_b = g2_to_strings(&point);
+        assert!(bad.to_proof().is_err());
+    }
+}
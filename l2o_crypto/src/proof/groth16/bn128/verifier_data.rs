@@ -5,6 +5,11 @@ use ark_bn254::G1Projective;
 use ark_bn254::G2Affine;
 use ark_bn254::G2Projective;
 use ark_groth16::VerifyingKey;
+use ark_serialize::CanonicalDeserialize;
+use ark_serialize::CanonicalSerialize;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use l2o_common::str_to_fq;
 use serde::Deserialize;
 use serde::Deserializer;
@@ -41,14 +46,30 @@ impl<'de> Deserialize<'de> for Groth16BN128VerifierData {
     }
 }
 
+impl KVQCompactCodec for Groth16BN128VerifierData {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        let mut bytes = Vec::new();
+        self.0.serialize_compressed(&mut bytes)?;
+        writer.write_var_bytes(&bytes)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        // points were validated when the key was first parsed, before it was stored
+        Ok(Groth16BN128VerifierData(
+            VerifyingKey::deserialize_compressed_unchecked(reader.read_var_bytes()?)?,
+        ))
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Groth16VerifierSerializable {
     pub vk_alpha_1: [String; 3],
     pub vk_beta_2: [[String; 2]; 3],
     pub vk_gamma_2: [[String; 2]; 3],
     pub vk_delta_2: [[String; 2]; 3],
+    // one point per public input, plus one for the constant term
     #[serde(rename = "IC")]
-    pub ic: [[String; 3]; 3],
+    pub ic: Vec<[String; 3]>,
 }
 
 impl Groth16VerifierSerializable {
@@ -112,7 +133,10 @@ impl Groth16VerifierSerializable {
             ),
         ));
 
-        let mut gamma_abc_g1: Vec<G1Affine> = Vec::new();
+        if self.ic.is_empty() {
+            anyhow::bail!("verifier data must have at least one IC point");
+        }
+        let mut gamma_abc_g1: Vec<G1Affine> = Vec::with_capacity(self.ic.len());
         for coords in self.ic.iter() {
             let c = G1Affine::from(G1Projective::new(
                 str_to_fq(&coords[0])?,
@@ -190,23 +214,10 @@ impl Groth16VerifierSerializable {
                     delta_g2_projective.z.c1.to_string(),
                 ],
             ],
-            ic: [
-                [
-                    ic_projective[0].x.to_string(),
-                    ic_projective[0].y.to_string(),
-                    ic_projective[0].z.to_string(),
-                ],
-                [
-                    ic_projective[1].x.to_string(),
-                    ic_projective[1].y.to_string(),
-                    ic_projective[1].z.to_string(),
-                ],
-                [
-                    ic_projective[2].x.to_string(),
-                    ic_projective[2].y.to_string(),
-                    ic_projective[2].z.to_string(),
-                ],
-            ],
+            ic: ic_projective
+                .iter()
+                .map(|c| [c.x.to_string(), c.y.to_string(), c.z.to_string()])
+                .collect(),
         }
     }
 }
@@ -243,4 +254,21 @@ mod tests {
         let r = Groth16::<Bn254>::verify_proof(&p2, &proof.proof, &proof.public_inputs).unwrap();
         assert_eq!(r, true, "verify proof")
     }
+
+    #[test]
+    fn test_variable_length_ic() {
+        let vk_json = include_str!("../../../../../static/example_vkey.json");
+        let mut raw = serde_json::from_str::<Groth16VerifierSerializable>(vk_json).unwrap();
+        let extra = raw.ic[1].clone();
+        raw.ic.push(extra.clone());
+        raw.ic.push(extra);
+
+        let vk = raw.to_vk().unwrap();
+        assert_eq!(vk.gamma_abc_g1.len(), 5);
+        let roundtrip = Groth16VerifierSerializable::from_vk(&vk).to_vk().unwrap();
+        assert_eq!(roundtrip, vk);
+
+        raw.ic.clear();
+        assert!(raw.to_vk().is_err());
+    }
 }
@@ -1,3 +1,6 @@
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::hash::hash_types::HashOut;
 use plonky2::hash::merkle_tree::MerkleCap;
@@ -37,3 +40,19 @@ impl<'de> Deserialize<'de> for Plonky2PoseidonGoldilocksVerifierData {
         ))
     }
 }
+
+impl KVQCompactCodec for Plonky2PoseidonGoldilocksVerifierData {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write(&self.0.constants_sigmas_cap.0)?;
+        writer.write(&self.0.circuit_digest)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(Plonky2PoseidonGoldilocksVerifierData(
+            VerifierOnlyCircuitData {
+                constants_sigmas_cap: MerkleCap(reader.read()?),
+                circuit_digest: reader.read()?,
+            },
+        ))
+    }
+}
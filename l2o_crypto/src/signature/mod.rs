@@ -1,3 +1,4 @@
+pub mod musig;
 pub mod schnorr;
 
 #[cfg(test)]
@@ -0,0 +1,61 @@
+use l2o_common::common::data::signature::L2OCompactPublicKey;
+use l2o_common::common::data::signature::L2OCompressedPublicKey;
+use musig2::secp::Point;
+use musig2::KeyAggContext;
+
+// BIP327 key aggregation. The key coefficients commit to the whole list, so
+// the same committee in a different order has a different aggregate key.
+pub fn aggregate_public_keys(
+    keys: &[L2OCompressedPublicKey],
+) -> anyhow::Result<L2OCompactPublicKey> {
+    let points = keys
+        .iter()
+        .map(|key| Point::from_slice(&key.0))
+        .collect::<Result<Vec<_>, _>>()?;
+    let context = KeyAggContext::new(points)?;
+    Ok(L2OCompactPublicKey(
+        context.aggregated_pubkey::<Point>().serialize_xonly(),
+    ))
+}
+
+pub fn verify_aggregate_public_key(
+    keys: &[L2OCompressedPublicKey],
+    aggregate: &L2OCompactPublicKey,
+) -> anyhow::Result<()> {
+    if keys.is_empty() {
+        anyhow::bail!("empty sequencer committee");
+    }
+    if aggregate_public_keys(keys)? != *aggregate {
+        anyhow::bail!("public key is not the aggregate key of the sequencer committee");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // key_agg_vectors.json from BIP327
+    #[test]
+    fn test_aggregate_public_keys() {
+        let keys = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        ]
+        .iter()
+        .map(|key| L2OCompressedPublicKey::from_hex(key).unwrap())
+        .collect::<Vec<_>>();
+        let aggregate = L2OCompactPublicKey::from_hex(
+            "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+        )
+        .unwrap();
+
+        assert_eq!(aggregate_public_keys(&keys).unwrap(), aggregate);
+        assert!(verify_aggregate_public_key(&keys, &aggregate).is_ok());
+
+        let reordered = [keys[2].clone(), keys[1].clone(), keys[0].clone()];
+        assert!(verify_aggregate_public_key(&reordered, &aggregate).is_err());
+        assert!(verify_aggregate_public_key(&[], &aggregate).is_err());
+    }
+}
@@ -1,9 +1,23 @@
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::point::DecompactPoint;
+use k256::schnorr::signature::Error as SchnorrSignatureError;
 use k256::schnorr::signature::Signer;
 use k256::schnorr::signature::Verifier;
 use k256::schnorr::Signature;
 use k256::schnorr::SigningKey;
+use k256::schnorr::VerifyingKey;
+use k256::AffinePoint;
+use k256::FieldBytes;
+use k256::NonZeroScalar;
+use k256::ProjectivePoint;
+use k256::Scalar;
+use k256::U256;
 use l2o_common::common::data::signature::L2OCompactPublicKey;
 use l2o_common::common::data::signature::L2OSignature512;
+use sha2::Digest;
+use sha2::Sha256;
+
+const BIP340_CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
 
 pub fn verify_sig(
     public_key: &L2OCompactPublicKey,
@@ -18,3 +32,112 @@ pub fn verify_sig(
 pub fn sign_msg(signing_key: &SigningKey, msg: &[u8]) -> l2o_common::Result<L2OSignature512> {
     Ok(L2OSignature512(signing_key.sign(msg).to_bytes()))
 }
+
+fn bip340_challenge(r: &[u8], public_key: &L2OCompactPublicKey, msg_digest: &[u8]) -> Scalar {
+    let tag = Sha256::digest(BIP340_CHALLENGE_TAG);
+    <Scalar as Reduce<U256>>::reduce_bytes(
+        &Sha256::new()
+            .chain_update(tag)
+            .chain_update(tag)
+            .chain_update(r)
+            .chain_update(public_key.0)
+            .chain_update(msg_digest)
+            .finalize(),
+    )
+}
+
+// Checks the signatures of verify_sig (BIP340 over the SHA-256 digest of the
+// message) together, as in the BIP340 batch verification algorithm. The
+// random coefficients are derived from the whole batch, so the result is
+// deterministic. An empty batch is valid.
+pub fn verify_sigs_batch(
+    batch: &[(&L2OCompactPublicKey, &L2OSignature512, &[u8])],
+) -> l2o_common::Result<()> {
+    let digests = batch
+        .iter()
+        .map(|(_, _, msg)| Sha256::digest(msg))
+        .collect::<Vec<_>>();
+    let mut seed = Sha256::new();
+    for ((public_key, sig, _), digest) in batch.iter().zip(digests.iter()) {
+        seed.update(public_key.0);
+        seed.update(sig.0);
+        seed.update(digest);
+    }
+    let seed = seed.finalize();
+
+    let mut s_sum = Scalar::ZERO;
+    let mut rhs = ProjectivePoint::IDENTITY;
+    for (i, ((public_key, sig, _), digest)) in batch.iter().zip(digests.iter()).enumerate() {
+        let verifying_key = VerifyingKey::from_bytes(&public_key.0)?;
+        let r: AffinePoint =
+            Option::from(AffinePoint::decompact(FieldBytes::from_slice(&sig.0[..32])))
+                .ok_or_else(SchnorrSignatureError::new)?;
+        let s = NonZeroScalar::try_from(&sig.0[32..]).map_err(|_| SchnorrSignatureError::new())?;
+        let e = bip340_challenge(&sig.0[..32], public_key, digest);
+
+        let a = if i == 0 {
+            Scalar::ONE
+        } else {
+            <Scalar as Reduce<U256>>::reduce_bytes(
+                &Sha256::new()
+                    .chain_update(seed)
+                    .chain_update((i as u64).to_le_bytes())
+                    .finalize(),
+            )
+        };
+        s_sum += a * *s;
+        rhs += ProjectivePoint::from(r) * a
+            + ProjectivePoint::from(*verifying_key.as_affine()) * (a * e);
+    }
+
+    if ProjectivePoint::GENERATOR * s_sum != rhs {
+        return Err(SchnorrSignatureError::new().into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_batch() -> Vec<(L2OCompactPublicKey, L2OSignature512, Vec<u8>)> {
+        (1..=3u8)
+            .map(|i| {
+                let signing_key = SigningKey::from_bytes(&[i; 32]).unwrap();
+                let msg = vec![i; 32 * i as usize];
+                let public_key = L2OCompactPublicKey(signing_key.verifying_key().to_bytes().into());
+                let sig = sign_msg(&signing_key, &msg).unwrap();
+                (public_key, sig, msg)
+            })
+            .collect()
+    }
+
+    fn verify(batch: &[(L2OCompactPublicKey, L2OSignature512, Vec<u8>)]) -> bool {
+        let batch = batch
+            .iter()
+            .map(|(public_key, sig, msg)| (public_key, sig, msg.as_slice()))
+            .collect::<Vec<_>>();
+        verify_sigs_batch(&batch).is_ok()
+    }
+
+    #[test]
+    fn test_verify_sigs_batch() {
+        let batch = signed_batch();
+        for (public_key, sig, msg) in batch.iter() {
+            assert!(verify_sig(public_key, sig, msg).is_ok());
+        }
+        assert!(verify(&batch));
+        assert!(verify(&batch[..1]));
+        assert!(verify(&[]));
+
+        let mut wrong_msg = batch.clone();
+        wrong_msg[2].2[0] ^= 1;
+        assert!(!verify(&wrong_msg));
+
+        let mut swapped = batch.clone();
+        let sig = swapped[0].1.clone();
+        swapped[0].1 = swapped[1].1.clone();
+        swapped[1].1 = sig;
+        assert!(!verify(&swapped));
+    }
+}
@@ -1 +1,2 @@
 pub mod proof;
+pub mod superchain;
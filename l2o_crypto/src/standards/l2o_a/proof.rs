@@ -1,3 +1,10 @@
+use ark_bn254::Bn254;
+use ark_bn254::Fr;
+use ark_groth16::Groth16;
+use ark_snark::SNARK;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::EnumIs;
@@ -8,6 +15,9 @@ use crate::proof::groth16::bn128::verifier_data::Groth16BN128VerifierData;
 use crate::proof::plonky2::poseidon_goldilocks::Plonky2PoseidonGoldilocksProofData;
 use crate::proof::plonky2::poseidon_goldilocks::Plonky2PoseidonGoldilocksVerifierData;
 
+const PROOF_TYPE_GROTH16_BN128: u8 = 0;
+const PROOF_TYPE_PLONKY2_POSEIDON_GOLDILOCKS: u8 = 1;
+
 #[derive(EnumIs, EnumTryAs, Clone, PartialEq, Serialize, Deserialize, Debug)]
 #[serde(tag = "proof_type", content = "proof")]
 pub enum L2OAProofData {
@@ -21,3 +31,74 @@ pub enum L2OAVerifierData {
     Groth16BN128(Groth16BN128VerifierData),
     Plonky2PoseidonGoldilocks(Plonky2PoseidonGoldilocksVerifierData),
 }
+
+impl L2OAVerifierData {
+    // Checks a block proof against the public inputs its block implies.
+    pub fn verify_proof(&self, proof: &L2OAProofData, public_inputs: &[Fr]) -> anyhow::Result<()> {
+        match (self, proof) {
+            (L2OAVerifierData::Groth16BN128(verifier_data), L2OAProofData::Groth16BN128(proof)) => {
+                if proof.public_inputs != public_inputs {
+                    anyhow::bail!("public inputs mismatch");
+                }
+                let processed_vk = Groth16::<Bn254>::process_vk(&verifier_data.0)?;
+                if !Groth16::<Bn254>::verify_proof(&processed_vk, &proof.proof, public_inputs)? {
+                    anyhow::bail!("invalid proof");
+                }
+                Ok(())
+            }
+            _ => anyhow::bail!("unsupported proof type"),
+        }
+    }
+}
+
+impl KVQCompactCodec for L2OAProofData {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        match self {
+            L2OAProofData::Groth16BN128(proof) => {
+                writer.write_u8(PROOF_TYPE_GROTH16_BN128);
+                writer.write(proof)
+            }
+            L2OAProofData::Plonky2PoseidonGoldilocks(proof) => {
+                // plonky2 proofs can only be read back with the circuit's common data, which
+                // is not part of the verifier data, so they keep their JSON form
+                writer.write_u8(PROOF_TYPE_PLONKY2_POSEIDON_GOLDILOCKS);
+                writer.write_var_bytes(&serde_json::to_vec(proof)?)
+            }
+        }
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        match reader.read_u8()? {
+            PROOF_TYPE_GROTH16_BN128 => Ok(L2OAProofData::Groth16BN128(reader.read()?)),
+            PROOF_TYPE_PLONKY2_POSEIDON_GOLDILOCKS => Ok(L2OAProofData::Plonky2PoseidonGoldilocks(
+                serde_json::from_slice(reader.read_var_bytes()?)?,
+            )),
+            proof_type => anyhow::bail!("Unknown proof type {}", proof_type),
+        }
+    }
+}
+
+impl KVQCompactCodec for L2OAVerifierData {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        match self {
+            L2OAVerifierData::Groth16BN128(verifier_data) => {
+                writer.write_u8(PROOF_TYPE_GROTH16_BN128);
+                writer.write(verifier_data)
+            }
+            L2OAVerifierData::Plonky2PoseidonGoldilocks(verifier_data) => {
+                writer.write_u8(PROOF_TYPE_PLONKY2_POSEIDON_GOLDILOCKS);
+                writer.write(verifier_data)
+            }
+        }
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        match reader.read_u8()? {
+            PROOF_TYPE_GROTH16_BN128 => Ok(L2OAVerifierData::Groth16BN128(reader.read()?)),
+            PROOF_TYPE_PLONKY2_POSEIDON_GOLDILOCKS => {
+                Ok(L2OAVerifierData::Plonky2PoseidonGoldilocks(reader.read()?))
+            }
+            proof_type => anyhow::bail!("Unknown proof type {}", proof_type),
+        }
+    }
+}
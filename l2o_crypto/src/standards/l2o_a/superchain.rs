@@ -0,0 +1,536 @@
+use l2o_common::common::data::hash::Hash256;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::hash::merkle::core::MerkleProofCore;
+use crate::hash::merkle::tree_hasher::proof_from_hash_256;
+use crate::hash::merkle::tree_hasher::L2OLeafEncoding;
+use crate::hash::merkle::tree_hasher::L2OTreeHasher;
+use crate::hash::traits::L2OHash;
+
+pub const BITCOIN_BLOCK_HEADER_SIZE: usize = 80;
+
+// Everything needed to show that an L2 had a state root at a bitcoin block:
+// the superchain root an L2O-A block of the L2 for that bitcoin block was
+// checked against, the proof of the state root under it and the transaction
+// that inscribed the block with its merkle branch in the bitcoin block that
+// included it. Bitcoin hashes are in display order, like the
+// bitcoin_block_hash of a block inscription.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct L2OASuperchainProofBundle {
+    pub l2id: u64,
+    pub bitcoin_block_number: u64,
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub bitcoin_block_header: Vec<u8>,
+
+    pub state_root: Hash256,
+    pub superchain_root: Hash256,
+    pub state_root_proof: MerkleProofCore<Hash256>,
+
+    pub txid: Hash256,
+    pub inscription_id: String,
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub transaction: Vec<u8>,
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub inclusion_block_header: Vec<u8>,
+    pub transaction_index: u32,
+    pub transaction_branch: Vec<Hash256>,
+}
+
+// The fields of an inscribed L2O-A block that a bundle is bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L2OAInscribedBlock {
+    pub l2id: u64,
+    pub bitcoin_block_number: u64,
+    pub bitcoin_block_hash: Hash256,
+    pub superchain_root: Hash256,
+}
+
+// Reads the raw transaction of a bundle. Implemented next to the ord envelope
+// parser, so that the verifier sees the block the indexer executed. Anyone can
+// inscribe a block for any l2id, so the block is only read once its proof
+// holds for the deployment of the L2, which the caller has to trust.
+pub trait L2OAInscribedBlockReader {
+    type Deployment;
+
+    fn txid(transaction: &[u8]) -> anyhow::Result<Hash256>;
+    fn inscribed_block(
+        transaction: &[u8],
+        inscription_index: u32,
+        deployment: &Self::Deployment,
+    ) -> anyhow::Result<L2OAInscribedBlock>;
+}
+
+// The bitcoin blocks a verified bundle depends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L2OASuperchainProofBlocks {
+    pub bitcoin_block_hash: Hash256,
+    pub inclusion_block_hash: Hash256,
+}
+
+fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+// bitcoin hashes display in the reverse of the order they are hashed in
+fn reversed(hash: [u8; 32]) -> [u8; 32] {
+    let mut hash = hash;
+    hash.reverse();
+    hash
+}
+
+fn check_header_size(header: &[u8]) -> anyhow::Result<()> {
+    if header.len() != BITCOIN_BLOCK_HEADER_SIZE {
+        anyhow::bail!(
+            "bitcoin block headers are {} bytes, got {}",
+            BITCOIN_BLOCK_HEADER_SIZE,
+            header.len()
+        );
+    }
+    Ok(())
+}
+
+// double sha256 of the raw header, in display order
+pub fn bitcoin_block_hash(header: &[u8]) -> anyhow::Result<Hash256> {
+    check_header_size(header)?;
+    Ok(Hash256(reversed(sha256d(header))))
+}
+
+// the transaction merkle root of the raw header, in display order
+pub fn bitcoin_block_merkle_root(header: &[u8]) -> anyhow::Result<Hash256> {
+    check_header_size(header)?;
+    let mut root = [0u8; 32];
+    root.copy_from_slice(&header[36..68]);
+    Ok(Hash256(reversed(root)))
+}
+
+// The siblings of the transaction at index in the merkle tree of the txids of
+// a bitcoin block, which repeats the last hash of a level with an odd length.
+pub fn bitcoin_merkle_branch(txids: &[Hash256], index: usize) -> anyhow::Result<Vec<Hash256>> {
+    if index >= txids.len() {
+        anyhow::bail!("transaction {} not in a block of {}", index, txids.len());
+    }
+    let mut level = txids
+        .iter()
+        .map(|txid| reversed(txid.0))
+        .collect::<Vec<_>>();
+    let mut index = index;
+    let mut branch = Vec::new();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        branch.push(Hash256(reversed(level[index ^ 1])));
+        level = level
+            .chunks(2)
+            .map(|pair| sha256d(&[pair[0], pair[1]].concat()))
+            .collect();
+        index >>= 1;
+    }
+    Ok(branch)
+}
+
+pub fn bitcoin_merkle_root_from_branch(
+    txid: &Hash256,
+    index: u32,
+    branch: &[Hash256],
+) -> anyhow::Result<Hash256> {
+    if branch.len() < 32 && index >> branch.len() != 0 {
+        anyhow::bail!("transaction index {} is too deep for the branch", index);
+    }
+    let mut hash = reversed(txid.0);
+    let mut index = index;
+    for sibling in branch {
+        let sibling = reversed(sibling.0);
+        hash = if index & 1 == 0 {
+            sha256d(&[hash, sibling].concat())
+        } else {
+            sha256d(&[sibling, hash].concat())
+        };
+        index >>= 1;
+    }
+    Ok(Hash256(reversed(hash)))
+}
+
+// Checks a bundle offline against the deployment of the L2, with the hash
+// function it declared, and returns the hashes of its bitcoin blocks, which the
+// caller still has to find in a header chain it trusts.
+pub fn verify_superchain_proof_bundle<H: L2OTreeHasher, R: L2OAInscribedBlockReader>(
+    bundle: &L2OASuperchainProofBundle,
+    deployment: &R::Deployment,
+) -> anyhow::Result<L2OASuperchainProofBlocks> {
+    let block_hash = bitcoin_block_hash(&bundle.bitcoin_block_header)?;
+    let inclusion_block_hash = bitcoin_block_hash(&bundle.inclusion_block_header)?;
+
+    let proof = &bundle.state_root_proof;
+    if proof.index != bundle.l2id {
+        anyhow::bail!("proof is for l2id {}, not {}", proof.index, bundle.l2id);
+    }
+    if proof.root != bundle.superchain_root {
+        anyhow::bail!("proof root does not match the superchain root");
+    }
+    let leaf = H::to_leaf(&bundle.state_root, L2OLeafEncoding::FieldElements);
+    if proof.value != leaf.to_hash_256() {
+        anyhow::bail!("proof value does not match the state root");
+    }
+    if !proof_from_hash_256::<H>(proof).verify_marked_if::<H::Hasher>(false) {
+        anyhow::bail!("invalid state root proof");
+    }
+
+    if R::txid(&bundle.transaction)? != bundle.txid {
+        anyhow::bail!("transaction does not match the txid");
+    }
+    let merkle_root = bitcoin_merkle_root_from_branch(
+        &bundle.txid,
+        bundle.transaction_index,
+        &bundle.transaction_branch,
+    )?;
+    if merkle_root != bitcoin_block_merkle_root(&bundle.inclusion_block_header)? {
+        anyhow::bail!("transaction is not in the inclusion block");
+    }
+
+    let inscription_index = bundle
+        .inscription_id
+        .strip_prefix(&bundle.txid.to_hex())
+        .and_then(|index| index.strip_prefix('i'))
+        .ok_or_else(|| anyhow::anyhow!("inscription is not in the bundle transaction"))?
+        .parse::<u32>()?;
+    let block = R::inscribed_block(&bundle.transaction, inscription_index, deployment)?;
+    if block.l2id != bundle.l2id {
+        anyhow::bail!(
+            "inscribed block is for l2id {}, not {}",
+            block.l2id,
+            bundle.l2id
+        );
+    }
+    if block.bitcoin_block_number != bundle.bitcoin_block_number {
+        anyhow::bail!(
+            "inscribed block is for bitcoin block {}, not {}",
+            block.bitcoin_block_number,
+            bundle.bitcoin_block_number
+        );
+    }
+    if block.bitcoin_block_hash != block_hash {
+        anyhow::bail!("inscribed block hash does not match the bitcoin block header");
+    }
+    if block.superchain_root != bundle.superchain_root {
+        anyhow::bail!("inscribed superchain root does not match the bundle");
+    }
+
+    Ok(L2OASuperchainProofBlocks {
+        bitcoin_block_hash: block_hash,
+        inclusion_block_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+    use crate::hash::hash_functions::sha256::Sha256Hasher;
+    use crate::hash::merkle::core::calc_merkle_root_marked_if;
+    use crate::hash::merkle::traits::MerkleZeroHasher;
+
+    const GENESIS_HEADER: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+    const GENESIS_HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const GENESIS_COINBASE_TXID: &str =
+        "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    // the test transactions are the json of the block they inscribe with a
+    // proof, which only the sequencer of the deployment can make
+    struct TestReader;
+
+    #[derive(Serialize, Deserialize)]
+    struct TestInscription {
+        block: L2OAInscribedBlock,
+        proof: Hash256,
+    }
+
+    const TEST_DEPLOYMENT: Hash256 = Hash256([4u8; 32]);
+
+    fn test_proof(block: &L2OAInscribedBlock, deployment: &Hash256) -> Hash256 {
+        let block = serde_json::to_vec(block).unwrap();
+        Hash256(sha256d(&[deployment.0.as_slice(), &block].concat()))
+    }
+
+    impl L2OAInscribedBlockReader for TestReader {
+        type Deployment = Hash256;
+
+        fn txid(transaction: &[u8]) -> anyhow::Result<Hash256> {
+            Ok(Hash256(reversed(sha256d(transaction))))
+        }
+        fn inscribed_block(
+            transaction: &[u8],
+            inscription_index: u32,
+            deployment: &Hash256,
+        ) -> anyhow::Result<L2OAInscribedBlock> {
+            if inscription_index != 0 {
+                anyhow::bail!("no inscription {}", inscription_index);
+            }
+            let inscription: TestInscription = serde_json::from_slice(transaction)?;
+            if inscription.proof != test_proof(&inscription.block, deployment) {
+                anyhow::bail!("invalid proof");
+            }
+            Ok(inscription.block)
+        }
+    }
+
+    fn inclusion_header(merkle_root: &Hash256) -> Vec<u8> {
+        let mut header = hex::decode(GENESIS_HEADER).unwrap();
+        header[4..36].copy_from_slice(&reversed(Hash256::from_hex(GENESIS_HASH).unwrap().0));
+        header[36..68].copy_from_slice(&reversed(merkle_root.0));
+        header
+    }
+
+    // includes the transaction as the second of three in its block
+    fn with_block(
+        bundle: L2OASuperchainProofBundle,
+        block: &L2OAInscribedBlock,
+    ) -> L2OASuperchainProofBundle {
+        with_proven_block(bundle, block, &TEST_DEPLOYMENT)
+    }
+
+    fn with_proven_block(
+        bundle: L2OASuperchainProofBundle,
+        block: &L2OAInscribedBlock,
+        deployment: &Hash256,
+    ) -> L2OASuperchainProofBundle {
+        let transaction = serde_json::to_vec(&TestInscription {
+            block: *block,
+            proof: test_proof(block, deployment),
+        })
+        .unwrap();
+        let txid = TestReader::txid(&transaction).unwrap();
+        let txids = [Hash256([1u8; 32]), txid, Hash256([2u8; 32])];
+        let transaction_branch = bitcoin_merkle_branch(&txids, 1).unwrap();
+        let merkle_root = bitcoin_merkle_root_from_branch(&txid, 1, &transaction_branch).unwrap();
+        L2OASuperchainProofBundle {
+            txid,
+            inscription_id: format!("{}i0", txid.to_hex()),
+            transaction,
+            inclusion_block_header: inclusion_header(&merkle_root),
+            transaction_index: 1,
+            transaction_branch,
+            ..bundle
+        }
+    }
+
+    fn test_block(bundle: &L2OASuperchainProofBundle) -> L2OAInscribedBlock {
+        L2OAInscribedBlock {
+            l2id: bundle.l2id,
+            bitcoin_block_number: bundle.bitcoin_block_number,
+            bitcoin_block_hash: Hash256::from_hex(GENESIS_HASH).unwrap(),
+            superchain_root: bundle.superchain_root,
+        }
+    }
+
+    fn test_bundle<H: L2OTreeHasher>() -> L2OASuperchainProofBundle {
+        let l2id = 3;
+        let state_root = Hash256([9u8; 32]);
+        let value = H::to_leaf(&state_root, L2OLeafEncoding::FieldElements);
+        let siblings = (0..32).map(H::Hasher::get_zero_hash).collect::<Vec<_>>();
+        let root = calc_merkle_root_marked_if::<H::Hash, H::Hasher>(value, &siblings, l2id, false);
+        let bundle = L2OASuperchainProofBundle {
+            l2id,
+            bitcoin_block_number: 0,
+            bitcoin_block_header: hex::decode(GENESIS_HEADER).unwrap(),
+            state_root,
+            superchain_root: root.to_hash_256(),
+            state_root_proof: MerkleProofCore {
+                root,
+                value,
+                index: l2id,
+                siblings,
+            }
+            .to_hash_256(),
+            txid: Hash256([0u8; 32]),
+            inscription_id: String::new(),
+            transaction: vec![],
+            inclusion_block_header: vec![],
+            transaction_index: 0,
+            transaction_branch: vec![],
+        };
+        let block = test_block(&bundle);
+        with_block(bundle, &block)
+    }
+
+    #[test]
+    fn test_bitcoin_merkle_branch() {
+        let genesis_txid = Hash256::from_hex(GENESIS_COINBASE_TXID).unwrap();
+        let header = hex::decode(GENESIS_HEADER).unwrap();
+        assert!(bitcoin_merkle_branch(&[genesis_txid], 0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(bitcoin_block_merkle_root(&header).unwrap(), genesis_txid);
+
+        let txids = (0..5u8).map(|i| Hash256([i; 32])).collect::<Vec<_>>();
+        let root = bitcoin_merkle_root_from_branch(
+            &txids[0],
+            0,
+            &bitcoin_merkle_branch(&txids, 0).unwrap(),
+        )
+        .unwrap();
+        for (index, txid) in txids.iter().enumerate() {
+            let branch = bitcoin_merkle_branch(&txids, index).unwrap();
+            assert_eq!(branch.len(), 3);
+            assert_eq!(
+                bitcoin_merkle_root_from_branch(txid, index as u32, &branch).unwrap(),
+                root
+            );
+        }
+        assert!(bitcoin_merkle_branch(&txids, 5).is_err());
+        assert!(bitcoin_merkle_root_from_branch(&txids[0], 8, &[txids[1]; 3]).is_err());
+    }
+
+    #[test]
+    fn test_verify_superchain_proof_bundle() {
+        let genesis_hash = Hash256::from_hex(GENESIS_HASH).unwrap();
+        let bundle = test_bundle::<Sha256Hasher>();
+        let blocks =
+            verify_superchain_proof_bundle::<Sha256Hasher, TestReader>(&bundle, &TEST_DEPLOYMENT)
+                .unwrap();
+        assert_eq!(blocks.bitcoin_block_hash, genesis_hash);
+        assert_eq!(
+            blocks.inclusion_block_hash,
+            bitcoin_block_hash(&bundle.inclusion_block_header).unwrap()
+        );
+        let bundle = test_bundle::<PoseidonHasher>();
+        assert!(
+            verify_superchain_proof_bundle::<PoseidonHasher, TestReader>(&bundle, &TEST_DEPLOYMENT)
+                .is_ok()
+        );
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(json.contains(GENESIS_HEADER));
+        assert_eq!(
+            serde_json::from_str::<L2OASuperchainProofBundle>(&json).unwrap(),
+            bundle
+        );
+    }
+
+    #[test]
+    fn test_reject_tampered_bundles() {
+        let bundle = test_bundle::<Sha256Hasher>();
+        let verify = |bundle: &L2OASuperchainProofBundle| {
+            verify_superchain_proof_bundle::<Sha256Hasher, TestReader>(bundle, &TEST_DEPLOYMENT)
+        };
+
+        let mut tampered = bundle.clone();
+        tampered.state_root = Hash256([8u8; 32]);
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = bundle.clone();
+        tampered.bitcoin_block_header.pop();
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = bundle.clone();
+        tampered.inscription_id = format!("{}i0", Hash256([1u8; 32]).to_hex());
+        assert!(verify(&tampered).is_err());
+
+        // the leaf was reduced to field elements, the proof is not valid for
+        // another hash function
+        assert!(
+            verify_superchain_proof_bundle::<PoseidonHasher, TestReader>(&bundle, &TEST_DEPLOYMENT)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_reject_bundles_not_in_the_inclusion_block() {
+        let bundle = test_bundle::<Sha256Hasher>();
+        let verify = |bundle: &L2OASuperchainProofBundle| {
+            verify_superchain_proof_bundle::<Sha256Hasher, TestReader>(bundle, &TEST_DEPLOYMENT)
+        };
+
+        let mut tampered = bundle.clone();
+        tampered.transaction_index = 0;
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = bundle.clone();
+        tampered.transaction_branch[0] = Hash256([3u8; 32]);
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = bundle.clone();
+        tampered.inclusion_block_header = hex::decode(GENESIS_HEADER).unwrap();
+        assert!(verify(&tampered).is_err());
+
+        // another transaction under the same txid
+        let mut tampered = bundle.clone();
+        tampered.transaction.push(b' ');
+        assert!(verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_reject_bundles_not_bound_to_the_inscribed_block() {
+        let bundle = test_bundle::<Sha256Hasher>();
+        let block = test_block(&bundle);
+        let verify = |bundle: &L2OASuperchainProofBundle| {
+            verify_superchain_proof_bundle::<Sha256Hasher, TestReader>(bundle, &TEST_DEPLOYMENT)
+        };
+
+        let other = L2OAInscribedBlock {
+            superchain_root: Hash256([5u8; 32]),
+            ..block
+        };
+        assert!(verify(&with_block(bundle.clone(), &other)).is_err());
+
+        let other = L2OAInscribedBlock {
+            bitcoin_block_number: block.bitcoin_block_number + 1,
+            ..block
+        };
+        assert!(verify(&with_block(bundle.clone(), &other)).is_err());
+
+        let other = L2OAInscribedBlock {
+            bitcoin_block_hash: Hash256([6u8; 32]),
+            ..block
+        };
+        assert!(verify(&with_block(bundle.clone(), &other)).is_err());
+
+        let other = L2OAInscribedBlock {
+            l2id: block.l2id + 1,
+            ..block
+        };
+        assert!(verify(&with_block(bundle.clone(), &other)).is_err());
+
+        assert!(verify(&with_block(bundle, &block)).is_ok());
+    }
+
+    #[test]
+    fn test_reject_bundles_with_forged_blocks() {
+        let bundle = test_bundle::<Sha256Hasher>();
+        let block = test_block(&bundle);
+        let verify = |bundle: &L2OASuperchainProofBundle| {
+            verify_superchain_proof_bundle::<Sha256Hasher, TestReader>(bundle, &TEST_DEPLOYMENT)
+        };
+
+        // a block inscribed by someone else than the sequencer of the L2
+        let forged = with_proven_block(bundle.clone(), &block, &Hash256([5u8; 32]));
+        assert!(verify(&forged).is_err());
+
+        // a block without a proof
+        let unproven = L2OASuperchainProofBundle {
+            transaction: serde_json::to_vec(&block).unwrap(),
+            ..bundle.clone()
+        };
+        let unproven = L2OASuperchainProofBundle {
+            txid: TestReader::txid(&unproven.transaction).unwrap(),
+            ..unproven
+        };
+        assert!(verify(&unproven).is_err());
+
+        // the proof of a block does not carry over to another superchain root
+        let mut transaction: TestInscription = serde_json::from_slice(&bundle.transaction).unwrap();
+        transaction.block.superchain_root = Hash256([6u8; 32]);
+        let tampered = L2OASuperchainProofBundle {
+            transaction: serde_json::to_vec(&transaction).unwrap(),
+            ..bundle.clone()
+        };
+        assert!(verify(&tampered).is_err());
+
+        assert!(verify(&bundle).is_ok());
+    }
+}
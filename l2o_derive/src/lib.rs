@@ -1 +1,341 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse_macro_input;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Expr;
+use syn::Fields;
+use syn::Generics;
+use syn::Ident;
+use syn::Index;
+use syn::Token;
+use syn::Type;
 
+/// Derives `KVQSerializable` from a `#[kvq(...)]` attribute.
+///
+/// Keys are encoded field by field with `KVQKeyPart` (big-endian, so byte order
+/// matches field order), optionally prefixed by a 2 byte table type:
+///
+/// `#[kvq(key, table_type = TABLE_TYPE)]`
+///
+/// Values are encoded with the chosen codec:
+///
+/// `#[kvq(value, codec = json)]`
+/// `#[kvq(value, codec = compact, tag = KVQ_COMPACT_TAG_L2_DEPOSIT)]`
+/// `#[kvq(value, codec = compact, tag = 5, json_fallback)]`
+#[proc_macro_derive(KVQSerializable, attributes(kvq))]
+pub fn derive_kvq_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_kvq_serializable(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `KVQCompactCodec` by writing the fields in declaration order.
+#[proc_macro_derive(KVQCompactCodec)]
+pub fn derive_kvq_compact_codec(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_kvq_compact_codec(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+enum KVQArg {
+    Flag(Ident),
+    Value(Ident, Box<Expr>),
+}
+
+impl Parse for KVQArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Ok(KVQArg::Value(name, Box::new(input.parse()?)))
+        } else {
+            Ok(KVQArg::Flag(name))
+        }
+    }
+}
+
+enum KVQMode {
+    Key { table_type: Option<Expr> },
+    Json,
+    Compact { tag: Expr, json_fallback: bool },
+}
+
+fn expr_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_kvq_mode(input: &DeriveInput) -> syn::Result<KVQMode> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("kvq"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                input.ident.span(),
+                "missing #[kvq(key, ...)] or #[kvq(value, ...)] attribute",
+            )
+        })?;
+    let args = attr.parse_args_with(Punctuated::<KVQArg, Token![,]>::parse_terminated)?;
+
+    let mut is_key = false;
+    let mut is_value = false;
+    let mut json_fallback = false;
+    let mut table_type = None;
+    let mut codec = None;
+    let mut tag = None;
+    for arg in args {
+        match arg {
+            KVQArg::Flag(name) if name == "key" => is_key = true,
+            KVQArg::Flag(name) if name == "value" => is_value = true,
+            KVQArg::Flag(name) if name == "json_fallback" => json_fallback = true,
+            KVQArg::Value(name, expr) if name == "table_type" => table_type = Some(*expr),
+            KVQArg::Value(name, expr) if name == "codec" => codec = Some(*expr),
+            KVQArg::Value(name, expr) if name == "tag" => tag = Some(*expr),
+            KVQArg::Flag(name) | KVQArg::Value(name, _) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unknown kvq attribute `{}`", name),
+                ))
+            }
+        }
+    }
+
+    match (is_key, is_value) {
+        (true, false) => {
+            if codec.is_some() || tag.is_some() || json_fallback {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "codec options are only valid for values",
+                ));
+            }
+            Ok(KVQMode::Key { table_type })
+        }
+        (false, true) => {
+            if table_type.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "table_type is only valid for keys",
+                ));
+            }
+            let codec = codec.ok_or_else(|| {
+                syn::Error::new(attr.span(), "values need a codec = json or codec = compact")
+            })?;
+            match expr_ident(&codec).as_deref() {
+                Some("json") => {
+                    if tag.is_some() || json_fallback {
+                        return Err(syn::Error::new(
+                            codec.span(),
+                            "tag and json_fallback are only valid for the compact codec",
+                        ));
+                    }
+                    Ok(KVQMode::Json)
+                }
+                Some("compact") => Ok(KVQMode::Compact {
+                    tag: tag.ok_or_else(|| {
+                        syn::Error::new(codec.span(), "the compact codec needs a tag")
+                    })?,
+                    json_fallback,
+                }),
+                _ => Err(syn::Error::new(codec.span(), "unknown codec")),
+            }
+        }
+        _ => Err(syn::Error::new(
+            attr.span(),
+            "expected exactly one of `key` or `value`",
+        )),
+    }
+}
+
+struct FieldInfo {
+    member: TokenStream2,
+    ident: Option<Ident>,
+    ty: Type,
+}
+
+fn struct_fields(input: &DeriveInput) -> syn::Result<(Vec<FieldInfo>, &Fields)> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "KVQ derives only support structs",
+            ))
+        }
+    };
+    let infos = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => quote!(#ident),
+                None => {
+                    let index = Index::from(i);
+                    quote!(#index)
+                }
+            };
+            FieldInfo {
+                member,
+                ident: field.ident.clone(),
+                ty: field.ty.clone(),
+            }
+        })
+        .collect();
+    Ok((infos, fields))
+}
+
+fn construct(fields: &Fields, infos: &[FieldInfo], read: TokenStream2) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => {
+            let idents = infos.iter().map(|info| &info.ident);
+            quote!(Self { #(#idents: #read,)* })
+        }
+        Fields::Unnamed(_) => {
+            let reads = infos.iter().map(|_| &read);
+            quote!(Self(#(#reads,)*))
+        }
+        Fields::Unit => quote!(Self),
+    }
+}
+
+fn add_trait_bounds(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+fn is_tail_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "String" || segment.ident == "Vec")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn expand_kvq_serializable(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mode = parse_kvq_mode(input)?;
+
+    let (to_bytes, from_bytes, generics) = match mode {
+        KVQMode::Key { table_type } => {
+            let (infos, fields) = struct_fields(input)?;
+            for info in infos.iter().take(infos.len().saturating_sub(1)) {
+                if is_tail_type(&info.ty) {
+                    return Err(syn::Error::new(
+                        info.ty.span(),
+                        "variable length key parts must be the last field",
+                    ));
+                }
+            }
+
+            let write_table_type = table_type
+                .as_ref()
+                .map(|table_type| quote!(::kvq::key::write_table_type(#table_type, &mut result);));
+            let read_table_type = table_type
+                .as_ref()
+                .map(|table_type| quote!(::kvq::key::read_table_type(#table_type, &mut reader)?;));
+            let members = infos.iter().map(|info| &info.member);
+            let read = construct(
+                fields,
+                &infos,
+                quote!(::kvq::key::KVQKeyPart::read_key_part(&mut reader)?),
+            );
+
+            (
+                quote! {
+                    let mut result: Vec<u8> = Vec::new();
+                    #write_table_type
+                    #(::kvq::key::KVQKeyPart::write_key_part(&self.#members, &mut result);)*
+                    Ok(result)
+                },
+                quote! {
+                    let mut reader = ::kvq::codec::KVQCompactReader::new(bytes);
+                    #read_table_type
+                    let result = #read;
+                    reader.finish()?;
+                    Ok(result)
+                },
+                add_trait_bounds(&input.generics, quote!(::kvq::key::KVQKeyPart)),
+            )
+        }
+        KVQMode::Json => (
+            quote!(::kvq::codec::to_json_bytes(self)),
+            quote!(::kvq::codec::from_json_bytes(bytes)),
+            input.generics.clone(),
+        ),
+        KVQMode::Compact { tag, json_fallback } => {
+            let from_bytes = if json_fallback {
+                quote!(::kvq::codec::from_compact_or_json_bytes(#tag, bytes))
+            } else {
+                quote!(::kvq::codec::from_compact_bytes(#tag, bytes))
+            };
+            (
+                quote!(::kvq::codec::to_compact_bytes(#tag, self)),
+                from_bytes,
+                input.generics.clone(),
+            )
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::kvq::traits::KVQSerializable for #name #ty_generics #where_clause {
+            fn to_bytes(&self) -> ::anyhow::Result<Vec<u8>> {
+                #to_bytes
+            }
+
+            fn from_bytes(bytes: &[u8]) -> ::anyhow::Result<Self> {
+                #from_bytes
+            }
+        }
+    })
+}
+
+fn expand_kvq_compact_codec(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (infos, fields) = struct_fields(input)?;
+    let members = infos.iter().map(|info| &info.member);
+    let read = construct(fields, &infos, quote!(reader.read()?));
+
+    let generics = add_trait_bounds(&input.generics, quote!(::kvq::codec::KVQCompactCodec));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::kvq::codec::KVQCompactCodec for #name #ty_generics #where_clause {
+            fn encode_compact(
+                &self,
+                writer: &mut ::kvq::codec::KVQCompactWriter,
+            ) -> ::anyhow::Result<()> {
+                #(writer.write(&self.#members)?;)*
+                Ok(())
+            }
+
+            fn decode_compact(
+                reader: &mut ::kvq::codec::KVQCompactReader,
+            ) -> ::anyhow::Result<Self> {
+                Ok(#read)
+            }
+        }
+    })
+}
@@ -14,6 +14,8 @@ use bitcoincore_rpc::RpcApi;
 use jsonrpc_core::types::Request as JsonRpcRequest;
 use jsonrpc_core::types::Response as JsonRpcResponse;
 use jsonrpc_core::Success;
+use l2o_common::metrics::FETCHER_BATCH_SIZE;
+use l2o_common::metrics::FETCHER_RETRIES;
 use l2o_macros::quick;
 use serde_json::json;
 
@@ -104,6 +106,7 @@ impl Indexer {
             let err = quick!(f());
 
             retries += 1;
+            FETCHER_RETRIES.inc();
             let seconds = 1 << retries;
             tracing::warn!("retrying in {seconds}s: {err}");
 
@@ -152,6 +155,7 @@ impl Indexer {
                     };
                     outpoints.push(outpoint);
                 }
+                FETCHER_BATCH_SIZE.observe(outpoints.len() as f64);
                 // Break outpoints into chunks for parallel requests
                 let chunk_size = (outpoints.len() / PARALLEL_REQUESTS) + 1;
                 let mut results = Vec::with_capacity(PARALLEL_REQUESTS);
@@ -7,6 +7,12 @@ use base64::Engine;
 use bitcoincore_rpc::Auth;
 use bitcoincore_rpc::Client;
 use bitcoincore_rpc::RpcApi;
+use l2o_common::metrics::BLOCK_INDEX_SECONDS;
+use l2o_common::metrics::CHAIN_TIP;
+use l2o_common::metrics::DB_SIZE_BYTES;
+use l2o_common::metrics::INDEXED_HEIGHT;
+use l2o_common::metrics::RECEIPTS;
+use l2o_common::metrics::REORGS;
 use l2o_common::IndexerArgs;
 use l2o_ord::chain::Chain;
 use l2o_ord::height::Height;
@@ -20,14 +26,21 @@ use redb::Database;
 use redb::ReadableTable;
 use tokio::task::spawn_blocking;
 
+use crate::webhook::Webhook;
+use crate::webhook::WebhookDispatcher;
+
 pub mod fetcher;
 pub mod rpc_server;
+pub mod webhook;
 
 pub struct Indexer {
     addr: SocketAddr,
     http: Arc<reqwest::blocking::Client>,
     db: Arc<Database>,
+    db_path: String,
     chain: Chain,
+    index_content: bool,
+    webhooks: Option<WebhookDispatcher>,
     bitcoin_rpc_url: &'static str,
     bitcoin_rpc_auth: &'static str,
     bitcoin_rpc: Arc<Client>,
@@ -39,7 +52,10 @@ impl Clone for Indexer {
             addr: self.addr,
             http: Arc::clone(&self.http),
             db: Arc::clone(&self.db),
+            db_path: self.db_path.clone(),
             chain: self.chain.clone(),
+            index_content: self.index_content,
+            webhooks: self.webhooks.clone(),
             bitcoin_rpc_url: self.bitcoin_rpc_url,
             bitcoin_rpc_auth: self.bitcoin_rpc_auth,
             bitcoin_rpc: Arc::clone(&self.bitcoin_rpc),
@@ -66,6 +82,14 @@ impl Indexer {
             ),
         )?);
         let chain = args.network.parse()?;
+        let webhooks = match args.webhooks {
+            Some(ref path) => Some(WebhookDispatcher::new(
+                Arc::clone(&db),
+                Arc::new(Database::create(&args.webhooks_db_path)?),
+                serde_json::from_slice::<Vec<Webhook>>(&std::fs::read(path)?)?,
+            )?),
+            None => None,
+        };
         let indexer = spawn_blocking(move || {
             let http = Arc::new(reqwest::blocking::Client::new());
 
@@ -73,7 +97,10 @@ impl Indexer {
                 addr,
                 http,
                 db,
+                db_path: args.db_path,
                 chain,
+                index_content: args.index_content,
+                webhooks,
                 bitcoin_rpc_url: Box::leak(args.bitcoin_rpc.into_boxed_str()),
                 bitcoin_rpc_auth: Box::leak(bitcoin_rpc_auth.into_boxed_str()),
                 bitcoin_rpc,
@@ -120,6 +147,13 @@ impl Indexer {
 
                 let rpc_block_count = self.bitcoin_rpc.get_block_count();
 
+                if let Ok(ref height) = db_block_height {
+                    INDEXED_HEIGHT.set(i64::from(height.n()) - 1);
+                }
+                if let Ok(block_count) = rpc_block_count {
+                    CHAIN_TIP.set(block_count as i64);
+                }
+
                 match (&db_block_height, &rpc_block_count) {
                     (Ok(db_block_height), Ok(rpc_block_count))
                         if u64::from(db_block_height.n() + 1) <= *rpc_block_count => {}
@@ -131,8 +165,12 @@ impl Indexer {
 
                 if let Err(err) = db_block_height
                     .and_then(|height| {
-                        self.get_block_with_retries(height.n())
-                            .map(|block| (height, block))
+                        let timer = BLOCK_INDEX_SECONDS
+                            .with_label_values(&["fetch"])
+                            .start_timer();
+                        let block = self.get_block_with_retries(height.n())?;
+                        timer.observe_duration();
+                        Ok((height, block))
                     })
                     .and_then(|(height, block)| {
                         let block_data = BlockData::from(block);
@@ -146,6 +184,7 @@ impl Indexer {
                                         panic!("unrecoverable reorg")
                                     }
                                     Some(&ReorgError::Recoverable { height, depth }) => {
+                                        REORGS.with_label_values(&[&depth.to_string()]).inc();
                                         let mut wxn = self.db.begin_write()?;
                                         wxn.set_durability(redb::Durability::Immediate);
                                         wxn.handle_reorg(height, depth)?;
@@ -164,11 +203,16 @@ impl Indexer {
                             chain: self.chain,
                             blockheight: height.n(),
                             blocktime: block_data.header.time,
+                            index_content: self.index_content,
                             bitcoin_rpc: Arc::clone(&self.bitcoin_rpc),
                         };
 
-                        wxn.index_block(chain_ctx, block_data, &sender, &receiver)?;
+                        let receipt_counts =
+                            wxn.index_block(chain_ctx, block_data, &sender, &receiver)?;
                         wxn.commit()?;
+                        for ((op, outcome), count) in receipt_counts {
+                            RECEIPTS.with_label_values(&[&op, outcome]).inc_by(count);
+                        }
 
                         let mut wxn = self.db.begin_write()?;
                         wxn.set_durability(redb::Durability::Immediate);
@@ -180,6 +224,11 @@ impl Indexer {
                         wxn.persistent_savepoint()?;
 
                         wxn.commit()?;
+
+                        match std::fs::metadata(&self.db_path) {
+                            Ok(metadata) => DB_SIZE_BYTES.set(metadata.len() as i64),
+                            Err(err) => tracing::warn!("failed to read the db size: {}", err),
+                        }
                         Ok(())
                     })
                 {
@@ -1,9 +1,22 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use bitcoin::BlockHash;
 use bytes::Buf;
 use bytes::Bytes;
 use http_body_util::BodyExt;
 use http_body_util::Full;
+use hyper::body::Body;
+use hyper::body::Frame;
 use hyper::body::Incoming as IncomingBody;
 use hyper::header;
+use hyper::header::HeaderValue;
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::Method;
@@ -11,17 +24,39 @@ use hyper::Request;
 use hyper::Response;
 use hyper::StatusCode;
 use hyper_util::rt::TokioIo;
+use l2o_common::common::data::hash::Hash256;
+use l2o_common::metrics;
+use l2o_common::metrics::RPC_REQUESTS;
+use l2o_common::metrics::RPC_REQUEST_SECONDS;
+use l2o_crypto::standards::l2o_a::superchain::L2OASuperchainProofBundle;
+use l2o_ord::chain::Chain;
+use l2o_ord::inscription::inscription_id::InscriptionId;
+use l2o_ord::operation::l2o_a::L2OAHashFunction;
+use l2o_ord::operation::ProtocolType;
+use l2o_ord::tick::Tick;
+use l2o_ord_store::block_event::EventFilter;
+use l2o_ord_store::block_event::ReorgEvent;
+use l2o_ord_store::reorg::MAX_ROLLBACK_DEPTH;
 use l2o_ord_store::rtx::Rtx;
 use l2o_ord_store::table::KV;
+use l2o_rpc::block_number::AtHeight;
+use l2o_rpc::block_number::BlockNumber;
 use l2o_rpc::request;
+use l2o_rpc::request::Id;
 use l2o_rpc::request::RequestParams;
 use l2o_rpc::request::RpcRequest;
+use l2o_rpc::response::ErrorCode;
 use l2o_rpc::response::ResponseResult;
+use l2o_rpc::response::RpcError;
 use l2o_rpc::response::RpcResponse;
 use l2o_store::core::store::L2OStoreV1Core;
 use l2o_store::core::traits::L2OStoreReaderV1;
 use l2o_store_redb::KVQReDBStore;
+use redb::Database;
+use redb::ReadTransaction;
+use serde::Deserialize;
 use tokio::net::TcpListener;
+use tokio::sync::mpsc;
 
 use crate::Indexer;
 
@@ -35,17 +70,174 @@ fn full<T: Into<Bytes>>(chunk: T) -> BoxBody {
         .boxed()
 }
 
+fn rpc_error(id: Option<Id>, code: ErrorCode, err: anyhow::Error) -> RpcResponse {
+    RpcResponse {
+        jsonrpc: request::Version::V2,
+        id,
+        result: ResponseResult::Error(RpcError {
+            code,
+            message: err.to_string().into(),
+            data: None,
+        }),
+    }
+}
+
+// how often subscribers of /events look for new blocks
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+// idle subscribers are sent a comment this often, which also finds the gone
+// ones
+const EVENTS_KEEP_ALIVE: Duration = Duration::from_secs(15);
+// blocks sent to a subscriber in one read of the index
+const EVENTS_BATCH: u32 = 100;
+// blocks sent to a subscriber that are checked for reorgs
+const EVENTS_REORG_WINDOW: usize = MAX_ROLLBACK_DEPTH as usize + 1;
+
+// the body of a GET /events response, fed by the task polling the index
+struct EventStream(mpsc::Receiver<Bytes>);
+
+impl Body for EventStream {
+    type Data = Bytes;
+    type Error = hyper::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, hyper::Error>>> {
+        self.0
+            .poll_recv(cx)
+            .map(|chunk| chunk.map(|chunk| Ok(Frame::data(chunk))))
+    }
+}
+
+// blocks are sent with the id height:hash, so that a subscriber resuming after
+// a reorg can be told
+fn sse(event: &str, id: Option<(u32, BlockHash)>, data: &serde_json::Value) -> Bytes {
+    let id = id
+        .map(|(height, hash)| format!("id: {}:{}\n", height, hash))
+        .unwrap_or_default();
+    Bytes::from(format!("event: {}\n{}data: {}\n\n", event, id, data))
+}
+
+fn parse_event_id(id: &str) -> Option<(u32, BlockHash)> {
+    let (height, hash) = id.split_once(':')?;
+    Some((height.parse().ok()?, BlockHash::from_str(hash).ok()?))
+}
+
+fn percent_decode(value: &str) -> anyhow::Result<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [
+                    chars.next().unwrap_or_default(),
+                    chars.next().unwrap_or_default(),
+                ];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex)?, 16)?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+// from_height, protocol, tick, address and l2id of GET /events
+fn parse_events_query(query: &str) -> anyhow::Result<(Option<u32>, EventFilter)> {
+    let mut from_height = None;
+    let mut filter = EventFilter::default();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value)?;
+        match key {
+            "from_height" => from_height = Some(value.parse()?),
+            "protocol" => {
+                filter.protocol = Some(match value.to_lowercase().replace('-', "").as_str() {
+                    "brc20" => ProtocolType::BRC20,
+                    "brc21" => ProtocolType::BRC21,
+                    "l2oa" => ProtocolType::L2OA,
+                    _ => anyhow::bail!("unknown protocol {}", value),
+                })
+            }
+            "tick" => filter.tick = Some(Tick::from_str(&value)?),
+            "address" => filter.address = Some(value),
+            "l2id" => filter.l2id = Some(value.parse()?),
+            _ => anyhow::bail!("unknown parameter {}", key),
+        }
+    }
+    Ok((from_height, filter))
+}
+
+// The L2O-A block of an L2 for a bitcoin block was checked against the
+// superchain root at that checkpoint, which holds the state root it started
+// from.
+fn get_superchain_proof_bundle<S: L2OStoreReaderV1>(
+    rxn: &ReadTransaction,
+    store: &S,
+    l2id: u64,
+    bitcoin_block_number: u64,
+    hash: L2OAHashFunction,
+) -> anyhow::Result<L2OASuperchainProofBundle> {
+    let location = store.get_block_inscription_location(l2id, bitcoin_block_number)?;
+    let inclusion = store.get_block_inscription_inclusion(l2id, bitcoin_block_number)?;
+    let header = rxn
+        .block_header(u32::try_from(bitcoin_block_number)?)?
+        .ok_or_else(|| anyhow::anyhow!("bitcoin block {} not indexed", bitcoin_block_number))?;
+    let inclusion_header = rxn
+        .block_header(inclusion.height)?
+        .ok_or_else(|| anyhow::anyhow!("bitcoin block {} not indexed", inclusion.height))?;
+    let state_root_proof =
+        store.get_merkle_proof_state_root_at_block(l2id, bitcoin_block_number, hash)?;
+    Ok(L2OASuperchainProofBundle {
+        l2id,
+        bitcoin_block_number,
+        bitcoin_block_header: bitcoin::consensus::serialize(&header),
+        state_root: location.start_state_root,
+        superchain_root: state_root_proof.root,
+        state_root_proof,
+        txid: Hash256::from_hex(&location.txid.to_string())?,
+        inscription_id: location.inscription_id.to_string(),
+        transaction: bitcoin::consensus::serialize(&inclusion.transaction),
+        inclusion_block_header: bitcoin::consensus::serialize(&inclusion_header),
+        transaction_index: inclusion.transaction_index,
+        transaction_branch: inclusion.transaction_branch,
+    })
+}
+
 impl Indexer {
     pub async fn listen(&self) -> anyhow::Result<()> {
         let listener = TcpListener::bind(&self.addr).await?;
         tracing::info!("Listening on http://{}", self.addr);
+        if let Some(webhooks) = self.webhooks.clone() {
+            tokio::spawn(webhooks.run());
+        }
+        RpcServer::new(Arc::clone(&self.db), self.chain)
+            .serve(listener)
+            .await
+    }
+}
+
+// Serves the RPC from the index database, without the indexer around it.
+#[derive(Clone)]
+pub struct RpcServer {
+    db: Arc<Database>,
+    // of the addresses of inscription owners
+    chain: Chain,
+}
+
+impl RpcServer {
+    pub fn new(db: Arc<Database>, chain: Chain) -> Self {
+        Self { db, chain }
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> anyhow::Result<()> {
         loop {
             let (stream, _) = listener.accept().await?;
             let io = TokioIo::new(stream);
-            let indexer = self.clone();
+            let server = self.clone();
 
             tokio::task::spawn(async move {
-                let service = service_fn(|req| async { indexer.route(req).await });
+                let service = service_fn(|req| async { server.route(req).await });
 
                 if let Err(err) = http1::Builder::new().serve_connection(io, service).await {
                     tracing::error!("{:?}", err);
@@ -88,18 +280,58 @@ impl Indexer {
                     store.get_superchainroot_at_block(block_number, hash_function)?;
                 serde_json::to_value(superchain_state_root)?
             }
-            RequestParams::BRC20GetTickInfo(ref tick) => {
-                serde_json::to_value(rxn.brc20_get_tick_info(tick)?)?
+            RequestParams::L2OGetSuperchainDeltaProof((l2id, block_number, hash_function)) => {
+                let delta_proof =
+                    store.get_superchain_delta_proof(l2id, block_number, hash_function)?;
+                serde_json::to_value(delta_proof)?
+            }
+            RequestParams::L2OGetSuperchainProofBundle((l2id, block_number, hash_function)) => {
+                let bundle =
+                    get_superchain_proof_bundle(&rxn, &store, l2id, block_number, hash_function)?;
+                serde_json::to_value(bundle)?
+            }
+            RequestParams::OrdGetInscription(ref query) => {
+                serde_json::to_value(rxn.ord_get_inscription(query, self.chain)?)?
             }
-            RequestParams::BRC20GetAllTickInfo => {
-                serde_json::to_value(rxn.brc20_get_all_tick_info()?)?
+            RequestParams::OrdGetInscriptionsOnOutput(outpoint) => {
+                serde_json::to_value(rxn.ord_get_inscriptions_on_output(outpoint, self.chain)?)?
             }
-            RequestParams::BRC20GetBalanceByAddress((ref tick, ref script_key)) => {
-                serde_json::to_value(rxn.brc20_get_balance_by_address(tick, script_key.clone())?)?
+            RequestParams::OrdGetInscriptionsByHeight(height) => {
+                serde_json::to_value(rxn.ord_get_inscriptions_by_height(height, self.chain)?)?
             }
-            RequestParams::BRC20GetAllBalanceByAddress(ref script_key) => {
-                serde_json::to_value(rxn.brc20_get_all_balance_by_address(script_key.clone())?)?
+            RequestParams::BRC20GetTickInfo(AtHeight {
+                params: ref tick,
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => rxn.brc20_get_tick_info(tick)?,
+                BlockNumber::Number(height) => rxn.brc20_get_tick_info_at_height(tick, height)?,
+            })?,
+            RequestParams::BRC20GetAllTickInfo(AtHeight { at_height, .. }) => {
+                serde_json::to_value(match at_height {
+                    BlockNumber::Latest => rxn.brc20_get_all_tick_info()?,
+                    BlockNumber::Number(height) => rxn.brc20_get_all_tick_info_at_height(height)?,
+                })?
             }
+            RequestParams::BRC20GetBalanceByAddress(AtHeight {
+                params: (ref tick, ref script_key),
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => {
+                    rxn.brc20_get_balance_by_address(tick, script_key.clone())?
+                }
+                BlockNumber::Number(height) => {
+                    rxn.brc20_get_balance_by_address_at_height(tick, script_key.clone(), height)?
+                }
+            })?,
+            RequestParams::BRC20GetAllBalanceByAddress(AtHeight {
+                params: ref script_key,
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => rxn.brc20_get_all_balance_by_address(script_key.clone())?,
+                BlockNumber::Number(height) => {
+                    rxn.brc20_get_all_balance_by_address_at_height(script_key.clone(), height)?
+                }
+            })?,
             RequestParams::BRC20TransactionIdToTransactionReceipt(ref txid) => {
                 serde_json::to_value(
                     rxn.brc20_transaction_id_to_transaction_receipt(txid.clone())?,
@@ -120,18 +352,45 @@ impl Indexer {
                     rxn.brc20_transferable_assets_on_output_with_satpoints(outpoint.clone())?,
                 )?
             }
-            RequestParams::BRC21GetTickInfo(ref tick) => {
-                serde_json::to_value(rxn.brc21_get_tick_info(tick)?)?
-            }
-            RequestParams::BRC21GetAllTickInfo => {
-                serde_json::to_value(rxn.brc21_get_all_tick_info()?)?
+            RequestParams::BRC20GetAddressActivity((ref script_key, ref filter)) => {
+                serde_json::to_value(rxn.brc20_get_address_activity(script_key.clone(), filter)?)?
             }
-            RequestParams::BRC21GetBalanceByAddress((ref tick, ref script_key)) => {
-                serde_json::to_value(rxn.brc21_get_balance_by_address(tick, script_key.clone())?)?
+            RequestParams::BRC20GetTickHolders((ref tick, ref cursor, limit)) => {
+                serde_json::to_value(rxn.brc20_get_tick_holders(tick, cursor.as_deref(), limit)?)?
             }
-            RequestParams::BRC21GetAllBalanceByAddress(ref script_key) => {
-                serde_json::to_value(rxn.brc21_get_all_balance_by_address(script_key.clone())?)?
+            RequestParams::BRC21GetTickInfo(AtHeight {
+                params: ref tick,
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => rxn.brc21_get_tick_info(tick)?,
+                BlockNumber::Number(height) => rxn.brc21_get_tick_info_at_height(tick, height)?,
+            })?,
+            RequestParams::BRC21GetAllTickInfo(AtHeight { at_height, .. }) => {
+                serde_json::to_value(match at_height {
+                    BlockNumber::Latest => rxn.brc21_get_all_tick_info()?,
+                    BlockNumber::Number(height) => rxn.brc21_get_all_tick_info_at_height(height)?,
+                })?
             }
+            RequestParams::BRC21GetBalanceByAddress(AtHeight {
+                params: (ref tick, ref script_key),
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => {
+                    rxn.brc21_get_balance_by_address(tick, script_key.clone())?
+                }
+                BlockNumber::Number(height) => {
+                    rxn.brc21_get_balance_by_address_at_height(tick, script_key.clone(), height)?
+                }
+            })?,
+            RequestParams::BRC21GetAllBalanceByAddress(AtHeight {
+                params: ref script_key,
+                at_height,
+            }) => serde_json::to_value(match at_height {
+                BlockNumber::Latest => rxn.brc21_get_all_balance_by_address(script_key.clone())?,
+                BlockNumber::Number(height) => {
+                    rxn.brc21_get_all_balance_by_address_at_height(script_key.clone(), height)?
+                }
+            })?,
             RequestParams::BRC21TransactionIdToTransactionReceipt(ref txid) => {
                 serde_json::to_value(
                     rxn.brc21_transaction_id_to_transaction_receipt(txid.clone())?,
@@ -152,6 +411,12 @@ impl Indexer {
                     rxn.brc21_transferable_assets_on_output_with_satpoints(outpoint.clone())?,
                 )?
             }
+            RequestParams::BRC21GetAddressActivity((ref script_key, ref filter)) => {
+                serde_json::to_value(rxn.brc21_get_address_activity(script_key.clone(), filter)?)?
+            }
+            RequestParams::BRC21GetTickHolders((ref tick, ref cursor, limit)) => {
+                serde_json::to_value(rxn.brc21_get_tick_holders(tick, cursor.as_deref(), limit)?)?
+            }
         };
         Ok(RpcResponse {
             jsonrpc: request::Version::V2,
@@ -164,11 +429,42 @@ impl Indexer {
         &self,
         req: Request<IncomingBody>,
     ) -> anyhow::Result<Response<BoxBody>> {
+        let start = Instant::now();
         // Aggregate the body...
         let whole_body = req.collect().await?.aggregate();
         // Decode as JSON...
-        let data = serde_json::from_reader::<_, RpcRequest>(whole_body.reader())?;
-        let response = self.process_rpc_requests(&data).await?;
+        let value = serde_json::from_reader::<_, serde_json::Value>(whole_body.reader());
+        let data = value
+            .map_err(anyhow::Error::from)
+            .and_then(|value| Ok(RpcRequest::deserialize(value)?));
+        // only methods that parse are used as labels
+        let method = match data {
+            Ok(ref data) => serde_json::to_value(&data.request)?["method"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+            Err(_) => "unknown".to_string(),
+        };
+        // failures are answered with an rpc error so clients can tell them
+        // apart from a broken connection
+        let response = match data {
+            Ok(data) => self
+                .process_rpc_requests(&data)
+                .await
+                .unwrap_or_else(|err| {
+                    rpc_error(Some(data.id.clone()), ErrorCode::InternalError, err)
+                }),
+            Err(err) => rpc_error(None, ErrorCode::InvalidRequest, err),
+        };
+
+        let outcome = match response.result {
+            ResponseResult::Success(_) => "ok",
+            ResponseResult::Error(_) => "error",
+        };
+        RPC_REQUESTS.with_label_values(&[&method, outcome]).inc();
+        RPC_REQUEST_SECONDS
+            .with_label_values(&[&method])
+            .observe(start.elapsed().as_secs_f64());
 
         Ok(Response::builder()
             .status(StatusCode::OK)
@@ -176,9 +472,186 @@ impl Indexer {
             .body(full(serde_json::to_vec(&response)?))?)
     }
 
+    pub async fn handle_metrics(&self) -> anyhow::Result<Response<BoxBody>> {
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+            .body(full(metrics::encode()?))?)
+    }
+
+    // the body of an inscription stored with --index-content
+    pub async fn handle_content(&self, inscription_id: &str) -> anyhow::Result<Response<BoxBody>> {
+        let Ok(inscription_id) = InscriptionId::from_str(inscription_id) else {
+            return Ok(Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(full(format!("invalid inscription id {}", inscription_id)))?);
+        };
+        let Some(content) = self
+            .db
+            .begin_read()?
+            .ord_get_inscription_content(inscription_id)?
+        else {
+            return Ok(Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(full(NOTFOUND))?);
+        };
+
+        let content_type = content
+            .content_type
+            .as_deref()
+            .and_then(|content_type| HeaderValue::from_bytes(content_type).ok())
+            .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+        // the same policy as ord, so that bodies can not load resources from
+        // elsewhere or be sniffed into another content type
+        let mut response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+            )
+            .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+        if let Some(content_encoding) = content
+            .content_encoding
+            .as_deref()
+            .and_then(|content_encoding| HeaderValue::from_bytes(content_encoding).ok())
+        {
+            response = response.header(header::CONTENT_ENCODING, content_encoding);
+        }
+        Ok(response.body(full(content.body))?)
+    }
+
+    // Streams the blocks indexed from from_height on as server-sent events.
+    // Without from_height it resumes after the Last-Event-ID, or starts at the
+    // next block. A reorg event is sent when blocks already sent were rolled
+    // back, then the blocks replacing them. Only the last block a previous
+    // connection sent is known, so when the block of the Last-Event-ID was
+    // rolled back the reorg reaches back EVENTS_REORG_WINDOW blocks below it.
+    pub async fn handle_events(
+        &self,
+        req: &Request<IncomingBody>,
+    ) -> anyhow::Result<Response<BoxBody>> {
+        let (from_height, filter) = match parse_events_query(req.uri().query().unwrap_or_default())
+        {
+            Ok(query) => query,
+            Err(err) => {
+                return Ok(Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(full(err.to_string()))?)
+            }
+        };
+        let last_event_id = req
+            .headers()
+            .get("last-event-id")
+            .and_then(|id| id.to_str().ok())
+            .and_then(parse_event_id);
+        let (next_height, resumed) = match (from_height, last_event_id) {
+            (Some(height), _) => (height, None),
+            (None, Some((height, hash))) => (height + 1, Some((height, hash))),
+            (None, None) => (self.db.begin_read()?.block_count()?, None),
+        };
+
+        let (tx, rx) = mpsc::channel(EVENTS_BATCH as usize);
+        let server = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = server.stream_events(tx, next_height, resumed, filter).await {
+                tracing::error!("events stream: {:?}", err);
+            }
+        });
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/event-stream")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(EventStream(rx).boxed())?)
+    }
+
+    // returns once the subscriber is gone, resumed is the block a previous
+    // connection sent last
+    async fn stream_events(
+        &self,
+        tx: mpsc::Sender<Bytes>,
+        mut next_height: u32,
+        resumed: Option<(u32, BlockHash)>,
+        filter: EventFilter,
+    ) -> anyhow::Result<()> {
+        let mut sent = VecDeque::with_capacity(EVENTS_REORG_WINDOW);
+        sent.extend(resumed);
+        let mut last_sent = Instant::now();
+        loop {
+            let mut chunks = vec![];
+            {
+                let rxn = self.db.begin_read()?;
+
+                let mut rolled_back = None;
+                for (height, hash) in &sent {
+                    if rxn.block_hash(Some(*height))? != Some(*hash) {
+                        rolled_back = Some(if resumed == Some((*height, *hash)) {
+                            height.saturating_sub(EVENTS_REORG_WINDOW as u32)
+                        } else {
+                            *height
+                        });
+                        break;
+                    }
+                }
+                if let Some(from_height) = rolled_back {
+                    let reorg = ReorgEvent {
+                        from_height,
+                        to_height: next_height - 1,
+                    };
+                    chunks.push(sse("reorg", None, &serde_json::to_value(reorg)?));
+                    sent.retain(|(height, _)| *height < from_height);
+                    next_height = from_height;
+                }
+
+                let count = rxn.block_count()?;
+                while next_height < count && chunks.len() < EVENTS_BATCH as usize {
+                    let Some(events) = rxn.block_events(next_height)? else {
+                        break;
+                    };
+                    if sent.len() == EVENTS_REORG_WINDOW {
+                        sent.pop_front();
+                    }
+                    sent.push_back((events.height, events.hash));
+                    let events = events.filter(&filter);
+                    chunks.push(sse(
+                        "block",
+                        Some((events.height, events.hash)),
+                        &serde_json::to_value(events)?,
+                    ));
+                    next_height += 1;
+                }
+            }
+
+            let caught_up = chunks.len() < EVENTS_BATCH as usize;
+            if chunks.is_empty() && last_sent.elapsed() >= EVENTS_KEEP_ALIVE {
+                chunks.push(Bytes::from_static(b": keep-alive\n\n"));
+            }
+            if !chunks.is_empty() {
+                last_sent = Instant::now();
+            }
+            for chunk in chunks {
+                if tx.send(chunk).await.is_err() {
+                    return Ok(());
+                }
+            }
+            if tx.is_closed() {
+                return Ok(());
+            }
+            if caught_up {
+                tokio::time::sleep(EVENTS_POLL_INTERVAL).await;
+            }
+        }
+    }
+
     pub async fn route(&self, req: Request<IncomingBody>) -> anyhow::Result<Response<BoxBody>> {
         match (req.method(), req.uri().path()) {
             (&Method::POST, "/") => self.handle_rpc_requests(req).await,
+            (&Method::GET, "/events") => self.handle_events(&req).await,
+            (&Method::GET, "/metrics") => self.handle_metrics().await,
+            (&Method::GET, path) if path.starts_with("/content/") => {
+                self.handle_content(&path["/content/".len()..]).await
+            }
             _ => {
                 // Return 404 not found response.
                 Ok(Response::builder()
@@ -188,3 +661,1581 @@ impl Indexer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+    use bitcoin::Address;
+    use bitcoin::Amount;
+    use bitcoin::OutPoint;
+    use bitcoin::Script;
+    use bitcoin::Transaction;
+    use bitcoin::TxIn;
+    use bitcoin::TxOut;
+    use bitcoin::Txid;
+    use l2o_ord::chain::Chain;
+    use l2o_ord::inscription::inscription_id::InscriptionId;
+    use l2o_ord::operation::l2o_a::RawL2OAOperation;
+    use l2o_ord::operation::OperationType;
+    use l2o_ord::sat::Sat;
+    use l2o_ord::sat_point::SatPoint;
+    use l2o_ord::script_key::ScriptKey;
+    use l2o_ord_store::activity::ActivityPage;
+    use l2o_ord_store::activity::ActivityPosition;
+    use l2o_ord_store::balance::Balance;
+    use l2o_ord_store::block_event::BlockEventsLog;
+    use l2o_ord_store::block_event::L2OABlockEvent;
+    use l2o_ord_store::charm::Charm;
+    use l2o_ord_store::entry::Entry;
+    use l2o_ord_store::entry::InscriptionEntry;
+    use l2o_ord_store::entry::SatPointValue;
+    use l2o_ord_store::entry::TxidValue;
+    use l2o_ord_store::event::Event;
+    use l2o_ord_store::event::MintEvent;
+    use l2o_ord_store::event::Receipt;
+    use l2o_ord_store::event::TransferEvent;
+    use l2o_ord_store::holder::TickHoldersPage;
+    use l2o_ord_store::inscription_info::InscriptionContent;
+    use l2o_ord_store::inscription_info::InscriptionInfo;
+    use l2o_ord_store::log::TransferableLog;
+    use l2o_ord_store::statistic::Statistic;
+    use l2o_ord_store::table::get_tick_holders;
+    use l2o_ord_store::table::index_tick_holders;
+    use l2o_ord_store::table::insert_address_activity;
+    use l2o_ord_store::table::insert_block_events_log;
+    use l2o_ord_store::table::insert_inscription_content;
+    use l2o_ord_store::table::insert_token_info;
+    use l2o_ord_store::table::insert_transferable_asset;
+    use l2o_ord_store::table::save_transaction_receipts;
+    use l2o_ord_store::table::update_statistic_to_count;
+    use l2o_ord_store::table::update_tick_holder;
+    use l2o_ord_store::table::update_token_balance;
+    use l2o_ord_store::table::update_token_balance_history;
+    use l2o_ord_store::table::update_token_info_history;
+    use l2o_ord_store::table::BRC20_ADDRESS_ACTIVITY;
+    use l2o_ord_store::table::BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
+    use l2o_ord_store::table::BRC20_BALANCES;
+    use l2o_ord_store::table::BRC20_BALANCE_HISTORY;
+    use l2o_ord_store::table::BRC20_EVENTS;
+    use l2o_ord_store::table::BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS;
+    use l2o_ord_store::table::BRC20_TICK_HOLDERS;
+    use l2o_ord_store::table::BRC20_TOKEN;
+    use l2o_ord_store::table::BRC20_TOKEN_HISTORY;
+    use l2o_ord_store::table::BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
+    use l2o_ord_store::table::BRC21_BALANCES;
+    use l2o_ord_store::table::BRC21_EVENTS;
+    use l2o_ord_store::table::BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS;
+    use l2o_ord_store::table::BRC21_TOKEN;
+    use l2o_ord_store::table::HEIGHT_TO_BLOCK_EVENTS;
+    use l2o_ord_store::table::HEIGHT_TO_BLOCK_HEADER;
+    use l2o_ord_store::table::INSCRIPTION_ID_TO_SEQUENCE_NUMBER;
+    use l2o_ord_store::table::INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER;
+    use l2o_ord_store::table::KV;
+    use l2o_ord_store::table::OUTPOINT_TO_ENTRY;
+    use l2o_ord_store::table::SATPOINT_TO_SEQUENCE_NUMBER;
+    use l2o_ord_store::table::SEQUENCE_NUMBER_TO_CONTENT;
+    use l2o_ord_store::table::SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY;
+    use l2o_ord_store::table::SEQUENCE_NUMBER_TO_SATPOINT;
+    use l2o_ord_store::table::STATISTIC_TO_COUNT;
+    use l2o_ord_store::token_info::TokenInfo;
+    use l2o_rpc::query::ActivityFilter;
+    use l2o_rpc::query::InscriptionQuery;
+    use l2o_rpc_provider::quorum::QuorumProvider;
+    use l2o_rpc_provider::BRC20Provider;
+    use l2o_rpc_provider::BRC21Provider;
+    use l2o_rpc_provider::L2OAProvider;
+    use l2o_rpc_provider::OrdProvider;
+    use l2o_rpc_provider::Provider;
+    use l2o_rpc_provider::ProviderConfig;
+    use l2o_store::core::location::L2OABlockInclusion;
+    use l2o_store::core::location::L2OABlockLocation;
+    use l2o_store::core::store::L2OStoreV1Core;
+    use l2o_store::core::traits::L2OStoreReaderV1;
+    use l2o_store::core::traits::L2OStoreV1;
+    use l2o_store_redb::KVQReDBStore;
+    use redb::Database;
+    use redb::MultimapTableDefinition;
+    use redb::TableDefinition;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    struct Fixture {
+        tick: Tick,
+        owner: ScriptKey,
+        txid: Txid,
+        satpoint: SatPoint,
+        token_info: TokenInfo,
+        balance: Balance,
+        receipt: Receipt,
+        transferable: TransferableLog,
+    }
+
+    fn fixture(tick: &str, op: OperationType) -> Fixture {
+        let tick = Tick::from_str(tick).unwrap();
+        let owner = ScriptKey::from_address(
+            Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+                .unwrap()
+                .assume_checked(),
+        );
+        let inscription_id = InscriptionId::from_str(
+            "1111111111111111111111111111111111111111111111111111111111111111i0",
+        )
+        .unwrap();
+        let satpoint = SatPoint::from_str(
+            "2222222222222222222222222222222222222222222222222222222222222222:1:5",
+        )
+        .unwrap();
+        Fixture {
+            txid: inscription_id.txid,
+            token_info: TokenInfo {
+                tick: tick.clone(),
+                inscription_id,
+                inscription_number: 1,
+                supply: 21_000_000,
+                burned_supply: 0,
+                minted: 1_000,
+                limit_per_mint: 1_000,
+                decimal: 18,
+                deploy_by: owner.clone(),
+                is_self_mint: false,
+                deployed_number: 830_000,
+                deployed_timestamp: 1_700_000_000,
+                latest_mint_number: 830_001,
+                holders: 1,
+            },
+            balance: Balance {
+                tick: tick.clone(),
+                overall_balance: 1_000,
+                transferable_balance: 400,
+            },
+            receipt: Receipt {
+                inscription_id,
+                inscription_number: 1,
+                old_satpoint: satpoint,
+                new_satpoint: satpoint,
+                op,
+                from: owner.clone(),
+                to: owner.clone(),
+                result: Ok(Event::Mint(MintEvent {
+                    tick: tick.clone(),
+                    amount: 1_000,
+                    msg: None,
+                })),
+            },
+            transferable: TransferableLog {
+                inscription_id,
+                inscription_number: 2,
+                amount: 400,
+                tick: tick.clone(),
+                owner: owner.clone(),
+            },
+            tick,
+            owner,
+            satpoint,
+        }
+    }
+
+    fn seed(
+        db: &Database,
+        fixture: &Fixture,
+        token: TableDefinition<&'static str, &'static [u8]>,
+        balances: TableDefinition<&'static str, &'static [u8]>,
+        events: TableDefinition<&'static TxidValue, &'static [u8]>,
+        satpoints: TableDefinition<&'static SatPointValue, &'static [u8]>,
+        addresses: MultimapTableDefinition<&'static str, &'static SatPointValue>,
+    ) {
+        let wtx = db.begin_write().unwrap();
+        wtx.open_table(KV).unwrap();
+        insert_token_info(
+            &mut wtx.open_table(token).unwrap(),
+            &fixture.tick,
+            &fixture.token_info,
+        )
+        .unwrap();
+        update_token_balance(
+            &mut wtx.open_table(balances).unwrap(),
+            &fixture.owner,
+            fixture.balance.clone(),
+        )
+        .unwrap();
+        save_transaction_receipts(
+            &mut wtx.open_table(events).unwrap(),
+            &fixture.txid,
+            std::slice::from_ref(&fixture.receipt),
+        )
+        .unwrap();
+        insert_transferable_asset(
+            &mut wtx.open_table(satpoints).unwrap(),
+            &mut wtx.open_multimap_table(addresses).unwrap(),
+            fixture.satpoint,
+            &fixture.transferable,
+        )
+        .unwrap();
+        wtx.commit().unwrap();
+    }
+
+    async fn spawn_server_url(db: Database) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = RpcServer::new(Arc::new(db), Chain::Mainnet);
+        tokio::spawn(async move { server.serve(listener).await });
+        url
+    }
+
+    async fn spawn_server(db: Database) -> Provider {
+        Provider::new(spawn_server_url(db).await).unwrap()
+    }
+
+    // nothing listens on the port once the listener is dropped
+    async fn dead_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn brc20_db(dir: &tempfile::TempDir, tick: &str) -> (Database, Fixture) {
+        let db = Database::create(dir.path().join(format!("{}.redb", tick))).unwrap();
+        let fixture = fixture(tick, OperationType::BRC20Mint);
+        seed(
+            &db,
+            &fixture,
+            BRC20_TOKEN,
+            BRC20_BALANCES,
+            BRC20_EVENTS,
+            BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+            BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+        );
+        (db, fixture)
+    }
+
+    fn test_config() -> ProviderConfig {
+        ProviderConfig {
+            timeout: Duration::from_secs(5),
+            retries: 1,
+            backoff: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_brc2x_providers_against_rpc_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let brc20 = fixture("ordi", OperationType::BRC20Mint);
+        let brc21 = fixture("l2oa", OperationType::BRC21Mint);
+        seed(
+            &db,
+            &brc20,
+            BRC20_TOKEN,
+            BRC20_BALANCES,
+            BRC20_EVENTS,
+            BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+            BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+        );
+        seed(
+            &db,
+            &brc21,
+            BRC21_TOKEN,
+            BRC21_BALANCES,
+            BRC21_EVENTS,
+            BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS,
+            BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+        );
+        let provider = spawn_server(db).await;
+        let unknown = Tick::from_str("none").unwrap();
+        let transferable = vec![(brc20.satpoint, brc20.transferable.clone())];
+
+        assert_eq!(
+            provider.brc20_get_tick_info(&brc20.tick).await.unwrap(),
+            Some(brc20.token_info.clone())
+        );
+        assert_eq!(provider.brc20_get_tick_info(&unknown).await.unwrap(), None);
+        assert_eq!(
+            provider.brc20_get_all_tick_info().await.unwrap(),
+            vec![brc20.token_info.clone()]
+        );
+        assert_eq!(
+            provider
+                .brc20_get_balance_by_address(&brc20.tick, &brc20.owner)
+                .await
+                .unwrap(),
+            Some(brc20.balance.clone())
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_balance_by_address(&brc20.owner)
+                .await
+                .unwrap(),
+            vec![brc20.balance.clone()]
+        );
+        assert_eq!(
+            provider
+                .brc20_transaction_id_to_transaction_receipt(brc20.txid)
+                .await
+                .unwrap(),
+            Some(vec![brc20.receipt.clone()])
+        );
+        assert_eq!(
+            provider
+                .brc20_get_tick_transferable_by_address(&brc20.tick, &brc20.owner)
+                .await
+                .unwrap(),
+            transferable
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_transferable_by_address(&brc20.owner)
+                .await
+                .unwrap(),
+            transferable
+        );
+        assert_eq!(
+            provider
+                .brc20_transferable_assets_on_output_with_satpoints(brc20.satpoint.outpoint)
+                .await
+                .unwrap(),
+            transferable
+        );
+
+        let transferable = vec![(brc21.satpoint, brc21.transferable.clone())];
+        assert_eq!(
+            provider.brc21_get_tick_info(&brc21.tick).await.unwrap(),
+            Some(brc21.token_info.clone())
+        );
+        assert_eq!(
+            provider.brc21_get_tick_info(&brc20.tick).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            provider.brc21_get_all_tick_info().await.unwrap(),
+            vec![brc21.token_info.clone()]
+        );
+        assert_eq!(
+            provider
+                .brc21_get_balance_by_address(&brc21.tick, &brc21.owner)
+                .await
+                .unwrap(),
+            Some(brc21.balance.clone())
+        );
+        assert_eq!(
+            provider
+                .brc21_get_all_balance_by_address(&brc21.owner)
+                .await
+                .unwrap(),
+            vec![brc21.balance.clone()]
+        );
+        assert_eq!(
+            provider
+                .brc21_transaction_id_to_transaction_receipt(brc21.txid)
+                .await
+                .unwrap(),
+            Some(vec![brc21.receipt.clone()])
+        );
+        assert_eq!(
+            provider
+                .brc21_get_tick_transferable_by_address(&brc21.tick, &brc21.owner)
+                .await
+                .unwrap(),
+            transferable
+        );
+        assert_eq!(
+            provider
+                .brc21_get_all_transferable_by_address(&brc21.owner)
+                .await
+                .unwrap(),
+            transferable
+        );
+        assert_eq!(
+            provider
+                .brc21_transferable_assets_on_output_with_satpoints(brc21.satpoint.outpoint)
+                .await
+                .unwrap(),
+            transferable
+        );
+    }
+
+    #[tokio::test]
+    async fn test_l2o_provider_against_rpc_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let RawL2OAOperation::Deploy(deploy) =
+            serde_json::from_str(include_str!("../../static/deploy.json")).unwrap()
+        else {
+            panic!("expected deploy");
+        };
+        let RawL2OAOperation::Block(block) =
+            serde_json::from_str(include_str!("../../static/block.json")).unwrap()
+        else {
+            panic!("expected block");
+        };
+        let l2id = block.l2id;
+        let bitcoin_block_number = block.bitcoin_block_number;
+        let hash = deploy.hash_function;
+        let inscription_id = InscriptionId::from_str(
+            "3333333333333333333333333333333333333333333333333333333333333333i0",
+        )
+        .unwrap();
+        let transaction = Transaction {
+            version: Version(2),
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn::default()],
+            output: vec![],
+        };
+        {
+            let wtx = db.begin_write().unwrap();
+            {
+                let mut store = L2OStoreV1Core::new(KVQReDBStore::new(wtx.open_table(KV).unwrap()));
+                store.report_deploy_inscription(deploy.clone()).unwrap();
+                store.set_last_block_inscription(block.clone()).unwrap();
+                store
+                    .report_block_inscription_location(
+                        l2id,
+                        bitcoin_block_number,
+                        L2OABlockLocation {
+                            txid: inscription_id.txid,
+                            inscription_id,
+                            start_state_root: block.start_state_root,
+                        },
+                    )
+                    .unwrap();
+                store
+                    .report_block_inscription_inclusion(
+                        l2id,
+                        bitcoin_block_number,
+                        L2OABlockInclusion {
+                            height: bitcoin_block_number as u32 + 1,
+                            transaction_index: 0,
+                            transaction: transaction.clone(),
+                            transaction_branch: vec![],
+                        },
+                    )
+                    .unwrap();
+            }
+            let mut headers = wtx.open_table(HEIGHT_TO_BLOCK_HEADER).unwrap();
+            headers
+                .insert(bitcoin_block_number as u32, &[0u8; 80])
+                .unwrap();
+            headers
+                .insert(bitcoin_block_number as u32 + 1, &[1u8; 80])
+                .unwrap();
+            drop(headers);
+            wtx.commit().unwrap();
+        }
+        // what the provider returns is what the store reads
+        let (state_root, superchain_root, proof, delta) = {
+            let rxn = db.begin_read().unwrap();
+            let store = L2OStoreV1Core::new(KVQReDBStore::new(rxn.open_table(KV).unwrap()));
+            (
+                store
+                    .get_state_root_at_block(l2id, bitcoin_block_number + 1, hash)
+                    .unwrap(),
+                store
+                    .get_superchainroot_at_block(bitcoin_block_number + 1, hash)
+                    .unwrap(),
+                store
+                    .get_merkle_proof_state_root_at_block(l2id, bitcoin_block_number + 1, hash)
+                    .unwrap(),
+                store
+                    .get_superchain_delta_proof(l2id, bitcoin_block_number, hash)
+                    .unwrap(),
+            )
+        };
+        let provider = spawn_server(db).await;
+
+        assert_eq!(
+            provider.get_last_block_inscription(l2id).await.unwrap(),
+            block
+        );
+        assert!(provider.get_last_block_inscription(l2id + 1).await.is_err());
+        assert_eq!(provider.get_deploy_inscription(l2id).await.unwrap(), deploy);
+        assert!(provider.get_deploy_inscription(l2id + 1).await.is_err());
+        assert_eq!(
+            provider
+                .get_state_root_at_block(l2id, bitcoin_block_number + 1, hash)
+                .await
+                .unwrap(),
+            state_root
+        );
+        assert_eq!(
+            provider
+                .get_superchainroot_at_block(bitcoin_block_number + 1, hash)
+                .await
+                .unwrap(),
+            superchain_root
+        );
+        assert_eq!(
+            provider
+                .get_merkle_proof_state_root_at_block(l2id, bitcoin_block_number + 1, hash)
+                .await
+                .unwrap(),
+            proof
+        );
+        assert_eq!(proof.root, superchain_root);
+        assert_eq!(
+            provider
+                .get_superchain_delta_proof(l2id, bitcoin_block_number, hash)
+                .await
+                .unwrap(),
+            delta
+        );
+        assert_eq!(delta.new_root, superchain_root);
+        assert!(provider
+            .get_superchain_delta_proof(l2id, bitcoin_block_number + 1, hash)
+            .await
+            .is_err());
+
+        let bundle = provider
+            .get_superchain_proof_bundle(l2id, bitcoin_block_number, hash)
+            .await
+            .unwrap();
+        assert_eq!(bundle.l2id, l2id);
+        assert_eq!(bundle.bitcoin_block_header, vec![0u8; 80]);
+        assert_eq!(bundle.inclusion_block_header, vec![1u8; 80]);
+        assert_eq!(bundle.state_root, block.start_state_root);
+        assert_eq!(bundle.superchain_root, bundle.state_root_proof.root);
+        assert_eq!(bundle.inscription_id, inscription_id.to_string());
+        assert_eq!(
+            bundle.transaction,
+            bitcoin::consensus::serialize(&transaction)
+        );
+        assert!(provider
+            .get_superchain_proof_bundle(l2id, bitcoin_block_number + 1, hash)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_provider_failover() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, fixture) = brc20_db(&dir, "ordi");
+        let urls = vec![dead_url().await, spawn_server_url(db).await];
+
+        let provider = Provider::with_config(urls.clone(), test_config()).unwrap();
+        assert_eq!(
+            provider.brc20_get_all_tick_info().await.unwrap(),
+            vec![fixture.token_info]
+        );
+
+        let provider = Provider::with_config(urls[..1].to_vec(), test_config()).unwrap();
+        assert!(provider.brc20_get_all_tick_info().await.is_err());
+        assert!(Provider::with_config(vec![], test_config()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_provider_does_not_retry_rpc_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, _) = brc20_db(&dir, "ordi");
+        let config = ProviderConfig {
+            backoff: Duration::from_secs(60),
+            ..test_config()
+        };
+        let provider = Provider::with_config(vec![spawn_server_url(db).await], config).unwrap();
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            provider.brc20_get_all_tick_info_at_height(100),
+        )
+        .await
+        .expect("an rpc error must not be retried");
+        assert!(result.unwrap_err().to_string().starts_with("rpc error"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, _) = brc20_db(&dir, "ordi");
+        let url = spawn_server_url(db).await;
+        Provider::new(url.clone())
+            .unwrap()
+            .brc20_get_all_tick_info()
+            .await
+            .unwrap();
+
+        let response = reqwest::get(format!("{}/metrics", url)).await.unwrap();
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/plain; version=0.0.4"
+        );
+        let metrics = response.text().await.unwrap();
+        assert!(metrics
+            .contains(r#"l2o_rpc_requests_total{method="brc20_getAllTickInfo",outcome="ok"}"#));
+        assert!(metrics.contains(r#"l2o_rpc_request_seconds_count{method="brc20_getAllTickInfo"}"#));
+        assert!(metrics.contains("l2o_indexed_height"));
+    }
+
+    #[tokio::test]
+    async fn test_quorum_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let (ordi, fixture) = brc20_db(&dir, "ordi");
+        let (ordj, _) = brc20_db(&dir, "ordj");
+        let (sats, _) = brc20_db(&dir, "sats");
+        let ordi = spawn_server_url(ordi).await;
+        let ordj = spawn_server_url(ordj).await;
+        let sats = spawn_server_url(sats).await;
+        let dead = dead_url().await;
+        let quorum = |urls: &[&String], quorum: usize| {
+            let providers = urls
+                .iter()
+                .map(|url| Provider::with_config(vec![url.to_string()], test_config()).unwrap())
+                .collect();
+            QuorumProvider::new(providers, quorum)
+        };
+        let request = RequestParams::BRC20GetAllTickInfo(AtHeight::latest(()));
+
+        // every indexer that answers has to agree
+        let provider = quorum(&[&ordi, &dead], 1).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Vec<TokenInfo>>(
+                provider.call_agreed(request.clone()).await.unwrap()
+            )
+            .unwrap(),
+            vec![fixture.token_info]
+        );
+        let provider = quorum(&[&ordi, &sats], 1).unwrap();
+        let err = provider.call_agreed(request.clone()).await.unwrap_err();
+        assert!(err.to_string().contains("indexers disagree"));
+
+        // and enough of them have to answer
+        let provider = quorum(&[&ordi, &dead], 2).unwrap();
+        assert!(provider.call_agreed(request.clone()).await.is_err());
+
+        // other calls fail over
+        let provider = quorum(&[&dead, &ordj], 2).unwrap();
+        assert_eq!(
+            provider
+                .call(request)
+                .await
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(quorum(&[&ordi], 0).is_err());
+        assert!(quorum(&[&ordi], 2).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_brc20_provider_at_height() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let ordi = fixture("ordi", OperationType::BRC20Mint);
+        let sats = fixture("sats", OperationType::BRC20Mint);
+        // the history of ordis sorts right after the history of ordi
+        let ordis = fixture("ordis", OperationType::BRC20Mint);
+        let deployed = TokenInfo {
+            minted: 0,
+            ..ordi.token_info.clone()
+        };
+        let half = Balance {
+            overall_balance: 500,
+            ..ordi.balance.clone()
+        };
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            update_statistic_to_count(
+                &mut wtx.open_table(STATISTIC_TO_COUNT).unwrap(),
+                &Statistic::TokenHistoryStartHeight,
+                9,
+            )
+            .unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_HEADER)
+                .unwrap()
+                .insert(12, &[0u8; 80])
+                .unwrap();
+            let mut tokens = wtx.open_table(BRC20_TOKEN_HISTORY).unwrap();
+            update_token_info_history(&mut tokens, &ordi.tick, 10, &deployed).unwrap();
+            update_token_info_history(&mut tokens, &ordi.tick, 12, &ordi.token_info).unwrap();
+            update_token_info_history(&mut tokens, &ordis.tick, 12, &ordis.token_info).unwrap();
+            let mut balances = wtx.open_table(BRC20_BALANCE_HISTORY).unwrap();
+            update_token_balance_history(&mut balances, &ordi.owner, 10, &half).unwrap();
+            update_token_balance_history(&mut balances, &ordi.owner, 12, &ordi.balance).unwrap();
+            update_token_balance_history(&mut balances, &sats.owner, 11, &sats.balance).unwrap();
+            drop((tokens, balances));
+            wtx.commit().unwrap();
+        }
+        let provider = spawn_server(db).await;
+
+        for (height, info) in [(9, None), (10, Some(&deployed)), (11, Some(&deployed))] {
+            assert_eq!(
+                provider
+                    .brc20_get_tick_info_at_height(&ordi.tick, height)
+                    .await
+                    .unwrap()
+                    .as_ref(),
+                info
+            );
+        }
+        assert_eq!(
+            provider
+                .brc20_get_tick_info_at_height(&ordi.tick, 12)
+                .await
+                .unwrap(),
+            Some(ordi.token_info.clone())
+        );
+        assert_eq!(
+            provider.brc20_get_all_tick_info_at_height(9).await.unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_tick_info_at_height(11)
+                .await
+                .unwrap(),
+            vec![deployed]
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_tick_info_at_height(12)
+                .await
+                .unwrap(),
+            vec![ordi.token_info.clone(), ordis.token_info]
+        );
+
+        assert_eq!(
+            provider
+                .brc20_get_balance_by_address_at_height(&ordi.tick, &ordi.owner, 9)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            provider
+                .brc20_get_balance_by_address_at_height(&ordi.tick, &ordi.owner, 11)
+                .await
+                .unwrap(),
+            Some(half.clone())
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_balance_by_address_at_height(&ordi.owner, 11)
+                .await
+                .unwrap(),
+            vec![half, sats.balance.clone()]
+        );
+        assert_eq!(
+            provider
+                .brc20_get_all_balance_by_address_at_height(&ordi.owner, 12)
+                .await
+                .unwrap(),
+            vec![ordi.balance.clone(), sats.balance]
+        );
+
+        // before the history starts and after the tip
+        assert!(provider.brc20_get_all_tick_info_at_height(8).await.is_err());
+        assert!(provider
+            .brc20_get_all_tick_info_at_height(13)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_brc20_provider_address_activity() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let ordi = fixture("ordi", OperationType::BRC20Mint);
+        let sats = fixture("sats", OperationType::BRC20Mint);
+        let other = ScriptKey::from_address(
+            Address::from_str("bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297")
+                .unwrap()
+                .assume_checked(),
+        );
+        let transfer = Receipt {
+            op: OperationType::BRC20Transfer,
+            to: other.clone(),
+            result: Ok(Event::Transfer(TransferEvent {
+                tick: sats.tick.clone(),
+                amount: 10,
+                msg: None,
+            })),
+            ..sats.receipt.clone()
+        };
+        let txs = [
+            (
+                10,
+                3,
+                Txid::from_str(&"a".repeat(64)).unwrap(),
+                vec![ordi.receipt.clone(), transfer],
+            ),
+            (
+                11,
+                1,
+                Txid::from_str(&"b".repeat(64)).unwrap(),
+                vec![sats.receipt.clone()],
+            ),
+            (
+                12,
+                7,
+                Txid::from_str(&"c".repeat(64)).unwrap(),
+                vec![ordi.receipt.clone()],
+            ),
+        ];
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            let mut events = wtx.open_table(BRC20_EVENTS).unwrap();
+            let mut activity = wtx.open_table(BRC20_ADDRESS_ACTIVITY).unwrap();
+            for (height, tx_index, txid, receipts) in txs.iter() {
+                save_transaction_receipts(&mut events, txid, receipts).unwrap();
+                for (index, receipt) in receipts.iter().enumerate() {
+                    let position = ActivityPosition {
+                        height: *height,
+                        tx_index: *tx_index,
+                        index: index as u32,
+                    };
+                    insert_address_activity(&mut activity, &receipt.from, &position, txid).unwrap();
+                    if receipt.to != receipt.from {
+                        insert_address_activity(&mut activity, &receipt.to, &position, txid)
+                            .unwrap();
+                    }
+                }
+            }
+            drop((events, activity));
+            wtx.commit().unwrap();
+        }
+        let provider = spawn_server(db).await;
+        let activity = |filter: ActivityFilter, owner: &ScriptKey| {
+            let provider = &provider;
+            let owner = owner.clone();
+            async move {
+                provider
+                    .brc20_get_address_activity(&owner, &filter)
+                    .await
+                    .unwrap()
+            }
+        };
+        let positions = |page: &ActivityPage| {
+            page.activities
+                .iter()
+                .map(|activity| (activity.height, activity.receipt.op.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // pages follow each other through the cursor
+        let page = activity(
+            ActivityFilter {
+                limit: Some(2),
+                ..Default::default()
+            },
+            &ordi.owner,
+        )
+        .await;
+        assert_eq!(
+            positions(&page),
+            vec![
+                (10, OperationType::BRC20Mint),
+                (10, OperationType::BRC20Transfer)
+            ]
+        );
+        assert_eq!(page.activities[0].txid, txs[0].2);
+        assert_eq!(page.activities[0].tx_index, 3);
+        assert_eq!(page.activities[1].receipt, txs[0].3[1]);
+        let page = activity(
+            ActivityFilter {
+                limit: Some(2),
+                cursor: page.next_cursor,
+                ..Default::default()
+            },
+            &ordi.owner,
+        )
+        .await;
+        assert_eq!(
+            positions(&page),
+            vec![
+                (11, OperationType::BRC20Mint),
+                (12, OperationType::BRC20Mint)
+            ]
+        );
+        assert_eq!(page.next_cursor, None);
+
+        // filters
+        let page = activity(
+            ActivityFilter {
+                tick: Some(Tick::from_str("ORDI").unwrap()),
+                ..Default::default()
+            },
+            &ordi.owner,
+        )
+        .await;
+        assert_eq!(
+            positions(&page),
+            vec![
+                (10, OperationType::BRC20Mint),
+                (12, OperationType::BRC20Mint)
+            ]
+        );
+        let page = activity(
+            ActivityFilter {
+                op: Some(OperationType::BRC20Transfer),
+                ..Default::default()
+            },
+            &ordi.owner,
+        )
+        .await;
+        assert_eq!(positions(&page), vec![(10, OperationType::BRC20Transfer)]);
+        let page = activity(
+            ActivityFilter {
+                from_height: Some(11),
+                to_height: Some(11),
+                ..Default::default()
+            },
+            &ordi.owner,
+        )
+        .await;
+        assert_eq!(positions(&page), vec![(11, OperationType::BRC20Mint)]);
+
+        // the receiver sees the transfer too
+        let page = activity(ActivityFilter::default(), &other).await;
+        assert_eq!(positions(&page), vec![(10, OperationType::BRC20Transfer)]);
+
+        let filter = ActivityFilter {
+            cursor: Some("10".to_string()),
+            ..Default::default()
+        };
+        assert!(provider
+            .brc20_get_address_activity(&ordi.owner, &filter)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_brc20_provider_tick_holders() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let ordi = fixture("ordi", OperationType::BRC20Mint);
+        let sats = fixture("sats", OperationType::BRC20Mint);
+        let whale = ScriptKey::from_address(
+            Address::from_str("bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297")
+                .unwrap()
+                .assume_checked(),
+        );
+        let script = ScriptKey::from_script(Script::from_bytes(&[0x51]), Chain::Mainnet);
+        let burn = ScriptKey::from_script(Script::from_bytes(&[0x6a]), Chain::Mainnet);
+        let gone = ScriptKey::from_address(
+            Address::from_str("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM")
+                .unwrap()
+                .assume_checked(),
+        );
+        let ordi_balance = |overall_balance| Balance {
+            overall_balance,
+            ..ordi.balance.clone()
+        };
+        let counts = {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            let mut balances = wtx.open_table(BRC20_BALANCES).unwrap();
+            let mut holders = wtx.open_table(BRC20_TICK_HOLDERS).unwrap();
+            for (owner, balance) in [
+                (&ordi.owner, ordi_balance(1_000)),
+                (&whale, ordi_balance(5_000)),
+                (&script, ordi_balance(1_000)),
+                (&gone, ordi_balance(0)),
+                (&whale, sats.balance.clone()),
+            ] {
+                update_token_balance(&mut balances, owner, balance).unwrap();
+            }
+            let counts = index_tick_holders(&balances, &mut holders).unwrap();
+            // balances change after they were indexed
+            update_tick_holder(
+                &mut holders,
+                &script,
+                Some(&ordi_balance(1_000)),
+                &ordi_balance(2_000),
+            )
+            .unwrap();
+            update_tick_holder(&mut holders, &gone, None, &ordi_balance(10)).unwrap();
+            update_tick_holder(
+                &mut holders,
+                &gone,
+                Some(&ordi_balance(10)),
+                &ordi_balance(0),
+            )
+            .unwrap();
+            let burned = Balance {
+                tick: Tick::from_str("burn").unwrap(),
+                ..ordi_balance(1)
+            };
+            update_tick_holder(&mut holders, &burn, None, &burned).unwrap();
+            drop((balances, holders));
+            wtx.commit().unwrap();
+            counts
+        };
+        assert_eq!(counts, vec![(ordi.tick.clone(), 3), (sats.tick.clone(), 1)]);
+
+        // the op_return flag is not serialized, so it is compared on the table
+        let page = get_tick_holders(
+            &db.begin_read()
+                .unwrap()
+                .open_table(BRC20_TICK_HOLDERS)
+                .unwrap(),
+            &Tick::from_str("burn").unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(page.holders.len(), 1);
+        assert_eq!(page.holders[0].script_key, burn);
+
+        let provider = spawn_server(db).await;
+        let holders = |page: &TickHoldersPage| {
+            page.holders
+                .iter()
+                .map(|holder| {
+                    (
+                        holder.script_key.to_string(),
+                        holder.balance.overall_balance,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let page = provider
+            .brc20_get_tick_holders(&Tick::from_str("ORDI").unwrap(), None, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            holders(&page),
+            vec![(whale.to_string(), 5_000), (script.to_string(), 2_000)]
+        );
+        assert_eq!(page.holders[0].balance, ordi_balance(5_000));
+        let page = provider
+            .brc20_get_tick_holders(&ordi.tick, page.next_cursor, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(holders(&page), vec![(ordi.owner.to_string(), 1_000)]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = provider
+            .brc20_get_tick_holders(&sats.tick, None, None)
+            .await
+            .unwrap();
+        assert_eq!(holders(&page), vec![(whale.to_string(), 1_000)]);
+        let page = provider
+            .brc20_get_tick_holders(&Tick::from_str("none").unwrap(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(page, TickHoldersPage::default());
+
+        assert!(provider
+            .brc20_get_tick_holders(&ordi.tick, Some("5000".to_string()), None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ord_provider_inscriptions() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let owner =
+            Address::from_str("bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297")
+                .unwrap()
+                .assume_checked();
+        let bound = OutPoint::from_str(&format!("{}:0", "a".repeat(64))).unwrap();
+        let unknown = OutPoint::from_str(&format!("{}:1", "b".repeat(64))).unwrap();
+        let id = |index| InscriptionId {
+            txid: Txid::from_str(&"c".repeat(64)).unwrap(),
+            index,
+        };
+        let mut cursed = 0;
+        Charm::Cursed.set(&mut cursed);
+        // (height, number, charms, parent, satpoint)
+        let inscriptions = [
+            (
+                10,
+                0,
+                0,
+                None,
+                SatPoint {
+                    outpoint: bound,
+                    offset: 0,
+                },
+            ),
+            (
+                12,
+                -1,
+                cursed,
+                Some(0),
+                SatPoint {
+                    outpoint: bound,
+                    offset: 5,
+                },
+            ),
+            (
+                12,
+                1,
+                0,
+                None,
+                SatPoint {
+                    outpoint: unknown,
+                    offset: 0,
+                },
+            ),
+            (
+                15,
+                2,
+                0,
+                None,
+                SatPoint {
+                    outpoint: unknown,
+                    offset: 9,
+                },
+            ),
+        ];
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            let mut entries = wtx
+                .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)
+                .unwrap();
+            let mut satpoints = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT).unwrap();
+            let mut satpoint_to_sequence_number = wtx
+                .open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)
+                .unwrap();
+            let mut ids = wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER).unwrap();
+            let mut numbers = wtx
+                .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)
+                .unwrap();
+            for (sequence_number, (height, number, charms, parent, satpoint)) in
+                inscriptions.iter().enumerate()
+            {
+                let sequence_number = sequence_number as u32;
+                let entry = InscriptionEntry {
+                    charms: *charms,
+                    fee: 100,
+                    height: *height,
+                    id: id(sequence_number),
+                    inscription_number: *number,
+                    parent: *parent,
+                    sat: Some(Sat(1_000 + sequence_number as u64)),
+                    sequence_number,
+                    timestamp: 1_700_000_000 + height,
+                };
+                entries.insert(sequence_number, entry.store()).unwrap();
+                satpoints
+                    .insert(sequence_number, &satpoint.store())
+                    .unwrap();
+                satpoint_to_sequence_number
+                    .insert(&satpoint.store(), sequence_number)
+                    .unwrap();
+                ids.insert(id(sequence_number).store(), sequence_number)
+                    .unwrap();
+                numbers.insert(number, sequence_number).unwrap();
+            }
+            let txout = TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: owner.script_pubkey(),
+            };
+            wtx.open_table(OUTPOINT_TO_ENTRY)
+                .unwrap()
+                .insert(
+                    &bound.store(),
+                    bitcoin::consensus::serialize(&txout).as_slice(),
+                )
+                .unwrap();
+            drop((
+                entries,
+                satpoints,
+                satpoint_to_sequence_number,
+                ids,
+                numbers,
+            ));
+            wtx.commit().unwrap();
+        }
+        let provider = spawn_server(db).await;
+        let numbers = |inscriptions: Vec<InscriptionInfo>| {
+            inscriptions
+                .iter()
+                .map(|inscription| inscription.number)
+                .collect::<Vec<_>>()
+        };
+
+        let first = provider
+            .get_inscription(&InscriptionQuery::Id(id(0)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            first,
+            InscriptionInfo {
+                id: id(0),
+                number: 0,
+                sequence_number: 0,
+                charms: vec![],
+                fee: 100,
+                height: 10,
+                sat: Some(1_000),
+                parent: None,
+                timestamp: 1_700_000_010,
+                satpoint: inscriptions[0].4,
+                owner: Some(ScriptKey::from_address(owner)),
+                value: Some(10_000),
+            }
+        );
+        let child = provider
+            .get_inscription(&InscriptionQuery::Number(-1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(child.id, id(1));
+        assert_eq!(child.charms, vec!["cursed".to_string()]);
+        assert_eq!(child.parent, Some(id(0)));
+        let unbound = provider
+            .get_inscription(&InscriptionQuery::Id(id(2)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((unbound.owner, unbound.value), (None, None));
+        assert_eq!(
+            provider
+                .get_inscription(&InscriptionQuery::Number(99))
+                .await
+                .unwrap(),
+            None
+        );
+
+        let on_output = |outpoint| {
+            let provider = &provider;
+            async move {
+                provider
+                    .get_inscriptions_on_output(&outpoint)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(numbers(on_output(bound).await), vec![0, -1]);
+        assert_eq!(numbers(on_output(unknown).await), vec![1, 2]);
+
+        let by_height = |height| {
+            let provider = &provider;
+            async move { provider.get_inscriptions_by_height(height).await.unwrap() }
+        };
+        assert_eq!(numbers(by_height(10).await), vec![0]);
+        assert_eq!(numbers(by_height(11).await), Vec::<i32>::new());
+        assert_eq!(numbers(by_height(12).await), vec![-1, 1]);
+        assert_eq!(numbers(by_height(15).await), vec![2]);
+        assert_eq!(numbers(by_height(16).await), Vec::<i32>::new());
+    }
+
+    #[tokio::test]
+    async fn test_inscription_content_route() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let id = |index| InscriptionId {
+            txid: Txid::from_str(&"d".repeat(64)).unwrap(),
+            index,
+        };
+        let deploy = br#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"21000000","lim":"1000"}"#;
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            let mut ids = wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER).unwrap();
+            let mut contents = wtx.open_table(SEQUENCE_NUMBER_TO_CONTENT).unwrap();
+            for (sequence_number, content) in [
+                InscriptionContent {
+                    content_type: Some(b"text/plain;charset=utf-8".to_vec()),
+                    content_encoding: None,
+                    body: deploy.to_vec(),
+                },
+                InscriptionContent {
+                    content_type: None,
+                    content_encoding: Some(b"identity".to_vec()),
+                    body: vec![0, 159, 146, 150],
+                },
+            ]
+            .iter()
+            .enumerate()
+            {
+                ids.insert(id(sequence_number as u32).store(), sequence_number as u32)
+                    .unwrap();
+                insert_inscription_content(&mut contents, sequence_number as u32, content).unwrap();
+            }
+            // indexed without --index-content
+            ids.insert(id(2).store(), 2).unwrap();
+            drop((ids, contents));
+            wtx.commit().unwrap();
+        }
+        let url = spawn_server_url(db).await;
+        let get = |path: String| {
+            let url = &url;
+            async move { reqwest::get(format!("{}{}", url, path)).await.unwrap() }
+        };
+
+        let response = get(format!("/content/{}", id(0))).await;
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/plain;charset=utf-8"
+        );
+        assert!(response.headers().get("content-encoding").is_none());
+        assert_eq!(
+            response.headers()["content-security-policy"],
+            "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:"
+        );
+        assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+        assert_eq!(response.bytes().await.unwrap().as_ref(), deploy);
+
+        let response = get(format!("/content/{}", id(1))).await;
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/octet-stream"
+        );
+        assert_eq!(response.headers()["content-encoding"], "identity");
+        assert_eq!(
+            response.bytes().await.unwrap().as_ref(),
+            &[0, 159, 146, 150]
+        );
+
+        let response = get(format!("/content/{}", id(2))).await;
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let response = get(format!("/content/{}", id(3))).await;
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let response = get("/content/ordi".to_string()).await;
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    }
+
+    // the name, id and data of the next server-sent event, the id is the
+    // height and hash of a block
+    async fn next_event(
+        response: &mut reqwest::Response,
+        buffer: &mut String,
+    ) -> (String, Option<(u32, String)>, serde_json::Value) {
+        loop {
+            if let Some(end) = buffer.find("\n\n") {
+                let event = buffer[..end].to_string();
+                buffer.drain(..end + 2);
+                let field = |name: &str| {
+                    event
+                        .lines()
+                        .find_map(|line| line.strip_prefix(name))
+                        .map(str::to_string)
+                };
+                return (
+                    field("event: ").unwrap(),
+                    field("id: ").map(|id| {
+                        let (height, hash) = id.split_once(':').unwrap();
+                        (height.parse().unwrap(), hash.to_string())
+                    }),
+                    serde_json::from_str(&field("data: ").unwrap()).unwrap(),
+                );
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(10), response.chunk())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("index.redb")).unwrap());
+        let txid = |c: &str| Txid::from_str(&c.repeat(64)).unwrap();
+        let ordi = fixture("ordi", OperationType::BRC20Mint).receipt;
+        let sats = fixture("sats", OperationType::BRC20Mint).receipt;
+        let l2o_a_block = Receipt {
+            inscription_id: InscriptionId {
+                txid: txid("c"),
+                index: 0,
+            },
+            op: OperationType::L2OABlock,
+            result: Ok(Event::L2OABlock),
+            ..ordi.clone()
+        };
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            let mut headers = wtx.open_table(HEIGHT_TO_BLOCK_HEADER).unwrap();
+            headers.insert(0, &[0u8; 80]).unwrap();
+            headers.insert(1, &[1u8; 80]).unwrap();
+            let mut events = wtx.open_table(BRC20_EVENTS).unwrap();
+            for (txid, receipt) in [
+                (txid("a"), &ordi),
+                (txid("b"), &sats),
+                (txid("c"), &l2o_a_block),
+            ] {
+                save_transaction_receipts(&mut events, &txid, std::slice::from_ref(receipt))
+                    .unwrap();
+            }
+            insert_block_events_log(
+                &mut wtx.open_table(HEIGHT_TO_BLOCK_EVENTS).unwrap(),
+                1,
+                &BlockEventsLog {
+                    txids: vec![txid("a"), txid("b"), txid("c")],
+                    l2o_a_blocks: vec![L2OABlockEvent {
+                        txid: txid("c"),
+                        inscription_id: l2o_a_block.inscription_id,
+                        l2id: 7,
+                        l2_block_number: 0,
+                        bitcoin_block_number: 1,
+                        accepted: false,
+                    }],
+                },
+            )
+            .unwrap();
+            drop((headers, events));
+            wtx.commit().unwrap();
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = RpcServer::new(Arc::clone(&db), Chain::Mainnet);
+        tokio::spawn(async move { server.serve(listener).await });
+
+        let mut ticks = reqwest::get(format!("{}?from_height=0&tick=ORDI", url))
+            .await
+            .unwrap();
+        assert_eq!(ticks.headers()["content-type"], "text/event-stream");
+        let mut ticks_buffer = String::new();
+        let (event, id, block) = next_event(&mut ticks, &mut ticks_buffer).await;
+        let (_, hash) = id.clone().unwrap();
+        assert_eq!((event.as_str(), id), ("block", Some((0, hash.clone()))));
+        assert_eq!(block["hash"], hash);
+        assert_eq!(block["transactions"], serde_json::json!([]));
+        let (_, id, block) = next_event(&mut ticks, &mut ticks_buffer).await;
+        let last_event_id = id.clone().unwrap();
+        assert_eq!(id.map(|id| id.0), Some(1));
+        let transactions = block["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0]["txid"], txid("a").to_string());
+        assert_eq!(block["l2o_a_blocks"], serde_json::json!([]));
+
+        let mut l2o = reqwest::get(format!("{}?from_height=1&l2id=7", url))
+            .await
+            .unwrap();
+        let (_, id, block) = next_event(&mut l2o, &mut String::new()).await;
+        assert_eq!(id.map(|id| id.0), Some(1));
+        let transactions = block["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0]["txid"], txid("c").to_string());
+        assert_eq!(block["l2o_a_blocks"][0]["l2id"], 7);
+        assert_eq!(block["l2o_a_blocks"][0]["accepted"], false);
+
+        // resumes after the last event id
+        let mut resumed = reqwest::Client::new()
+            .get(&url)
+            .header("Last-Event-ID", format!("0:{}", hash))
+            .send()
+            .await
+            .unwrap();
+        let (event, id, block) = next_event(&mut resumed, &mut String::new()).await;
+        assert_eq!((event.as_str(), id.map(|id| id.0)), ("block", Some(1)));
+        assert_eq!(block["transactions"].as_array().unwrap().len(), 3);
+
+        // block 1 is replaced, and block 2 follows it
+        {
+            let wtx = db.begin_write().unwrap();
+            let mut headers = wtx.open_table(HEIGHT_TO_BLOCK_HEADER).unwrap();
+            headers.insert(1, &[9u8; 80]).unwrap();
+            headers.insert(2, &[2u8; 80]).unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_EVENTS)
+                .unwrap()
+                .remove(1)
+                .unwrap();
+            drop(headers);
+            wtx.commit().unwrap();
+        }
+        let (event, id, reorg) = next_event(&mut ticks, &mut ticks_buffer).await;
+        assert_eq!((event.as_str(), id), ("reorg", None));
+        assert_eq!(reorg, serde_json::json!({"from_height": 1, "to_height": 1}));
+        let (_, id, block) = next_event(&mut ticks, &mut ticks_buffer).await;
+        assert_eq!(id.as_ref().map(|id| id.0), Some(1));
+        assert_ne!(id.unwrap().1, last_event_id.1);
+        assert_eq!(block["transactions"], serde_json::json!([]));
+        let (_, id, _) = next_event(&mut ticks, &mut ticks_buffer).await;
+        assert_eq!(id.map(|id| id.0), Some(2));
+
+        // a subscriber that reconnects after the reorg is told about it too
+        let mut reconnected = reqwest::Client::new()
+            .get(&url)
+            .header(
+                "Last-Event-ID",
+                format!("{}:{}", last_event_id.0, last_event_id.1),
+            )
+            .send()
+            .await
+            .unwrap();
+        let mut reconnected_buffer = String::new();
+        let (event, id, reorg) = next_event(&mut reconnected, &mut reconnected_buffer).await;
+        assert_eq!((event.as_str(), id), ("reorg", None));
+        assert_eq!(reorg, serde_json::json!({"from_height": 0, "to_height": 1}));
+        let (_, id, _) = next_event(&mut reconnected, &mut reconnected_buffer).await;
+        assert_eq!(id.map(|id| id.0), Some(0));
+        let (_, id, _) = next_event(&mut reconnected, &mut reconnected_buffer).await;
+        assert_eq!(id.map(|id| id.0), Some(1));
+        let (_, id, _) = next_event(&mut reconnected, &mut reconnected_buffer).await;
+        assert_eq!(id.map(|id| id.0), Some(2));
+
+        for query in [
+            "protocol=erc20",
+            "from_height=x",
+            "tick=toolongtick",
+            "limit=1",
+        ] {
+            let response = reqwest::get(format!("{}?{}", url, query)).await.unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_resumes_across_a_deep_reorg() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("index.redb")).unwrap());
+        let set_headers = |headers: &[(u32, u8)]| {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(KV).unwrap();
+            wtx.open_table(BRC20_EVENTS).unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_EVENTS).unwrap();
+            let mut table = wtx.open_table(HEIGHT_TO_BLOCK_HEADER).unwrap();
+            for (height, byte) in headers {
+                table.insert(height, &[*byte; 80]).unwrap();
+            }
+            drop(table);
+            wtx.commit().unwrap();
+        };
+        set_headers(&[(0, 0), (1, 1), (2, 2), (3, 3)]);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = RpcServer::new(Arc::clone(&db), Chain::Mainnet);
+        tokio::spawn(async move { server.serve(listener).await });
+
+        let mut events = reqwest::get(format!("{}?from_height=0", url))
+            .await
+            .unwrap();
+        let mut buffer = String::new();
+        let mut ids = vec![];
+        for _ in 0..4 {
+            let (_, id, _) = next_event(&mut events, &mut buffer).await;
+            ids.push(id.unwrap());
+        }
+        drop(events);
+
+        // blocks 1 to 3 are replaced while the subscriber is away, the server
+        // only learns that block 3 is gone
+        set_headers(&[(1, 9), (2, 8), (3, 7)]);
+        let (height, hash) = ids[3].clone();
+        let mut reconnected = reqwest::Client::new()
+            .get(&url)
+            .header("Last-Event-ID", format!("{}:{}", height, hash))
+            .send()
+            .await
+            .unwrap();
+        let mut buffer = String::new();
+        let (event, id, reorg) = next_event(&mut reconnected, &mut buffer).await;
+        assert_eq!((event.as_str(), id), ("reorg", None));
+        assert!(reorg["from_height"].as_u64().unwrap() <= 1);
+        assert_eq!(reorg["to_height"], 3);
+
+        let from_height = reorg["from_height"].as_u64().unwrap() as u32;
+        for height in from_height..4 {
+            let (event, id, _) = next_event(&mut reconnected, &mut buffer).await;
+            let (id_height, id_hash) = id.unwrap();
+            assert_eq!((event.as_str(), id_height), ("block", height));
+            assert_eq!(id_hash == ids[height as usize].1, height == 0);
+        }
+    }
+}
@@ -0,0 +1,780 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use bitcoin::BlockHash;
+use hmac::Hmac;
+use hmac::Mac;
+use l2o_macros::define_table;
+use l2o_ord::operation::OperationType;
+use l2o_ord::tick::Tick;
+use l2o_ord_store::activity::receipt_tick;
+use l2o_ord_store::block_event::BlockEvents;
+use l2o_ord_store::event::Receipt;
+use l2o_ord_store::reorg::MAX_ROLLBACK_DEPTH;
+use l2o_ord_store::rtx::Rtx;
+use redb::Database;
+use redb::ReadableTable;
+use redb::TableDefinition;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+
+// how often the outbox is filled from the index and delivered
+const WEBHOOKS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const WEBHOOKS_TIMEOUT: Duration = Duration::from_secs(10);
+// a failed delivery is retried after WEBHOOKS_BACKOFF, which doubles with
+// every attempt up to WEBHOOKS_MAX_BACKOFF
+const WEBHOOKS_BACKOFF: Duration = Duration::from_secs(1);
+const WEBHOOKS_MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+// blocks queued in one write of the outbox
+const WEBHOOKS_BATCH: u32 = 100;
+
+// hex HMAC-SHA256 of the body with the secret of the webhook, as sha256=...
+pub const SIGNATURE_HEADER: &str = "x-l2o-signature";
+// the same for every attempt of a delivery
+pub const DELIVERY_HEADER: &str = "x-l2o-delivery";
+
+// deliveries in the order they were queued, until their webhook takes them
+define_table! { WEBHOOK_OUTBOX, u64, &[u8] }
+// the blocks the outbox was filled from, kept to revert them on reorgs
+define_table! { WEBHOOK_BLOCKS, u32, &[u8] }
+
+// A webhook of the --webhooks file. Empty ops and ticks match any receipt.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Webhook {
+    pub url: String,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub ops: Vec<OperationType>,
+    #[serde(default)]
+    pub ticks: Vec<Tick>,
+    pub l2id: Option<u64>,
+}
+
+impl Webhook {
+    pub fn matches(&self, receipt: &Receipt, l2id: Option<u64>) -> bool {
+        if !self.ops.is_empty() && !self.ops.contains(&receipt.op) {
+            return false;
+        }
+        if self.l2id.is_some() && self.l2id != l2id {
+            return false;
+        }
+        self.ticks.is_empty()
+            || receipt_tick(receipt).is_some_and(|receipt_tick| {
+                self.ticks
+                    .iter()
+                    .any(|tick| tick.to_lowercase() == receipt_tick.to_lowercase())
+            })
+    }
+
+    fn signature(&self, body: &[u8]) -> anyhow::Result<Option<String>> {
+        let Some(ref secret) = self.secret else {
+            return Ok(None);
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+        mac.update(body);
+        Ok(Some(format!(
+            "sha256={}",
+            hex::encode(mac.finalize().into_bytes())
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, strum::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum WebhookEventKind {
+    Block,
+    // a block delivered before was rolled back
+    Revert,
+}
+
+// the body POSTed to a webhook, the receipts of the block it matches
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WebhookEvent {
+    #[serde(rename = "type")]
+    pub kind: WebhookEventKind,
+    #[serde(flatten)]
+    pub block: BlockEvents,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct OutboxEntry {
+    // the index of the webhook in the --webhooks file, its url tells whether
+    // the file still has it there
+    pub webhook: usize,
+    pub url: String,
+    pub delivery: String,
+    pub body: String,
+    pub attempts: u32,
+    // in unix milliseconds
+    pub next_attempt: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+struct QueuedBlock {
+    hash: BlockHash,
+    // the webhook, its url and the body of every block event queued
+    deliveries: Vec<(usize, String, String)>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn backoff(attempts: u32) -> Duration {
+    WEBHOOKS_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+        .min(WEBHOOKS_MAX_BACKOFF)
+}
+
+// POSTs the receipts of indexed blocks to the webhooks they match, at least
+// once. The outbox is its own database, so that the savepoints the index is
+// rolled back to on reorgs do not roll back deliveries.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    db: Arc<Database>,
+    outbox: Arc<Database>,
+    webhooks: Arc<Vec<Webhook>>,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(
+        db: Arc<Database>,
+        outbox: Arc<Database>,
+        webhooks: Vec<Webhook>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            db,
+            outbox,
+            webhooks: Arc::new(webhooks),
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOKS_TIMEOUT)
+                .build()?,
+        })
+    }
+
+    pub async fn run(self) {
+        loop {
+            if let Err(err) = self.dispatch().await {
+                tracing::error!("webhooks: {:?}", err);
+            }
+            tokio::time::sleep(WEBHOOKS_POLL_INTERVAL).await;
+        }
+    }
+
+    // queues the blocks indexed since the last call, then delivers what is due
+    pub async fn dispatch(&self) -> anyhow::Result<()> {
+        while self.enqueue()? == WEBHOOKS_BATCH {}
+        self.deliver().await
+    }
+
+    // queues the events of the next blocks, after reverting those queued
+    // before that were rolled back
+    fn enqueue(&self) -> anyhow::Result<u32> {
+        let rxn = self.db.begin_read()?;
+        let wtx = self.outbox.begin_write()?;
+        let mut queued = 0;
+        {
+            let mut outbox = wtx.open_table(WEBHOOK_OUTBOX)?;
+            let mut blocks = wtx.open_table(WEBHOOK_BLOCKS)?;
+            let mut next_key = outbox
+                .last()?
+                .map(|(key, _)| key.value() + 1)
+                .unwrap_or_default();
+            let mut push = |outbox: &mut redb::Table<u64, &[u8]>, entry: OutboxEntry| {
+                outbox.insert(next_key, serde_json::to_vec(&entry)?.as_slice())?;
+                next_key += 1;
+                Ok::<_, anyhow::Error>(())
+            };
+
+            let mut queued_blocks = vec![];
+            for block in blocks.range::<u32>(..)? {
+                let (height, block) = block?;
+                queued_blocks.push((
+                    height.value(),
+                    serde_json::from_slice::<QueuedBlock>(block.value())?,
+                ));
+            }
+            let mut rolled_back = None;
+            for (height, block) in &queued_blocks {
+                if rxn.block_hash(Some(*height))? != Some(block.hash) {
+                    rolled_back = Some(*height);
+                    break;
+                }
+            }
+            if let Some(from_height) = rolled_back {
+                for (height, block) in queued_blocks.iter().rev() {
+                    if *height < from_height {
+                        break;
+                    }
+                    for (webhook, url, body) in &block.deliveries {
+                        let mut event = serde_json::from_str::<WebhookEvent>(body)?;
+                        event.kind = WebhookEventKind::Revert;
+                        push(
+                            &mut outbox,
+                            OutboxEntry {
+                                webhook: *webhook,
+                                url: url.clone(),
+                                delivery: format!("{}:{}:{}", event.kind, height, block.hash),
+                                body: serde_json::to_string(&event)?,
+                                attempts: 0,
+                                next_attempt: 0,
+                            },
+                        )?;
+                    }
+                    blocks.remove(height)?;
+                }
+            }
+
+            let mut next_height = match (rolled_back, queued_blocks.last()) {
+                (Some(height), _) => height,
+                (None, Some((height, _))) => height + 1,
+                // queueing starts after the tip, which is kept to find reorgs of it
+                (None, None) => match rxn.latest_block()? {
+                    Some((height, hash)) => {
+                        blocks.insert(
+                            height.n(),
+                            serde_json::to_vec(&QueuedBlock {
+                                hash,
+                                deliveries: vec![],
+                            })?
+                            .as_slice(),
+                        )?;
+                        height.n() + 1
+                    }
+                    None => 0,
+                },
+            };
+            let count = rxn.block_count()?;
+            while next_height < count && queued < WEBHOOKS_BATCH {
+                let Some(events) = rxn.block_events(next_height)? else {
+                    break;
+                };
+                let mut deliveries = vec![];
+                for (index, webhook) in self.webhooks.iter().enumerate() {
+                    let block = events
+                        .clone()
+                        .retain_receipts(|receipt, l2id| webhook.matches(receipt, l2id));
+                    if block.is_empty() {
+                        continue;
+                    }
+                    let event = WebhookEvent {
+                        kind: WebhookEventKind::Block,
+                        block,
+                    };
+                    let body = serde_json::to_string(&event)?;
+                    push(
+                        &mut outbox,
+                        OutboxEntry {
+                            webhook: index,
+                            url: webhook.url.clone(),
+                            delivery: format!(
+                                "{}:{}:{}",
+                                event.kind, event.block.height, event.block.hash
+                            ),
+                            body: body.clone(),
+                            attempts: 0,
+                            next_attempt: 0,
+                        },
+                    )?;
+                    deliveries.push((index, webhook.url.clone(), body));
+                }
+                blocks.insert(
+                    next_height,
+                    serde_json::to_vec(&QueuedBlock {
+                        hash: events.hash,
+                        deliveries,
+                    })?
+                    .as_slice(),
+                )?;
+                if let Some(oldest) = next_height.checked_sub(MAX_ROLLBACK_DEPTH + 1) {
+                    blocks.remove(oldest)?;
+                }
+                queued += 1;
+                next_height += 1;
+            }
+        }
+        wtx.commit()?;
+        Ok(queued)
+    }
+
+    // Delivers the entries of every webhook in order while they are due and
+    // delivered. A webhook takes its deliveries in order, the next waits for
+    // the failing one.
+    async fn deliver(&self) -> anyhow::Result<()> {
+        let mut queues = BTreeMap::<usize, Vec<u64>>::new();
+        {
+            let rxn = self.outbox.begin_read()?;
+            for entry in rxn.open_table(WEBHOOK_OUTBOX)?.range::<u64>(..)? {
+                let (key, entry) = entry?;
+                let entry = serde_json::from_slice::<OutboxEntry>(entry.value())?;
+                queues.entry(entry.webhook).or_default().push(key.value());
+            }
+        }
+
+        for keys in queues.into_values() {
+            for key in keys {
+                if !self.deliver_entry(key).await? {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // returns whether the entry was delivered
+    async fn deliver_entry(&self, key: u64) -> anyhow::Result<bool> {
+        let mut entry = {
+            let rxn = self.outbox.begin_read()?;
+            let outbox = rxn.open_table(WEBHOOK_OUTBOX)?;
+            let Some(entry) = outbox.get(key)? else {
+                return Ok(true);
+            };
+            serde_json::from_slice::<OutboxEntry>(entry.value())?
+        };
+        let now = now();
+        if entry.next_attempt > now {
+            return Ok(false);
+        }
+
+        let delivered = match self
+            .webhooks
+            .get(entry.webhook)
+            .filter(|webhook| webhook.url == entry.url)
+        {
+            Some(webhook) => self.post(webhook, &entry).await,
+            None => {
+                tracing::warn!("webhook {} is no longer configured", entry.url);
+                true
+            }
+        };
+        let wtx = self.outbox.begin_write()?;
+        {
+            let mut outbox = wtx.open_table(WEBHOOK_OUTBOX)?;
+            if delivered {
+                outbox.remove(key)?;
+            } else {
+                entry.attempts += 1;
+                entry.next_attempt = now + backoff(entry.attempts).as_millis() as u64;
+                outbox.insert(key, serde_json::to_vec(&entry)?.as_slice())?;
+            }
+        }
+        wtx.commit()?;
+        Ok(delivered)
+    }
+
+    async fn post(&self, webhook: &Webhook, entry: &OutboxEntry) -> bool {
+        let mut request = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(DELIVERY_HEADER, &entry.delivery);
+        match webhook.signature(entry.body.as_bytes()) {
+            Ok(Some(signature)) => request = request.header(SIGNATURE_HEADER, signature),
+            Ok(None) => {}
+            Err(err) => {
+                tracing::error!("webhook {}: {:?}", webhook.url, err);
+                return false;
+            }
+        }
+        match request.body(entry.body.clone()).send().await {
+            Ok(response) if response.status().is_success() => true,
+            Ok(response) => {
+                tracing::warn!(
+                    "webhook {} delivery {} attempt {}: {}",
+                    webhook.url,
+                    entry.delivery,
+                    entry.attempts + 1,
+                    response.status()
+                );
+                false
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "webhook {} delivery {} attempt {}: {}",
+                    webhook.url,
+                    entry.delivery,
+                    entry.attempts + 1,
+                    err
+                );
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Mutex;
+
+    use bitcoin::Address;
+    use bitcoin::Txid;
+    use http_body_util::BodyExt;
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper::Response;
+    use hyper::StatusCode;
+    use hyper_util::rt::TokioIo;
+    use l2o_ord::inscription::inscription_id::InscriptionId;
+    use l2o_ord::sat_point::SatPoint;
+    use l2o_ord::script_key::ScriptKey;
+    use l2o_ord_store::block_event::BlockEventsLog;
+    use l2o_ord_store::block_event::L2OABlockEvent;
+    use l2o_ord_store::event::Event;
+    use l2o_ord_store::event::MintEvent;
+    use l2o_ord_store::table::insert_block_events_log;
+    use l2o_ord_store::table::save_transaction_receipts;
+    use l2o_ord_store::table::BRC20_EVENTS;
+    use l2o_ord_store::table::HEIGHT_TO_BLOCK_EVENTS;
+    use l2o_ord_store::table::HEIGHT_TO_BLOCK_HEADER;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    // the path, headers and body of every request, answered with the status
+    // of failures before 200
+    type Requests = Arc<Mutex<Vec<(String, hyper::HeaderMap, Bytes)>>>;
+
+    async fn spawn_stand_in(failures: Arc<Mutex<Vec<StatusCode>>>) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let received = Arc::clone(&requests);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let received = Arc::clone(&received);
+                let failures = Arc::clone(&failures);
+                tokio::spawn(async move {
+                    let service = service_fn(|req: hyper::Request<hyper::body::Incoming>| {
+                        let received = Arc::clone(&received);
+                        let failures = Arc::clone(&failures);
+                        async move {
+                            let path = req.uri().path().to_string();
+                            let headers = req.headers().clone();
+                            let body = req.collect().await.unwrap().to_bytes();
+                            received.lock().unwrap().push((path, headers, body));
+                            let status = failures.lock().unwrap().pop().unwrap_or(StatusCode::OK);
+                            Response::builder()
+                                .status(status)
+                                .body(Full::new(Bytes::new()))
+                        }
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+        (url, requests)
+    }
+
+    fn receipt(inscription_id: InscriptionId, op: OperationType, result: Event) -> Receipt {
+        let owner = ScriptKey::from_address(
+            Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+                .unwrap()
+                .assume_checked(),
+        );
+        let satpoint = SatPoint::from_str(
+            "2222222222222222222222222222222222222222222222222222222222222222:1:5",
+        )
+        .unwrap();
+        Receipt {
+            inscription_id,
+            inscription_number: 1,
+            old_satpoint: satpoint,
+            new_satpoint: satpoint,
+            op,
+            from: owner.clone(),
+            to: owner,
+            result: Ok(result),
+        }
+    }
+
+    fn mint(tick: &str) -> Event {
+        Event::Mint(MintEvent {
+            tick: Tick::from_str(tick).unwrap(),
+            amount: 1_000,
+            msg: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dispatcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("index.redb")).unwrap());
+        let outbox = Arc::new(Database::create(dir.path().join("webhooks.redb")).unwrap());
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_HEADER)
+                .unwrap()
+                .insert(0, &[0u8; 80])
+                .unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_EVENTS).unwrap();
+            wtx.open_table(BRC20_EVENTS).unwrap();
+            wtx.commit().unwrap();
+        }
+        let failures = Arc::new(Mutex::new(vec![StatusCode::INTERNAL_SERVER_ERROR]));
+        let (url, requests) = spawn_stand_in(failures).await;
+        let dispatcher = WebhookDispatcher::new(
+            Arc::clone(&db),
+            outbox,
+            vec![
+                Webhook {
+                    url: format!("{}/mints", url),
+                    secret: Some("secret".to_string()),
+                    ops: vec![OperationType::BRC20Mint],
+                    ticks: vec![Tick::from_str("ORDI").unwrap()],
+                    l2id: None,
+                },
+                Webhook {
+                    url: format!("{}/l2o", url),
+                    secret: None,
+                    ops: vec![OperationType::L2OABlock],
+                    ticks: vec![],
+                    l2id: Some(7),
+                },
+            ],
+        )
+        .unwrap();
+
+        // starts at the next block
+        dispatcher.dispatch().await.unwrap();
+        assert!(requests.lock().unwrap().is_empty());
+
+        let txid = |c: &str| Txid::from_str(&c.repeat(64)).unwrap();
+        let id = |c: &str| InscriptionId {
+            txid: txid(c),
+            index: 0,
+        };
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_HEADER)
+                .unwrap()
+                .insert(1, &[1u8; 80])
+                .unwrap();
+            let mut events = wtx.open_table(BRC20_EVENTS).unwrap();
+            for (c, receipt) in [
+                (
+                    "a",
+                    receipt(id("a"), OperationType::BRC20Mint, mint("ordi")),
+                ),
+                (
+                    "b",
+                    receipt(id("b"), OperationType::BRC20Mint, mint("sats")),
+                ),
+                (
+                    "c",
+                    receipt(id("c"), OperationType::L2OABlock, Event::L2OABlock),
+                ),
+            ] {
+                save_transaction_receipts(&mut events, &txid(c), &[receipt]).unwrap();
+            }
+            insert_block_events_log(
+                &mut wtx.open_table(HEIGHT_TO_BLOCK_EVENTS).unwrap(),
+                1,
+                &BlockEventsLog {
+                    txids: vec![txid("a"), txid("b"), txid("c")],
+                    l2o_a_blocks: vec![L2OABlockEvent {
+                        txid: txid("c"),
+                        inscription_id: id("c"),
+                        l2id: 7,
+                        l2_block_number: 0,
+                        bitcoin_block_number: 1,
+                        accepted: true,
+                    }],
+                },
+            )
+            .unwrap();
+            drop(events);
+            wtx.commit().unwrap();
+        }
+
+        // the first delivery to /mints fails, /l2o takes its own
+        dispatcher.dispatch().await.unwrap();
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 2);
+            let (_, headers, l2o) = requests.iter().find(|(path, ..)| path == "/l2o").unwrap();
+            let l2o = serde_json::from_slice::<serde_json::Value>(l2o).unwrap();
+            assert!(headers.get(SIGNATURE_HEADER).is_none());
+            assert_eq!(l2o["type"], "block");
+            assert_eq!(l2o["l2o_a_blocks"][0]["accepted"], true);
+            assert_eq!(l2o["transactions"].as_array().unwrap().len(), 1);
+        }
+        dispatcher.dispatch().await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        tokio::time::sleep(WEBHOOKS_BACKOFF).await;
+        dispatcher.dispatch().await.unwrap();
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 3);
+            let (path, headers, mints) = &requests[2];
+            assert_eq!(path, "/mints");
+            assert_eq!(headers[DELIVERY_HEADER], requests[0].1[DELIVERY_HEADER]);
+            let signature = Webhook {
+                url: String::new(),
+                secret: Some("secret".to_string()),
+                ops: vec![],
+                ticks: vec![],
+                l2id: None,
+            }
+            .signature(mints)
+            .unwrap()
+            .unwrap();
+            assert_eq!(headers[SIGNATURE_HEADER], signature.as_str());
+            let mints = serde_json::from_slice::<serde_json::Value>(mints).unwrap();
+            assert_eq!(mints["type"], "block");
+            assert_eq!(mints["height"], 1);
+            let transactions = mints["transactions"].as_array().unwrap();
+            assert_eq!(transactions.len(), 1);
+            assert_eq!(transactions[0]["txid"], txid("a").to_string());
+        }
+
+        // block 1 is rolled back, and replaced by one without receipts
+        {
+            let wtx = db.begin_write().unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_HEADER)
+                .unwrap()
+                .insert(1, &[9u8; 80])
+                .unwrap();
+            wtx.open_table(HEIGHT_TO_BLOCK_EVENTS)
+                .unwrap()
+                .remove(1)
+                .unwrap();
+            wtx.commit().unwrap();
+        }
+        dispatcher.dispatch().await.unwrap();
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 5);
+            let hash = serde_json::from_slice::<serde_json::Value>(&requests[2].2).unwrap()["hash"]
+                .clone();
+            for (_, _, revert) in &requests[3..] {
+                let revert = serde_json::from_slice::<serde_json::Value>(revert).unwrap();
+                assert_eq!(revert["type"], "revert");
+                assert_eq!(revert["height"], 1);
+                assert_eq!(revert["hash"], hash);
+            }
+        }
+        dispatcher.dispatch().await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 5);
+        assert!(dispatcher
+            .outbox
+            .begin_read()
+            .unwrap()
+            .open_table(WEBHOOK_OUTBOX)
+            .unwrap()
+            .is_empty()
+            .unwrap());
+    }
+
+    // the header and one receipt per transaction of the block at height
+    fn index_block(db: &Database, height: u32, receipts: Vec<(Txid, Receipt)>) {
+        let wtx = db.begin_write().unwrap();
+        wtx.open_table(HEIGHT_TO_BLOCK_HEADER)
+            .unwrap()
+            .insert(height, &[height as u8; 80])
+            .unwrap();
+        {
+            let mut events = wtx.open_table(BRC20_EVENTS).unwrap();
+            for (txid, receipt) in &receipts {
+                save_transaction_receipts(&mut events, txid, std::slice::from_ref(receipt))
+                    .unwrap();
+            }
+        }
+        insert_block_events_log(
+            &mut wtx.open_table(HEIGHT_TO_BLOCK_EVENTS).unwrap(),
+            height,
+            &BlockEventsLog {
+                txids: receipts.iter().map(|(txid, _)| *txid).collect(),
+                l2o_a_blocks: vec![],
+            },
+        )
+        .unwrap();
+        wtx.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dispatcher_queues() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("index.redb")).unwrap());
+        let outbox = Arc::new(Database::create(dir.path().join("webhooks.redb")).unwrap());
+        index_block(&db, 0, vec![]);
+        let (url, requests) = spawn_stand_in(Arc::default()).await;
+        // two webhooks of one url, told apart by their secrets
+        let webhook = |tick: &str| Webhook {
+            url: url.clone(),
+            secret: Some(tick.to_string()),
+            ops: vec![],
+            ticks: vec![Tick::from_str(tick).unwrap()],
+            l2id: None,
+        };
+        let dispatcher = WebhookDispatcher::new(
+            Arc::clone(&db),
+            outbox,
+            vec![webhook("ordi"), webhook("sats")],
+        )
+        .unwrap();
+        dispatcher.dispatch().await.unwrap();
+
+        let txid = |c: &str| Txid::from_str(&c.repeat(64)).unwrap();
+        let mint_receipt = |c: &str, tick: &str| {
+            let id = InscriptionId {
+                txid: txid(c),
+                index: 0,
+            };
+            (txid(c), receipt(id, OperationType::BRC20Mint, mint(tick)))
+        };
+        index_block(
+            &db,
+            1,
+            vec![mint_receipt("a", "ordi"), mint_receipt("b", "sats")],
+        );
+        index_block(
+            &db,
+            2,
+            vec![mint_receipt("c", "ordi"), mint_receipt("d", "sats")],
+        );
+
+        // every webhook takes both blocks in one poll, in order
+        dispatcher.dispatch().await.unwrap();
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        for (tick, txids) in [
+            ("ordi", [txid("a"), txid("c")]),
+            ("sats", [txid("b"), txid("d")]),
+        ] {
+            let signed = requests
+                .iter()
+                .filter(|(_, headers, body)| {
+                    headers[SIGNATURE_HEADER]
+                        == webhook(tick).signature(body).unwrap().unwrap().as_str()
+                })
+                .map(|(_, _, body)| {
+                    let body = serde_json::from_slice::<serde_json::Value>(body).unwrap();
+                    body["transactions"][0]["txid"].clone()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                signed,
+                txids
+                    .iter()
+                    .map(|txid| serde_json::json!(txid.to_string()))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -1,10 +1,15 @@
+use ark_bn254::Fr;
 use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::fields::bn254::hash::bn254_to_hash256;
+use l2o_crypto::fields::bn254::hash::bytes_to_bn254;
 use l2o_crypto::fields::goldilocks::hash::hash256_to_goldilocks_hash;
 use l2o_crypto::fields::goldilocks::hash::hash256_to_goldilocks_u32;
 use l2o_crypto::hash::hash_functions::blake3;
 use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
 use l2o_crypto::hash::hash_functions::keccak256;
 use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
 use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
 use l2o_crypto::hash::hash_functions::sha256;
 use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
@@ -18,6 +23,7 @@ use plonky2::plonk::config::Hasher;
 use crate::operation::brc21::l2deposit::L2Deposit;
 use crate::operation::brc21::L2WithdrawV1;
 use crate::operation::l2o_a::L2OABlockV1;
+use crate::operation::l2o_a::L2OAHashFunction;
 
 pub trait L2OBlockHasher {
     fn get_l2_block_hash(block: &L2OABlockV1) -> Hash256;
@@ -84,6 +90,37 @@ impl L2OBlockHasher for PoseidonHasher {
     }
 }
 
+impl L2OBlockHasher for PoseidonBN254Hasher {
+    fn get_l2_block_hash(block: &L2OABlockV1) -> Hash256 {
+        let payload = get_block_payload_bn254(block);
+        bn254_to_hash256(&poseidon_bn254::hash_no_pad(&payload))
+    }
+}
+
+// Hashes are split into two elements like the Groth16 public inputs, see
+// `From<Hash256> for [Fr; 2]`.
+pub fn get_block_payload_bn254(block: &L2OABlockV1) -> Vec<Fr> {
+    let mut payload: Vec<Fr> = Vec::new();
+    payload.push(Fr::from(block.l2id));
+    payload.push(Fr::from(block.l2_block_number));
+    payload.push(Fr::from(block.bitcoin_block_number));
+
+    for hash in [
+        block.bitcoin_block_hash,
+        Hash256(block.public_key.0),
+        block.start_state_root,
+        block.end_state_root,
+        block.deposit_state_root,
+        block.start_withdrawal_state_root,
+        block.end_withdrawal_state_root,
+        block.superchain_root,
+    ] {
+        payload.extend_from_slice(&<[Fr; 2]>::from(hash));
+    }
+
+    payload
+}
+
 pub fn get_block_payload_goldilocks_hash_u32_mode(block: &L2OABlockV1) -> Vec<GoldilocksField> {
     let mut payload_bytes: Vec<GoldilocksField> = Vec::new();
     payload_bytes.push(GoldilocksField::from_canonical_u64(block.l2id));
@@ -157,8 +194,73 @@ impl L2ODepositHasher for Sha256Hasher {
 }
 
 impl L2ODepositHasher for PoseidonHasher {
-    fn get_l2_deposit_hash(_l2deposit: &L2Deposit) -> Hash256 {
-        todo!()
+    fn get_l2_deposit_hash(l2deposit: &L2Deposit) -> Hash256 {
+        let payload = get_l2_deposit_payload_goldilocks_u32_mode(l2deposit);
+        PoseidonHash::hash_no_pad(&payload).to_hash_256()
+    }
+}
+
+impl L2ODepositHasher for PoseidonBN254Hasher {
+    fn get_l2_deposit_hash(l2deposit: &L2Deposit) -> Hash256 {
+        let payload = get_l2_deposit_payload_bn254(l2deposit);
+        bn254_to_hash256(&poseidon_bn254::hash_no_pad(&payload))
+    }
+}
+
+pub fn get_l2_deposit_payload_bn254(l2deposit: &L2Deposit) -> Vec<Fr> {
+    let mut payload: Vec<Fr> = Vec::new();
+    payload.push(Fr::from(l2deposit.l2id));
+    payload.extend_from_slice(&bytes_to_bn254(l2deposit.tick.as_bytes()));
+    payload.extend_from_slice(&bytes_to_bn254(l2deposit.to.as_bytes()));
+    payload.extend_from_slice(&bytes_to_bn254(l2deposit.amount.as_bytes()));
+
+    payload
+}
+
+// Strings are length prefixed and packed into u32 limbs (little endian, zero
+// padded), so that circuits can range check every element like
+// hash256_to_goldilocks_u32.
+pub fn bytes_to_goldilocks_u32(bytes: &[u8]) -> Vec<GoldilocksField> {
+    let mut result = Vec::with_capacity(1 + bytes.len().div_ceil(4));
+    result.push(GoldilocksField::from_canonical_u32(bytes.len() as u32));
+    for chunk in bytes.chunks(4) {
+        let mut limb = [0u8; 4];
+        limb[..chunk.len()].copy_from_slice(chunk);
+        result.push(GoldilocksField::from_canonical_u32(u32::from_le_bytes(
+            limb,
+        )));
+    }
+    result
+}
+
+pub fn get_l2_deposit_payload_goldilocks_u32_mode(l2deposit: &L2Deposit) -> Vec<GoldilocksField> {
+    let mut payload: Vec<GoldilocksField> = Vec::new();
+    payload.push(GoldilocksField::from_noncanonical_u64(l2deposit.l2id));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2deposit.tick.as_bytes()));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2deposit.to.as_bytes()));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2deposit.amount.as_bytes()));
+
+    payload
+}
+
+pub fn get_l2_block_hash_with(hash_function: L2OAHashFunction, block: &L2OABlockV1) -> Hash256 {
+    match hash_function {
+        L2OAHashFunction::Sha256 => Sha256Hasher::get_l2_block_hash(block),
+        L2OAHashFunction::BLAKE3 => Blake3Hasher::get_l2_block_hash(block),
+        L2OAHashFunction::Keccak256 => Keccak256Hasher::get_l2_block_hash(block),
+        L2OAHashFunction::PoseidonGoldilocks => PoseidonHasher::get_l2_block_hash(block),
+        L2OAHashFunction::PoseidonBN254 => PoseidonBN254Hasher::get_l2_block_hash(block),
+    }
+}
+
+// deposits are committed to in the hash function the target L2 declared
+pub fn get_l2_deposit_hash_with(hash_function: L2OAHashFunction, l2deposit: &L2Deposit) -> Hash256 {
+    match hash_function {
+        L2OAHashFunction::Sha256 => Sha256Hasher::get_l2_deposit_hash(l2deposit),
+        L2OAHashFunction::BLAKE3 => Blake3Hasher::get_l2_deposit_hash(l2deposit),
+        L2OAHashFunction::Keccak256 => Keccak256Hasher::get_l2_deposit_hash(l2deposit),
+        L2OAHashFunction::PoseidonGoldilocks => PoseidonHasher::get_l2_deposit_hash(l2deposit),
+        L2OAHashFunction::PoseidonBN254 => PoseidonBN254Hasher::get_l2_deposit_hash(l2deposit),
     }
 }
 
@@ -199,8 +301,51 @@ impl L2OWithdrawHasher for Sha256Hasher {
 }
 
 impl L2OWithdrawHasher for PoseidonHasher {
-    fn get_l2_withdraw_hash(_l2withdraw: &L2WithdrawV1) -> Hash256 {
-        todo!()
+    fn get_l2_withdraw_hash(l2withdraw: &L2WithdrawV1) -> Hash256 {
+        let payload = get_l2_withdraw_payload_goldilocks_u32_mode(l2withdraw);
+        PoseidonHash::hash_no_pad(&payload).to_hash_256()
+    }
+}
+
+impl L2OWithdrawHasher for PoseidonBN254Hasher {
+    fn get_l2_withdraw_hash(l2withdraw: &L2WithdrawV1) -> Hash256 {
+        let payload = get_l2_withdraw_payload_bn254(l2withdraw);
+        bn254_to_hash256(&poseidon_bn254::hash_no_pad(&payload))
+    }
+}
+
+pub fn get_l2_withdraw_payload_bn254(l2withdraw: &L2WithdrawV1) -> Vec<Fr> {
+    let mut payload: Vec<Fr> = Vec::new();
+    payload.push(Fr::from(l2withdraw.l2id));
+    payload.extend_from_slice(&bytes_to_bn254(l2withdraw.tick.as_bytes()));
+    payload.extend_from_slice(&bytes_to_bn254(l2withdraw.to.as_bytes()));
+    payload.extend_from_slice(&bytes_to_bn254(l2withdraw.amount.as_bytes()));
+
+    payload
+}
+
+pub fn get_l2_withdraw_payload_goldilocks_u32_mode(
+    l2withdraw: &L2WithdrawV1,
+) -> Vec<GoldilocksField> {
+    let mut payload: Vec<GoldilocksField> = Vec::new();
+    payload.push(GoldilocksField::from_noncanonical_u64(l2withdraw.l2id));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2withdraw.tick.as_bytes()));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2withdraw.to.as_bytes()));
+    payload.extend_from_slice(&bytes_to_goldilocks_u32(l2withdraw.amount.as_bytes()));
+
+    payload
+}
+
+pub fn get_l2_withdraw_hash_with(
+    hash_function: L2OAHashFunction,
+    l2withdraw: &L2WithdrawV1,
+) -> Hash256 {
+    match hash_function {
+        L2OAHashFunction::Sha256 => Sha256Hasher::get_l2_withdraw_hash(l2withdraw),
+        L2OAHashFunction::BLAKE3 => Blake3Hasher::get_l2_withdraw_hash(l2withdraw),
+        L2OAHashFunction::Keccak256 => Keccak256Hasher::get_l2_withdraw_hash(l2withdraw),
+        L2OAHashFunction::PoseidonGoldilocks => PoseidonHasher::get_l2_withdraw_hash(l2withdraw),
+        L2OAHashFunction::PoseidonBN254 => PoseidonBN254Hasher::get_l2_withdraw_hash(l2withdraw),
     }
 }
 
@@ -214,3 +359,38 @@ pub fn get_l2_withdraw_payload_bytes(l2withdraw: &L2WithdrawV1) -> Vec<u8> {
 
     payload_bytes
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goldilocks_u32_packing() {
+        let packed = bytes_to_goldilocks_u32(b"ordi!");
+        assert_eq!(
+            packed,
+            vec![
+                GoldilocksField::from_canonical_u32(5),
+                GoldilocksField::from_canonical_u32(u32::from_le_bytes(*b"ordi")),
+                GoldilocksField::from_canonical_u32(b'!' as u32),
+            ]
+        );
+
+        // the length prefixes keep adjacent strings from running into each other
+        let a = L2Deposit {
+            l2id: 1,
+            tick: "ordi".to_string(),
+            to: "ab".to_string(),
+            amount: "c".to_string(),
+        };
+        let b = L2Deposit {
+            to: "a".to_string(),
+            amount: "bc".to_string(),
+            ..a.clone()
+        };
+        assert_ne!(
+            get_l2_deposit_payload_goldilocks_u32_mode(&a),
+            get_l2_deposit_payload_goldilocks_u32_mode(&b)
+        );
+    }
+}
@@ -1,8 +1,11 @@
-use kvq::traits::KVQSerializable;
+use kvq::codec::KVQ_COMPACT_TAG_L2_DEPOSIT;
+use l2o_derive::KVQCompactCodec;
+use l2o_derive::KVQSerializable;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, KVQCompactCodec, KVQSerializable)]
+#[kvq(value, codec = compact, tag = KVQ_COMPACT_TAG_L2_DEPOSIT, json_fallback)]
 pub struct L2Deposit {
     #[serde(rename = "l2id")]
     pub l2id: u64,
@@ -23,13 +26,3 @@ impl L2Deposit {
         }
     }
 }
-
-impl KVQSerializable for L2Deposit {
-    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        Ok(serde_json::to_vec(&self)?)
-    }
-
-    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(serde_json::from_slice(bytes)?)
-    }
-}
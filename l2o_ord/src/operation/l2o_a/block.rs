@@ -1,3 +1,10 @@
+use ark_bn254::Fr;
+use kvq::codec::from_compact_or_json_bytes;
+use kvq::codec::to_compact_bytes;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
+use kvq::codec::KVQ_COMPACT_TAG_L2OA_BLOCK;
 use kvq::traits::KVQSerializable;
 use l2o_common::common::data::hash::Hash256;
 use l2o_common::common::data::signature::L2OCompactPublicKey;
@@ -5,6 +12,8 @@ use l2o_common::common::data::signature::L2OSignature512;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::operation::l2o_a::L2OAPublicInput;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(bound = "Proof: Serialize, for<'de2> Proof: Deserialize<'de2>")]
 pub struct Block<Proof>
@@ -36,15 +45,94 @@ where
     pub signature: L2OSignature512,
 }
 
-impl<V: Serialize + Clone + PartialEq> KVQSerializable for Block<V>
+impl<Proof> Block<Proof>
+where
+    Proof: Serialize,
+    for<'de2> Proof: Deserialize<'de2>,
+{
+    // Assembles the public inputs a block proof is expected to carry for the
+    // layout declared at deploy time.
+    pub fn get_public_inputs(&self, layout: &[L2OAPublicInput], block_hash: Hash256) -> Vec<Fr> {
+        let mut result = Vec::with_capacity(layout.iter().map(|p| p.field_count()).sum());
+        for public_input in layout.iter() {
+            let hash = match public_input {
+                L2OAPublicInput::L2Id => {
+                    result.push(Fr::from(self.l2id));
+                    continue;
+                }
+                L2OAPublicInput::L2BlockNumber => {
+                    result.push(Fr::from(self.l2_block_number));
+                    continue;
+                }
+                L2OAPublicInput::BitcoinBlockNumber => {
+                    result.push(Fr::from(self.bitcoin_block_number));
+                    continue;
+                }
+                L2OAPublicInput::BlockHash => block_hash,
+                L2OAPublicInput::BitcoinBlockHash => self.bitcoin_block_hash,
+                L2OAPublicInput::PublicKey => Hash256(self.public_key.0),
+                L2OAPublicInput::StartStateRoot => self.start_state_root,
+                L2OAPublicInput::EndStateRoot => self.end_state_root,
+                L2OAPublicInput::DepositStateRoot => self.deposit_state_root,
+                L2OAPublicInput::StartWithdrawalStateRoot => self.start_withdrawal_state_root,
+                L2OAPublicInput::EndWithdrawalStateRoot => self.end_withdrawal_state_root,
+                L2OAPublicInput::SuperchainRoot => self.superchain_root,
+            };
+            let fields: [Fr; 2] = hash.into();
+            result.extend_from_slice(&fields);
+        }
+        result
+    }
+}
+
+impl<Proof: Serialize + KVQCompactCodec> KVQCompactCodec for Block<Proof>
+where
+    for<'de2> Proof: Deserialize<'de2>,
+{
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write(&self.l2id)?;
+        writer.write(&self.l2_block_number)?;
+        writer.write(&self.bitcoin_block_number)?;
+        writer.write(&self.bitcoin_block_hash)?;
+        writer.write(&self.public_key)?;
+        writer.write(&self.start_state_root)?;
+        writer.write(&self.end_state_root)?;
+        writer.write(&self.deposit_state_root)?;
+        writer.write(&self.start_withdrawal_state_root)?;
+        writer.write(&self.end_withdrawal_state_root)?;
+        writer.write(&self.proof)?;
+        writer.write(&self.superchain_root)?;
+        writer.write(&self.signature)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(Block {
+            l2id: reader.read()?,
+            l2_block_number: reader.read()?,
+            bitcoin_block_number: reader.read()?,
+            bitcoin_block_hash: reader.read()?,
+            public_key: reader.read()?,
+            start_state_root: reader.read()?,
+            end_state_root: reader.read()?,
+            deposit_state_root: reader.read()?,
+            start_withdrawal_state_root: reader.read()?,
+            end_withdrawal_state_root: reader.read()?,
+            proof: reader.read()?,
+            superchain_root: reader.read()?,
+            signature: reader.read()?,
+        })
+    }
+}
+
+impl<V: Serialize + KVQCompactCodec + Clone + PartialEq> KVQSerializable for Block<V>
 where
     for<'de2> V: Deserialize<'de2>,
 {
     fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        Ok(serde_json::to_vec(self)?)
+        to_compact_bytes(KVQ_COMPACT_TAG_L2OA_BLOCK, self)
     }
 
     fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(serde_json::from_slice(bytes)?)
+        from_compact_or_json_bytes(KVQ_COMPACT_TAG_L2OA_BLOCK, bytes)
     }
 }
@@ -1,10 +1,21 @@
+use kvq::codec::from_compact_or_json_bytes;
+use kvq::codec::to_compact_bytes;
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
+use kvq::codec::KVQ_COMPACT_TAG_L2OA_DEPLOY;
 use kvq::traits::KVQSerializable;
 use l2o_common::common::data::hash::Hash256;
 use l2o_common::common::data::signature::L2OCompactPublicKey;
+use l2o_common::common::data::signature::L2OCompressedPublicKey;
+use l2o_crypto::standards::l2o_a::proof::L2OAVerifierData;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::hasher::get_l2_block_hash_with;
+use crate::operation::l2o_a::L2OABlockV1;
 use crate::operation::l2o_a::L2OAHashFunction;
+use crate::operation::l2o_a::L2OAPublicInput;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(bound = "V: Serialize, for<'de2> V: Deserialize<'de2>")]
@@ -20,19 +31,84 @@ where
 
     pub hash_function: L2OAHashFunction,
 
+    #[serde(default = "L2OAPublicInput::default_layout")]
+    pub public_inputs: Vec<L2OAPublicInput>,
+
+    // MuSig2 sequencer committee, public_key must be its aggregate key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequencer_keys: Vec<L2OCompressedPublicKey>,
+
     #[serde(flatten)]
     pub verifier_data: V,
 }
 
-impl<V: Serialize + Clone + PartialEq> KVQSerializable for Deploy<V>
+impl Deploy<L2OAVerifierData> {
+    // Checks the proof of a block of this deployment on its own, without the
+    // blocks before it. Only layouts with the block hash bind every field of
+    // the block to the proof.
+    pub fn verify_block_proof(&self, block: &L2OABlockV1) -> anyhow::Result<()> {
+        if block.l2id != self.l2id {
+            anyhow::bail!("block is for l2id {}, not {}", block.l2id, self.l2id);
+        }
+        if !self.public_inputs.contains(&L2OAPublicInput::BlockHash) {
+            anyhow::bail!(
+                "block proofs of l2id {} do not commit to the block hash",
+                self.l2id
+            );
+        }
+        let block_hash = get_l2_block_hash_with(self.hash_function, block);
+        self.verifier_data.verify_proof(
+            &block.proof,
+            &block.get_public_inputs(&self.public_inputs, block_hash),
+        )
+    }
+}
+
+impl<V: Serialize + KVQCompactCodec> KVQCompactCodec for Deploy<V>
+where
+    for<'de2> V: Deserialize<'de2>,
+{
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write(&self.l2id)?;
+        writer.write(&self.public_key)?;
+        writer.write(&self.start_state_root)?;
+        writer.write(&self.hash_function)?;
+        writer.write(&self.verifier_data)?;
+        writer.write(&self.public_inputs)?;
+        writer.write(&self.sequencer_keys)
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        Ok(Deploy {
+            l2id: reader.read()?,
+            public_key: reader.read()?,
+            start_state_root: reader.read()?,
+            hash_function: reader.read()?,
+            verifier_data: reader.read()?,
+            // written before the layout was declared
+            public_inputs: if reader.version() < 2 {
+                L2OAPublicInput::default_layout()
+            } else {
+                reader.read()?
+            },
+            sequencer_keys: if reader.version() < 3 {
+                Vec::new()
+            } else {
+                reader.read()?
+            },
+        })
+    }
+}
+
+impl<V: Serialize + KVQCompactCodec + Clone + PartialEq> KVQSerializable for Deploy<V>
 where
     for<'de2> V: Deserialize<'de2>,
 {
     fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        Ok(serde_json::to_vec(self)?)
+        to_compact_bytes(KVQ_COMPACT_TAG_L2OA_DEPLOY, self)
     }
 
     fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(serde_json::from_slice(bytes)?)
+        from_compact_or_json_bytes(KVQ_COMPACT_TAG_L2OA_DEPLOY, bytes)
     }
 }
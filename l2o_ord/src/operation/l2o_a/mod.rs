@@ -1,3 +1,6 @@
+use kvq::codec::KVQCompactCodec;
+use kvq::codec::KVQCompactReader;
+use kvq::codec::KVQCompactWriter;
 use l2o_crypto::standards::l2o_a::proof::L2OAProofData;
 use l2o_crypto::standards::l2o_a::proof::L2OAVerifierData;
 use serde::Deserialize;
@@ -10,6 +13,7 @@ use crate::operation::l2o_a::deploy::Deploy;
 
 pub mod block;
 pub mod deploy;
+pub mod reader;
 
 #[derive(EnumIs, EnumString, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash, Debug)]
 pub enum L2OAHashFunction {
@@ -17,6 +21,104 @@ pub enum L2OAHashFunction {
     BLAKE3,
     Keccak256,
     PoseidonGoldilocks,
+    PoseidonBN254,
+}
+
+impl KVQCompactCodec for L2OAHashFunction {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_u8(match self {
+            L2OAHashFunction::Sha256 => 0,
+            L2OAHashFunction::BLAKE3 => 1,
+            L2OAHashFunction::Keccak256 => 2,
+            L2OAHashFunction::PoseidonGoldilocks => 3,
+            L2OAHashFunction::PoseidonBN254 => 4,
+        });
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        match reader.read_u8()? {
+            0 => Ok(L2OAHashFunction::Sha256),
+            1 => Ok(L2OAHashFunction::BLAKE3),
+            2 => Ok(L2OAHashFunction::Keccak256),
+            3 => Ok(L2OAHashFunction::PoseidonGoldilocks),
+            4 => Ok(L2OAHashFunction::PoseidonBN254),
+            hash_function => anyhow::bail!("Unknown hash function {}", hash_function),
+        }
+    }
+}
+
+// Public inputs of a deployment's block circuit, in the order the circuit
+// exposes them. Hashes and keys are split into two field elements like
+// `From<Hash256> for [Fr; 2]`, numbers take one.
+#[derive(EnumIs, EnumString, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum L2OAPublicInput {
+    BlockHash,
+    L2Id,
+    L2BlockNumber,
+    BitcoinBlockNumber,
+    BitcoinBlockHash,
+    PublicKey,
+    StartStateRoot,
+    EndStateRoot,
+    DepositStateRoot,
+    StartWithdrawalStateRoot,
+    EndWithdrawalStateRoot,
+    SuperchainRoot,
+}
+
+impl L2OAPublicInput {
+    // deployments made before the layout was declared only expose the block hash
+    pub fn default_layout() -> Vec<Self> {
+        vec![L2OAPublicInput::BlockHash]
+    }
+
+    pub fn field_count(&self) -> usize {
+        match self {
+            L2OAPublicInput::L2Id
+            | L2OAPublicInput::L2BlockNumber
+            | L2OAPublicInput::BitcoinBlockNumber => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl KVQCompactCodec for L2OAPublicInput {
+    fn encode_compact(&self, writer: &mut KVQCompactWriter) -> anyhow::Result<()> {
+        writer.write_u8(match self {
+            L2OAPublicInput::BlockHash => 0,
+            L2OAPublicInput::L2Id => 1,
+            L2OAPublicInput::L2BlockNumber => 2,
+            L2OAPublicInput::BitcoinBlockNumber => 3,
+            L2OAPublicInput::BitcoinBlockHash => 4,
+            L2OAPublicInput::PublicKey => 5,
+            L2OAPublicInput::StartStateRoot => 6,
+            L2OAPublicInput::EndStateRoot => 7,
+            L2OAPublicInput::DepositStateRoot => 8,
+            L2OAPublicInput::StartWithdrawalStateRoot => 9,
+            L2OAPublicInput::EndWithdrawalStateRoot => 10,
+            L2OAPublicInput::SuperchainRoot => 11,
+        });
+        Ok(())
+    }
+
+    fn decode_compact(reader: &mut KVQCompactReader) -> anyhow::Result<Self> {
+        match reader.read_u8()? {
+            0 => Ok(L2OAPublicInput::BlockHash),
+            1 => Ok(L2OAPublicInput::L2Id),
+            2 => Ok(L2OAPublicInput::L2BlockNumber),
+            3 => Ok(L2OAPublicInput::BitcoinBlockNumber),
+            4 => Ok(L2OAPublicInput::BitcoinBlockHash),
+            5 => Ok(L2OAPublicInput::PublicKey),
+            6 => Ok(L2OAPublicInput::StartStateRoot),
+            7 => Ok(L2OAPublicInput::EndStateRoot),
+            8 => Ok(L2OAPublicInput::DepositStateRoot),
+            9 => Ok(L2OAPublicInput::StartWithdrawalStateRoot),
+            10 => Ok(L2OAPublicInput::EndWithdrawalStateRoot),
+            11 => Ok(L2OAPublicInput::SuperchainRoot),
+            public_input => anyhow::bail!("Unknown public input {}", public_input),
+        }
+    }
 }
 
 #[derive(EnumIs, EnumString, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash, Debug)]
@@ -45,6 +147,13 @@ pub enum RawL2OAOperation {
 
 #[cfg(test)]
 mod tests {
+    use ark_bn254::Fr;
+    use kvq::codec::KVQ_COMPACT_MAGIC;
+    use kvq::codec::KVQ_COMPACT_TAG_L2OA_DEPLOY;
+    use kvq::codec::KVQ_COMPACT_VERSION;
+    use kvq::traits::KVQSerializable;
+    use l2o_common::common::data::hash::Hash256;
+
     use super::*;
 
     #[test]
@@ -60,4 +169,118 @@ mod tests {
         let p = serde_json::from_str::<RawL2OAOperation>(block_json).unwrap();
         assert!(matches!(p, RawL2OAOperation::Block(_)));
     }
+
+    #[test]
+    fn test_public_input_layout() {
+        let deploy_json = include_str!("../../../../static/deploy.json");
+        let block_json = include_str!("../../../../static/block.json");
+        let RawL2OAOperation::Deploy(deploy) =
+            serde_json::from_str::<RawL2OAOperation>(deploy_json).unwrap()
+        else {
+            panic!("expected deploy");
+        };
+        let RawL2OAOperation::Block(block) =
+            serde_json::from_str::<RawL2OAOperation>(block_json).unwrap()
+        else {
+            panic!("expected block");
+        };
+        assert_eq!(deploy.public_inputs, L2OAPublicInput::default_layout());
+
+        let block_hash = Hash256([3u8; 32]);
+        let expected: [Fr; 2] = block_hash.into();
+        assert_eq!(
+            block.get_public_inputs(&deploy.public_inputs, block_hash),
+            expected.to_vec()
+        );
+
+        let layout = vec![
+            L2OAPublicInput::BlockHash,
+            L2OAPublicInput::L2BlockNumber,
+            L2OAPublicInput::DepositStateRoot,
+        ];
+        let inputs = block.get_public_inputs(&layout, block_hash);
+        assert_eq!(inputs.len(), 5);
+        assert_eq!(inputs[2], Fr::from(block.l2_block_number));
+        let deposit_root: [Fr; 2] = block.deposit_state_root.into();
+        assert_eq!(inputs[3..], deposit_root);
+    }
+
+    #[test]
+    fn test_compact_bytes() {
+        let deploy_json = include_str!("../../../../static/deploy.json");
+        let block_json = include_str!("../../../../static/block.json");
+        let RawL2OAOperation::Deploy(deploy) =
+            serde_json::from_str::<RawL2OAOperation>(deploy_json).unwrap()
+        else {
+            panic!("expected deploy");
+        };
+        let RawL2OAOperation::Block(block) =
+            serde_json::from_str::<RawL2OAOperation>(block_json).unwrap()
+        else {
+            panic!("expected block");
+        };
+
+        let deploy_bytes = deploy.to_bytes().unwrap();
+        assert!(deploy_bytes.len() < serde_json::to_vec(&deploy).unwrap().len());
+        assert_eq!(L2OADeployV1::from_bytes(&deploy_bytes).unwrap(), deploy);
+
+        let block_bytes = block.to_bytes().unwrap();
+        assert!(block_bytes.len() < serde_json::to_vec(&block).unwrap().len());
+        assert_eq!(L2OABlockV1::from_bytes(&block_bytes).unwrap(), block);
+
+        // entries written before the compact codec are still readable
+        let legacy_deploy = serde_json::to_vec(&deploy).unwrap();
+        assert_eq!(L2OADeployV1::from_bytes(&legacy_deploy).unwrap(), deploy);
+        let legacy_block = serde_json::to_vec(&block).unwrap();
+        assert_eq!(L2OABlockV1::from_bytes(&legacy_block).unwrap(), block);
+    }
+
+    #[test]
+    fn test_compact_deploy_versions() {
+        let deploy_json = include_str!("../../../../static/deploy.json");
+        let RawL2OAOperation::Deploy(deploy) =
+            serde_json::from_str::<RawL2OAOperation>(deploy_json).unwrap()
+        else {
+            panic!("expected deploy");
+        };
+
+        // version 1 deploys end after the verifier data
+        let mut writer = KVQCompactWriter::new();
+        writer.write_u8(KVQ_COMPACT_MAGIC);
+        writer.write_u8(1);
+        writer.write_u8(KVQ_COMPACT_TAG_L2OA_DEPLOY);
+        writer.write(&deploy.l2id).unwrap();
+        writer.write(&deploy.public_key).unwrap();
+        writer.write(&deploy.start_state_root).unwrap();
+        writer.write(&deploy.hash_function).unwrap();
+        writer.write(&deploy.verifier_data).unwrap();
+        let v1 = L2OADeployV1::from_bytes(&writer.into_bytes()).unwrap();
+        assert_eq!(v1.public_inputs, L2OAPublicInput::default_layout());
+        assert_eq!(v1.verifier_data, deploy.verifier_data);
+        assert!(v1.sequencer_keys.is_empty());
+
+        // version 2 deploys end after the public inputs
+        let mut writer = KVQCompactWriter::new();
+        writer.write_u8(KVQ_COMPACT_MAGIC);
+        writer.write_u8(2);
+        writer.write_u8(KVQ_COMPACT_TAG_L2OA_DEPLOY);
+        writer.write(&deploy.l2id).unwrap();
+        writer.write(&deploy.public_key).unwrap();
+        writer.write(&deploy.start_state_root).unwrap();
+        writer.write(&deploy.hash_function).unwrap();
+        writer.write(&deploy.verifier_data).unwrap();
+        writer.write(&deploy.public_inputs).unwrap();
+        let v2 = L2OADeployV1::from_bytes(&writer.into_bytes()).unwrap();
+        assert_eq!(v2.public_inputs, deploy.public_inputs);
+        assert!(v2.sequencer_keys.is_empty());
+
+        // current deploys must carry every field, even an empty layout
+        let empty = L2OADeployV1 {
+            public_inputs: Vec::new(),
+            ..deploy.clone()
+        };
+        let bytes = empty.to_bytes().unwrap();
+        assert_eq!(bytes[1], KVQ_COMPACT_VERSION);
+        assert_eq!(L2OADeployV1::from_bytes(&bytes).unwrap(), empty);
+    }
 }
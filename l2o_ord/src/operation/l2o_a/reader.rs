@@ -0,0 +1,242 @@
+use bitcoin::Transaction;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::standards::l2o_a::superchain::L2OAInscribedBlock;
+use l2o_crypto::standards::l2o_a::superchain::L2OAInscribedBlockReader;
+
+use crate::action::deserialize_operation;
+use crate::action::Action;
+use crate::inscription::envelope::ParsedEnvelope;
+use crate::operation::l2o_a::L2OADeployV1;
+use crate::operation::l2o_a::L2OAOperation;
+use crate::operation::Operation;
+
+// Reads L2O-A blocks from raw transactions the way the indexer does, for
+// verifying superchain proof bundles against the deploy inscription of the L2.
+pub struct OrdInscribedBlockReader;
+
+impl L2OAInscribedBlockReader for OrdInscribedBlockReader {
+    type Deployment = L2OADeployV1;
+
+    fn txid(transaction: &[u8]) -> anyhow::Result<Hash256> {
+        let transaction: Transaction = bitcoin::consensus::deserialize(transaction)?;
+        Ok(Hash256::from_hex(&transaction.txid().to_string())?)
+    }
+
+    fn inscribed_block(
+        transaction: &[u8],
+        inscription_index: u32,
+        deploy: &L2OADeployV1,
+    ) -> anyhow::Result<L2OAInscribedBlock> {
+        let transaction: Transaction = bitcoin::consensus::deserialize(transaction)?;
+        let envelope = ParsedEnvelope::from_transaction(&transaction)
+            .into_iter()
+            .nth(inscription_index as usize)
+            .ok_or_else(|| anyhow::anyhow!("no inscription {}", inscription_index))?;
+        let action = Action::New {
+            cursed: false,
+            unbound: false,
+            inscription: envelope.payload.clone(),
+            vindicated: false,
+            parent: None,
+        };
+        match deserialize_operation(&envelope.payload, &action)? {
+            Operation::L2OA(L2OAOperation::Block(block)) => {
+                deploy.verify_block_proof(&block)?;
+                Ok(L2OAInscribedBlock {
+                    l2id: block.l2id,
+                    bitcoin_block_number: block.bitcoin_block_number,
+                    bitcoin_block_hash: block.bitcoin_block_hash,
+                    superchain_root: block.superchain_root,
+                })
+            }
+            _ => anyhow::bail!("inscription {} is not an l2o-a block", inscription_index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Bn254;
+    use ark_bn254::Fr;
+    use ark_groth16::Groth16;
+    use ark_relations::lc;
+    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_relations::r1cs::ConstraintSystemRef;
+    use ark_relations::r1cs::SynthesisError;
+    use ark_relations::r1cs::Variable;
+    use ark_snark::CircuitSpecificSetupSNARK;
+    use ark_snark::SNARK;
+    use ark_std::rand::rngs::StdRng;
+    use ark_std::rand::SeedableRng;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+    use bitcoin::OutPoint;
+    use l2o_crypto::proof::groth16::bn128::proof_data::Groth16BN128ProofData;
+    use l2o_crypto::proof::groth16::bn128::verifier_data::Groth16BN128VerifierData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAProofData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAVerifierData;
+
+    use super::*;
+    use crate::hasher::get_l2_block_hash_with;
+    use crate::operation::l2o_a::L2OABlockV1;
+    use crate::operation::l2o_a::L2OAPublicInput;
+    use crate::operation::l2o_a::RawL2OAOperation;
+    use crate::test_helpers::envelope;
+    use crate::test_helpers::tx_in;
+
+    // exposes the block hash and nothing else
+    struct BlockHashCircuit(Option<[Fr; 2]>);
+
+    impl ConstraintSynthesizer<Fr> for BlockHashCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            for i in 0..2 {
+                let input = cs.new_input_variable(|| {
+                    self.0
+                        .map(|block_hash| block_hash[i])
+                        .ok_or(SynthesisError::AssignmentMissing)
+                })?;
+                cs.enforce_constraint(lc!() + input, lc!() + Variable::One, lc!() + input)?;
+            }
+            Ok(())
+        }
+    }
+
+    // the static block, proven for a deployment of the block hash circuit
+    fn proven_block() -> (L2OADeployV1, L2OABlockV1) {
+        let RawL2OAOperation::Deploy(deploy) = serde_json::from_str::<RawL2OAOperation>(
+            include_str!("../../../../static/deploy.json"),
+        )
+        .unwrap() else {
+            panic!("expected deploy");
+        };
+        let RawL2OAOperation::Block(block) =
+            serde_json::from_str::<RawL2OAOperation>(include_str!("../../../../static/block.json"))
+                .unwrap()
+        else {
+            panic!("expected block");
+        };
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let (pk, vk) = Groth16::<Bn254>::setup(BlockHashCircuit(None), &mut rng).unwrap();
+        let public_inputs: [Fr; 2] = get_l2_block_hash_with(deploy.hash_function, &block).into();
+        let proof =
+            Groth16::<Bn254>::prove(&pk, BlockHashCircuit(Some(public_inputs)), &mut rng).unwrap();
+        let deploy = L2OADeployV1 {
+            verifier_data: L2OAVerifierData::Groth16BN128(Groth16BN128VerifierData(vk)),
+            ..deploy
+        };
+        let block = L2OABlockV1 {
+            proof: L2OAProofData::Groth16BN128(Groth16BN128ProofData {
+                proof,
+                public_inputs: public_inputs.to_vec(),
+            }),
+            ..block
+        };
+        (deploy, block)
+    }
+
+    fn block_json(block: &L2OABlockV1) -> String {
+        let mut json = serde_json::to_value(RawL2OAOperation::Block(block.clone())).unwrap();
+        json["p"] = "l2o-a".into();
+        json.to_string()
+    }
+
+    fn inscribe(bodies: &[&[u8]]) -> Vec<u8> {
+        let input = bodies
+            .iter()
+            .map(|body| {
+                let mut input = tx_in(OutPoint::null());
+                input.witness = envelope(&[b"ord", &[1], b"application/json", &[], body]);
+                input
+            })
+            .collect();
+        bitcoin::consensus::serialize(&Transaction {
+            version: Version(2),
+            lock_time: LockTime::ZERO,
+            input,
+            output: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_read_inscribed_block() {
+        let (deploy, block) = proven_block();
+        let deploy_json = include_str!("../../../../static/deploy.json");
+        let block_json = block_json(&block);
+        let transaction = inscribe(&[deploy_json.as_bytes(), block_json.as_bytes()]);
+
+        let txid = bitcoin::consensus::deserialize::<Transaction>(&transaction)
+            .unwrap()
+            .txid();
+        assert_eq!(
+            OrdInscribedBlockReader::txid(&transaction).unwrap(),
+            Hash256::from_hex(&txid.to_string()).unwrap()
+        );
+
+        let inscribed = OrdInscribedBlockReader::inscribed_block(&transaction, 1, &deploy).unwrap();
+        assert_eq!(inscribed.bitcoin_block_number, 100);
+        assert_eq!(
+            inscribed.superchain_root,
+            Hash256::from_hex("0192aba6b6a128c7622035d91d79fb77ad8f7fb9ca1f533ca2b6a8e3051ece39")
+                .unwrap()
+        );
+        assert!(OrdInscribedBlockReader::inscribed_block(&transaction, 0, &deploy).is_err());
+        assert!(OrdInscribedBlockReader::inscribed_block(&transaction, 2, &deploy).is_err());
+        assert!(OrdInscribedBlockReader::txid(&transaction[1..]).is_err());
+    }
+
+    #[test]
+    fn test_reject_forged_inscribed_blocks() {
+        let (deploy, block) = proven_block();
+        let read = |block: &L2OABlockV1, deploy: &L2OADeployV1| {
+            let transaction = inscribe(&[block_json(block).as_bytes()]);
+            OrdInscribedBlockReader::inscribed_block(&transaction, 0, deploy)
+        };
+        assert!(read(&block, &deploy).is_ok());
+
+        // the static block was never proven for the deployment
+        let RawL2OAOperation::Block(unproven) =
+            serde_json::from_str::<RawL2OAOperation>(include_str!("../../../../static/block.json"))
+                .unwrap()
+        else {
+            panic!("expected block");
+        };
+        assert!(read(&unproven, &deploy).is_err());
+
+        // the proof does not carry over to another superchain root
+        let forged = L2OABlockV1 {
+            superchain_root: Hash256([6u8; 32]),
+            ..block.clone()
+        };
+        assert!(read(&forged, &deploy).is_err());
+
+        // a deployment whose proofs do not commit to the block hash
+        let unbound = L2OADeployV1 {
+            public_inputs: vec![L2OAPublicInput::L2Id],
+            ..deploy.clone()
+        };
+        assert!(read(&block, &unbound).is_err());
+
+        // the deployment of another L2
+        let other = L2OADeployV1 {
+            l2id: deploy.l2id + 1,
+            ..deploy
+        };
+        assert!(read(&block, &other).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_block_proofs() {
+        let (deploy, mut block) = proven_block();
+        let L2OAProofData::Groth16BN128(proof) = &mut block.proof else {
+            panic!("expected groth16 proof");
+        };
+        // a proof over the right public inputs that does not verify is an
+        // error, not a panic
+        proof.proof.a = proof.proof.c;
+        assert_eq!(
+            deploy.verify_block_proof(&block).unwrap_err().to_string(),
+            "invalid proof"
+        );
+    }
+}
@@ -43,6 +43,24 @@ pub enum ProtocolType {
     L2OA,
 }
 
+impl OperationType {
+    pub fn p_type(&self) -> ProtocolType {
+        match self {
+            OperationType::BRC20Deploy
+            | OperationType::BRC20Mint
+            | OperationType::BRC20InscribeTransfer
+            | OperationType::BRC20Transfer => ProtocolType::BRC20,
+            OperationType::BRC21Deploy
+            | OperationType::BRC21Mint
+            | OperationType::BRC21InscribeTransfer
+            | OperationType::BRC21Transfer
+            | OperationType::BRC21L2Deposit
+            | OperationType::BRC21L2Withdraw => ProtocolType::BRC21,
+            OperationType::L2OABlock | OperationType::L2ODeploy => ProtocolType::L2OA,
+        }
+    }
+}
+
 impl Operation {
     pub fn p_type(&self) -> ProtocolType {
         match self {
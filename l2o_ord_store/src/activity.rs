@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use bitcoin::Txid;
+use l2o_ord::tick::Tick;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::event::Event;
+use crate::event::Receipt;
+
+// A receipt an address was the sender or the receiver of.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Activity {
+    pub height: u32,
+    pub tx_index: u32,
+    pub txid: Txid,
+    pub receipt: Receipt,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ActivityPage {
+    pub activities: Vec<Activity>,
+    pub next_cursor: Option<String>,
+}
+
+// Where an activity is in the chain, activities of an address are sorted by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ActivityPosition {
+    pub height: u32,
+    pub tx_index: u32,
+    // of the receipt in the receipts of the transaction
+    pub index: u32,
+}
+
+impl ActivityPosition {
+    // zero padded, so that positions sort like their keys
+    pub fn to_key(&self) -> String {
+        format!(
+            "{:010}_{:010}_{:05}",
+            self.height, self.tx_index, self.index
+        )
+    }
+}
+
+impl FromStr for ActivityPosition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut parts = s.splitn(3, '_');
+        let mut next = || -> anyhow::Result<u32> {
+            Ok(parts
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("invalid activity position {}", s))?
+                .parse()?)
+        };
+        Ok(Self {
+            height: next()?,
+            tx_index: next()?,
+            index: next()?,
+        })
+    }
+}
+
+pub fn receipt_tick(receipt: &Receipt) -> Option<Tick> {
+    match receipt.result.as_ref().ok()? {
+        Event::Deploy(event) => Some(event.tick.clone()),
+        Event::Mint(event) => Some(event.tick.clone()),
+        Event::InscribeTransfer(event) => Some(event.tick.clone()),
+        Event::Transfer(event) => Some(event.tick.clone()),
+        Event::L2Deposit(event) => Tick::from_str(&event.tick).ok(),
+        Event::L2Withdraw(event) => Tick::from_str(&event.tick).ok(),
+        Event::L2OADeploy | Event::L2OABlock => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_position() {
+        let position = ActivityPosition {
+            height: 840_000,
+            tx_index: 12,
+            index: 1,
+        };
+        assert_eq!(position.to_key(), "0000840000_0000000012_00001");
+        assert_eq!(
+            ActivityPosition::from_str(&position.to_key()).unwrap(),
+            position
+        );
+        assert!(
+            ActivityPosition {
+                height: 9,
+                tx_index: 100,
+                index: 0,
+            }
+            .to_key()
+                < ActivityPosition {
+                    height: 10,
+                    tx_index: 0,
+                    index: 0,
+                }
+                .to_key()
+        );
+        assert!(ActivityPosition::from_str("840000_12").is_err());
+        assert!(ActivityPosition::from_str("840000_12_x").is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use bitcoin::BlockHash;
+use bitcoin::Txid;
+use l2o_ord::inscription::inscription_id::InscriptionId;
+use l2o_ord::operation::ProtocolType;
+use l2o_ord::tick::Tick;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::activity::receipt_tick;
+use crate::event::Event;
+use crate::event::Receipt;
+
+// An L2O-A block inscribed in a bitcoin block. It is accepted when it became
+// the block of its l2 at its bitcoin block number.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct L2OABlockEvent {
+    pub txid: Txid,
+    pub inscription_id: InscriptionId,
+    pub l2id: u64,
+    pub l2_block_number: u64,
+    pub bitcoin_block_number: u64,
+    pub accepted: bool,
+}
+
+// what is kept of a block for subscribers, the receipts of the transactions
+// are those saved in BRC20_EVENTS
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct BlockEventsLog {
+    pub txids: Vec<Txid>,
+    pub l2o_a_blocks: Vec<L2OABlockEvent>,
+}
+
+impl BlockEventsLog {
+    pub fn is_empty(&self) -> bool {
+        self.txids.is_empty() && self.l2o_a_blocks.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TransactionReceipts {
+    pub txid: Txid,
+    pub receipts: Vec<Receipt>,
+}
+
+// an indexed block with the receipts of its transactions, in block order
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BlockEvents {
+    pub height: u32,
+    pub hash: BlockHash,
+    pub transactions: Vec<TransactionReceipts>,
+    pub l2o_a_blocks: Vec<L2OABlockEvent>,
+}
+
+// the blocks from from_height up to to_height were rolled back
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ReorgEvent {
+    pub from_height: u32,
+    pub to_height: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EventFilter {
+    pub protocol: Option<ProtocolType>,
+    pub tick: Option<Tick>,
+    // the sender or the receiver
+    pub address: Option<String>,
+    pub l2id: Option<u64>,
+}
+
+pub fn receipt_l2id(receipt: &Receipt) -> Option<u64> {
+    match receipt.result.as_ref().ok()? {
+        Event::L2Deposit(event) => Some(event.l2id),
+        Event::L2Withdraw(event) => Some(event.l2id),
+        _ => None,
+    }
+}
+
+impl EventFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // l2id is that of the receipt, or of the L2O-A block it inscribed
+    pub fn matches(&self, receipt: &Receipt, l2id: Option<u64>) -> bool {
+        if self
+            .protocol
+            .is_some_and(|protocol| protocol != receipt.op.p_type())
+        {
+            return false;
+        }
+        if self.l2id.is_some() && self.l2id != l2id {
+            return false;
+        }
+        if self.address.as_ref().is_some_and(|address| {
+            receipt.from.to_string() != *address && receipt.to.to_string() != *address
+        }) {
+            return false;
+        }
+        match self.tick {
+            Some(ref tick) => receipt_tick(receipt)
+                .is_some_and(|receipt_tick| receipt_tick.to_lowercase() == tick.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+impl BlockEvents {
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty() && self.l2o_a_blocks.is_empty()
+    }
+
+    // keeps the receipts the filter matches, and the L2O-A blocks they inscribed
+    pub fn filter(self, filter: &EventFilter) -> Self {
+        if filter.is_empty() {
+            return self;
+        }
+        self.retain_receipts(|receipt, l2id| filter.matches(receipt, l2id))
+    }
+
+    // Keeps the receipts matches is true of, and the L2O-A blocks they
+    // inscribed. matches is given the l2id of the receipt, or that of the
+    // L2O-A block it inscribed.
+    pub fn retain_receipts<F>(self, matches: F) -> Self
+    where
+        F: Fn(&Receipt, Option<u64>) -> bool,
+    {
+        let l2o_a_blocks = self.l2o_a_blocks;
+        let transactions = self
+            .transactions
+            .into_iter()
+            .filter_map(|transaction| {
+                let receipts = transaction
+                    .receipts
+                    .into_iter()
+                    .filter(|receipt| {
+                        let l2id = receipt_l2id(receipt).or_else(|| {
+                            l2o_a_blocks
+                                .iter()
+                                .find(|block| block.inscription_id == receipt.inscription_id)
+                                .map(|block| block.l2id)
+                        });
+                        matches(receipt, l2id)
+                    })
+                    .collect::<Vec<_>>();
+                (!receipts.is_empty()).then_some(TransactionReceipts {
+                    txid: transaction.txid,
+                    receipts,
+                })
+            })
+            .collect::<Vec<_>>();
+        let l2o_a_blocks = l2o_a_blocks
+            .into_iter()
+            .filter(|block| {
+                transactions.iter().any(|transaction| {
+                    transaction
+                        .receipts
+                        .iter()
+                        .any(|receipt| receipt.inscription_id == block.inscription_id)
+                })
+            })
+            .collect();
+        Self {
+            height: self.height,
+            hash: self.hash,
+            transactions,
+            l2o_a_blocks,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use bitcoincore_rpc::Client;
@@ -13,12 +14,15 @@ use crate::entry::InscriptionIdValue;
 use crate::entry::OutPointValue;
 use crate::entry::SatPointValue;
 use crate::entry::TxidValue;
+use crate::executor::L2OABlockSignature;
 
 #[derive(Debug, Clone)]
 pub struct ChainContext {
     pub chain: Chain,
     pub blockheight: u32,
     pub blocktime: u32,
+    // store the bodies of new inscriptions
+    pub index_content: bool,
     pub bitcoin_rpc: Arc<Client>,
 }
 
@@ -29,6 +33,7 @@ pub struct Context<'a, 'db, 'txn> {
 
     pub height_to_block_header: &'a mut Table<'db, 'txn, u32, &'static HeaderValue>,
     pub height_to_last_sequence_number: &'a mut Table<'db, 'txn, u32, u32>,
+    pub height_to_block_events: &'a mut Table<'db, 'txn, u32, &'static [u8]>,
 
     pub brc21_deposits_holding_balances: &'a mut Table<'db, 'txn, &'static [u8], u128>,
 
@@ -43,24 +48,36 @@ pub struct Context<'a, 'db, 'txn> {
     pub inscription_number_to_sequence_number: &'a mut Table<'db, 'txn, i32, u32>,
     pub sequence_number_to_inscription_entry: &'a mut Table<'db, 'txn, u32, InscriptionEntryValue>,
     pub sequence_number_to_satpoint: &'a mut Table<'db, 'txn, u32, &'static SatPointValue>,
+    pub sequence_number_to_content: &'a mut Table<'db, 'txn, u32, &'static [u8]>,
 
     pub statistic_to_count: &'a mut Table<'db, 'txn, u64, u64>,
 
     // BRC20 tables
     pub brc20_balances: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
     pub brc20_token: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+    pub brc20_balance_history: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+    pub brc20_token_history: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
     pub brc20_events: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
     pub brc20_satpoint_to_transferable_assets:
         &'a mut Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
     pub brc20_address_ticker_to_transferable_assets:
         &'a mut MultimapTable<'db, 'txn, &'static str, &'static SatPointValue>,
+    pub brc20_address_activity: &'a mut Table<'db, 'txn, &'static str, &'static TxidValue>,
+    pub brc20_tick_holders: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
 
     // BRC21 tables
     pub brc21_balances: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
     pub brc21_token: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+    pub brc21_balance_history: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+    pub brc21_token_history: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
     pub brc21_events: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
     pub brc21_satpoint_to_transferable_assets:
         &'a mut Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
     pub brc21_address_ticker_to_transferable_assets:
         &'a mut MultimapTable<'db, 'txn, &'static str, &'static SatPointValue>,
+    pub brc21_address_activity: &'a mut Table<'db, 'txn, &'static str, &'static TxidValue>,
+    pub brc21_tick_holders: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+
+    // the l2o-a block signatures the batch of the bitcoin block verified
+    pub verified_l2o_a_signatures: HashSet<L2OABlockSignature>,
 }
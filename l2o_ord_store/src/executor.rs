@@ -1,26 +1,31 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
-use ark_bn254::Bn254;
-use ark_bn254::Fr;
-use ark_groth16::Groth16;
 use ark_serialize::CanonicalSerialize;
-use ark_snark::SNARK;
 use bigdecimal::num_bigint::Sign;
 use bitcoin::Address;
 use bitcoin::Txid;
 use bitcoincore_rpc::RpcApi;
 use l2o_common::common::data::hash::Hash256;
+use l2o_common::common::data::signature::L2OCompactPublicKey;
+use l2o_common::common::data::signature::L2OSignature512;
+use l2o_common::metrics::L2OA_PROOF_VERIFICATION_SECONDS;
+use l2o_crypto::fields::goldilocks::hash::GHashOut;
 use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
 use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
 use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
 use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+use l2o_crypto::hash::merkle::core::MerkleProofCore;
+use l2o_crypto::signature::musig::verify_aggregate_public_key;
 use l2o_crypto::signature::schnorr::verify_sig;
+use l2o_crypto::signature::schnorr::verify_sigs_batch;
 use l2o_ord::chain::Chain;
 use l2o_ord::decimal::Decimal;
 use l2o_ord::error::BRC2XError;
 use l2o_ord::error::Error;
-use l2o_ord::hasher::L2OBlockHasher;
-use l2o_ord::hasher::L2OWithdrawHasher;
+use l2o_ord::hasher::get_l2_block_hash_with;
+use l2o_ord::hasher::get_l2_withdraw_hash_with;
 use l2o_ord::inscription::inscription_id::InscriptionId;
 use l2o_ord::operation::brc20::deploy::Deploy;
 use l2o_ord::operation::brc20::mint::Mint;
@@ -31,6 +36,7 @@ use l2o_ord::operation::brc21::BRC21Operation;
 use l2o_ord::operation::brc21::L2WithdrawV1;
 use l2o_ord::operation::l2o_a::L2OABlockV1;
 use l2o_ord::operation::l2o_a::L2OADeployV1;
+use l2o_ord::operation::l2o_a::L2OAHashFunction;
 use l2o_ord::operation::l2o_a::L2OAOperation;
 use l2o_ord::operation::Operation;
 use l2o_ord::operation::ProtocolType;
@@ -41,6 +47,7 @@ use l2o_ord::tick::Tick;
 use l2o_ord::BIGDECIMAL_TEN;
 use l2o_ord::MAXIMUM_SUPPLY;
 use l2o_ord::MAX_DECIMAL_WIDTH;
+use l2o_store::core::location::L2OABlockLocation;
 use l2o_store::core::traits::L2OStoreReaderV1;
 use l2o_store::core::traits::L2OStoreV1;
 
@@ -70,6 +77,14 @@ pub struct Message {
     pub sat_in_outputs: bool,
 }
 
+// a signature over an l2o-a block hash, with the key it is checked against
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct L2OABlockSignature {
+    pub public_key: L2OCompactPublicKey,
+    pub signature: L2OSignature512,
+    pub block_hash: Hash256,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExecutionMessage {
     pub txid: Txid,
@@ -260,6 +275,7 @@ impl ExecutionMessage {
             deployed_number: context.chain_ctx.blockheight,
             latest_mint_number: context.chain_ctx.blockheight,
             deployed_timestamp: context.chain_ctx.blocktime,
+            holders: 0,
         };
         context
             .insert_token_info(&tick, &new_info, ptype)
@@ -556,6 +572,19 @@ impl ExecutionMessage {
 
         tracing::info!("processing l2deposit");
 
+        // deposits to an l2 that is not deployed yet still execute, into the
+        // sha256 tree they always went into, and are committed to again in the
+        // hash function of the l2 once it deploys
+        let deployed = context.kv.has_deployed_l2id(l2deposit.l2id)?;
+        let hash_function = if deployed {
+            context
+                .kv
+                .get_deploy_inscription(l2deposit.l2id)?
+                .hash_function
+        } else {
+            L2OAHashFunction::Sha256
+        };
+
         let tick = l2deposit.tick.parse::<Tick>()?;
 
         let token_info = context
@@ -602,7 +631,12 @@ impl ExecutionMessage {
             &tick,
             holding_balance.checked_add(amt.checked_to_u128()?).unwrap(),
         )?;
-        context.kv.append_l2_deposit(l2deposit.clone())?;
+        context
+            .kv
+            .append_l2_deposit(l2deposit.clone(), hash_function)?;
+        if !deployed {
+            context.kv.append_pending_l2_deposit(l2deposit.clone())?;
+        }
 
         Ok(Event::L2Deposit(L2DepositEvent {
             l2id: l2deposit.l2id,
@@ -622,6 +656,12 @@ impl ExecutionMessage {
 
         tracing::info!("processing l2withdraw");
 
+        let hash_function = context
+            .kv
+            .get_deploy_inscription(l2withdraw.l2id)?
+            .hash_function;
+        Self::verify_l2_withdraw_proof(hash_function, &l2withdraw)?;
+
         let tick = l2withdraw.tick.parse::<Tick>()?;
 
         let token_info = context
@@ -669,15 +709,6 @@ impl ExecutionMessage {
             holding_balance.checked_sub(amt.checked_to_u128()?).unwrap(),
         )?;
 
-        if Sha256Hasher::get_l2_withdraw_hash(&l2withdraw) != l2withdraw.proof.value {
-            anyhow::bail!("proof value mismatch");
-        }
-
-        if !l2withdraw.proof.verify_marked_if::<Sha256Hasher>(false) {
-            // TODO: check if root is valid
-            anyhow::bail!("invalid proof");
-        }
-
         Ok(Event::L2Withdraw(L2WithdrawEvent {
             l2id: l2withdraw.l2id,
             tick: l2withdraw.tick,
@@ -686,6 +717,37 @@ impl ExecutionMessage {
         }))
     }
 
+    // withdrawals are proven against the L2's withdrawal tree, which is kept in
+    // the hash function the L2 declared at deploy time
+    fn verify_l2_withdraw_proof(
+        hash_function: L2OAHashFunction,
+        l2withdraw: &L2WithdrawV1,
+    ) -> anyhow::Result<()> {
+        if get_l2_withdraw_hash_with(hash_function, l2withdraw) != l2withdraw.proof.value {
+            anyhow::bail!("proof value mismatch");
+        }
+
+        // TODO: check if root is valid
+        let valid = match hash_function {
+            L2OAHashFunction::Sha256 => l2withdraw.proof.verify_marked_if::<Sha256Hasher>(false),
+            L2OAHashFunction::BLAKE3 => l2withdraw.proof.verify_marked_if::<Blake3Hasher>(false),
+            L2OAHashFunction::Keccak256 => {
+                l2withdraw.proof.verify_marked_if::<Keccak256Hasher>(false)
+            }
+            L2OAHashFunction::PoseidonGoldilocks => {
+                MerkleProofCore::<GHashOut>::from(&l2withdraw.proof)
+                    .verify_marked_if::<PoseidonHasher>(false)
+            }
+            L2OAHashFunction::PoseidonBN254 => l2withdraw
+                .proof
+                .verify_marked_if::<PoseidonBN254Hasher>(false),
+        };
+        if !valid {
+            anyhow::bail!("invalid proof");
+        }
+        Ok(())
+    }
+
     fn process_l2o_a_deploy(
         context: &mut Context,
         _msg: &ExecutionMessage,
@@ -696,9 +758,27 @@ impl ExecutionMessage {
             tracing::debug!("l2o {} already deployed", l2id);
             return Ok(Event::L2OADeploy);
         }
-        if !deploy.verifier_data.is_groth_16_bn_128() {
-            anyhow::bail!("unsupported verifier type");
-        };
+        let vk = deploy
+            .verifier_data
+            .clone()
+            .try_as_groth_16_bn_128()
+            .ok_or(anyhow::anyhow!("unsupported verifier type"))?
+            .0;
+        let public_input_count = deploy
+            .public_inputs
+            .iter()
+            .map(|public_input| public_input.field_count())
+            .sum::<usize>();
+        if vk.gamma_abc_g1.len() != public_input_count + 1 {
+            anyhow::bail!(
+                "verifier expects {} public inputs, layout declares {}",
+                vk.gamma_abc_g1.len() - 1,
+                public_input_count
+            );
+        }
+        if !deploy.sequencer_keys.is_empty() {
+            verify_aggregate_public_key(&deploy.sequencer_keys, &deploy.public_key)?;
+        }
         context.kv.report_deploy_inscription(deploy)?;
         tracing::info!("l2o {} deployed", l2id);
         Ok(Event::L2OADeploy)
@@ -706,7 +786,7 @@ impl ExecutionMessage {
 
     fn process_l2o_a_block(
         context: &mut Context,
-        _msg: &ExecutionMessage,
+        msg: &ExecutionMessage,
         block: L2OABlockV1,
     ) -> anyhow::Result<Event> {
         let l2id = block.l2id;
@@ -745,7 +825,9 @@ impl ExecutionMessage {
             anyhow::bail!("superchain root mismatch");
         }
 
-        let last_public_key = if let Ok(last_block) = context.kv.get_last_block_inscription(l2id) {
+        let last_public_key = if let Some(last_block) =
+            context.kv.get_last_block_inscription_if_exists(l2id)?
+        {
             if block.l2_block_number != last_block.l2_block_number + 1 {
                 anyhow::bail!("block must be consecutive");
             }
@@ -779,44 +861,92 @@ impl ExecutionMessage {
         let mut uncompressed_bytes = Vec::new();
         block_proof.serialize_uncompressed(&mut uncompressed_bytes)?;
 
-        let block_hash = if deploy.hash_function.is_sha_256() {
-            Sha256Hasher::get_l2_block_hash(&block)
-        } else if deploy.hash_function.is_blake_3() {
-            Blake3Hasher::get_l2_block_hash(&block)
-        } else if deploy.hash_function.is_keccak_256() {
-            Keccak256Hasher::get_l2_block_hash(&block)
-        } else if deploy.hash_function.is_poseidon_goldilocks() {
-            PoseidonHasher::get_l2_block_hash(&block)
-        } else {
-            anyhow::bail!("unsupported hash function");
-        };
+        let block_hash = get_l2_block_hash_with(deploy.hash_function, &block);
 
-        let public_inputs: [Fr; 2] = block_hash.into();
-        if public_inputs.to_vec() != block_proof.public_inputs {
-            anyhow::bail!("public inputs mismatch");
-        }
+        let public_inputs = block.get_public_inputs(&deploy.public_inputs, block_hash);
 
-        let vk = deploy
+        let timer = L2OA_PROOF_VERIFICATION_SECONDS.start_timer();
+        deploy
             .verifier_data
-            .try_as_groth_16_bn_128()
-            .ok_or(anyhow::anyhow!("marformed verifier"))?
-            .0;
-
-        let processed_vk = Groth16::<Bn254>::process_vk(&vk)?;
+            .verify_proof(&block.proof, &public_inputs)?;
+        timer.observe_duration();
 
-        assert!(Groth16::<Bn254>::verify_proof(
-            &processed_vk,
-            &block_proof.proof,
-            &block_proof.public_inputs,
-        )?);
-
-        if !last_public_key.is_zero() {
-            verify_sig(&last_public_key, &block.signature, &block_hash.0)?;
+        let signature = L2OABlockSignature {
+            public_key: last_public_key,
+            signature: block.signature.clone(),
+            block_hash,
+        };
+        if !signature.public_key.is_zero()
+            && !context.verified_l2o_a_signatures.contains(&signature)
+        {
+            verify_sig(&signature.public_key, &signature.signature, &block_hash.0)?;
         }
 
+        let bitcoin_block_number = block.bitcoin_block_number;
+        let location = L2OABlockLocation {
+            txid: msg.txid,
+            inscription_id: msg.inscription_id,
+            start_state_root: block.start_state_root,
+        };
         context.kv.set_last_block_inscription(block)?;
+        context
+            .kv
+            .report_block_inscription_location(l2id, bitcoin_block_number, location)?;
         tracing::info!("l2id {} block", l2id);
 
         return Ok(Event::L2OABlock);
     }
+
+    // Checks the signatures of the l2o-a blocks inscribed in a bitcoin block in
+    // one batch, before they execute. Each block is expected to be signed with
+    // the key of the block before it, as process_l2o_a_block checks. When the
+    // batch holds, the executor skips the signatures it covered, otherwise every
+    // block is checked on its own.
+    pub fn verify_l2o_a_block_signatures(
+        context: &mut Context,
+        blocks: &[L2OABlockV1],
+    ) -> anyhow::Result<()> {
+        let mut public_keys = HashMap::<u64, L2OCompactPublicKey>::new();
+        let mut signatures = vec![];
+        for block in blocks {
+            if !context.kv.has_deployed_l2id(block.l2id)? {
+                continue;
+            }
+            let deploy = context.kv.get_deploy_inscription(block.l2id)?;
+            let public_key = match public_keys.get(&block.l2id) {
+                Some(public_key) => public_key.clone(),
+                None => match context
+                    .kv
+                    .get_last_block_inscription_if_exists(block.l2id)?
+                {
+                    Some(last_block) => last_block.public_key,
+                    None => deploy.public_key,
+                },
+            };
+            public_keys.insert(block.l2id, block.public_key.clone());
+            if public_key.is_zero() {
+                continue;
+            }
+            signatures.push(L2OABlockSignature {
+                public_key,
+                signature: block.signature.clone(),
+                block_hash: get_l2_block_hash_with(deploy.hash_function, block),
+            });
+        }
+
+        let batch = signatures
+            .iter()
+            .map(|signature| {
+                (
+                    &signature.public_key,
+                    &signature.signature,
+                    signature.block_hash.0.as_slice(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if verify_sigs_batch(&batch).is_ok() {
+            context.verified_l2o_a_signatures.extend(signatures);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,40 @@
+use l2o_ord::script_key::ScriptKey;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::balance::Balance;
+
+pub const DEFAULT_HOLDERS_LIMIT: usize = 100;
+pub const MAX_HOLDERS_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TickHolder {
+    pub script_key: ScriptKey,
+    pub balance: Balance,
+}
+
+// the value of a holder key, whose display of the script key drops its
+// op_return flag
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TickHolderValue {
+    pub is_op_return: bool,
+    pub balance: Balance,
+}
+
+// holders of a tick from the largest overall balance down
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct TickHoldersPage {
+    pub holders: Vec<TickHolder>,
+    pub next_cursor: Option<String>,
+}
+
+pub fn holders_limit(limit: Option<usize>) -> usize {
+    limit
+        .unwrap_or(DEFAULT_HOLDERS_LIMIT)
+        .clamp(1, MAX_HOLDERS_LIMIT)
+}
+
+// an address holds a tick while its overall balance is not zero
+pub fn is_holder(balance: Option<&Balance>) -> bool {
+    balance.is_some_and(|balance| balance.overall_balance > 0)
+}
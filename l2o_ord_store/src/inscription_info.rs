@@ -0,0 +1,48 @@
+use l2o_ord::inscription::inscription_id::InscriptionId;
+use l2o_ord::sat_point::SatPoint;
+use l2o_ord::script_key::ScriptKey;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::Bytes;
+
+use crate::charm::Charm;
+
+// An InscriptionEntry with where the inscription is now. The owner and value
+// are those of the output it is on, unbound inscriptions have neither.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InscriptionInfo {
+    pub id: InscriptionId,
+    pub number: i32,
+    pub sequence_number: u32,
+    pub charms: Vec<String>,
+    pub fee: u64,
+    pub height: u32,
+    pub sat: Option<u64>,
+    pub parent: Option<InscriptionId>,
+    pub timestamp: u32,
+    pub satpoint: SatPoint,
+    pub owner: Option<ScriptKey>,
+    pub value: Option<u64>,
+}
+
+// the body of an inscription with the headers it is served with, stored when
+// the indexer runs with --index-content
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InscriptionContent {
+    #[serde_as(as = "Option<Bytes>")]
+    pub content_type: Option<Vec<u8>>,
+    #[serde_as(as = "Option<Bytes>")]
+    pub content_encoding: Option<Vec<u8>>,
+    #[serde_as(as = "Bytes")]
+    pub body: Vec<u8>,
+}
+
+pub fn charm_titles(charms: u16) -> Vec<String> {
+    Charm::ALL
+        .iter()
+        .filter(|charm| charm.is_set(charms))
+        .map(|charm| charm.title().to_string())
+        .collect()
+}
@@ -1,9 +1,13 @@
+pub mod activity;
 pub mod balance;
+pub mod block_event;
 pub mod charm;
 pub mod ctx;
 pub mod entry;
 pub mod event;
 pub mod executor;
+pub mod holder;
+pub mod inscription_info;
 pub mod log;
 pub mod lru;
 pub mod reader;
@@ -22,3 +22,5 @@ impl std::error::Error for ReorgError {}
 pub const MAX_SAVEPOINTS: u32 = 2;
 pub const SAVEPOINT_INTERVAL: u32 = 10;
 pub const CHAIN_TIP_DISTANCE: u32 = 21;
+// the most blocks a reorg rolls back, that of the oldest savepoint
+pub const MAX_ROLLBACK_DEPTH: u32 = MAX_SAVEPOINTS * SAVEPOINT_INTERVAL;
@@ -7,40 +7,78 @@ use bitcoin::TxOut;
 use bitcoin::Txid;
 use bitcoincore_rpc::Client;
 use bitcoincore_rpc::RpcApi;
+use l2o_ord::chain::Chain;
 use l2o_ord::height::Height;
+use l2o_ord::inscription::inscription_id::InscriptionId;
 use l2o_ord::sat_point::SatPoint;
 use l2o_ord::script_key::ScriptKey;
 use l2o_ord::tick::Tick;
+use l2o_rpc::query::ActivityFilter;
+use l2o_rpc::query::InscriptionQuery;
 use redb::ReadableTable;
 
+use crate::activity::ActivityPage;
 use crate::balance::Balance;
+use crate::block_event::BlockEvents;
+use crate::block_event::TransactionReceipts;
 use crate::entry::Entry;
 use crate::event::Receipt;
+use crate::holder::TickHoldersPage;
+use crate::inscription_info::InscriptionContent;
+use crate::inscription_info::InscriptionInfo;
 use crate::log::TransferableLog;
 use crate::reorg::ReorgError;
 use crate::reorg::MAX_SAVEPOINTS;
 use crate::reorg::SAVEPOINT_INTERVAL;
+use crate::statistic::Statistic;
+use crate::table::full_inscriptions_on_output;
+use crate::table::get_address_activity;
 use crate::table::get_balance;
+use crate::table::get_balance_at_height;
 use crate::table::get_balances;
+use crate::table::get_balances_at_height;
+use crate::table::get_block_events_log;
+use crate::table::get_inscription_content;
+use crate::table::get_inscription_info;
+use crate::table::get_sequence_numbers_at_height;
+use crate::table::get_tick_holders;
 use crate::table::get_token_info;
+use crate::table::get_token_info_at_height;
 use crate::table::get_tokens_info;
+use crate::table::get_tokens_info_at_height;
 use crate::table::get_transaction_receipts;
 use crate::table::get_transferable_assets_by_account;
 use crate::table::get_transferable_assets_by_account_ticker;
 use crate::table::get_transferable_assets_by_outpoint;
 use crate::table::get_txout_by_outpoint;
+use crate::table::BRC20_ADDRESS_ACTIVITY;
 use crate::table::BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
 use crate::table::BRC20_BALANCES;
+use crate::table::BRC20_BALANCE_HISTORY;
 use crate::table::BRC20_EVENTS;
 use crate::table::BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS;
+use crate::table::BRC20_TICK_HOLDERS;
 use crate::table::BRC20_TOKEN;
+use crate::table::BRC20_TOKEN_HISTORY;
+use crate::table::BRC21_ADDRESS_ACTIVITY;
 use crate::table::BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
 use crate::table::BRC21_BALANCES;
+use crate::table::BRC21_BALANCE_HISTORY;
 use crate::table::BRC21_EVENTS;
 use crate::table::BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS;
+use crate::table::BRC21_TICK_HOLDERS;
 use crate::table::BRC21_TOKEN;
+use crate::table::BRC21_TOKEN_HISTORY;
+use crate::table::HEIGHT_TO_BLOCK_EVENTS;
 use crate::table::HEIGHT_TO_BLOCK_HEADER;
+use crate::table::INSCRIPTION_ID_TO_SEQUENCE_NUMBER;
+use crate::table::INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER;
 use crate::table::OUTPOINT_TO_ENTRY;
+use crate::table::SATPOINT_TO_SEQUENCE_NUMBER;
+use crate::table::SEQUENCE_NUMBER_TO_CONTENT;
+use crate::table::SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY;
+use crate::table::SEQUENCE_NUMBER_TO_SATPOINT;
+use crate::table::STATISTIC_TO_COUNT;
 use crate::token_info::TokenInfo;
 use crate::wtx::BlockData;
 
@@ -49,7 +87,29 @@ pub trait Rtx {
     fn block_count(&self) -> anyhow::Result<u32>;
     fn block_hash(&self, height: Option<u32>) -> anyhow::Result<Option<BlockHash>>;
     fn latest_block(&self) -> anyhow::Result<Option<(Height, BlockHash)>>;
+    fn block_header(&self, height: u32) -> anyhow::Result<Option<Header>>;
+    fn block_events(&self, height: u32) -> anyhow::Result<Option<BlockEvents>>;
     fn outpoint_to_entry(&self, outpoint: OutPoint) -> anyhow::Result<Option<TxOut>>;
+    fn check_token_history_height(&self, height: u32) -> anyhow::Result<()>;
+    fn ord_get_inscription(
+        &self,
+        query: &InscriptionQuery,
+        chain: Chain,
+    ) -> anyhow::Result<Option<InscriptionInfo>>;
+    fn ord_get_inscription_content(
+        &self,
+        id: InscriptionId,
+    ) -> anyhow::Result<Option<InscriptionContent>>;
+    fn ord_get_inscriptions_on_output(
+        &self,
+        outpoint: OutPoint,
+        chain: Chain,
+    ) -> anyhow::Result<Vec<InscriptionInfo>>;
+    fn ord_get_inscriptions_by_height(
+        &self,
+        height: u32,
+        chain: Chain,
+    ) -> anyhow::Result<Vec<InscriptionInfo>>;
     fn brc20_get_tick_info(&self, name: &Tick) -> anyhow::Result<Option<TokenInfo>>;
     fn brc20_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>>;
     fn brc20_get_balance_by_address(
@@ -61,6 +121,23 @@ pub trait Rtx {
         &self,
         script_key: ScriptKey,
     ) -> anyhow::Result<Vec<Balance>>;
+    fn brc20_get_tick_info_at_height(
+        &self,
+        name: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>>;
+    fn brc20_get_all_tick_info_at_height(&self, height: u32) -> anyhow::Result<Vec<TokenInfo>>;
+    fn brc20_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>>;
+    fn brc20_get_all_balance_by_address_at_height(
+        &self,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>>;
     fn brc20_transaction_id_to_transaction_receipt(
         &self,
         txid: Txid,
@@ -78,6 +155,17 @@ pub trait Rtx {
         &self,
         outpoint: OutPoint,
     ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    fn brc20_get_address_activity(
+        &self,
+        script_key: ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage>;
+    fn brc20_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage>;
     fn brc21_get_tick_info(&self, name: &Tick) -> anyhow::Result<Option<TokenInfo>>;
     fn brc21_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>>;
     fn brc21_get_balance_by_address(
@@ -89,6 +177,23 @@ pub trait Rtx {
         &self,
         script_key: ScriptKey,
     ) -> anyhow::Result<Vec<Balance>>;
+    fn brc21_get_tick_info_at_height(
+        &self,
+        name: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>>;
+    fn brc21_get_all_tick_info_at_height(&self, height: u32) -> anyhow::Result<Vec<TokenInfo>>;
+    fn brc21_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>>;
+    fn brc21_get_all_balance_by_address_at_height(
+        &self,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>>;
     fn brc21_transaction_id_to_transaction_receipt(
         &self,
         txid: Txid,
@@ -106,6 +211,17 @@ pub trait Rtx {
         &self,
         outpoint: OutPoint,
     ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    fn brc21_get_address_activity(
+        &self,
+        script_key: ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage>;
+    fn brc21_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage>;
     fn detect_reorg(
         &self,
         client: Arc<Client>,
@@ -162,11 +278,151 @@ impl<'a> Rtx for redb::ReadTransaction<'a> {
             }))
     }
 
+    fn block_header(&self, height: u32) -> anyhow::Result<Option<Header>> {
+        Ok(self
+            .open_table(HEIGHT_TO_BLOCK_HEADER)?
+            .get(height)?
+            .map(|header| Header::load(*header.value())))
+    }
+
+    fn block_events(&self, height: u32) -> anyhow::Result<Option<BlockEvents>> {
+        let Some(hash) = self.block_hash(Some(height))? else {
+            return Ok(None);
+        };
+        let log = get_block_events_log(&self.open_table(HEIGHT_TO_BLOCK_EVENTS)?, height)?
+            .unwrap_or_default();
+        let events = self.open_table(BRC20_EVENTS)?;
+        let transactions = log
+            .txids
+            .into_iter()
+            .map(|txid| {
+                Ok(TransactionReceipts {
+                    txid,
+                    receipts: get_transaction_receipts(&events, &txid)?.unwrap_or_default(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Some(BlockEvents {
+            height,
+            hash,
+            transactions,
+            l2o_a_blocks: log.l2o_a_blocks,
+        }))
+    }
+
     fn outpoint_to_entry(&self, outpoint: OutPoint) -> anyhow::Result<Option<TxOut>> {
         let table = self.open_table(OUTPOINT_TO_ENTRY)?;
         get_txout_by_outpoint(&table, &outpoint)
     }
 
+    fn check_token_history_height(&self, height: u32) -> anyhow::Result<()> {
+        match self.block_height()? {
+            Some(tip) if height <= tip.0 => {}
+            _ => anyhow::bail!("block {} is not indexed yet", height),
+        }
+        let start = self
+            .open_table(STATISTIC_TO_COUNT)?
+            .get(&Statistic::TokenHistoryStartHeight.key())?
+            .map(|start| start.value());
+        match start {
+            Some(start) if height as u64 >= start => Ok(()),
+            Some(start) => anyhow::bail!("token history starts at block {}", start),
+            None => anyhow::bail!("no token history has been indexed"),
+        }
+    }
+
+    fn ord_get_inscription(
+        &self,
+        query: &InscriptionQuery,
+        chain: Chain,
+    ) -> anyhow::Result<Option<InscriptionInfo>> {
+        let sequence_number = match query {
+            InscriptionQuery::Id(id) => self
+                .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+                .get(id.store())?
+                .map(|sequence_number| sequence_number.value()),
+            InscriptionQuery::Number(number) => self
+                .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+                .get(number)?
+                .map(|sequence_number| sequence_number.value()),
+        };
+        match sequence_number {
+            Some(sequence_number) => get_inscription_info(
+                &self.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?,
+                &self.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?,
+                &self.open_table(OUTPOINT_TO_ENTRY)?,
+                sequence_number,
+                chain,
+            ),
+            None => Ok(None),
+        }
+    }
+
+    fn ord_get_inscription_content(
+        &self,
+        id: InscriptionId,
+    ) -> anyhow::Result<Option<InscriptionContent>> {
+        match self
+            .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+            .get(id.store())?
+        {
+            Some(sequence_number) => get_inscription_content(
+                &self.open_table(SEQUENCE_NUMBER_TO_CONTENT)?,
+                sequence_number.value(),
+            ),
+            None => Ok(None),
+        }
+    }
+
+    fn ord_get_inscriptions_on_output(
+        &self,
+        outpoint: OutPoint,
+        chain: Chain,
+    ) -> anyhow::Result<Vec<InscriptionInfo>> {
+        let entry_table = self.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        let satpoint_table = self.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        let outpoint_table = self.open_table(OUTPOINT_TO_ENTRY)?;
+        full_inscriptions_on_output(
+            &self.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?,
+            &entry_table,
+            outpoint,
+        )?
+        .into_iter()
+        .flat_map(|(sequence_number, _, _)| {
+            get_inscription_info(
+                &entry_table,
+                &satpoint_table,
+                &outpoint_table,
+                sequence_number,
+                chain,
+            )
+            .transpose()
+        })
+        .collect()
+    }
+
+    fn ord_get_inscriptions_by_height(
+        &self,
+        height: u32,
+        chain: Chain,
+    ) -> anyhow::Result<Vec<InscriptionInfo>> {
+        let entry_table = self.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        let satpoint_table = self.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        let outpoint_table = self.open_table(OUTPOINT_TO_ENTRY)?;
+        get_sequence_numbers_at_height(&entry_table, height)?
+            .flat_map(|sequence_number| {
+                get_inscription_info(
+                    &entry_table,
+                    &satpoint_table,
+                    &outpoint_table,
+                    sequence_number,
+                    chain,
+                )
+                .transpose()
+            })
+            .collect()
+    }
+
     fn brc20_get_tick_info(&self, name: &Tick) -> anyhow::Result<Option<TokenInfo>> {
         let table = self.open_table(BRC20_TOKEN)?;
         get_token_info(&table, name)
@@ -194,6 +450,43 @@ impl<'a> Rtx for redb::ReadTransaction<'a> {
         get_balances(&table, &script_key)
     }
 
+    fn brc20_get_tick_info_at_height(
+        &self,
+        name: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC20_TOKEN_HISTORY)?;
+        get_token_info_at_height(&table, name, height)
+    }
+
+    fn brc20_get_all_tick_info_at_height(&self, height: u32) -> anyhow::Result<Vec<TokenInfo>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC20_TOKEN_HISTORY)?;
+        get_tokens_info_at_height(&table, height)
+    }
+
+    fn brc20_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC20_BALANCE_HISTORY)?;
+        get_balance_at_height(&table, &script_key, tick, height)
+    }
+
+    fn brc20_get_all_balance_by_address_at_height(
+        &self,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC20_BALANCE_HISTORY)?;
+        get_balances_at_height(&table, &script_key, height)
+    }
+
     fn brc20_transaction_id_to_transaction_receipt(
         &self,
         txid: Txid,
@@ -236,6 +529,26 @@ impl<'a> Rtx for redb::ReadTransaction<'a> {
         get_transferable_assets_by_outpoint(&satpoint_to_sequence_number, outpoint)
     }
 
+    fn brc20_get_address_activity(
+        &self,
+        script_key: ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage> {
+        let activity_table = self.open_table(BRC20_ADDRESS_ACTIVITY)?;
+        let events_table = self.open_table(BRC20_EVENTS)?;
+        get_address_activity(&activity_table, &events_table, &script_key, filter)
+    }
+
+    fn brc20_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage> {
+        let table = self.open_table(BRC20_TICK_HOLDERS)?;
+        get_tick_holders(&table, tick, cursor, limit)
+    }
+
     fn brc21_get_tick_info(&self, name: &Tick) -> anyhow::Result<Option<TokenInfo>> {
         let table = self.open_table(BRC21_TOKEN)?;
         get_token_info(&table, name)
@@ -263,6 +576,43 @@ impl<'a> Rtx for redb::ReadTransaction<'a> {
         get_balances(&table, &script_key)
     }
 
+    fn brc21_get_tick_info_at_height(
+        &self,
+        name: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC21_TOKEN_HISTORY)?;
+        get_token_info_at_height(&table, name, height)
+    }
+
+    fn brc21_get_all_tick_info_at_height(&self, height: u32) -> anyhow::Result<Vec<TokenInfo>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC21_TOKEN_HISTORY)?;
+        get_tokens_info_at_height(&table, height)
+    }
+
+    fn brc21_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC21_BALANCE_HISTORY)?;
+        get_balance_at_height(&table, &script_key, tick, height)
+    }
+
+    fn brc21_get_all_balance_by_address_at_height(
+        &self,
+        script_key: ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>> {
+        self.check_token_history_height(height)?;
+        let table = self.open_table(BRC21_BALANCE_HISTORY)?;
+        get_balances_at_height(&table, &script_key, height)
+    }
+
     fn brc21_transaction_id_to_transaction_receipt(
         &self,
         txid: Txid,
@@ -305,6 +655,27 @@ impl<'a> Rtx for redb::ReadTransaction<'a> {
         get_transferable_assets_by_outpoint(&satpoint_to_sequence_number, outpoint)
     }
 
+    fn brc21_get_address_activity(
+        &self,
+        script_key: ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage> {
+        let activity_table = self.open_table(BRC21_ADDRESS_ACTIVITY)?;
+        // the receipts of every protocol are saved with save_brc20_transaction_receipts
+        let events_table = self.open_table(BRC20_EVENTS)?;
+        get_address_activity(&activity_table, &events_table, &script_key, filter)
+    }
+
+    fn brc21_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage> {
+        let table = self.open_table(BRC21_TICK_HOLDERS)?;
+        get_tick_holders(&table, tick, cursor, limit)
+    }
+
     fn detect_reorg(
         &self,
         client: Arc<Client>,
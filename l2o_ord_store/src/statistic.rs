@@ -13,6 +13,10 @@ pub enum Statistic {
     SatRanges = 10,
     UnboundInscriptions = 11,
     IndexTransactions = 12,
+    // the first height the BRC20/BRC21 history tables know the state at
+    TokenHistoryStartHeight = 13,
+    // set once the BRC20/BRC21 tick holders were indexed from the balances
+    TickHoldersIndexed = 14,
 }
 
 impl Statistic {
@@ -1,25 +1,39 @@
+use std::collections::BTreeMap;
 use std::io;
+use std::ops::Bound;
+use std::ops::Range;
+use std::str::FromStr;
 
 use anyhow::Result;
 use bitcoin::consensus::Decodable;
+use bitcoin::Address;
 use bitcoin::OutPoint;
+use bitcoin::ScriptHash;
 use bitcoin::TxOut;
 use bitcoin::Txid;
 use l2o_macros::define_multimap_table;
 use l2o_macros::define_table;
+use l2o_ord::chain::Chain;
 use l2o_ord::inscription::inscription_id::InscriptionId;
 use l2o_ord::sat_point::SatPoint;
 use l2o_ord::script_key::ScriptKey;
 use l2o_ord::tick::LowerTick;
 use l2o_ord::tick::Tick;
+use l2o_rpc::query::ActivityFilter;
 use redb::MultimapTable;
 use redb::MultimapTableDefinition;
 use redb::ReadableMultimapTable;
 use redb::ReadableTable;
 use redb::Table;
 use redb::TableDefinition;
+use serde::de::DeserializeOwned;
 
+use crate::activity::receipt_tick;
+use crate::activity::Activity;
+use crate::activity::ActivityPage;
+use crate::activity::ActivityPosition;
 use crate::balance::Balance;
+use crate::block_event::BlockEventsLog;
 use crate::entry::Entry;
 use crate::entry::HeaderValue;
 use crate::entry::InscriptionEntry;
@@ -29,12 +43,21 @@ use crate::entry::OutPointValue;
 use crate::entry::SatPointValue;
 use crate::entry::TxidValue;
 use crate::event::Receipt;
+use crate::holder::holders_limit;
+use crate::holder::is_holder;
+use crate::holder::TickHolder;
+use crate::holder::TickHolderValue;
+use crate::holder::TickHoldersPage;
+use crate::inscription_info::charm_titles;
+use crate::inscription_info::InscriptionContent;
+use crate::inscription_info::InscriptionInfo;
 use crate::log::TransferableLog;
 use crate::statistic::Statistic;
 use crate::token_info::TokenInfo;
 
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
+define_table! { HEIGHT_TO_BLOCK_EVENTS, u32, &[u8] }
 
 define_table! { BRC21_DEPOSITS_HOLDING_BALANCES, &[u8], u128 }
 
@@ -49,20 +72,29 @@ define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT, u32, &[u8] }
 
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 
 define_table! { BRC20_BALANCES, &str, &[u8] }
 define_table! { BRC20_TOKEN, &str, &[u8] }
+define_table! { BRC20_BALANCE_HISTORY, &str, &[u8] }
+define_table! { BRC20_TOKEN_HISTORY, &str, &[u8] }
 define_table! { BRC20_EVENTS, &TxidValue, &[u8] }
 define_table! { BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &SatPointValue, &[u8] }
 define_multimap_table! { BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, &str, &SatPointValue }
+define_table! { BRC20_ADDRESS_ACTIVITY, &str, &TxidValue }
+define_table! { BRC20_TICK_HOLDERS, &str, &[u8] }
 
 define_table! { BRC21_BALANCES, &str, &[u8] }
 define_table! { BRC21_TOKEN, &str, &[u8] }
+define_table! { BRC21_BALANCE_HISTORY, &str, &[u8] }
+define_table! { BRC21_TOKEN_HISTORY, &str, &[u8] }
 define_table! { BRC21_EVENTS, &TxidValue, &[u8] }
 define_table! { BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS, &SatPointValue, &[u8] }
 define_multimap_table! { BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, &str, &SatPointValue }
+define_table! { BRC21_ADDRESS_ACTIVITY, &str, &TxidValue }
+define_table! { BRC21_TICK_HOLDERS, &str, &[u8] }
 
 define_table! { KV, &[u8], &[u8] }
 
@@ -80,6 +112,26 @@ fn script_tick_key(script: &ScriptKey, tick: &Tick) -> String {
     format!("{}_{}", script, tick.to_lowercase().hex())
 }
 
+// the inverse of the `ScriptKey` display in keys, which does not show the
+// op_return flag
+fn parse_script_key(script: &str, is_op_return: bool) -> Result<ScriptKey> {
+    Ok(match Address::from_str(script) {
+        Ok(address) => ScriptKey::Address(address),
+        Err(_) => ScriptKey::ScriptHash {
+            script_hash: ScriptHash::from_str(script)?,
+            is_op_return,
+        },
+    })
+}
+
+fn split_script_tick_key(key: &str) -> Result<ScriptKey> {
+    let (script, _) = key
+        .rsplit_once('_')
+        .ok_or_else(|| anyhow::anyhow!("invalid balance key {}", key))?;
+    // balances are not stored with the flag
+    parse_script_key(script, false)
+}
+
 fn min_script_tick_key(script: &ScriptKey) -> String {
     format!("{}_{}", script, LowerTick::min_hex())
 }
@@ -88,6 +140,83 @@ fn max_script_tick_key(script: &ScriptKey) -> String {
     format!("{}_{}", script, LowerTick::max_hex())
 }
 
+// the holders of a tick sort by their overall balance subtracted from
+// u128::MAX, zero padded to its 39 digits, so that the largest balances come
+// first and equal balances by address
+fn tick_holder_key(script: &ScriptKey, balance: &Balance) -> String {
+    format!(
+        "{}_{:039}_{}",
+        balance.tick.to_lowercase().hex(),
+        u128::MAX - balance.overall_balance,
+        script
+    )
+}
+
+// the keys of the history tables are the keys of the current tables followed
+// by the zero padded height of the block that changed the value, so that the
+// changes of one balance or token sort by height
+fn history_key(key: &str, height: u32) -> String {
+    format!("{}_{:010}", key, height)
+}
+
+fn split_history_key(key: &str) -> (&str, u32) {
+    let (key, height) = key.rsplit_once('_').unwrap();
+    (key, height.parse().unwrap())
+}
+
+// the last change of the key made at or before the height, seeking back from
+// the height
+fn get_history_entry_at_height<T, V>(table: &T, key: &str, height: u32) -> Result<Option<V>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+    V: DeserializeOwned,
+{
+    let min_key = history_key(key, 0);
+    let max_key = history_key(key, height);
+    Ok(table
+        .range(min_key.as_str()..=max_key.as_str())?
+        .next_back()
+        .transpose()?
+        .map(|(_, data)| rmp_serde::from_slice::<V>(data.value()).unwrap()))
+}
+
+// the last change of every key from min_key to max_key made at or before the
+// height, found with one seek back per key and one seek on to the next key,
+// so that the other changes of a key are never read
+fn get_history_at_height<T, V>(
+    table: &T,
+    min_key: Option<&str>,
+    max_key: Option<&str>,
+    height: u32,
+) -> Result<Vec<V>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+    V: DeserializeOwned,
+{
+    let max_key = max_key.map(|key| history_key(key, u32::MAX));
+    let upper = match &max_key {
+        Some(max_key) => Bound::Included(max_key.as_str()),
+        None => Bound::Unbounded,
+    };
+    let mut lower = match min_key {
+        Some(min_key) => Bound::Included(history_key(min_key, 0)),
+        None => Bound::Unbounded,
+    };
+    let mut values = Vec::new();
+    loop {
+        let key = match table
+            .range::<&str>((lower.as_ref().map(|key| key.as_str()), upper))?
+            .next()
+        {
+            Some(result) => split_history_key(result?.0.value()).0.to_string(),
+            None => break,
+        };
+        values.extend(get_history_entry_at_height(table, &key, height)?);
+        lower = Bound::Excluded(history_key(&key, u32::MAX));
+    }
+    Ok(values)
+}
+
 // BRC20_BALANCES
 pub fn get_balances<T>(table: &T, script_key: &ScriptKey) -> Result<Vec<Balance>>
 where
@@ -134,6 +263,52 @@ where
         .collect())
 }
 
+// BRC20_BALANCE_HISTORY
+pub fn get_balances_at_height<T>(
+    table: &T,
+    script_key: &ScriptKey,
+    height: u32,
+) -> Result<Vec<Balance>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    get_history_at_height(
+        table,
+        Some(&min_script_tick_key(script_key)),
+        Some(&max_script_tick_key(script_key)),
+        height,
+    )
+}
+
+// BRC20_BALANCE_HISTORY
+pub fn get_balance_at_height<T>(
+    table: &T,
+    script_key: &ScriptKey,
+    tick: &Tick,
+    height: u32,
+) -> Result<Option<Balance>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    get_history_entry_at_height(table, &script_tick_key(script_key, tick), height)
+}
+
+// BRC20_TOKEN_HISTORY
+pub fn get_token_info_at_height<T>(table: &T, tick: &Tick, height: u32) -> Result<Option<TokenInfo>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    get_history_entry_at_height(table, &tick.to_lowercase().hex(), height)
+}
+
+// BRC20_TOKEN_HISTORY
+pub fn get_tokens_info_at_height<T>(table: &T, height: u32) -> Result<Vec<TokenInfo>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    get_history_at_height(table, None, None, height)
+}
+
 // BRC20_EVENTS
 pub fn get_transaction_receipts<T>(table: &T, txid: &Txid) -> Result<Option<Vec<Receipt>>>
 where
@@ -144,6 +319,124 @@ where
         .map(|x| rmp_serde::from_slice::<Vec<Receipt>>(x.value()).unwrap()))
 }
 
+fn address_activity_key(script: &ScriptKey, position: &ActivityPosition) -> String {
+    format!("{}_{}", script, position.to_key())
+}
+
+// BRC20_ADDRESS_ACTIVITY
+// BRC20_EVENTS
+pub fn get_address_activity<T, E>(
+    activity_table: &T,
+    events_table: &E,
+    script: &ScriptKey,
+    filter: &ActivityFilter,
+) -> Result<ActivityPage>
+where
+    T: ReadableTable<&'static str, &'static TxidValue>,
+    E: ReadableTable<&'static TxidValue, &'static [u8]>,
+{
+    let start = match filter.cursor {
+        Some(ref cursor) => Bound::Excluded(address_activity_key(
+            script,
+            &ActivityPosition::from_str(cursor)?,
+        )),
+        None => Bound::Included(format!(
+            "{}_{:010}",
+            script,
+            filter.from_height.unwrap_or(0)
+        )),
+    };
+    // `g` sorts after the digits of every position at the height
+    let end = format!("{}_{:010}_g", script, filter.to_height.unwrap_or(u32::MAX));
+
+    let limit = filter.limit();
+    let mut page = ActivityPage::default();
+    let mut range = activity_table.range::<&str>((
+        start.as_ref().map(String::as_str),
+        Bound::Included(end.as_str()),
+    ))?;
+    for result in range.by_ref() {
+        let (key, txid) = result?;
+        let (_, position) = key.value().split_once('_').unwrap();
+        let position = ActivityPosition::from_str(position)?;
+        let txid = Txid::load(*txid.value());
+        let receipt = get_transaction_receipts(events_table, &txid)?
+            .and_then(|receipts| receipts.into_iter().nth(position.index as usize))
+            .ok_or_else(|| anyhow::anyhow!("missing receipt {} of {}", position.index, txid))?;
+        if !filter.matches(&receipt.op, receipt_tick(&receipt)) {
+            continue;
+        }
+        page.activities.push(Activity {
+            height: position.height,
+            tx_index: position.tx_index,
+            txid,
+            receipt,
+        });
+        if page.activities.len() == limit {
+            page.next_cursor = Some(position.to_key());
+            break;
+        }
+    }
+    // a full page is only followed by another one if there are activities left
+    if page.next_cursor.is_some() && range.next().is_none() {
+        page.next_cursor = None;
+    }
+    Ok(page)
+}
+
+// BRC20_TICK_HOLDERS
+pub fn get_tick_holders<T>(
+    table: &T,
+    tick: &Tick,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+) -> Result<TickHoldersPage>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    let tick_hex = tick.to_lowercase().hex();
+    let start = match cursor {
+        Some(cursor) => {
+            // the key of the last holder of the previous page without the tick
+            let valid = cursor.split_once('_').is_some_and(|(rank, script)| {
+                rank.len() == 39 && rank.parse::<u128>().is_ok() && !script.is_empty()
+            });
+            if !valid {
+                anyhow::bail!("invalid holders cursor {}", cursor);
+            }
+            Bound::Excluded(format!("{}_{}", tick_hex, cursor))
+        }
+        None => Bound::Included(format!("{}_", tick_hex)),
+    };
+    // `g` sorts after the digits of every rank
+    let end = format!("{}_g", tick_hex);
+
+    let limit = holders_limit(limit);
+    let mut page = TickHoldersPage::default();
+    let mut range = table.range::<&str>((
+        start.as_ref().map(String::as_str),
+        Bound::Excluded(end.as_str()),
+    ))?;
+    for result in range.by_ref() {
+        let (key, data) = result?;
+        let (_, cursor) = key.value().split_once('_').unwrap();
+        let (_, script) = cursor.split_once('_').unwrap();
+        let value = rmp_serde::from_slice::<TickHolderValue>(data.value()).unwrap();
+        page.holders.push(TickHolder {
+            script_key: parse_script_key(script, value.is_op_return)?,
+            balance: value.balance,
+        });
+        if page.holders.len() == limit {
+            page.next_cursor = Some(cursor.to_string());
+            break;
+        }
+    }
+    if page.next_cursor.is_some() && range.next().is_none() {
+        page.next_cursor = None;
+    }
+    Ok(page)
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
 pub fn get_transferable_assets_by_account<T, S>(
@@ -262,6 +555,65 @@ pub fn update_token_balance(
     Ok(())
 }
 
+// BRC20_TICK_HOLDERS
+pub fn update_tick_holder(
+    table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+    script_key: &ScriptKey,
+    old_balance: Option<&Balance>,
+    new_balance: &Balance,
+) -> Result<()> {
+    if let Some(old_balance) = old_balance.filter(|balance| is_holder(Some(balance))) {
+        table.remove(tick_holder_key(script_key, old_balance).as_str())?;
+    }
+    if is_holder(Some(new_balance)) {
+        let value = TickHolderValue {
+            is_op_return: matches!(
+                script_key,
+                ScriptKey::ScriptHash {
+                    is_op_return: true,
+                    ..
+                }
+            ),
+            balance: new_balance.clone(),
+        };
+        table.insert(
+            tick_holder_key(script_key, new_balance).as_str(),
+            rmp_serde::to_vec(&value).unwrap().as_slice(),
+        )?;
+    }
+    Ok(())
+}
+
+// BRC20_BALANCES -> BRC20_TICK_HOLDERS
+// returns every held tick with its number of holders
+pub fn index_tick_holders<T>(
+    table: &T,
+    holders: &mut Table<'_, '_, &'static str, &'static [u8]>,
+) -> Result<Vec<(Tick, u64)>>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    let mut counts: BTreeMap<String, (Tick, u64)> = BTreeMap::new();
+    for result in table.range::<&str>(..)? {
+        let (key, data) = result?;
+        let balance = rmp_serde::from_slice::<Balance>(data.value()).unwrap();
+        if !is_holder(Some(&balance)) {
+            continue;
+        }
+        update_tick_holder(
+            holders,
+            &split_script_tick_key(key.value())?,
+            None,
+            &balance,
+        )?;
+        counts
+            .entry(balance.tick.to_lowercase().hex())
+            .or_insert_with(|| (balance.tick.clone(), 0))
+            .1 += 1;
+    }
+    Ok(counts.into_values().collect())
+}
+
 // BRC20_TOKEN
 pub fn insert_token_info(
     table: &mut Table<'_, '_, &'static str, &'static [u8]>,
@@ -295,6 +647,22 @@ pub fn update_mint_token_info(
     Ok(())
 }
 
+// BRC20_TOKEN
+pub fn update_holders_token_info(
+    table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+    tick: &Tick,
+    holders: u64,
+) -> Result<()> {
+    let mut info =
+        get_token_info(table, tick)?.unwrap_or_else(|| panic!("token {} not exist", tick.as_str()));
+    info.holders = holders;
+    table.insert(
+        tick.to_lowercase().hex().as_str(),
+        rmp_serde::to_vec(&info).unwrap().as_slice(),
+    )?;
+    Ok(())
+}
+
 pub fn update_burned_token_info(
     table: &mut Table<'_, '_, &'static str, &'static [u8]>,
     tick: &Tick,
@@ -310,6 +678,65 @@ pub fn update_burned_token_info(
     Ok(())
 }
 
+// BRC20_BALANCE_HISTORY
+pub fn update_token_balance_history(
+    table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+    script_key: &ScriptKey,
+    height: u32,
+    balance: &Balance,
+) -> Result<()> {
+    table.insert(
+        history_key(&script_tick_key(script_key, &balance.tick), height).as_str(),
+        rmp_serde::to_vec(balance).unwrap().as_slice(),
+    )?;
+    Ok(())
+}
+
+// BRC20_TOKEN_HISTORY
+pub fn update_token_info_history(
+    table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+    tick: &Tick,
+    height: u32,
+    info: &TokenInfo,
+) -> Result<()> {
+    table.insert(
+        history_key(&tick.to_lowercase().hex(), height).as_str(),
+        rmp_serde::to_vec(info).unwrap().as_slice(),
+    )?;
+    Ok(())
+}
+
+// BRC20_BALANCES -> BRC20_BALANCE_HISTORY
+// BRC20_TOKEN -> BRC20_TOKEN_HISTORY
+pub fn copy_to_history<T>(
+    table: &T,
+    history: &mut Table<'_, '_, &'static str, &'static [u8]>,
+    height: u32,
+) -> Result<()>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    for result in table.range::<&str>(..)? {
+        let (key, data) = result?;
+        history.insert(history_key(key.value(), height).as_str(), data.value())?;
+    }
+    Ok(())
+}
+
+// BRC20_ADDRESS_ACTIVITY
+pub fn insert_address_activity(
+    table: &mut Table<'_, '_, &'static str, &'static TxidValue>,
+    script: &ScriptKey,
+    position: &ActivityPosition,
+    txid: &Txid,
+) -> Result<()> {
+    table.insert(
+        address_activity_key(script, position).as_str(),
+        &txid.store(),
+    )?;
+    Ok(())
+}
+
 // BRC20_EVENTS
 pub fn save_transaction_receipts(
     table: &mut Table<'_, '_, &'static TxidValue, &'static [u8]>,
@@ -423,6 +850,126 @@ pub fn inscriptions_on_output<'a: 'tx, 'tx>(
     .collect())
 }
 
+// SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY
+// SEQUENCE_NUMBER_TO_SATPOINT
+// OUTPOINT_TO_ENTRY
+pub fn get_inscription_info<E, S, O>(
+    entry_table: &E,
+    satpoint_table: &S,
+    outpoint_table: &O,
+    sequence_number: u32,
+    chain: Chain,
+) -> Result<Option<InscriptionInfo>>
+where
+    E: ReadableTable<u32, InscriptionEntryValue>,
+    S: ReadableTable<u32, &'static SatPointValue>,
+    O: ReadableTable<&'static OutPointValue, &'static [u8]>,
+{
+    let Some(entry) = entry_table.get(sequence_number)? else {
+        return Ok(None);
+    };
+    let entry = InscriptionEntry::load(entry.value());
+    let parent = match entry.parent {
+        Some(parent) => entry_table
+            .get(parent)?
+            .map(|parent| InscriptionEntry::load(parent.value()).id),
+        None => None,
+    };
+    let satpoint = satpoint_table
+        .get(sequence_number)?
+        .map(|satpoint| SatPoint::load(*satpoint.value()))
+        .ok_or_else(|| anyhow::anyhow!("missing satpoint of inscription {}", entry.id))?;
+    let txout = get_txout_by_outpoint(outpoint_table, &satpoint.outpoint)?;
+    Ok(Some(InscriptionInfo {
+        id: entry.id,
+        number: entry.inscription_number,
+        sequence_number,
+        charms: charm_titles(entry.charms),
+        fee: entry.fee,
+        height: entry.height,
+        sat: entry.sat.map(|sat| sat.0),
+        parent,
+        timestamp: entry.timestamp,
+        satpoint,
+        owner: txout
+            .as_ref()
+            .map(|txout| ScriptKey::from_script(&txout.script_pubkey, chain)),
+        value: txout.map(|txout| txout.value.to_sat()),
+    }))
+}
+
+// SEQUENCE_NUMBER_TO_CONTENT
+pub fn get_inscription_content<T>(
+    table: &T,
+    sequence_number: u32,
+) -> Result<Option<InscriptionContent>>
+where
+    T: ReadableTable<u32, &'static [u8]>,
+{
+    Ok(table
+        .get(sequence_number)?
+        .map(|v| rmp_serde::from_slice::<InscriptionContent>(v.value()).unwrap()))
+}
+
+// SEQUENCE_NUMBER_TO_CONTENT
+pub fn insert_inscription_content(
+    table: &mut Table<'_, '_, u32, &'static [u8]>,
+    sequence_number: u32,
+    content: &InscriptionContent,
+) -> Result<()> {
+    table.insert(
+        sequence_number,
+        rmp_serde::to_vec(content).unwrap().as_slice(),
+    )?;
+    Ok(())
+}
+
+// HEIGHT_TO_BLOCK_EVENTS
+pub fn get_block_events_log<T>(table: &T, height: u32) -> Result<Option<BlockEventsLog>>
+where
+    T: ReadableTable<u32, &'static [u8]>,
+{
+    Ok(table
+        .get(height)?
+        .map(|v| rmp_serde::from_slice::<BlockEventsLog>(v.value()).unwrap()))
+}
+
+// HEIGHT_TO_BLOCK_EVENTS
+pub fn insert_block_events_log(
+    table: &mut Table<'_, '_, u32, &'static [u8]>,
+    height: u32,
+    log: &BlockEventsLog,
+) -> Result<()> {
+    table.insert(height, rmp_serde::to_vec(log).unwrap().as_slice())?;
+    Ok(())
+}
+
+// SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY
+// inscriptions are numbered in the order they are indexed in, so the heights
+// of their entries never go down
+pub fn get_sequence_numbers_at_height<T>(table: &T, height: u32) -> Result<Range<u32>>
+where
+    T: ReadableTable<u32, InscriptionEntryValue>,
+{
+    let next = get_next_sequence_number(table)?;
+    let first_at = |height: u32| -> Result<u32> {
+        let (mut low, mut high) = (0, next);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry = table
+                .get(mid)?
+                .ok_or_else(|| anyhow::anyhow!("missing inscription entry {}", mid))?;
+            if InscriptionEntry::load(entry.value()).height < height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    };
+    Ok(first_at(height)?..first_at(height.saturating_add(1))?)
+}
+
 pub fn get_statistic_to_count<T>(table: &T, key: &Statistic) -> Result<u64>
 where
     T: ReadableTable<u64, u64>,
@@ -26,4 +26,50 @@ pub struct TokenInfo {
     pub deployed_number: u32,
     pub deployed_timestamp: u32,
     pub latest_mint_number: u32,
+    // addresses with a non zero overall balance
+    #[serde(default)]
+    pub holders: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::Address;
+
+    use super::*;
+
+    #[test]
+    fn test_token_info_without_holders() {
+        let info = TokenInfo {
+            tick: Tick::from_str("ordi").unwrap(),
+            inscription_id: InscriptionId::from_str(
+                "1111111111111111111111111111111111111111111111111111111111111111i0",
+            )
+            .unwrap(),
+            inscription_number: 1,
+            supply: 21_000_000,
+            burned_supply: 0,
+            minted: 1_000,
+            limit_per_mint: 1_000,
+            decimal: 18,
+            deploy_by: ScriptKey::Address(
+                Address::from_str("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM").unwrap(),
+            ),
+            is_self_mint: false,
+            deployed_number: 830_000,
+            deployed_timestamp: 1_700_000_000,
+            latest_mint_number: 830_001,
+            holders: 7,
+        };
+        // token infos written before the holders were counted are arrays
+        // without the last field
+        let mut data = rmp_serde::to_vec(&info).unwrap();
+        assert_eq!((data[0], data.pop()), (0x9e, Some(7)));
+        data[0] = 0x9d;
+        assert_eq!(
+            rmp_serde::from_slice::<TokenInfo>(&data).unwrap(),
+            TokenInfo { holders: 0, ..info }
+        );
+    }
 }
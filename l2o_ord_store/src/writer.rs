@@ -1,39 +1,167 @@
+use bitcoin::Transaction;
 use bitcoin::Txid;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::standards::l2o_a::superchain::bitcoin_merkle_branch;
+use l2o_ord::inscription::inscription::Inscription;
+use l2o_ord::operation::l2o_a::L2OAOperation;
+use l2o_ord::operation::Operation;
 use l2o_ord::operation::ProtocolType;
 use l2o_ord::sat_point::SatPoint;
 use l2o_ord::script_key::ScriptKey;
 use l2o_ord::tick::Tick;
+use l2o_store::core::location::L2OABlockInclusion;
+use l2o_store::core::traits::L2OStoreReaderV1;
+use l2o_store::core::traits::L2OStoreV1;
+use redb::ReadableTable;
 
+use crate::activity::ActivityPosition;
 use crate::balance::Balance;
+use crate::block_event::BlockEventsLog;
+use crate::block_event::L2OABlockEvent;
 use crate::ctx::Context;
 use crate::event::Receipt;
+use crate::executor::ExecutionMessage;
+use crate::holder::is_holder;
+use crate::inscription_info::InscriptionContent;
 use crate::log::TransferableLog;
+use crate::statistic::Statistic;
+use crate::table::copy_to_history;
+use crate::table::get_balance;
+use crate::table::get_token_info;
+use crate::table::index_tick_holders;
+use crate::table::insert_address_activity;
+use crate::table::insert_block_events_log;
+use crate::table::insert_inscription_content;
 use crate::table::insert_token_info;
 use crate::table::insert_transferable_asset;
 use crate::table::remove_transferable_asset;
 use crate::table::save_transaction_receipts;
 use crate::table::update_brc21_deposits_holding_balance;
 use crate::table::update_burned_token_info;
+use crate::table::update_holders_token_info;
 use crate::table::update_mint_token_info;
+use crate::table::update_statistic_to_count;
+use crate::table::update_tick_holder;
 use crate::table::update_token_balance;
+use crate::table::update_token_balance_history;
+use crate::table::update_token_info_history;
 use crate::token_info::TokenInfo;
 
 impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
+    // The history tables only log changes, so the first block indexed with them
+    // starts them with a copy of the state before it.
+    pub fn start_token_history(&mut self) -> anyhow::Result<()> {
+        if self
+            .statistic_to_count
+            .get(&Statistic::TokenHistoryStartHeight.key())?
+            .is_some()
+        {
+            return Ok(());
+        }
+        let height = self.chain_ctx.blockheight.saturating_sub(1);
+        copy_to_history(&*self.brc20_balances, self.brc20_balance_history, height)?;
+        copy_to_history(&*self.brc20_token, self.brc20_token_history, height)?;
+        copy_to_history(&*self.brc21_balances, self.brc21_balance_history, height)?;
+        copy_to_history(&*self.brc21_token, self.brc21_token_history, height)?;
+        update_statistic_to_count(
+            self.statistic_to_count,
+            &Statistic::TokenHistoryStartHeight,
+            height as u64,
+        )
+    }
+
+    // The tick holders are indexed as the balances change, the first block
+    // indexed with them indexes the balances from before it.
+    pub fn start_tick_holders(&mut self) -> anyhow::Result<()> {
+        if self
+            .statistic_to_count
+            .get(&Statistic::TickHoldersIndexed.key())?
+            .is_some()
+        {
+            return Ok(());
+        }
+        let height = self.chain_ctx.blockheight.saturating_sub(1);
+        for ptype in [ProtocolType::BRC20, ProtocolType::BRC21] {
+            let (balances, tick_holders, token, token_history) = match ptype {
+                ProtocolType::BRC20 => (
+                    &*self.brc20_balances,
+                    &mut *self.brc20_tick_holders,
+                    &mut *self.brc20_token,
+                    &mut *self.brc20_token_history,
+                ),
+                ProtocolType::BRC21 => (
+                    &*self.brc21_balances,
+                    &mut *self.brc21_tick_holders,
+                    &mut *self.brc21_token,
+                    &mut *self.brc21_token_history,
+                ),
+                ProtocolType::L2OA => unreachable!(),
+            };
+            for (tick, holders) in index_tick_holders(balances, tick_holders)? {
+                update_holders_token_info(token, &tick, holders)?;
+                let info = get_token_info(&*token, &tick)?
+                    .unwrap_or_else(|| panic!("token {} not exist", tick.as_str()));
+                update_token_info_history(token_history, &tick, height, &info)?;
+            }
+        }
+        update_statistic_to_count(self.statistic_to_count, &Statistic::TickHoldersIndexed, 1)
+    }
+
     pub fn update_token_balance(
         &mut self,
         script_key: &ScriptKey,
         new_balance: Balance,
         ptype: ProtocolType,
     ) -> anyhow::Result<()> {
-        match ptype {
-            ProtocolType::BRC20 => {
-                update_token_balance(self.brc20_balances, script_key, new_balance)
-            }
-            ProtocolType::BRC21 => {
-                update_token_balance(self.brc21_balances, script_key, new_balance)
-            }
+        let height = self.chain_ctx.blockheight;
+        let (balances, balance_history, tick_holders) = match ptype {
+            ProtocolType::BRC20 => (
+                &mut *self.brc20_balances,
+                &mut *self.brc20_balance_history,
+                &mut *self.brc20_tick_holders,
+            ),
+            ProtocolType::BRC21 => (
+                &mut *self.brc21_balances,
+                &mut *self.brc21_balance_history,
+                &mut *self.brc21_tick_holders,
+            ),
             ProtocolType::L2OA => unreachable!(),
+        };
+        let old_balance = get_balance(&*balances, script_key, &new_balance.tick)?;
+        let was_holder = is_holder(old_balance.as_ref());
+        let holds = is_holder(Some(&new_balance));
+        let tick = new_balance.tick.clone();
+
+        update_tick_holder(tick_holders, script_key, old_balance.as_ref(), &new_balance)?;
+        update_token_balance_history(balance_history, script_key, height, &new_balance)?;
+        update_token_balance(balances, script_key, new_balance)?;
+        if was_holder != holds {
+            self.update_token_holders(&tick, holds, ptype)?;
         }
+        Ok(())
+    }
+
+    // counts an address that started or stopped holding the tick
+    fn update_token_holders(
+        &mut self,
+        tick: &Tick,
+        holds: bool,
+        ptype: ProtocolType,
+    ) -> anyhow::Result<()> {
+        let token = match ptype {
+            ProtocolType::BRC20 => &mut *self.brc20_token,
+            ProtocolType::BRC21 => &mut *self.brc21_token,
+            ProtocolType::L2OA => unreachable!(),
+        };
+        let holders = get_token_info(&*token, tick)?
+            .unwrap_or_else(|| panic!("token {} not exist", tick.as_str()))
+            .holders;
+        let holders = match holds {
+            true => holders + 1,
+            false => holders.saturating_sub(1),
+        };
+        update_holders_token_info(token, tick, holders)?;
+        self.copy_token_info_to_history(tick, ptype)
     }
 
     pub fn insert_token_info(
@@ -42,9 +170,16 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         new_info: &TokenInfo,
         ptype: ProtocolType,
     ) -> anyhow::Result<()> {
+        let height = self.chain_ctx.blockheight;
         match ptype {
-            ProtocolType::BRC20 => insert_token_info(self.brc20_token, tick, new_info),
-            ProtocolType::BRC21 => insert_token_info(self.brc21_token, tick, new_info),
+            ProtocolType::BRC20 => {
+                update_token_info_history(self.brc20_token_history, tick, height, new_info)?;
+                insert_token_info(self.brc20_token, tick, new_info)
+            }
+            ProtocolType::BRC21 => {
+                update_token_info_history(self.brc21_token_history, tick, height, new_info)?;
+                insert_token_info(self.brc21_token, tick, new_info)
+            }
             ProtocolType::L2OA => unreachable!(),
         }
     }
@@ -58,13 +193,14 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
     ) -> anyhow::Result<()> {
         match ptype {
             ProtocolType::BRC20 => {
-                update_mint_token_info(self.brc20_token, tick, minted_amt, minted_block_number)
+                update_mint_token_info(self.brc20_token, tick, minted_amt, minted_block_number)?
             }
             ProtocolType::BRC21 => {
-                update_mint_token_info(self.brc21_token, tick, minted_amt, minted_block_number)
+                update_mint_token_info(self.brc21_token, tick, minted_amt, minted_block_number)?
             }
             ProtocolType::L2OA => unreachable!(),
         }
+        self.copy_token_info_to_history(tick, ptype)
     }
 
     pub fn update_burned_token_info(
@@ -74,10 +210,27 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         ptype: ProtocolType,
     ) -> anyhow::Result<()> {
         match ptype {
-            ProtocolType::BRC20 => update_burned_token_info(self.brc20_token, tick, burned_amt),
-            ProtocolType::BRC21 => update_burned_token_info(self.brc21_token, tick, burned_amt),
+            ProtocolType::BRC20 => update_burned_token_info(self.brc20_token, tick, burned_amt)?,
+            ProtocolType::BRC21 => update_burned_token_info(self.brc21_token, tick, burned_amt)?,
             ProtocolType::L2OA => unreachable!(),
         }
+        self.copy_token_info_to_history(tick, ptype)
+    }
+
+    // records the token info as it is after an update at the current height
+    fn copy_token_info_to_history(
+        &mut self,
+        tick: &Tick,
+        ptype: ProtocolType,
+    ) -> anyhow::Result<()> {
+        let (token, history) = match ptype {
+            ProtocolType::BRC20 => (&*self.brc20_token, &mut *self.brc20_token_history),
+            ProtocolType::BRC21 => (&*self.brc21_token, &mut *self.brc21_token_history),
+            ProtocolType::L2OA => unreachable!(),
+        };
+        let info = get_token_info(token, tick)?
+            .unwrap_or_else(|| panic!("token {} not exist", tick.as_str()));
+        update_token_info_history(history, tick, self.chain_ctx.blockheight, &info)
     }
 
     pub fn save_transaction_receipts(
@@ -93,6 +246,119 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         }
     }
 
+    // indexes the receipts of a transaction under their senders and receivers,
+    // the activities point at the receipts saved for the transaction
+    pub fn index_address_activity(
+        &mut self,
+        tx_index: u32,
+        txid: &Txid,
+        receipts: &[Receipt],
+    ) -> anyhow::Result<()> {
+        for (index, receipt) in receipts.iter().enumerate() {
+            let table = match receipt.op.p_type() {
+                ProtocolType::BRC20 => &mut *self.brc20_address_activity,
+                ProtocolType::BRC21 => &mut *self.brc21_address_activity,
+                ProtocolType::L2OA => continue,
+            };
+            let position = ActivityPosition {
+                height: self.chain_ctx.blockheight,
+                tx_index,
+                index: index as u32,
+            };
+            insert_address_activity(table, &receipt.from, &position, txid)?;
+            if receipt.to != receipt.from {
+                insert_address_activity(table, &receipt.to, &position, txid)?;
+            }
+        }
+        Ok(())
+    }
+
+    // an L2O-A block is accepted when its location was reported, rejected
+    // blocks leave the location of their bitcoin block number as it was
+    pub fn l2o_a_block_event(
+        &mut self,
+        msg: &ExecutionMessage,
+        receipt: &Receipt,
+    ) -> anyhow::Result<Option<L2OABlockEvent>> {
+        let Operation::L2OA(L2OAOperation::Block(ref block)) = msg.op else {
+            return Ok(None);
+        };
+        let accepted = receipt.result.is_ok()
+            && self
+                .kv
+                .get_block_inscription_location(block.l2id, block.bitcoin_block_number)
+                .is_ok_and(|location| location.inscription_id == msg.inscription_id);
+        Ok(Some(L2OABlockEvent {
+            txid: msg.txid,
+            inscription_id: msg.inscription_id,
+            l2id: block.l2id,
+            l2_block_number: block.l2_block_number,
+            bitcoin_block_number: block.bitcoin_block_number,
+            accepted,
+        }))
+    }
+
+    // the transaction of an accepted block with its branch in the bitcoin
+    // block, for superchain proof bundles
+    pub fn report_l2o_a_block_inclusion(
+        &mut self,
+        event: &L2OABlockEvent,
+        transaction: &Transaction,
+        transaction_index: u32,
+        txids: &[Hash256],
+    ) -> anyhow::Result<()> {
+        if !event.accepted {
+            return Ok(());
+        }
+        let inclusion = L2OABlockInclusion {
+            height: self.chain_ctx.blockheight,
+            transaction_index,
+            transaction: transaction.clone(),
+            transaction_branch: bitcoin_merkle_branch(txids, transaction_index as usize)?,
+        };
+        self.kv.report_block_inscription_inclusion(
+            event.l2id,
+            event.bitcoin_block_number,
+            inclusion,
+        )
+    }
+
+    // blocks without receipts are not stored
+    pub fn save_block_events(&mut self, log: &BlockEventsLog) -> anyhow::Result<()> {
+        if log.is_empty() {
+            return Ok(());
+        }
+        insert_block_events_log(self.height_to_block_events, self.chain_ctx.blockheight, log)
+    }
+
+    // bodies over the content size limit of the chain are not stored
+    pub fn insert_inscription_content(
+        &mut self,
+        sequence_number: u32,
+        inscription: &Inscription,
+    ) -> anyhow::Result<()> {
+        let Some(body) = inscription.body() else {
+            return Ok(());
+        };
+        if self
+            .chain_ctx
+            .chain
+            .inscription_content_size_limit()
+            .is_some_and(|limit| body.len() > limit)
+        {
+            return Ok(());
+        }
+        insert_inscription_content(
+            self.sequence_number_to_content,
+            sequence_number,
+            &InscriptionContent {
+                content_type: inscription.content_type.clone(),
+                content_encoding: inscription.content_encoding.clone(),
+                body: body.to_vec(),
+            },
+        )
+    }
+
     pub fn insert_transferable_asset(
         &mut self,
         satpoint: SatPoint,
@@ -141,7 +407,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         script_key: &ScriptKey,
         new_balance: Balance,
     ) -> anyhow::Result<()> {
-        update_token_balance(self.brc20_balances, script_key, new_balance)
+        self.update_token_balance(script_key, new_balance, ProtocolType::BRC20)
     }
 
     pub fn insert_brc20_token_info(
@@ -149,7 +415,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         tick: &Tick,
         new_info: &TokenInfo,
     ) -> anyhow::Result<()> {
-        insert_token_info(self.brc20_token, tick, new_info)
+        self.insert_token_info(tick, new_info, ProtocolType::BRC20)
     }
 
     pub fn update_brc20_mint_token_info(
@@ -158,7 +424,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         minted_amt: u128,
         minted_block_number: u32,
     ) -> anyhow::Result<()> {
-        update_mint_token_info(self.brc20_token, tick, minted_amt, minted_block_number)
+        self.update_mint_token_info(tick, minted_amt, minted_block_number, ProtocolType::BRC20)
     }
 
     pub fn update_brc20_burned_token_info(
@@ -166,7 +432,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         tick: &Tick,
         burned_amt: u128,
     ) -> anyhow::Result<()> {
-        update_burned_token_info(self.brc20_token, tick, burned_amt)
+        self.update_burned_token_info(tick, burned_amt, ProtocolType::BRC20)
     }
 
     pub fn save_brc20_transaction_receipts(
@@ -203,7 +469,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         script_key: &ScriptKey,
         new_balance: Balance,
     ) -> anyhow::Result<()> {
-        update_token_balance(self.brc21_balances, script_key, new_balance)
+        self.update_token_balance(script_key, new_balance, ProtocolType::BRC21)
     }
 
     pub fn insert_brc21_token_info(
@@ -211,7 +477,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         tick: &Tick,
         new_info: &TokenInfo,
     ) -> anyhow::Result<()> {
-        insert_token_info(self.brc21_token, tick, new_info)
+        self.insert_token_info(tick, new_info, ProtocolType::BRC21)
     }
 
     pub fn update_brc21_mint_token_info(
@@ -220,7 +486,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         minted_amt: u128,
         minted_block_number: u32,
     ) -> anyhow::Result<()> {
-        update_mint_token_info(self.brc21_token, tick, minted_amt, minted_block_number)
+        self.update_mint_token_info(tick, minted_amt, minted_block_number, ProtocolType::BRC21)
     }
 
     pub fn update_brc21_burned_token_info(
@@ -228,7 +494,7 @@ impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
         tick: &Tick,
         burned_amt: u128,
     ) -> anyhow::Result<()> {
-        update_burned_token_info(self.brc21_token, tick, burned_amt)
+        self.update_burned_token_info(tick, burned_amt, ProtocolType::BRC21)
     }
 
     pub fn save_brc21_transaction_receipts(
@@ -17,11 +17,15 @@ use bitcoin::TxOut;
 use bitcoin::Txid;
 use bitcoincore_rpc::Client;
 use bitcoincore_rpc::RpcApi;
+use l2o_common::common::data::hash::Hash256;
+use l2o_common::metrics::BLOCK_INDEX_SECONDS;
 use l2o_ord::action::Action;
 use l2o_ord::height::Height;
 use l2o_ord::inscription::envelope::ParsedEnvelope;
 use l2o_ord::inscription::inscription::Inscription;
 use l2o_ord::inscription::inscription_id::InscriptionId;
+use l2o_ord::operation::l2o_a::L2OAOperation;
+use l2o_ord::operation::Operation;
 use l2o_ord::rarity::Rarity;
 use l2o_ord::sat::Sat;
 use l2o_ord::sat_point::SatPoint;
@@ -31,6 +35,7 @@ use redb::ReadableTable;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::block_event::BlockEventsLog;
 use crate::charm::Charm;
 use crate::ctx::ChainContext;
 use crate::ctx::Context;
@@ -51,17 +56,26 @@ use crate::table::get_transferable_assets_by_outpoint;
 use crate::table::get_txout_by_outpoint;
 use crate::table::inscriptions_on_output;
 use crate::table::update_statistic_to_count;
+use crate::table::BRC20_ADDRESS_ACTIVITY;
 use crate::table::BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
 use crate::table::BRC20_BALANCES;
+use crate::table::BRC20_BALANCE_HISTORY;
 use crate::table::BRC20_EVENTS;
 use crate::table::BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS;
+use crate::table::BRC20_TICK_HOLDERS;
 use crate::table::BRC20_TOKEN;
+use crate::table::BRC20_TOKEN_HISTORY;
+use crate::table::BRC21_ADDRESS_ACTIVITY;
 use crate::table::BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS;
 use crate::table::BRC21_BALANCES;
+use crate::table::BRC21_BALANCE_HISTORY;
 use crate::table::BRC21_DEPOSITS_HOLDING_BALANCES;
 use crate::table::BRC21_EVENTS;
 use crate::table::BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS;
+use crate::table::BRC21_TICK_HOLDERS;
 use crate::table::BRC21_TOKEN;
+use crate::table::BRC21_TOKEN_HISTORY;
+use crate::table::HEIGHT_TO_BLOCK_EVENTS;
 use crate::table::HEIGHT_TO_BLOCK_HEADER;
 use crate::table::HEIGHT_TO_LAST_SEQUENCE_NUMBER;
 use crate::table::INSCRIPTION_ID_TO_SEQUENCE_NUMBER;
@@ -72,6 +86,7 @@ use crate::table::OUTPOINT_TO_SAT_RANGES;
 use crate::table::SATPOINT_TO_SEQUENCE_NUMBER;
 use crate::table::SAT_TO_SATPOINT;
 use crate::table::SAT_TO_SEQUENCE_NUMBER;
+use crate::table::SEQUENCE_NUMBER_TO_CONTENT;
 use crate::table::SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY;
 use crate::table::SEQUENCE_NUMBER_TO_SATPOINT;
 use crate::table::STATISTIC_TO_COUNT;
@@ -148,6 +163,10 @@ pub struct InscriptionOp {
     pub new_satpoint: Option<SatPoint>,
 }
 
+// the receipts of a block by their op and outcome, the labels of RECEIPTS,
+// counted once the block is committed
+pub type ReceiptCounts = BTreeMap<(String, &'static str), u64>;
+
 pub trait Wtx {
     fn index_block(
         &self,
@@ -155,7 +174,7 @@ pub trait Wtx {
         block: BlockData,
         sender: &SyncSender<OutPoint>,
         receiver: &Receiver<TxOut>,
-    ) -> anyhow::Result<()>;
+    ) -> anyhow::Result<ReceiptCounts>;
 
     fn index_envelopes<'a, 'db, 'txn>(
         &self,
@@ -189,7 +208,7 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
         block: BlockData,
         sender: &SyncSender<OutPoint>,
         receiver: &Receiver<TxOut>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<ReceiptCounts> {
         tracing::info!("indexing block: {}", chain_ctx.blockheight);
         let mut operations = HashMap::<Txid, Vec<InscriptionOp>>::new();
         let mut tx_out_cache = SimpleLru::<OutPoint, TxOut>::new(10000000);
@@ -245,6 +264,7 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
 
             height_to_block_header: &mut self.open_table(HEIGHT_TO_BLOCK_HEADER)?,
             height_to_last_sequence_number: &mut self.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?,
+            height_to_block_events: &mut self.open_table(HEIGHT_TO_BLOCK_EVENTS)?,
 
             brc21_deposits_holding_balances: &mut self
                 .open_table(BRC21_DEPOSITS_HOLDING_BALANCES)?,
@@ -264,31 +284,48 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
             sequence_number_to_inscription_entry: &mut self
                 .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?,
             sequence_number_to_satpoint: &mut self.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?,
+            sequence_number_to_content: &mut self.open_table(SEQUENCE_NUMBER_TO_CONTENT)?,
 
             statistic_to_count: &mut self.open_table(STATISTIC_TO_COUNT)?,
 
             brc20_balances: &mut self.open_table(BRC20_BALANCES)?,
             brc20_token: &mut self.open_table(BRC20_TOKEN)?,
+            brc20_balance_history: &mut self.open_table(BRC20_BALANCE_HISTORY)?,
+            brc20_token_history: &mut self.open_table(BRC20_TOKEN_HISTORY)?,
             brc20_events: &mut self.open_table(BRC20_EVENTS)?,
             brc20_satpoint_to_transferable_assets: &mut self
                 .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
             brc20_address_ticker_to_transferable_assets: &mut self
                 .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+            brc20_address_activity: &mut self.open_table(BRC20_ADDRESS_ACTIVITY)?,
+            brc20_tick_holders: &mut self.open_table(BRC20_TICK_HOLDERS)?,
 
             brc21_balances: &mut self.open_table(BRC21_BALANCES)?,
             brc21_token: &mut self.open_table(BRC21_TOKEN)?,
+            brc21_balance_history: &mut self.open_table(BRC21_BALANCE_HISTORY)?,
+            brc21_token_history: &mut self.open_table(BRC21_TOKEN_HISTORY)?,
             brc21_events: &mut self.open_table(BRC21_EVENTS)?,
             brc21_satpoint_to_transferable_assets: &mut self
                 .open_table(BRC21_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
             brc21_address_ticker_to_transferable_assets: &mut self
                 .open_multimap_table(BRC21_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+            brc21_address_activity: &mut self.open_table(BRC21_ADDRESS_ACTIVITY)?,
+            brc21_tick_holders: &mut self.open_table(BRC21_TICK_HOLDERS)?,
+
+            verified_l2o_a_signatures: HashSet::new(),
         };
 
         let ctx_mut = &mut ctx;
+        ctx_mut.start_tick_holders()?;
+        ctx_mut.start_token_history()?;
 
+        let mut block_events = BlockEventsLog::default();
         let mut reward = Height(ctx_mut.chain_ctx.blockheight).subsidy();
         let mut lost_sats =
             get_statistic_to_count(ctx_mut.statistic_to_count, &Statistic::LostSats)?;
+        let timer = BLOCK_INDEX_SECONDS
+            .with_label_values(&["index_envelopes"])
+            .start_timer();
         for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
             self.index_envelopes(
                 tx,
@@ -301,9 +338,38 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                 &mut lost_sats,
             )?;
         }
+        timer.observe_duration();
 
         // skip the coinbase transaction.
-        for (tx, txid) in block.txdata.iter() {
+        let timer = BLOCK_INDEX_SECONDS
+            .with_label_values(&["execution"])
+            .start_timer();
+        if ctx_mut.chain_ctx.blockheight >= chain_ctx.chain.first_brc20_height() {
+            // new inscriptions resolve without the transferable assets
+            let l2o_a_blocks = block
+                .txdata
+                .iter()
+                .filter(|(tx, _)| {
+                    !tx.input
+                        .first()
+                        .is_some_and(|tx_in| tx_in.previous_output.is_null())
+                })
+                .filter_map(|(_, txid)| operations.get(txid))
+                .flatten()
+                .filter(|operation| matches!(operation.action, Action::New { .. }))
+                .filter_map(|operation| Message::resolve(operation, HashMap::new()).ok()?)
+                .filter_map(|msg| match msg.op {
+                    Operation::L2OA(L2OAOperation::Block(block)) => Some(block),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            ExecutionMessage::verify_l2o_a_block_signatures(ctx_mut, &l2o_a_blocks)?;
+        }
+
+        let mut receipt_counts = ReceiptCounts::new();
+        // read once the first l2o-a block is accepted, for merkle branches
+        let mut txids = Vec::new();
+        for (tx_index, (tx, txid)) in block.txdata.iter().enumerate() {
             // skip coinbase transaction.
             if tx
                 .input
@@ -379,8 +445,37 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                         let msg =
                             ExecutionMessage::from_message(ctx_mut, msg, ctx_mut.chain_ctx.chain)?;
                         let receipt = ExecutionMessage::execute(ctx_mut, &msg)?;
+                        *receipt_counts
+                            .entry((
+                                receipt.op.to_string(),
+                                if receipt.result.is_ok() {
+                                    "ok"
+                                } else {
+                                    "error"
+                                },
+                            ))
+                            .or_default() += 1;
+                        if let Some(event) = ctx_mut.l2o_a_block_event(&msg, &receipt)? {
+                            if event.accepted && txids.is_empty() {
+                                txids = block
+                                    .txdata
+                                    .iter()
+                                    .map(|(_, txid)| Ok(Hash256::from_hex(&txid.to_string())?))
+                                    .collect::<anyhow::Result<Vec<_>>>()?;
+                            }
+                            ctx_mut.report_l2o_a_block_inclusion(
+                                &event,
+                                tx,
+                                tx_index as u32,
+                                &txids,
+                            )?;
+                            block_events.l2o_a_blocks.push(event);
+                        }
                         receipts.push(receipt);
                     }
+                    if !receipts.is_empty() {
+                        block_events.txids.push(*txid);
+                    }
 
                     ctx_mut
                         .save_brc20_transaction_receipts(txid, &receipts)
@@ -389,6 +484,7 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                                 "failed to add transaction receipt to state! error: {e}"
                             )
                         })?;
+                    ctx_mut.index_address_activity(tx_index as u32, txid, &receipts)?;
 
                     // let _brc20_inscriptions = receipts
                     //     .into_iter()
@@ -406,11 +502,13 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                 }?;
             }
         }
+        timer.observe_duration();
 
+        ctx.save_block_events(&block_events)?;
         ctx.height_to_block_header
             .insert(ctx.chain_ctx.blockheight, &block.header.store())?;
 
-        Ok(())
+        Ok(receipt_counts)
     }
 
     fn index_envelopes(
@@ -747,7 +845,7 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                 pointer: _,
                 reinscription,
                 unbound,
-                inscription: _,
+                ref inscription,
                 vindicated,
             } => {
                 let inscription_number = if cursed {
@@ -869,6 +967,10 @@ impl<'a> Wtx for redb::WriteTransaction<'a> {
                 ctx.inscription_id_to_sequence_number
                     .insert(&inscription_id.store(), sequence_number)?;
 
+                if ctx.chain_ctx.index_content {
+                    ctx.insert_inscription_content(sequence_number, inscription)?;
+                }
+
                 (unbound, sequence_number)
             }
         };
@@ -985,3 +1087,113 @@ fn unbound_outpoint() -> OutPoint {
         vout: 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+    use bitcoin::BlockHash;
+    use bitcoin::CompactTarget;
+    use bitcoin::TxIn;
+    use bitcoin::TxMerkleNode;
+    use bitcoincore_rpc::Auth;
+    use l2o_ord::chain::Chain;
+    use l2o_ord::test_helpers::address;
+    use l2o_ord::test_helpers::outpoint;
+    use l2o_ord::test_helpers::tx_in;
+    use l2o_ord::test_helpers::tx_out;
+    use redb::Database;
+
+    use super::*;
+    use crate::rtx::Rtx;
+
+    // indexes a block with an inscription of body and returns the body stored
+    // for it
+    fn index_inscription(chain: Chain, index_content: bool, body: &[u8]) -> Option<Vec<u8>> {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("index.redb")).unwrap();
+        let inscription = Inscription::new(Some(b"text/plain".to_vec()), Some(body.to_vec()));
+        let coinbase = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![tx_in(OutPoint::null())],
+            output: vec![tx_out(0, address())],
+        };
+        let reveal = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                witness: inscription.to_witness(),
+                ..tx_in(outpoint(1))
+            }],
+            output: vec![tx_out(10_000, address())],
+        };
+        let inscription_id = InscriptionId {
+            txid: reveal.txid(),
+            index: 0,
+        };
+        let block = BlockData {
+            header: Header {
+                version: bitcoin::block::Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: 0,
+                bits: CompactTarget::from_consensus(0),
+                nonce: 0,
+            },
+            txdata: [coinbase, reveal]
+                .into_iter()
+                .map(|tx| {
+                    let txid = tx.txid();
+                    (tx, txid)
+                })
+                .collect(),
+        };
+
+        // the output the inscription spends is fetched over the channels
+        let (sender, _outpoints) = mpsc::sync_channel(1);
+        let (tx_outs, receiver) = mpsc::channel();
+        tx_outs.send(tx_out(10_000, address())).unwrap();
+        let chain_ctx = ChainContext {
+            chain,
+            blockheight: chain.first_inscription_height(),
+            blocktime: 0,
+            index_content,
+            bitcoin_rpc: Arc::new(Client::new("http://127.0.0.1:1", Auth::None).unwrap()),
+        };
+        let wtx = db.begin_write().unwrap();
+        wtx.index_block(chain_ctx, block, &sender, &receiver)
+            .unwrap();
+        wtx.commit().unwrap();
+
+        let content = db
+            .begin_read()
+            .unwrap()
+            .ord_get_inscription_content(inscription_id)
+            .unwrap();
+        content.map(|content| content.body)
+    }
+
+    #[test]
+    fn test_index_content() {
+        assert_eq!(index_inscription(Chain::Regtest, false, b"hello"), None);
+        assert_eq!(
+            index_inscription(Chain::Regtest, true, b"hello"),
+            Some(b"hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_inscription_content_size_limit() {
+        let body = vec![0x61; 1024];
+        assert_eq!(index_inscription(Chain::Testnet, true, &body), Some(body));
+        assert_eq!(index_inscription(Chain::Testnet, true, &[0x61; 1025]), None);
+        // mainnet has no limit
+        assert_eq!(
+            index_inscription(Chain::Mainnet, true, &[0x61; 1025]),
+            Some(vec![0x61; 1025])
+        );
+    }
+}
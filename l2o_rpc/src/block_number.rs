@@ -1,6 +1,10 @@
 use std::fmt;
+use std::marker::PhantomData;
 use std::str::FromStr;
 
+use serde::de;
+use serde::de::value::UnitDeserializer;
+use serde::de::Visitor;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
@@ -59,8 +63,17 @@ impl<'de> Deserialize<'de> for BlockNumber {
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?.to_lowercase();
-        s.parse().map_err(serde::de::Error::custom)
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum BlockNumberRepr {
+            Number(u32),
+            Tag(String),
+        }
+
+        match BlockNumberRepr::deserialize(deserializer)? {
+            BlockNumberRepr::Number(num) => Ok(BlockNumber::Number(num)),
+            BlockNumberRepr::Tag(s) => s.to_lowercase().parse().map_err(serde::de::Error::custom),
+        }
     }
 }
 
@@ -91,3 +104,174 @@ impl fmt::Display for BlockNumber {
         }
     }
 }
+
+/// Params of a method that can also answer as of a past block height, sent
+/// either as the plain params or as `[params, at_height]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtHeight<T> {
+    pub params: T,
+    pub at_height: BlockNumber,
+}
+
+impl<T> AtHeight<T> {
+    pub fn latest(params: T) -> Self {
+        Self {
+            params,
+            at_height: BlockNumber::Latest,
+        }
+    }
+
+    pub fn at(params: T, height: u32) -> Self {
+        Self {
+            params,
+            at_height: BlockNumber::Number(height),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for AtHeight<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.at_height {
+            BlockNumber::Latest => self.params.serialize(serializer),
+            BlockNumber::Number(_) => (&self.params, self.at_height).serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for AtHeight<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // missing and null params stay the plain params of methods without any
+        deserializer.deserialize_option(AtHeightVisitor(PhantomData))
+    }
+}
+
+struct AtHeightVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for AtHeightVisitor<T> {
+    type Value = AtHeight<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("params or [params, at_height]")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(UnitDeserializer::new()).map(AtHeight::latest)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum AtHeightRepr<T> {
+            At(T, BlockNumber),
+            Latest(T),
+        }
+
+        Ok(match AtHeightRepr::deserialize(deserializer)? {
+            AtHeightRepr::At(params, at_height) => AtHeight { params, at_height },
+            AtHeightRepr::Latest(params) => AtHeight::latest(params),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::request::RequestParams;
+    use crate::request::RpcRequest;
+
+    #[test]
+    fn test_block_number() {
+        for (value, number) in [
+            (json!("latest"), BlockNumber::Latest),
+            (json!("0x10"), BlockNumber::Number(16)),
+            (json!("16"), BlockNumber::Number(16)),
+            (json!(16), BlockNumber::Number(16)),
+        ] {
+            assert_eq!(
+                serde_json::from_value::<BlockNumber>(value).unwrap(),
+                number
+            );
+        }
+    }
+
+    #[test]
+    fn test_at_height_params() {
+        let request = |params| {
+            let request = serde_json::from_value::<RpcRequest>(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "brc20_getBalanceByAddress",
+                "params": params,
+            }))
+            .unwrap();
+            match request.request {
+                RequestParams::BRC20GetBalanceByAddress(params) => {
+                    assert_eq!(params.params.0.as_str(), "ordi");
+                    params.at_height
+                }
+                _ => unreachable!(),
+            }
+        };
+        let address = "bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4";
+        assert_eq!(request(json!(["ordi", address])), BlockNumber::Latest);
+        assert_eq!(
+            request(json!([["ordi", address], 840000])),
+            BlockNumber::Number(840000)
+        );
+        assert_eq!(
+            request(json!([["ordi", address], "latest"])),
+            BlockNumber::Latest
+        );
+
+        let all_tick_info = |params| {
+            serde_json::from_value::<RpcRequest>(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "brc21_getAllTickInfo",
+                "params": params,
+            }))
+            .map(|request| match request.request {
+                RequestParams::BRC21GetAllTickInfo(params) => params.at_height,
+                _ => unreachable!(),
+            })
+        };
+        assert_eq!(all_tick_info(json!(null)).unwrap(), BlockNumber::Latest);
+        assert!(serde_json::from_value::<RpcRequest>(
+            json!({"jsonrpc": "2.0", "id": 1, "method": "brc21_getAllTickInfo"})
+        )
+        .is_ok());
+        assert_eq!(
+            all_tick_info(json!([null, "0x10"])).unwrap(),
+            BlockNumber::Number(16)
+        );
+
+        for at_height in [AtHeight::latest(()), AtHeight::at((), 16)] {
+            let json = serde_json::to_value(&at_height).unwrap();
+            assert_eq!(
+                serde_json::from_value::<AtHeight<()>>(json).unwrap(),
+                at_height
+            );
+        }
+    }
+}
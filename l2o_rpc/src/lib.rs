@@ -1,4 +1,5 @@
 // FORKED from anvil rpc
 pub mod block_number;
+pub mod query;
 pub mod request;
 pub mod response;
@@ -0,0 +1,49 @@
+use l2o_ord::inscription::inscription_id::InscriptionId;
+use l2o_ord::operation::OperationType;
+use l2o_ord::tick::Tick;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub const DEFAULT_ACTIVITY_LIMIT: usize = 100;
+pub const MAX_ACTIVITY_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ActivityFilter {
+    pub tick: Option<Tick>,
+    pub op: Option<OperationType>,
+    pub from_height: Option<u32>,
+    pub to_height: Option<u32>,
+    // next_cursor of the previous page
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl ActivityFilter {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_ACTIVITY_LIMIT)
+            .clamp(1, MAX_ACTIVITY_LIMIT)
+    }
+
+    // tick is the tick of the receipt, if it has one
+    pub fn matches(&self, op: &OperationType, tick: Option<Tick>) -> bool {
+        if self.op.as_ref().is_some_and(|filter_op| filter_op != op) {
+            return false;
+        }
+        match self.tick {
+            Some(ref filter_tick) => {
+                tick.is_some_and(|tick| tick.to_lowercase() == filter_tick.to_lowercase())
+            }
+            None => true,
+        }
+    }
+}
+
+// an inscription by its id, or by its number
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum InscriptionQuery {
+    Id(InscriptionId),
+    Number(i32),
+}
@@ -6,6 +6,10 @@ use l2o_ord::tick::Tick;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::block_number::AtHeight;
+use crate::query::ActivityFilter;
+use crate::query::InscriptionQuery;
+
 /// Represents the version of the RPC protocol
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Version {
@@ -35,15 +39,26 @@ pub enum RequestParams {
     L2OGetMerkleProofStateRootAtBlock((u64, u64, L2OAHashFunction)),
     #[serde(rename = "l2o_getSuperchainStateRootAtBlock")]
     L2OGetSuperchainStateRootAtBlock((u64, L2OAHashFunction)),
+    #[serde(rename = "l2o_getSuperchainDeltaProof")]
+    L2OGetSuperchainDeltaProof((u64, u64, L2OAHashFunction)),
+    #[serde(rename = "l2o_getSuperchainProofBundle")]
+    L2OGetSuperchainProofBundle((u64, u64, L2OAHashFunction)),
+    // ord
+    #[serde(rename = "ord_getInscription")]
+    OrdGetInscription(InscriptionQuery),
+    #[serde(rename = "ord_getInscriptionsOnOutput")]
+    OrdGetInscriptionsOnOutput(OutPoint),
+    #[serde(rename = "ord_getInscriptionsByHeight")]
+    OrdGetInscriptionsByHeight(u32),
     // BRC20
     #[serde(rename = "brc20_getTickInfo")]
-    BRC20GetTickInfo(Tick),
+    BRC20GetTickInfo(AtHeight<Tick>),
     #[serde(rename = "brc20_getAllTickInfo")]
-    BRC20GetAllTickInfo,
+    BRC20GetAllTickInfo(AtHeight<()>),
     #[serde(rename = "brc20_getBalanceByAddress")]
-    BRC20GetBalanceByAddress((Tick, ScriptKey)),
+    BRC20GetBalanceByAddress(AtHeight<(Tick, ScriptKey)>),
     #[serde(rename = "brc20_getAllBalanceByAddress")]
-    BRC20GetAllBalanceByAddress(ScriptKey),
+    BRC20GetAllBalanceByAddress(AtHeight<ScriptKey>),
     #[serde(rename = "brc20_transactionIdToTransactionReceipt")]
     BRC20TransactionIdToTransactionReceipt(Txid),
     #[serde(rename = "brc20_getTickTransferableByAddress")]
@@ -52,16 +67,20 @@ pub enum RequestParams {
     BRC20GetAllTransferableByAddress(ScriptKey),
     #[serde(rename = "brc20_transferableAssetsOnOutputWithSatpoints")]
     BRC20TransferableAssetsOnOutputWithSatpoints(OutPoint),
+    #[serde(rename = "brc20_getAddressActivity")]
+    BRC20GetAddressActivity((ScriptKey, ActivityFilter)),
+    #[serde(rename = "brc20_getTickHolders")]
+    BRC20GetTickHolders((Tick, Option<String>, Option<usize>)),
 
     // BRC21
     #[serde(rename = "brc21_getTickInfo")]
-    BRC21GetTickInfo(Tick),
+    BRC21GetTickInfo(AtHeight<Tick>),
     #[serde(rename = "brc21_getAllTickInfo")]
-    BRC21GetAllTickInfo,
+    BRC21GetAllTickInfo(AtHeight<()>),
     #[serde(rename = "brc21_getBalanceByAddress")]
-    BRC21GetBalanceByAddress((Tick, ScriptKey)),
+    BRC21GetBalanceByAddress(AtHeight<(Tick, ScriptKey)>),
     #[serde(rename = "brc21_getAllBalanceByAddress")]
-    BRC21GetAllBalanceByAddress(ScriptKey),
+    BRC21GetAllBalanceByAddress(AtHeight<ScriptKey>),
     #[serde(rename = "brc21_transactionIdToTransactionReceipt")]
     BRC21TransactionIdToTransactionReceipt(Txid),
     #[serde(rename = "brc21_getTickTransferableByAddress")]
@@ -70,6 +89,10 @@ pub enum RequestParams {
     BRC21GetAllTransferableByAddress(ScriptKey),
     #[serde(rename = "brc21_transferableAssetsOnOutputWithSatpoints")]
     BRC21TransferableAssetsOnOutputWithSatpoints(OutPoint),
+    #[serde(rename = "brc21_getAddressActivity")]
+    BRC21GetAddressActivity((ScriptKey, ActivityFilter)),
+    #[serde(rename = "brc21_getTickHolders")]
+    BRC21GetTickHolders((Tick, Option<String>, Option<usize>)),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1,14 +1,37 @@
+pub mod quorum;
+
+use std::time::Duration;
+
 use async_trait::async_trait;
+use bitcoin::OutPoint;
+use bitcoin::Txid;
 use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::merkle::core::DeltaMerkleProofCore;
 use l2o_crypto::hash::merkle::core::MerkleProofCore;
+use l2o_crypto::standards::l2o_a::superchain::L2OASuperchainProofBundle;
 use l2o_macros::rpc_call;
 use l2o_ord::operation::l2o_a::L2OABlockV1;
 use l2o_ord::operation::l2o_a::L2OADeployV1;
 use l2o_ord::operation::l2o_a::L2OAHashFunction;
+use l2o_ord::sat_point::SatPoint;
+use l2o_ord::script_key::ScriptKey;
+use l2o_ord::tick::Tick;
+use l2o_ord_store::activity::ActivityPage;
+use l2o_ord_store::balance::Balance;
+use l2o_ord_store::event::Receipt;
+use l2o_ord_store::holder::TickHoldersPage;
+use l2o_ord_store::inscription_info::InscriptionInfo;
+use l2o_ord_store::log::TransferableLog;
+use l2o_ord_store::token_info::TokenInfo;
+use l2o_rpc::block_number::AtHeight;
+use l2o_rpc::query::ActivityFilter;
+use l2o_rpc::query::InscriptionQuery;
 use l2o_rpc::request::Id;
 use l2o_rpc::request::RequestParams;
 use l2o_rpc::request::RpcRequest;
 use l2o_rpc::request::Version;
+use l2o_rpc::response::ResponseResult;
+use l2o_rpc::response::RpcResponse;
 use reqwest::Client;
 use serde_json::Value;
 
@@ -33,18 +56,278 @@ pub trait L2OAProvider {
         block_number: u64,
         hash: L2OAHashFunction,
     ) -> anyhow::Result<MerkleProofCore<Hash256>>;
+    async fn get_superchain_delta_proof(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>>;
+    async fn get_superchain_proof_bundle(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<L2OASuperchainProofBundle>;
+}
+
+#[async_trait]
+pub trait OrdProvider {
+    async fn get_inscription(
+        &self,
+        query: &InscriptionQuery,
+    ) -> anyhow::Result<Option<InscriptionInfo>>;
+    async fn get_inscriptions_on_output(
+        &self,
+        outpoint: &OutPoint,
+    ) -> anyhow::Result<Vec<InscriptionInfo>>;
+    async fn get_inscriptions_by_height(&self, height: u32)
+        -> anyhow::Result<Vec<InscriptionInfo>>;
+}
+
+#[async_trait]
+pub trait BRC20Provider {
+    async fn brc20_get_tick_info(&self, tick: &Tick) -> anyhow::Result<Option<TokenInfo>>;
+    async fn brc20_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>>;
+    async fn brc20_get_balance_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Option<Balance>>;
+    async fn brc20_get_all_balance_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<Balance>>;
+    async fn brc20_get_tick_info_at_height(
+        &self,
+        tick: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>>;
+    async fn brc20_get_all_tick_info_at_height(
+        &self,
+        height: u32,
+    ) -> anyhow::Result<Vec<TokenInfo>>;
+    async fn brc20_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>>;
+    async fn brc20_get_all_balance_by_address_at_height(
+        &self,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>>;
+    async fn brc20_transaction_id_to_transaction_receipt(
+        &self,
+        txid: Txid,
+    ) -> anyhow::Result<Option<Vec<Receipt>>>;
+    async fn brc20_get_tick_transferable_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc20_get_all_transferable_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc20_transferable_assets_on_output_with_satpoints(
+        &self,
+        outpoint: OutPoint,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc20_get_address_activity(
+        &self,
+        script_key: &ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage>;
+    async fn brc20_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage>;
+}
+
+#[async_trait]
+pub trait BRC21Provider {
+    async fn brc21_get_tick_info(&self, tick: &Tick) -> anyhow::Result<Option<TokenInfo>>;
+    async fn brc21_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>>;
+    async fn brc21_get_balance_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Option<Balance>>;
+    async fn brc21_get_all_balance_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<Balance>>;
+    async fn brc21_get_tick_info_at_height(
+        &self,
+        tick: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>>;
+    async fn brc21_get_all_tick_info_at_height(
+        &self,
+        height: u32,
+    ) -> anyhow::Result<Vec<TokenInfo>>;
+    async fn brc21_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>>;
+    async fn brc21_get_all_balance_by_address_at_height(
+        &self,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>>;
+    async fn brc21_transaction_id_to_transaction_receipt(
+        &self,
+        txid: Txid,
+    ) -> anyhow::Result<Option<Vec<Receipt>>>;
+    async fn brc21_get_tick_transferable_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc21_get_all_transferable_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc21_transferable_assets_on_output_with_satpoints(
+        &self,
+        outpoint: OutPoint,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>>;
+    async fn brc21_get_address_activity(
+        &self,
+        script_key: &ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage>;
+    async fn brc21_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage>;
+}
+
+#[derive(Clone, Debug)]
+pub struct ProviderConfig {
+    // per request, including reading the response
+    pub timeout: Duration,
+    // rounds over all the urls after the first one failed
+    pub retries: u32,
+    // wait before the first retry, doubled after every round
+    pub backoff: Duration,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
 }
 
 pub struct Provider {
-    url: String,
+    urls: Vec<String>,
     client: Client,
+    config: ProviderConfig,
 }
 
 impl Provider {
-    pub fn new(url: String) -> Self {
-        Self {
-            url: url,
-            client: Client::new(),
+    pub fn new(url: String) -> anyhow::Result<Self> {
+        Self::with_config(vec![url], ProviderConfig::default())
+    }
+
+    // the urls are tried in order, later ones are only used when the earlier
+    // ones fail
+    pub fn with_config(urls: Vec<String>, config: ProviderConfig) -> anyhow::Result<Self> {
+        if urls.is_empty() {
+            anyhow::bail!("a provider needs at least one url");
+        }
+        let client = Client::builder().timeout(config.timeout).build()?;
+        Ok(Self {
+            urls,
+            client,
+            config,
+        })
+    }
+
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    // only transport errors, timeouts and 5xx responses are retried, an rpc
+    // error is the answer of a healthy indexer and is returned at once
+    pub async fn call(&self, request: RequestParams) -> anyhow::Result<Value> {
+        let request = RpcRequest {
+            jsonrpc: Version::V2,
+            request,
+            id: Id::Number(1),
+        };
+        let mut backoff = self.config.backoff;
+        let mut last_err = None;
+        for round in 0..=self.config.retries {
+            if round > 0 {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            for url in self.urls.iter() {
+                match self.call_url(url, &request).await {
+                    Ok(result) => return Ok(result),
+                    Err(CallError::Fatal(err)) => return Err(err),
+                    Err(CallError::Retry(err)) => {
+                        tracing::warn!("rpc call to {} failed: {}", url, err);
+                        last_err = Some(err);
+                    }
+                }
+            }
+        }
+        Err(last_err.unwrap().context(format!(
+            "rpc call failed after {} retries",
+            self.config.retries
+        )))
+    }
+
+    async fn call_url(&self, url: &str, request: &RpcRequest) -> Result<Value, CallError> {
+        let response = self
+            .client
+            .post(url)
+            .json(request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(CallError::from)?
+            .json::<RpcResponse>()
+            .await
+            .map_err(CallError::from)?;
+        match response.result {
+            ResponseResult::Success(result) => Ok(result),
+            ResponseResult::Error(err) => Err(CallError::Fatal(anyhow::anyhow!(
+                "rpc error: {}",
+                err.message
+            ))),
+        }
+    }
+}
+
+enum CallError {
+    Retry(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+impl From<reqwest::Error> for CallError {
+    fn from(err: reqwest::Error) -> Self {
+        let retry = match err.status() {
+            Some(status) => status.is_server_error(),
+            None => !err.is_decode() && !err.is_builder(),
+        };
+        if retry {
+            Self::Retry(err.into())
+        } else {
+            Self::Fatal(err.into())
         }
     }
 }
@@ -104,4 +387,398 @@ impl L2OAProvider for Provider {
             MerkleProofCore<Hash256>
         )
     }
+
+    async fn get_superchain_delta_proof(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetSuperchainDeltaProof((l2id, block_number, hash)),
+            DeltaMerkleProofCore<Hash256>
+        )
+    }
+
+    async fn get_superchain_proof_bundle(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<L2OASuperchainProofBundle> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetSuperchainProofBundle((l2id, block_number, hash)),
+            L2OASuperchainProofBundle
+        )
+    }
+}
+
+#[async_trait]
+impl OrdProvider for Provider {
+    async fn get_inscription(
+        &self,
+        query: &InscriptionQuery,
+    ) -> anyhow::Result<Option<InscriptionInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::OrdGetInscription(query.clone()),
+            Option<InscriptionInfo>
+        )
+    }
+
+    async fn get_inscriptions_on_output(
+        &self,
+        outpoint: &OutPoint,
+    ) -> anyhow::Result<Vec<InscriptionInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::OrdGetInscriptionsOnOutput(*outpoint),
+            Vec<InscriptionInfo>
+        )
+    }
+
+    async fn get_inscriptions_by_height(
+        &self,
+        height: u32,
+    ) -> anyhow::Result<Vec<InscriptionInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::OrdGetInscriptionsByHeight(height),
+            Vec<InscriptionInfo>
+        )
+    }
+}
+
+#[async_trait]
+impl BRC20Provider for Provider {
+    async fn brc20_get_tick_info(&self, tick: &Tick) -> anyhow::Result<Option<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetTickInfo(AtHeight::latest(tick.clone())),
+            Option<TokenInfo>
+        )
+    }
+
+    async fn brc20_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAllTickInfo(AtHeight::latest(())),
+            Vec<TokenInfo>
+        )
+    }
+
+    async fn brc20_get_balance_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Option<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetBalanceByAddress(AtHeight::latest((
+                tick.clone(),
+                script_key.clone()
+            ))),
+            Option<Balance>
+        )
+    }
+
+    async fn brc20_get_all_balance_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAllBalanceByAddress(AtHeight::latest(script_key.clone())),
+            Vec<Balance>
+        )
+    }
+
+    async fn brc20_get_tick_info_at_height(
+        &self,
+        tick: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetTickInfo(AtHeight::at(tick.clone(), height)),
+            Option<TokenInfo>
+        )
+    }
+
+    async fn brc20_get_all_tick_info_at_height(
+        &self,
+        height: u32,
+    ) -> anyhow::Result<Vec<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAllTickInfo(AtHeight::at((), height)),
+            Vec<TokenInfo>
+        )
+    }
+
+    async fn brc20_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetBalanceByAddress(AtHeight::at(
+                (tick.clone(), script_key.clone()),
+                height
+            )),
+            Option<Balance>
+        )
+    }
+
+    async fn brc20_get_all_balance_by_address_at_height(
+        &self,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAllBalanceByAddress(AtHeight::at(script_key.clone(), height)),
+            Vec<Balance>
+        )
+    }
+
+    async fn brc20_transaction_id_to_transaction_receipt(
+        &self,
+        txid: Txid,
+    ) -> anyhow::Result<Option<Vec<Receipt>>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20TransactionIdToTransactionReceipt(txid),
+            Option<Vec<Receipt>>
+        )
+    }
+
+    async fn brc20_get_tick_transferable_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetTickTransferableByAddress((tick.clone(), script_key.clone())),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc20_get_all_transferable_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAllTransferableByAddress(script_key.clone()),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc20_transferable_assets_on_output_with_satpoints(
+        &self,
+        outpoint: OutPoint,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20TransferableAssetsOnOutputWithSatpoints(outpoint),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc20_get_address_activity(
+        &self,
+        script_key: &ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetAddressActivity((script_key.clone(), filter.clone())),
+            ActivityPage
+        )
+    }
+
+    async fn brc20_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage> {
+        rpc_call!(
+            self,
+            RequestParams::BRC20GetTickHolders((tick.clone(), cursor, limit)),
+            TickHoldersPage
+        )
+    }
+}
+
+#[async_trait]
+impl BRC21Provider for Provider {
+    async fn brc21_get_tick_info(&self, tick: &Tick) -> anyhow::Result<Option<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetTickInfo(AtHeight::latest(tick.clone())),
+            Option<TokenInfo>
+        )
+    }
+
+    async fn brc21_get_all_tick_info(&self) -> anyhow::Result<Vec<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAllTickInfo(AtHeight::latest(())),
+            Vec<TokenInfo>
+        )
+    }
+
+    async fn brc21_get_balance_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Option<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetBalanceByAddress(AtHeight::latest((
+                tick.clone(),
+                script_key.clone()
+            ))),
+            Option<Balance>
+        )
+    }
+
+    async fn brc21_get_all_balance_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAllBalanceByAddress(AtHeight::latest(script_key.clone())),
+            Vec<Balance>
+        )
+    }
+
+    async fn brc21_get_tick_info_at_height(
+        &self,
+        tick: &Tick,
+        height: u32,
+    ) -> anyhow::Result<Option<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetTickInfo(AtHeight::at(tick.clone(), height)),
+            Option<TokenInfo>
+        )
+    }
+
+    async fn brc21_get_all_tick_info_at_height(
+        &self,
+        height: u32,
+    ) -> anyhow::Result<Vec<TokenInfo>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAllTickInfo(AtHeight::at((), height)),
+            Vec<TokenInfo>
+        )
+    }
+
+    async fn brc21_get_balance_by_address_at_height(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Option<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetBalanceByAddress(AtHeight::at(
+                (tick.clone(), script_key.clone()),
+                height
+            )),
+            Option<Balance>
+        )
+    }
+
+    async fn brc21_get_all_balance_by_address_at_height(
+        &self,
+        script_key: &ScriptKey,
+        height: u32,
+    ) -> anyhow::Result<Vec<Balance>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAllBalanceByAddress(AtHeight::at(script_key.clone(), height)),
+            Vec<Balance>
+        )
+    }
+
+    async fn brc21_transaction_id_to_transaction_receipt(
+        &self,
+        txid: Txid,
+    ) -> anyhow::Result<Option<Vec<Receipt>>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21TransactionIdToTransactionReceipt(txid),
+            Option<Vec<Receipt>>
+        )
+    }
+
+    async fn brc21_get_tick_transferable_by_address(
+        &self,
+        tick: &Tick,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetTickTransferableByAddress((tick.clone(), script_key.clone())),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc21_get_all_transferable_by_address(
+        &self,
+        script_key: &ScriptKey,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAllTransferableByAddress(script_key.clone()),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc21_transferable_assets_on_output_with_satpoints(
+        &self,
+        outpoint: OutPoint,
+    ) -> anyhow::Result<Vec<(SatPoint, TransferableLog)>> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21TransferableAssetsOnOutputWithSatpoints(outpoint),
+            Vec<(SatPoint, TransferableLog)>
+        )
+    }
+
+    async fn brc21_get_address_activity(
+        &self,
+        script_key: &ScriptKey,
+        filter: &ActivityFilter,
+    ) -> anyhow::Result<ActivityPage> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetAddressActivity((script_key.clone(), filter.clone())),
+            ActivityPage
+        )
+    }
+
+    async fn brc21_get_tick_holders(
+        &self,
+        tick: &Tick,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<TickHoldersPage> {
+        rpc_call!(
+            self,
+            RequestParams::BRC21GetTickHolders((tick.clone(), cursor, limit)),
+            TickHoldersPage
+        )
+    }
 }
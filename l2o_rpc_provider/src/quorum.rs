@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use futures::future::join_all;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::merkle::core::DeltaMerkleProofCore;
+use l2o_crypto::hash::merkle::core::MerkleProofCore;
+use l2o_crypto::standards::l2o_a::superchain::L2OASuperchainProofBundle;
+use l2o_macros::rpc_call;
+use l2o_ord::operation::l2o_a::L2OABlockV1;
+use l2o_ord::operation::l2o_a::L2OADeployV1;
+use l2o_ord::operation::l2o_a::L2OAHashFunction;
+use l2o_rpc::request::RequestParams;
+use serde_json::Value;
+
+use crate::L2OAProvider;
+use crate::Provider;
+
+// Asks several independent indexers. The calls a sequencer builds a block on,
+// get_superchainroot_at_block and get_last_block_inscription, must get the
+// same answer from at least `quorum` indexers and from every indexer that
+// answered at all; everything else fails over to the next indexer.
+pub struct QuorumProvider {
+    providers: Vec<Provider>,
+    quorum: usize,
+}
+
+impl QuorumProvider {
+    pub fn new(providers: Vec<Provider>, quorum: usize) -> anyhow::Result<Self> {
+        if quorum == 0 || quorum > providers.len() {
+            anyhow::bail!(
+                "quorum must be between 1 and the {} indexers, got {}",
+                providers.len(),
+                quorum
+            );
+        }
+        Ok(Self { providers, quorum })
+    }
+
+    pub async fn call(&self, request: RequestParams) -> anyhow::Result<Value> {
+        let mut last_err = None;
+        for provider in self.providers.iter() {
+            match provider.call(request.clone()).await {
+                Ok(result) => return Ok(result),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.unwrap())
+    }
+
+    pub async fn call_agreed(&self, request: RequestParams) -> anyhow::Result<Value> {
+        let responses = join_all(
+            self.providers
+                .iter()
+                .map(|provider| provider.call(request.clone())),
+        )
+        .await;
+
+        let mut agreed: Option<(&str, Value)> = None;
+        let mut answered = 0;
+        for (provider, response) in self.providers.iter().zip(responses) {
+            let url = provider.urls()[0].as_str();
+            let result = match response {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::warn!("indexer {} did not answer {:?}: {}", url, request, err);
+                    continue;
+                }
+            };
+            answered += 1;
+            match agreed {
+                Some((first_url, ref first)) if *first != result => anyhow::bail!(
+                    "indexers disagree on {:?}: {} returned {}, {} returned {}",
+                    request,
+                    first_url,
+                    first,
+                    url,
+                    result
+                ),
+                Some(_) => {}
+                None => agreed = Some((url, result)),
+            }
+        }
+
+        match agreed {
+            Some((_, result)) if answered >= self.quorum => Ok(result),
+            _ => anyhow::bail!(
+                "only {} of {} indexers answered {:?}, the quorum is {}",
+                answered,
+                self.providers.len(),
+                request,
+                self.quorum
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl L2OAProvider for QuorumProvider {
+    async fn get_last_block_inscription(&self, l2id: u64) -> anyhow::Result<L2OABlockV1> {
+        let result = self
+            .call_agreed(RequestParams::L2OGetLastBlockInscription(l2id))
+            .await?;
+        Ok(serde_json::from_value::<L2OABlockV1>(result)?)
+    }
+
+    async fn get_deploy_inscription(&self, l2id: u64) -> anyhow::Result<L2OADeployV1> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetDeployInscription(l2id),
+            L2OADeployV1
+        )
+    }
+
+    async fn get_state_root_at_block(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<Hash256> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetStateRootAtBlock((l2id, block_number, hash)),
+            Hash256
+        )
+    }
+
+    async fn get_superchainroot_at_block(
+        &self,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<Hash256> {
+        let result = self
+            .call_agreed(RequestParams::L2OGetSuperchainStateRootAtBlock((
+                block_number,
+                hash,
+            )))
+            .await?;
+        Ok(serde_json::from_value::<Hash256>(result)?)
+    }
+
+    async fn get_merkle_proof_state_root_at_block(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<MerkleProofCore<Hash256>> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetMerkleProofStateRootAtBlock((l2id, block_number, hash)),
+            MerkleProofCore<Hash256>
+        )
+    }
+
+    async fn get_superchain_delta_proof(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetSuperchainDeltaProof((l2id, block_number, hash)),
+            DeltaMerkleProofCore<Hash256>
+        )
+    }
+
+    async fn get_superchain_proof_bundle(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<L2OASuperchainProofBundle> {
+        rpc_call!(
+            self,
+            RequestParams::L2OGetSuperchainProofBundle((l2id, block_number, hash)),
+            L2OASuperchainProofBundle
+        )
+    }
+}
@@ -0,0 +1,29 @@
+use bitcoin::Transaction;
+use bitcoin::Txid;
+use l2o_common::common::data::hash::Hash256;
+use l2o_derive::KVQSerializable;
+use l2o_ord::inscription::inscription_id::InscriptionId;
+use serde::Deserialize;
+use serde::Serialize;
+
+// Where the L2O-A block of an L2 for a bitcoin block was inscribed, with the
+// state root it started from, which is the one in the superchain root it was
+// checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, KVQSerializable)]
+#[kvq(value, codec = json)]
+pub struct L2OABlockLocation {
+    pub txid: Txid,
+    pub inscription_id: InscriptionId,
+    pub start_state_root: Hash256,
+}
+
+// The transaction that inscribed the L2O-A block of an L2 for a bitcoin block,
+// with its merkle branch in the bitcoin block at height that included it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, KVQSerializable)]
+#[kvq(value, codec = json)]
+pub struct L2OABlockInclusion {
+    pub height: u32,
+    pub transaction_index: u32,
+    pub transaction: Transaction,
+    pub transaction_branch: Vec<Hash256>,
+}
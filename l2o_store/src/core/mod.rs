@@ -1,4 +1,6 @@
+pub mod location;
 pub mod store;
 pub mod table_key;
 pub mod tables;
 pub mod traits;
+pub mod trees;
@@ -4,100 +4,27 @@ use kvq::traits::KVQBinaryStoreReader;
 use kvq::traits::KVQStoreAdapter;
 use kvq::traits::KVQStoreAdapterReader;
 use l2o_common::common::data::hash::Hash256;
-use l2o_crypto::fields::goldilocks::hash::hash256_to_goldilocks_hash;
-use l2o_crypto::fields::goldilocks::hash::GHashOut;
-use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
-use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
-use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
-use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+use l2o_crypto::hash::merkle::core::DeltaMerkleProofCore;
 use l2o_crypto::hash::merkle::core::MerkleProofCore;
-use l2o_crypto::hash::merkle::store::key::KVQAppendOnlyMerkleKey;
-use l2o_crypto::hash::merkle::store::key::KVQMerkleNodeKey;
-use l2o_crypto::hash::merkle::store::key::KVQTreeIdentifier;
 use l2o_crypto::hash::merkle::store::key::KVQTreeNodePosition;
-use l2o_crypto::hash::merkle::store::model::KVQAppendOnlyMerkleTreeModel;
-use l2o_crypto::hash::merkle::store::model::KVQMerkleTreeModel;
-use l2o_crypto::hash::traits::L2OHash;
-use l2o_macros::get_state;
-use l2o_macros::set_state;
-use l2o_ord::hasher::L2ODepositHasher;
+use l2o_ord::hasher::get_l2_deposit_hash_with;
 use l2o_ord::operation::brc21::l2deposit::L2Deposit;
 use l2o_ord::operation::l2o_a::L2OABlockV1;
 use l2o_ord::operation::l2o_a::L2OADeployV1;
 use l2o_ord::operation::l2o_a::L2OAHashFunction;
 
+use super::location::L2OABlockInclusion;
+use super::location::L2OABlockLocation;
+use super::tables::L2OBlockInclusionsKey;
+use super::tables::L2OBlockLocationsKey;
 use super::tables::L2ODeploymentsKey;
 use super::tables::L2OLatestBlockKey;
-use super::tables::L2OStateRootsMerkleNodeKey;
-use super::tables::SUB_TABLE_L2_STATE_ROOTS_BLAKE3;
-use super::tables::SUB_TABLE_L2_STATE_ROOTS_KECCACK256;
-use super::tables::SUB_TABLE_L2_STATE_ROOTS_POSEIDON_GOLDILOCKS;
-use super::tables::SUB_TABLE_L2_STATE_ROOTS_SHA256;
-use super::tables::TABLE_L2_STATE_ROOTS;
+use super::tables::L2OPendingDepositsKey;
+use super::tables::L2OStateRootDeltasKey;
 use super::traits::L2OStoreV1;
-use crate::core::tables::L2OBRC21DepositsKey;
-use crate::core::tables::SUB_TABLE_L2_BRC21_DEPOSITS_SHA256;
-use crate::core::tables::TABLE_L2_BRC21_DEPOSITS;
+use super::trees::L2O_TREES;
 use crate::core::traits::L2OStoreReaderV1;
 
-const TREE_HEIGHT: u8 = 32;
-
-pub const SHA256_STATE_ROOT_TREE_ID: KVQTreeIdentifier =
-    KVQTreeIdentifier::new(SUB_TABLE_L2_STATE_ROOTS_SHA256, 0, 0);
-type Sha256StateRootTree<S> = KVQMerkleTreeModel<
-    TABLE_L2_STATE_ROOTS,
-    TREE_HEIGHT,
-    false,
-    S,
-    Hash256,
-    Sha256Hasher,
-    KVQStandardAdapter<S, L2OStateRootsMerkleNodeKey, Hash256>,
->;
-pub const KECCAK256_STATE_ROOT_TREE_ID: KVQTreeIdentifier =
-    KVQTreeIdentifier::new(SUB_TABLE_L2_STATE_ROOTS_KECCACK256, 0, 0);
-type Keccak256StateRootTree<S> = KVQMerkleTreeModel<
-    TABLE_L2_STATE_ROOTS,
-    TREE_HEIGHT,
-    false,
-    S,
-    Hash256,
-    Keccak256Hasher,
-    KVQStandardAdapter<S, L2OStateRootsMerkleNodeKey, Hash256>,
->;
-pub const BLAKE3_STATE_ROOT_TREE_ID: KVQTreeIdentifier =
-    KVQTreeIdentifier::new(SUB_TABLE_L2_STATE_ROOTS_BLAKE3, 0, 0);
-type Blake3StateRootTree<S> = KVQMerkleTreeModel<
-    TABLE_L2_STATE_ROOTS,
-    TREE_HEIGHT,
-    false,
-    S,
-    Hash256,
-    Blake3Hasher,
-    KVQStandardAdapter<S, L2OStateRootsMerkleNodeKey, Hash256>,
->;
-pub const POSEIDONGOLDILOCKS_STATE_ROOT_TREE_ID: KVQTreeIdentifier =
-    KVQTreeIdentifier::new(SUB_TABLE_L2_STATE_ROOTS_POSEIDON_GOLDILOCKS, 0, 0);
-type PoseidonGoldilocksStateRootTree<S> = KVQMerkleTreeModel<
-    TABLE_L2_STATE_ROOTS,
-    TREE_HEIGHT,
-    false,
-    S,
-    GHashOut,
-    PoseidonHasher,
-    KVQStandardAdapter<S, L2OStateRootsMerkleNodeKey, GHashOut>,
->;
-
-pub const SHA256_BRC21_DEPOSITS_APPEND_ONLY_TREE_ID: KVQTreeIdentifier =
-    KVQTreeIdentifier::new(SUB_TABLE_L2_BRC21_DEPOSITS_SHA256, 0, 0);
-type Sha256BRC21DepositsAppendOnlyTree<S> = KVQAppendOnlyMerkleTreeModel<
-    TABLE_L2_BRC21_DEPOSITS,
-    TREE_HEIGHT,
-    S,
-    Hash256,
-    Sha256Hasher,
-    KVQStandardAdapter<S, L2OBRC21DepositsKey, MerkleProofCore<Hash256>>,
->;
-
 pub struct L2OStoreV1Core<S> {
     pub store: S,
 }
@@ -106,6 +33,20 @@ impl<S> L2OStoreV1Core<S> {
         Self { store }
     }
 }
+impl<S: KVQBinaryStoreReader> L2OStoreV1Core<S> {
+    fn get_pending_l2_deposits(&self, l2id: u64) -> anyhow::Result<Vec<L2Deposit>> {
+        let mut deposits = Vec::new();
+        while let Some(l2deposit) =
+            KVQStandardAdapter::<S, L2OPendingDepositsKey, L2Deposit>::get_exact_if_exists(
+                &self.store,
+                &L2OPendingDepositsKey::new(l2id, deposits.len() as u64),
+            )?
+        {
+            deposits.push(l2deposit);
+        }
+        Ok(deposits)
+    }
+}
 impl<S: KVQBinaryStoreReader> L2OStoreReaderV1 for L2OStoreV1Core<S> {
     fn get_deploy_inscription(&self, l2id: u64) -> anyhow::Result<L2OADeployV1> {
         KVQStandardAdapter::<S, L2ODeploymentsKey, L2OADeployV1>::get_exact(
@@ -121,22 +62,26 @@ impl<S: KVQBinaryStoreReader> L2OStoreReaderV1 for L2OStoreV1Core<S> {
         )
     }
 
+    fn get_last_block_inscription_if_exists(
+        &self,
+        l2id: u64,
+    ) -> anyhow::Result<Option<L2OABlockV1>> {
+        KVQStandardAdapter::<S, L2OLatestBlockKey, L2OABlockV1>::get_exact_if_exists(
+            &self.store,
+            &L2OLatestBlockKey::new(l2id),
+        )
+    }
+
     fn get_state_root_at_block(
         &self,
         l2id: u64,
         block_number: u64,
         hash: L2OAHashFunction,
     ) -> anyhow::Result<Hash256> {
-        let checkpoint_id = block_number;
-        let pos = KVQTreeNodePosition::new(TREE_HEIGHT, l2id);
-        get_state!(
-            hash,
-            &self.store,
-            checkpoint_id,
-            pos,
-            get_node,
-            L2OHash::to_hash_256
-        )
+        let position = KVQTreeNodePosition::new(L2O_TREES.state_roots.height(), l2id);
+        L2O_TREES
+            .state_roots
+            .get_node(&self.store, hash, block_number, &position)
     }
 
     fn get_superchainroot_at_block(
@@ -144,16 +89,9 @@ impl<S: KVQBinaryStoreReader> L2OStoreReaderV1 for L2OStoreV1Core<S> {
         block_number: u64,
         hash: L2OAHashFunction,
     ) -> anyhow::Result<Hash256> {
-        let checkpoint_id = block_number;
-        let pos = KVQTreeNodePosition::root();
-        get_state!(
-            hash,
-            &self.store,
-            checkpoint_id,
-            pos,
-            get_node,
-            L2OHash::to_hash_256
-        )
+        L2O_TREES
+            .state_roots
+            .get_root(&self.store, hash, block_number)
     }
 
     fn get_merkle_proof_state_root_at_block(
@@ -162,15 +100,42 @@ impl<S: KVQBinaryStoreReader> L2OStoreReaderV1 for L2OStoreV1Core<S> {
         block_number: u64,
         hash: L2OAHashFunction,
     ) -> anyhow::Result<MerkleProofCore<Hash256>> {
-        let checkpoint_id = block_number;
-        let pos = KVQTreeNodePosition::new(TREE_HEIGHT, l2id);
-        get_state!(
-            hash,
+        L2O_TREES
+            .state_roots
+            .get_leaf(&self.store, hash, block_number, l2id)
+    }
+
+    fn get_superchain_delta_proof(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>> {
+        KVQStandardAdapter::<S, L2OStateRootDeltasKey, DeltaMerkleProofCore<Hash256>>::get_exact(
             &self.store,
-            checkpoint_id,
-            pos,
-            get_leaf,
-            MerkleProofCore::<Hash256>::from
+            &L2OStateRootDeltasKey::new(l2id, block_number, L2O_TREES.state_roots.tree_id(hash)?),
+        )
+    }
+
+    fn get_block_inscription_location(
+        &self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+    ) -> anyhow::Result<L2OABlockLocation> {
+        KVQStandardAdapter::<S, L2OBlockLocationsKey, L2OABlockLocation>::get_exact(
+            &self.store,
+            &L2OBlockLocationsKey::new(l2id, bitcoin_block_number),
+        )
+    }
+
+    fn get_block_inscription_inclusion(
+        &self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+    ) -> anyhow::Result<L2OABlockInclusion> {
+        KVQStandardAdapter::<S, L2OBlockInclusionsKey, L2OABlockInclusion>::get_exact(
+            &self.store,
+            &L2OBlockInclusionsKey::new(l2id, bitcoin_block_number),
         )
     }
 
@@ -188,41 +153,278 @@ impl<S: KVQBinaryStoreReader> L2OStoreReaderV1 for L2OStoreV1Core<S> {
 
 impl<S: KVQBinaryStore> L2OStoreV1 for L2OStoreV1Core<S> {
     fn report_deploy_inscription(&mut self, deployment: L2OADeployV1) -> anyhow::Result<()> {
+        let l2id = deployment.l2id;
+        let hash = deployment.hash_function;
         KVQStandardAdapter::<S, L2ODeploymentsKey, L2OADeployV1>::set(
             &mut self.store,
-            L2ODeploymentsKey::new(deployment.l2id),
+            L2ODeploymentsKey::new(l2id),
             deployment,
         )?;
+
+        // deposits made before the deployment are only in the sha256 trees
+        if hash != L2OAHashFunction::Sha256 {
+            for l2deposit in self.get_pending_l2_deposits(l2id)? {
+                self.append_l2_deposit(l2deposit, hash)?;
+            }
+        }
         Ok(())
     }
 
     fn set_last_block_inscription(&mut self, block: L2OABlockV1) -> anyhow::Result<()> {
         let end_state_root = block.end_state_root;
         let checkpoint_id = block.bitcoin_block_number;
-        let pos = KVQTreeNodePosition::new(TREE_HEIGHT, block.l2id);
+        let l2id = block.l2id;
 
         KVQStandardAdapter::<S, L2OLatestBlockKey, L2OABlockV1>::set(
             &mut self.store,
-            L2OLatestBlockKey::new(block.l2id),
+            L2OLatestBlockKey::new(l2id),
             block,
         )?;
 
-        set_state!(self.store, checkpoint_id, pos, end_state_root);
+        // keep the superchain root transition of every hash function, a later
+        // block of the same L2 in this bitcoin block extends it so that the
+        // delta goes from the root before the first block to the latest root
+        let deltas = L2O_TREES.state_roots.set_leaf_all(
+            &mut self.store,
+            checkpoint_id,
+            l2id,
+            &end_state_root,
+        )?;
+        for (hash, mut delta) in deltas {
+            let key = L2OStateRootDeltasKey::new(
+                l2id,
+                checkpoint_id,
+                L2O_TREES.state_roots.tree_id(hash)?,
+            );
+            let previous = KVQStandardAdapter::<
+                S,
+                L2OStateRootDeltasKey,
+                DeltaMerkleProofCore<Hash256>,
+            >::get_exact_if_exists(&self.store, &key)?;
+            if let Some(previous) = previous {
+                delta.old_root = previous.old_root;
+                delta.old_value = previous.old_value;
+            }
+            KVQStandardAdapter::<S, L2OStateRootDeltasKey, DeltaMerkleProofCore<Hash256>>::set(
+                &mut self.store,
+                key,
+                delta,
+            )?;
+        }
+
+        Ok(())
+    }
 
+    fn report_block_inscription_location(
+        &mut self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+        location: L2OABlockLocation,
+    ) -> anyhow::Result<()> {
+        KVQStandardAdapter::<S, L2OBlockLocationsKey, L2OABlockLocation>::set(
+            &mut self.store,
+            L2OBlockLocationsKey::new(l2id, bitcoin_block_number),
+            location,
+        )?;
         Ok(())
     }
 
-    fn append_l2_deposit(&mut self, l2deposit: L2Deposit) -> anyhow::Result<()> {
-        let hash = Sha256Hasher::get_l2_deposit_hash(&l2deposit);
-        Sha256BRC21DepositsAppendOnlyTree::<S>::append_leaf(
+    fn report_block_inscription_inclusion(
+        &mut self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+        inclusion: L2OABlockInclusion,
+    ) -> anyhow::Result<()> {
+        KVQStandardAdapter::<S, L2OBlockInclusionsKey, L2OABlockInclusion>::set(
+            &mut self.store,
+            L2OBlockInclusionsKey::new(l2id, bitcoin_block_number),
+            inclusion,
+        )?;
+        Ok(())
+    }
+
+    fn append_l2_deposit(
+        &mut self,
+        l2deposit: L2Deposit,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<()> {
+        let value = get_l2_deposit_hash_with(hash, &l2deposit);
+        // every L2 has its own deposit tree per tick
+        L2O_TREES.brc21_deposits.append_leaf(
             &mut self.store,
-            &KVQAppendOnlyMerkleKey::from_identifier_ref(
-                &SHA256_BRC21_DEPOSITS_APPEND_ONLY_TREE_ID,
-                0,
-                l2deposit.tick,
-            ),
             hash,
+            l2deposit.l2id,
+            l2deposit.tick,
+            &value,
         )?;
         Ok(())
     }
+
+    fn append_pending_l2_deposit(&mut self, l2deposit: L2Deposit) -> anyhow::Result<()> {
+        let index = KVQStandardAdapter::<S, L2OPendingDepositsKey, L2Deposit>::get_leq_kv(
+            &self.store,
+            &L2OPendingDepositsKey::new(l2deposit.l2id, u64::MAX),
+            8,
+        )?
+        .map_or(0, |last| last.key.index + 1);
+        KVQStandardAdapter::<S, L2OPendingDepositsKey, L2Deposit>::set(
+            &mut self.store,
+            L2OPendingDepositsKey::new(l2deposit.l2id, index),
+            l2deposit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_groth16::Proof;
+    use ark_groth16::VerifyingKey;
+    use kvq::memory::simple::KVQSimpleMemoryBackingStore;
+    use l2o_common::common::data::signature::L2OCompactPublicKey;
+    use l2o_common::common::data::signature::L2OSignature512;
+    use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+    use l2o_crypto::proof::groth16::bn128::proof_data::Groth16BN128ProofData;
+    use l2o_crypto::proof::groth16::bn128::verifier_data::Groth16BN128VerifierData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAProofData;
+    use l2o_crypto::standards::l2o_a::proof::L2OAVerifierData;
+    use l2o_ord::operation::l2o_a::L2OAPublicInput;
+
+    use super::*;
+
+    fn test_block(l2id: u64, bitcoin_block_number: u64, end_state_root: Hash256) -> L2OABlockV1 {
+        L2OABlockV1 {
+            l2id,
+            l2_block_number: bitcoin_block_number,
+            bitcoin_block_number,
+            bitcoin_block_hash: Hash256([1u8; 32]),
+            public_key: L2OCompactPublicKey([2u8; 32]),
+            start_state_root: Hash256([0u8; 32]),
+            end_state_root,
+            deposit_state_root: Hash256([0u8; 32]),
+            start_withdrawal_state_root: Hash256([0u8; 32]),
+            end_withdrawal_state_root: Hash256([0u8; 32]),
+            proof: L2OAProofData::Groth16BN128(Groth16BN128ProofData {
+                proof: Proof::default(),
+                public_inputs: vec![],
+            }),
+            superchain_root: Hash256([0u8; 32]),
+            signature: L2OSignature512([0u8; 64]),
+        }
+    }
+
+    fn test_deploy(l2id: u64, hash_function: L2OAHashFunction) -> L2OADeployV1 {
+        L2OADeployV1 {
+            l2id,
+            public_key: L2OCompactPublicKey([2u8; 32]),
+            start_state_root: Hash256([0u8; 32]),
+            hash_function,
+            public_inputs: L2OAPublicInput::default_layout(),
+            sequencer_keys: vec![],
+            verifier_data: L2OAVerifierData::Groth16BN128(Groth16BN128VerifierData(
+                VerifyingKey::default(),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_superchain_delta_proofs() {
+        let mut store = L2OStoreV1Core::new(KVQSimpleMemoryBackingStore::new());
+        let hash = L2OAHashFunction::Sha256;
+        store
+            .set_last_block_inscription(test_block(1, 10, Hash256([3u8; 32])))
+            .unwrap();
+        store
+            .set_last_block_inscription(test_block(2, 11, Hash256([4u8; 32])))
+            .unwrap();
+
+        let first = store.get_superchain_delta_proof(1, 10, hash).unwrap();
+        let second = store.get_superchain_delta_proof(2, 11, hash).unwrap();
+        assert!(first.verify_marked_if::<Sha256Hasher>(false));
+        assert!(second.verify_marked_if::<Sha256Hasher>(false));
+        assert_eq!(second.index, 2);
+        assert_eq!(second.new_value, Hash256([4u8; 32]));
+        assert_eq!(second.old_root, first.new_root);
+        assert_eq!(
+            store.get_superchainroot_at_block(12, hash).unwrap(),
+            second.new_root
+        );
+
+        assert!(store.get_superchain_delta_proof(1, 11, hash).is_err());
+        for hash in L2O_TREES.state_roots.tree_ids.iter().map(|(hash, _)| *hash) {
+            assert!(store.get_superchain_delta_proof(2, 11, hash).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_superchain_delta_proof_of_two_blocks_in_one_checkpoint() {
+        let mut store = L2OStoreV1Core::new(KVQSimpleMemoryBackingStore::new());
+        let hash = L2OAHashFunction::Sha256;
+        store
+            .set_last_block_inscription(test_block(1, 10, Hash256([3u8; 32])))
+            .unwrap();
+        let first = store.get_superchain_delta_proof(1, 10, hash).unwrap();
+
+        let mut second_block = test_block(1, 10, Hash256([5u8; 32]));
+        second_block.l2_block_number = 11;
+        store.set_last_block_inscription(second_block).unwrap();
+
+        let delta = store.get_superchain_delta_proof(1, 10, hash).unwrap();
+        assert!(delta.verify_marked_if::<Sha256Hasher>(false));
+        assert_eq!(delta.old_root, first.old_root);
+        assert_eq!(delta.old_value, Hash256([0u8; 32]));
+        assert_eq!(delta.new_value, Hash256([5u8; 32]));
+        assert_eq!(
+            store.get_superchainroot_at_block(11, hash).unwrap(),
+            delta.new_root
+        );
+    }
+
+    #[test]
+    fn test_deposits_before_a_non_sha256_deploy() {
+        let hash = L2OAHashFunction::Keccak256;
+        let l2deposit = |amount: &str| {
+            L2Deposit::new(
+                4,
+                "ordi".to_string(),
+                "bc1qdeposit".to_string(),
+                amount.to_string(),
+            )
+        };
+
+        let mut early = L2OStoreV1Core::new(KVQSimpleMemoryBackingStore::new());
+        for amount in ["1", "2"] {
+            early
+                .append_l2_deposit(l2deposit(amount), L2OAHashFunction::Sha256)
+                .unwrap();
+            early.append_pending_l2_deposit(l2deposit(amount)).unwrap();
+        }
+        early
+            .report_deploy_inscription(test_deploy(4, hash))
+            .unwrap();
+
+        let mut late = L2OStoreV1Core::new(KVQSimpleMemoryBackingStore::new());
+        late.report_deploy_inscription(test_deploy(4, hash))
+            .unwrap();
+        for amount in ["1", "2"] {
+            late.append_l2_deposit(l2deposit(amount), hash).unwrap();
+        }
+
+        // the deposits made before the deploy are in the keccak256 tree, in order
+        let next = |store: &mut L2OStoreV1Core<KVQSimpleMemoryBackingStore>| {
+            L2O_TREES
+                .brc21_deposits
+                .append_leaf(
+                    &mut store.store,
+                    hash,
+                    4,
+                    "ordi".to_string(),
+                    &Hash256([9u8; 32]),
+                )
+                .unwrap()
+        };
+        let early_next = next(&mut early);
+        let late_next = next(&mut late);
+        assert_eq!(early_next.index, 2);
+        assert_eq!(early_next.old_root, late_next.old_root);
+    }
 }
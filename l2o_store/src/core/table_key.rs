@@ -1,6 +1,7 @@
-use kvq::traits::KVQSerializable;
+use l2o_derive::KVQSerializable;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
 pub struct L2TableKey<const TABLE_TYPE: u16> {
     pub l2id: u64,
 }
@@ -9,18 +10,49 @@ impl<const TABLE_TYPE: u16> L2TableKey<TABLE_TYPE> {
         Self { l2id }
     }
 }
-impl<const TABLE_TYPE: u16> KVQSerializable for L2TableKey<TABLE_TYPE> {
-    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        let mut result: Vec<u8> = Vec::with_capacity(10);
-        result.push(((TABLE_TYPE & 0xFF00) >> 8) as u8); // 1
-        result.push((TABLE_TYPE & 0xFF) as u8); // 2
-        result.extend_from_slice(&self.l2id.to_be_bytes()); // 11
-        Ok(result)
+
+// Values an L2 writes at a bitcoin block, kept for each tree it touches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
+pub struct L2CheckpointTableKey<const TABLE_TYPE: u16> {
+    pub l2id: u64,
+    pub checkpoint_id: u64,
+    pub tree_id: u8,
+}
+impl<const TABLE_TYPE: u16> L2CheckpointTableKey<TABLE_TYPE> {
+    pub fn new(l2id: u64, checkpoint_id: u64, tree_id: u8) -> Self {
+        Self {
+            l2id,
+            checkpoint_id,
+            tree_id,
+        }
     }
+}
 
-    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        Ok(Self {
-            l2id: u64::from_be_bytes(bytes[2..10].try_into()?),
-        })
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
+pub struct L2BlockTableKey<const TABLE_TYPE: u16> {
+    pub l2id: u64,
+    pub bitcoin_block_number: u64,
+}
+impl<const TABLE_TYPE: u16> L2BlockTableKey<TABLE_TYPE> {
+    pub fn new(l2id: u64, bitcoin_block_number: u64) -> Self {
+        Self {
+            l2id,
+            bitcoin_block_number,
+        }
+    }
+}
+
+// The entries an L2 has in a list, in the order they were added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, KVQSerializable)]
+#[kvq(key, table_type = TABLE_TYPE)]
+pub struct L2IndexTableKey<const TABLE_TYPE: u16> {
+    pub l2id: u64,
+    pub index: u64,
+}
+impl<const TABLE_TYPE: u16> L2IndexTableKey<TABLE_TYPE> {
+    pub fn new(l2id: u64, index: u64) -> Self {
+        Self { l2id, index }
     }
 }
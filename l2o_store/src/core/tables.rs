@@ -1,11 +1,20 @@
 use l2o_crypto::hash::merkle::store::key::KVQAppendOnlyMerkleKey;
 use l2o_crypto::hash::merkle::store::key::KVQMerkleNodeKey;
 
+use super::table_key::L2BlockTableKey;
+use super::table_key::L2CheckpointTableKey;
+use super::table_key::L2IndexTableKey;
 use super::table_key::L2TableKey;
 
 pub const TABLE_L2_DEPLOYMENTS: u16 = 1;
 pub const TABLE_L2_LATEST_BLOCK: u16 = 2;
 pub const TABLE_L2_BRC21_DEPOSITS: u16 = 3;
+pub const TABLE_L2_BRC21_WITHDRAWALS: u16 = 4;
+pub const TABLE_L2_NULLIFIERS: u16 = 5;
+pub const TABLE_L2_STATE_ROOT_DELTAS: u16 = 6;
+pub const TABLE_L2_BLOCK_LOCATIONS: u16 = 7;
+pub const TABLE_L2_BLOCK_INCLUSIONS: u16 = 9;
+pub const TABLE_L2_PENDING_DEPOSITS: u16 = 10;
 
 pub const TABLE_L2_STATE_ROOTS: u16 = 8;
 pub const SUB_TABLE_L2_STATE_ROOTS_SHA256: u8 = 1;
@@ -13,8 +22,27 @@ pub const SUB_TABLE_L2_STATE_ROOTS_KECCACK256: u8 = 2;
 pub const SUB_TABLE_L2_STATE_ROOTS_BLAKE3: u8 = 3;
 pub const SUB_TABLE_L2_STATE_ROOTS_POSEIDON_GOLDILOCKS: u8 = 4;
 pub const SUB_TABLE_L2_BRC21_DEPOSITS_SHA256: u8 = 5;
+pub const SUB_TABLE_L2_BRC21_DEPOSITS_KECCACK256: u8 = 6;
+pub const SUB_TABLE_L2_BRC21_DEPOSITS_BLAKE3: u8 = 7;
+pub const SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_GOLDILOCKS: u8 = 8;
+pub const SUB_TABLE_L2_STATE_ROOTS_POSEIDON_BN254: u8 = 9;
+pub const SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_BN254: u8 = 10;
+pub const SUB_TABLE_L2_BRC21_WITHDRAWALS_SHA256: u8 = 11;
+pub const SUB_TABLE_L2_BRC21_WITHDRAWALS_KECCACK256: u8 = 12;
+pub const SUB_TABLE_L2_BRC21_WITHDRAWALS_BLAKE3: u8 = 13;
+pub const SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_GOLDILOCKS: u8 = 14;
+pub const SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_BN254: u8 = 15;
+pub const SUB_TABLE_L2_NULLIFIERS_SHA256: u8 = 16;
+pub const SUB_TABLE_L2_NULLIFIERS_KECCACK256: u8 = 17;
+pub const SUB_TABLE_L2_NULLIFIERS_BLAKE3: u8 = 18;
+pub const SUB_TABLE_L2_NULLIFIERS_POSEIDON_GOLDILOCKS: u8 = 19;
+pub const SUB_TABLE_L2_NULLIFIERS_POSEIDON_BN254: u8 = 20;
 
 pub type L2OStateRootsMerkleNodeKey = KVQMerkleNodeKey<TABLE_L2_STATE_ROOTS>;
 pub type L2OBRC21DepositsKey = KVQAppendOnlyMerkleKey<TABLE_L2_BRC21_DEPOSITS>;
 pub type L2ODeploymentsKey = L2TableKey<TABLE_L2_DEPLOYMENTS>;
 pub type L2OLatestBlockKey = L2TableKey<TABLE_L2_LATEST_BLOCK>;
+pub type L2OStateRootDeltasKey = L2CheckpointTableKey<TABLE_L2_STATE_ROOT_DELTAS>;
+pub type L2OBlockLocationsKey = L2BlockTableKey<TABLE_L2_BLOCK_LOCATIONS>;
+pub type L2OBlockInclusionsKey = L2BlockTableKey<TABLE_L2_BLOCK_INCLUSIONS>;
+pub type L2OPendingDepositsKey = L2IndexTableKey<TABLE_L2_PENDING_DEPOSITS>;
@@ -1,14 +1,22 @@
 use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::merkle::core::DeltaMerkleProofCore;
 use l2o_crypto::hash::merkle::core::MerkleProofCore;
 use l2o_ord::operation::brc21::l2deposit::L2Deposit;
 use l2o_ord::operation::l2o_a::L2OABlockV1;
 use l2o_ord::operation::l2o_a::L2OADeployV1;
 use l2o_ord::operation::l2o_a::L2OAHashFunction;
 
+use super::location::L2OABlockInclusion;
+use super::location::L2OABlockLocation;
+
 pub trait L2OStoreReaderV1 {
     fn has_deployed_l2id(&self, l2id: u64) -> anyhow::Result<bool>;
     fn get_deploy_inscription(&self, l2id: u64) -> anyhow::Result<L2OADeployV1>;
     fn get_last_block_inscription(&self, l2id: u64) -> anyhow::Result<L2OABlockV1>;
+    fn get_last_block_inscription_if_exists(
+        &self,
+        l2id: u64,
+    ) -> anyhow::Result<Option<L2OABlockV1>>;
     fn get_state_root_at_block(
         &self,
         l2id: u64,
@@ -26,10 +34,45 @@ pub trait L2OStoreReaderV1 {
         block_number: u64,
         hash: L2OAHashFunction,
     ) -> anyhow::Result<MerkleProofCore<Hash256>>;
+    fn get_superchain_delta_proof(
+        &self,
+        l2id: u64,
+        block_number: u64,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>>;
+    fn get_block_inscription_location(
+        &self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+    ) -> anyhow::Result<L2OABlockLocation>;
+    fn get_block_inscription_inclusion(
+        &self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+    ) -> anyhow::Result<L2OABlockInclusion>;
 }
 
 pub trait L2OStoreV1: L2OStoreReaderV1 {
     fn report_deploy_inscription(&mut self, deployment: L2OADeployV1) -> anyhow::Result<()>;
     fn set_last_block_inscription(&mut self, block: L2OABlockV1) -> anyhow::Result<()>;
-    fn append_l2_deposit(&mut self, l2deposit: L2Deposit) -> anyhow::Result<()>;
+    fn report_block_inscription_location(
+        &mut self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+        location: L2OABlockLocation,
+    ) -> anyhow::Result<()>;
+    fn report_block_inscription_inclusion(
+        &mut self,
+        l2id: u64,
+        bitcoin_block_number: u64,
+        inclusion: L2OABlockInclusion,
+    ) -> anyhow::Result<()>;
+    fn append_l2_deposit(
+        &mut self,
+        l2deposit: L2Deposit,
+        hash: L2OAHashFunction,
+    ) -> anyhow::Result<()>;
+    // deposits to an L2 that is not deployed yet, committed to again in the
+    // hash function of its deployment when it is reported
+    fn append_pending_l2_deposit(&mut self, l2deposit: L2Deposit) -> anyhow::Result<()>;
 }
@@ -0,0 +1,580 @@
+use kvq::adapters::standard::KVQStandardAdapter;
+use kvq::traits::KVQBinaryStore;
+use kvq::traits::KVQBinaryStoreReader;
+use l2o_common::common::data::hash::Hash256;
+use l2o_crypto::hash::hash_functions::blake3::Blake3Hasher;
+use l2o_crypto::hash::hash_functions::keccak256::Keccak256Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_bn254::PoseidonBN254Hasher;
+use l2o_crypto::hash::hash_functions::poseidon_goldilocks::PoseidonHasher;
+use l2o_crypto::hash::hash_functions::sha256::Sha256Hasher;
+use l2o_crypto::hash::merkle::core::DeltaMerkleProofCore;
+use l2o_crypto::hash::merkle::core::MerkleProofCore;
+use l2o_crypto::hash::merkle::store::key::KVQAppendOnlyMerkleKey;
+use l2o_crypto::hash::merkle::store::key::KVQMerkleNodeKey;
+use l2o_crypto::hash::merkle::store::key::KVQTreeIdentifier;
+use l2o_crypto::hash::merkle::store::key::KVQTreeNodePosition;
+use l2o_crypto::hash::merkle::store::model::KVQAppendOnlyMerkleTreeModel;
+use l2o_crypto::hash::merkle::store::model::KVQMerkleTreeModel;
+use l2o_crypto::hash::merkle::tree_hasher::L2OLeafEncoding;
+use l2o_crypto::hash::merkle::tree_hasher::L2OTreeHasher;
+use l2o_crypto::hash::traits::L2OHash;
+use l2o_ord::operation::l2o_a::L2OAHashFunction;
+
+use super::tables::SUB_TABLE_L2_BRC21_DEPOSITS_BLAKE3;
+use super::tables::SUB_TABLE_L2_BRC21_DEPOSITS_KECCACK256;
+use super::tables::SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_BN254;
+use super::tables::SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_GOLDILOCKS;
+use super::tables::SUB_TABLE_L2_BRC21_DEPOSITS_SHA256;
+use super::tables::SUB_TABLE_L2_BRC21_WITHDRAWALS_BLAKE3;
+use super::tables::SUB_TABLE_L2_BRC21_WITHDRAWALS_KECCACK256;
+use super::tables::SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_BN254;
+use super::tables::SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_GOLDILOCKS;
+use super::tables::SUB_TABLE_L2_BRC21_WITHDRAWALS_SHA256;
+use super::tables::SUB_TABLE_L2_NULLIFIERS_BLAKE3;
+use super::tables::SUB_TABLE_L2_NULLIFIERS_KECCACK256;
+use super::tables::SUB_TABLE_L2_NULLIFIERS_POSEIDON_BN254;
+use super::tables::SUB_TABLE_L2_NULLIFIERS_POSEIDON_GOLDILOCKS;
+use super::tables::SUB_TABLE_L2_NULLIFIERS_SHA256;
+use super::tables::SUB_TABLE_L2_STATE_ROOTS_BLAKE3;
+use super::tables::SUB_TABLE_L2_STATE_ROOTS_KECCACK256;
+use super::tables::SUB_TABLE_L2_STATE_ROOTS_POSEIDON_BN254;
+use super::tables::SUB_TABLE_L2_STATE_ROOTS_POSEIDON_GOLDILOCKS;
+use super::tables::SUB_TABLE_L2_STATE_ROOTS_SHA256;
+use super::tables::TABLE_L2_BRC21_DEPOSITS;
+use super::tables::TABLE_L2_BRC21_WITHDRAWALS;
+use super::tables::TABLE_L2_NULLIFIERS;
+use super::tables::TABLE_L2_STATE_ROOTS;
+
+pub trait L2OTreeHasherVisitor {
+    type Output;
+
+    fn visit<H: L2OTreeHasher>(self) -> Self::Output;
+}
+
+// The only place that maps hash functions to hashers, a new hash function
+// needs an arm here and a tree id in every description below.
+pub fn visit_tree_hasher<V: L2OTreeHasherVisitor>(hash: L2OAHashFunction, visitor: V) -> V::Output {
+    match hash {
+        L2OAHashFunction::Sha256 => visitor.visit::<Sha256Hasher>(),
+        L2OAHashFunction::BLAKE3 => visitor.visit::<Blake3Hasher>(),
+        L2OAHashFunction::Keccak256 => visitor.visit::<Keccak256Hasher>(),
+        L2OAHashFunction::PoseidonGoldilocks => visitor.visit::<PoseidonHasher>(),
+        L2OAHashFunction::PoseidonBN254 => visitor.visit::<PoseidonBN254Hasher>(),
+    }
+}
+
+type NodeTree<const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool, S, H> =
+    KVQMerkleTreeModel<
+        TABLE_TYPE,
+        TREE_HEIGHT,
+        MARK_LEAVES,
+        S,
+        <H as L2OTreeHasher>::Hash,
+        <H as L2OTreeHasher>::Hasher,
+        KVQStandardAdapter<S, KVQMerkleNodeKey<TABLE_TYPE>, <H as L2OTreeHasher>::Hash>,
+    >;
+
+type AppendOnlyTree<const TABLE_TYPE: u16, const TREE_HEIGHT: u8, S, H> =
+    KVQAppendOnlyMerkleTreeModel<
+        TABLE_TYPE,
+        TREE_HEIGHT,
+        S,
+        <H as L2OTreeHasher>::Hash,
+        <H as L2OTreeHasher>::Hasher,
+        KVQStandardAdapter<
+            S,
+            KVQAppendOnlyMerkleKey<TABLE_TYPE>,
+            MerkleProofCore<<H as L2OTreeHasher>::Hash>,
+        >,
+    >;
+
+// One tree per hash function, all in the same table and told apart by their
+// tree id. Trees of an L2 are picked with the hash function of its deployment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L2OTreeDescription<const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool>
+{
+    pub leaf_encoding: L2OLeafEncoding,
+    pub tree_ids: &'static [(L2OAHashFunction, u8)],
+}
+
+// What the registry knows about a tree without its const parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L2OTreeRegistration {
+    pub table_type: u16,
+    pub height: u8,
+    pub mark_leaves: bool,
+    pub tree_ids: &'static [(L2OAHashFunction, u8)],
+}
+
+// Every tree of the store, the store only reaches its trees through here so a
+// new tree only needs a description and a field.
+#[derive(Clone, Copy, Debug)]
+pub struct L2OTrees {
+    pub state_roots: L2OTreeDescription<TABLE_L2_STATE_ROOTS, 32, false>,
+    pub brc21_deposits: L2OTreeDescription<TABLE_L2_BRC21_DEPOSITS, 32, false>,
+    pub brc21_withdrawals: L2OTreeDescription<TABLE_L2_BRC21_WITHDRAWALS, 32, false>,
+    pub nullifiers: L2OTreeDescription<TABLE_L2_NULLIFIERS, 64, true>,
+}
+
+impl L2OTrees {
+    pub const fn registrations(&self) -> [L2OTreeRegistration; 4] {
+        [
+            self.state_roots.registration(),
+            self.brc21_deposits.registration(),
+            self.brc21_withdrawals.registration(),
+            self.nullifiers.registration(),
+        ]
+    }
+}
+
+pub const L2O_TREES: L2OTrees = L2OTrees {
+    state_roots: STATE_ROOT_TREES,
+    brc21_deposits: BRC21_DEPOSIT_TREES,
+    brc21_withdrawals: BRC21_WITHDRAWAL_TREES,
+    nullifiers: NULLIFIER_TREES,
+};
+
+// The state root of every L2 at the index of its l2id, the roots of these
+// trees are the superchain roots.
+const STATE_ROOT_TREES: L2OTreeDescription<TABLE_L2_STATE_ROOTS, 32, false> = L2OTreeDescription {
+    leaf_encoding: L2OLeafEncoding::FieldElements,
+    tree_ids: &[
+        (L2OAHashFunction::Sha256, SUB_TABLE_L2_STATE_ROOTS_SHA256),
+        (
+            L2OAHashFunction::Keccak256,
+            SUB_TABLE_L2_STATE_ROOTS_KECCACK256,
+        ),
+        (L2OAHashFunction::BLAKE3, SUB_TABLE_L2_STATE_ROOTS_BLAKE3),
+        (
+            L2OAHashFunction::PoseidonGoldilocks,
+            SUB_TABLE_L2_STATE_ROOTS_POSEIDON_GOLDILOCKS,
+        ),
+        (
+            L2OAHashFunction::PoseidonBN254,
+            SUB_TABLE_L2_STATE_ROOTS_POSEIDON_BN254,
+        ),
+    ],
+};
+
+// Append only, one tree per L2 and tick.
+const BRC21_DEPOSIT_TREES: L2OTreeDescription<TABLE_L2_BRC21_DEPOSITS, 32, false> =
+    L2OTreeDescription {
+        leaf_encoding: L2OLeafEncoding::NativeHash,
+        tree_ids: &[
+            (L2OAHashFunction::Sha256, SUB_TABLE_L2_BRC21_DEPOSITS_SHA256),
+            (
+                L2OAHashFunction::Keccak256,
+                SUB_TABLE_L2_BRC21_DEPOSITS_KECCACK256,
+            ),
+            (L2OAHashFunction::BLAKE3, SUB_TABLE_L2_BRC21_DEPOSITS_BLAKE3),
+            (
+                L2OAHashFunction::PoseidonGoldilocks,
+                SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_GOLDILOCKS,
+            ),
+            (
+                L2OAHashFunction::PoseidonBN254,
+                SUB_TABLE_L2_BRC21_DEPOSITS_POSEIDON_BN254,
+            ),
+        ],
+    };
+
+// Append only, one tree per L2 and tick.
+const BRC21_WITHDRAWAL_TREES: L2OTreeDescription<TABLE_L2_BRC21_WITHDRAWALS, 32, false> =
+    L2OTreeDescription {
+        leaf_encoding: L2OLeafEncoding::NativeHash,
+        tree_ids: &[
+            (
+                L2OAHashFunction::Sha256,
+                SUB_TABLE_L2_BRC21_WITHDRAWALS_SHA256,
+            ),
+            (
+                L2OAHashFunction::Keccak256,
+                SUB_TABLE_L2_BRC21_WITHDRAWALS_KECCACK256,
+            ),
+            (
+                L2OAHashFunction::BLAKE3,
+                SUB_TABLE_L2_BRC21_WITHDRAWALS_BLAKE3,
+            ),
+            (
+                L2OAHashFunction::PoseidonGoldilocks,
+                SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_GOLDILOCKS,
+            ),
+            (
+                L2OAHashFunction::PoseidonBN254,
+                SUB_TABLE_L2_BRC21_WITHDRAWALS_POSEIDON_BN254,
+            ),
+        ],
+    };
+
+// Sparse trees indexed by the top bits of the nullifier, with marked leaves so
+// that a spent nullifier never hashes like an empty leaf.
+const NULLIFIER_TREES: L2OTreeDescription<TABLE_L2_NULLIFIERS, 64, true> = L2OTreeDescription {
+    leaf_encoding: L2OLeafEncoding::NativeHash,
+    tree_ids: &[
+        (L2OAHashFunction::Sha256, SUB_TABLE_L2_NULLIFIERS_SHA256),
+        (
+            L2OAHashFunction::Keccak256,
+            SUB_TABLE_L2_NULLIFIERS_KECCACK256,
+        ),
+        (L2OAHashFunction::BLAKE3, SUB_TABLE_L2_NULLIFIERS_BLAKE3),
+        (
+            L2OAHashFunction::PoseidonGoldilocks,
+            SUB_TABLE_L2_NULLIFIERS_POSEIDON_GOLDILOCKS,
+        ),
+        (
+            L2OAHashFunction::PoseidonBN254,
+            SUB_TABLE_L2_NULLIFIERS_POSEIDON_BN254,
+        ),
+    ],
+};
+
+struct GetNode<'a, S, const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool> {
+    store: &'a S,
+    key: KVQMerkleNodeKey<TABLE_TYPE>,
+}
+
+impl<
+        'a,
+        S: KVQBinaryStoreReader,
+        const TABLE_TYPE: u16,
+        const TREE_HEIGHT: u8,
+        const MARK_LEAVES: bool,
+    > L2OTreeHasherVisitor for GetNode<'a, S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES>
+{
+    type Output = anyhow::Result<Hash256>;
+
+    fn visit<H: L2OTreeHasher>(self) -> Self::Output {
+        let node = NodeTree::<TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES, S, H>::get_node(
+            self.store, &self.key,
+        )?;
+        Ok(node.to_hash_256())
+    }
+}
+
+struct GetLeaf<'a, S, const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool> {
+    store: &'a S,
+    key: KVQMerkleNodeKey<TABLE_TYPE>,
+}
+
+impl<
+        'a,
+        S: KVQBinaryStoreReader,
+        const TABLE_TYPE: u16,
+        const TREE_HEIGHT: u8,
+        const MARK_LEAVES: bool,
+    > L2OTreeHasherVisitor for GetLeaf<'a, S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES>
+{
+    type Output = anyhow::Result<MerkleProofCore<Hash256>>;
+
+    fn visit<H: L2OTreeHasher>(self) -> Self::Output {
+        let proof = NodeTree::<TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES, S, H>::get_leaf(
+            self.store, &self.key,
+        )?;
+        Ok(proof.to_hash_256())
+    }
+}
+
+struct SetLeaf<'a, S, const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool> {
+    store: &'a mut S,
+    key: KVQMerkleNodeKey<TABLE_TYPE>,
+    value: &'a Hash256,
+    encoding: L2OLeafEncoding,
+}
+
+impl<
+        'a,
+        S: KVQBinaryStore,
+        const TABLE_TYPE: u16,
+        const TREE_HEIGHT: u8,
+        const MARK_LEAVES: bool,
+    > L2OTreeHasherVisitor for SetLeaf<'a, S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES>
+{
+    type Output = anyhow::Result<DeltaMerkleProofCore<Hash256>>;
+
+    fn visit<H: L2OTreeHasher>(self) -> Self::Output {
+        let delta = NodeTree::<TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES, S, H>::set_leaf(
+            self.store,
+            &self.key,
+            H::to_leaf(self.value, self.encoding),
+        )?;
+        Ok(delta.to_hash_256())
+    }
+}
+
+struct AppendLeaf<'a, S, const TABLE_TYPE: u16, const TREE_HEIGHT: u8> {
+    store: &'a mut S,
+    key: KVQAppendOnlyMerkleKey<TABLE_TYPE>,
+    value: &'a Hash256,
+    encoding: L2OLeafEncoding,
+}
+
+impl<'a, S: KVQBinaryStore, const TABLE_TYPE: u16, const TREE_HEIGHT: u8> L2OTreeHasherVisitor
+    for AppendLeaf<'a, S, TABLE_TYPE, TREE_HEIGHT>
+{
+    type Output = anyhow::Result<DeltaMerkleProofCore<Hash256>>;
+
+    fn visit<H: L2OTreeHasher>(self) -> Self::Output {
+        let delta = AppendOnlyTree::<TABLE_TYPE, TREE_HEIGHT, S, H>::append_leaf(
+            self.store,
+            &self.key,
+            H::to_leaf(self.value, self.encoding),
+        )?;
+        Ok(delta.to_hash_256())
+    }
+}
+
+impl<const TABLE_TYPE: u16, const TREE_HEIGHT: u8, const MARK_LEAVES: bool>
+    L2OTreeDescription<TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES>
+{
+    pub const fn height(&self) -> u8 {
+        TREE_HEIGHT
+    }
+    pub const fn registration(&self) -> L2OTreeRegistration {
+        L2OTreeRegistration {
+            table_type: TABLE_TYPE,
+            height: TREE_HEIGHT,
+            mark_leaves: MARK_LEAVES,
+            tree_ids: self.tree_ids,
+        }
+    }
+    pub fn tree_id(&self, hash: L2OAHashFunction) -> anyhow::Result<u8> {
+        self.tree_ids
+            .iter()
+            .find(|(tree_hash, _)| *tree_hash == hash)
+            .map(|(_, tree_id)| *tree_id)
+            .ok_or_else(|| anyhow::anyhow!("no {:?} tree in table {}", hash, TABLE_TYPE))
+    }
+    pub fn node_key(
+        &self,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+        position: &KVQTreeNodePosition,
+    ) -> anyhow::Result<KVQMerkleNodeKey<TABLE_TYPE>> {
+        Ok(KVQMerkleNodeKey::from_identifier_position_ref(
+            &KVQTreeIdentifier::new(self.tree_id(hash)?, 0, 0),
+            checkpoint_id,
+            position,
+        ))
+    }
+    pub fn leaf_key(
+        &self,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+        index: u64,
+    ) -> anyhow::Result<KVQMerkleNodeKey<TABLE_TYPE>> {
+        self.node_key(
+            hash,
+            checkpoint_id,
+            &KVQTreeNodePosition::new(TREE_HEIGHT, index),
+        )
+    }
+    pub fn append_only_key(
+        &self,
+        hash: L2OAHashFunction,
+        primary_id: u64,
+        tick: String,
+    ) -> anyhow::Result<KVQAppendOnlyMerkleKey<TABLE_TYPE>> {
+        Ok(KVQAppendOnlyMerkleKey::new(
+            self.tree_id(hash)?,
+            primary_id,
+            0,
+            0,
+            tick,
+        ))
+    }
+
+    pub fn get_node<S: KVQBinaryStoreReader>(
+        &self,
+        store: &S,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+        position: &KVQTreeNodePosition,
+    ) -> anyhow::Result<Hash256> {
+        let key = self.node_key(hash, checkpoint_id, position)?;
+        visit_tree_hasher(
+            hash,
+            GetNode::<S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES> { store, key },
+        )
+    }
+    pub fn get_root<S: KVQBinaryStoreReader>(
+        &self,
+        store: &S,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+    ) -> anyhow::Result<Hash256> {
+        self.get_node(store, hash, checkpoint_id, &KVQTreeNodePosition::root())
+    }
+    pub fn get_leaf<S: KVQBinaryStoreReader>(
+        &self,
+        store: &S,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+        index: u64,
+    ) -> anyhow::Result<MerkleProofCore<Hash256>> {
+        let key = self.leaf_key(hash, checkpoint_id, index)?;
+        visit_tree_hasher(
+            hash,
+            GetLeaf::<S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES> { store, key },
+        )
+    }
+    pub fn set_leaf<S: KVQBinaryStore>(
+        &self,
+        store: &mut S,
+        hash: L2OAHashFunction,
+        checkpoint_id: u64,
+        index: u64,
+        value: &Hash256,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>> {
+        let key = self.leaf_key(hash, checkpoint_id, index)?;
+        visit_tree_hasher(
+            hash,
+            SetLeaf::<S, TABLE_TYPE, TREE_HEIGHT, MARK_LEAVES> {
+                store,
+                key,
+                value,
+                encoding: self.leaf_encoding,
+            },
+        )
+    }
+    // sets the leaf in the tree of every hash function
+    pub fn set_leaf_all<S: KVQBinaryStore>(
+        &self,
+        store: &mut S,
+        checkpoint_id: u64,
+        index: u64,
+        value: &Hash256,
+    ) -> anyhow::Result<Vec<(L2OAHashFunction, DeltaMerkleProofCore<Hash256>)>> {
+        self.tree_ids
+            .iter()
+            .map(|(hash, _)| {
+                let delta = self.set_leaf(store, *hash, checkpoint_id, index, value)?;
+                Ok((*hash, delta))
+            })
+            .collect()
+    }
+    pub fn append_leaf<S: KVQBinaryStore>(
+        &self,
+        store: &mut S,
+        hash: L2OAHashFunction,
+        primary_id: u64,
+        tick: String,
+        value: &Hash256,
+    ) -> anyhow::Result<DeltaMerkleProofCore<Hash256>> {
+        if MARK_LEAVES {
+            anyhow::bail!("append only trees do not mark their leaves");
+        }
+        let key = self.append_only_key(hash, primary_id, tick)?;
+        visit_tree_hasher(
+            hash,
+            AppendLeaf::<S, TABLE_TYPE, TREE_HEIGHT> {
+                store,
+                key,
+                value,
+                encoding: self.leaf_encoding,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kvq::memory::simple::KVQSimpleMemoryBackingStore;
+    use l2o_crypto::hash::merkle::traits::MerkleZeroHasher;
+
+    use super::*;
+
+    const HASHES: [L2OAHashFunction; 5] = [
+        L2OAHashFunction::Sha256,
+        L2OAHashFunction::BLAKE3,
+        L2OAHashFunction::Keccak256,
+        L2OAHashFunction::PoseidonGoldilocks,
+        L2OAHashFunction::PoseidonBN254,
+    ];
+
+    #[test]
+    fn test_tree_ids_are_unique() {
+        let registrations = L2O_TREES.registrations();
+        let mut tree_ids = registrations
+            .iter()
+            .flat_map(|tree| tree.tree_ids.iter().map(|(_, tree_id)| *tree_id))
+            .collect::<Vec<_>>();
+        let count = tree_ids.len();
+        tree_ids.sort_unstable();
+        tree_ids.dedup();
+        assert_eq!(tree_ids.len(), count);
+
+        for tree in registrations {
+            for hash in HASHES {
+                assert!(tree
+                    .tree_ids
+                    .iter()
+                    .any(|(tree_hash, _)| *tree_hash == hash));
+            }
+        }
+        assert_eq!(registrations[0], STATE_ROOT_TREES.registration());
+        assert_eq!(registrations[3].height, NULLIFIER_TREES.height());
+        assert!(registrations[3].mark_leaves);
+    }
+
+    #[test]
+    fn test_state_root_trees() {
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        let l2id = 5;
+        let state_root = Hash256([7u8; 32]);
+        let deltas = STATE_ROOT_TREES
+            .set_leaf_all(&mut store, 1, l2id, &state_root)
+            .unwrap();
+        assert_eq!(deltas.len(), HASHES.len());
+
+        // reads only see earlier checkpoints
+        for hash in HASHES {
+            let proof = STATE_ROOT_TREES.get_leaf(&store, hash, 2, l2id).unwrap();
+            assert_eq!(proof.siblings.len(), STATE_ROOT_TREES.height() as usize);
+            assert_eq!(
+                STATE_ROOT_TREES.get_root(&store, hash, 2).unwrap(),
+                proof.root
+            );
+        }
+
+        let sha256_proof = STATE_ROOT_TREES
+            .get_leaf(&store, L2OAHashFunction::Sha256, 2, l2id)
+            .unwrap();
+        assert_eq!(sha256_proof.value, state_root);
+        assert!(sha256_proof.verify_marked_if::<Sha256Hasher>(false));
+        let (_, sha256_delta) = deltas
+            .iter()
+            .find(|(hash, _)| *hash == L2OAHashFunction::Sha256)
+            .unwrap();
+        assert_eq!(sha256_delta.new_root, sha256_proof.root);
+        assert!(sha256_delta.verify_marked_if::<Sha256Hasher>(false));
+        assert_eq!(
+            STATE_ROOT_TREES
+                .get_root(&store, L2OAHashFunction::Sha256, 1)
+                .unwrap(),
+            Sha256Hasher::get_zero_hash(STATE_ROOT_TREES.height() as usize)
+        );
+    }
+
+    #[test]
+    fn test_append_only_trees() {
+        let mut store = KVQSimpleMemoryBackingStore::new();
+        let hash = L2OAHashFunction::Keccak256;
+        let first = BRC21_DEPOSIT_TREES
+            .append_leaf(&mut store, hash, 3, "ordi".to_string(), &Hash256([1u8; 32]))
+            .unwrap();
+        let second = BRC21_DEPOSIT_TREES
+            .append_leaf(&mut store, hash, 3, "ordi".to_string(), &Hash256([2u8; 32]))
+            .unwrap();
+        assert_eq!(first.index, 0);
+        assert_eq!(second.index, 1);
+        assert_eq!(second.old_root, first.new_root);
+        assert!(second.verify_marked_if::<Keccak256Hasher>(false));
+
+        // every tick of an L2 has its own tree
+        let other = BRC21_DEPOSIT_TREES
+            .append_leaf(&mut store, hash, 3, "sats".to_string(), &Hash256([1u8; 32]))
+            .unwrap();
+        assert_eq!(other.index, 0);
+        assert_eq!(other.new_root, first.new_root);
+    }
+}
@@ -27,6 +27,13 @@ where
         Ok(res)
     }
 
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self
+            .kv
+            .get(key.as_slice())?
+            .map(|value| value.value().to_vec()))
+    }
+
     fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
         let mut result = Vec::new();
         for key in keys {
@@ -25,6 +25,10 @@ impl KVQBinaryStoreReader for KVQRocksDBStore {
         }
     }
 
+    fn get_exact_if_exists(&self, key: &Vec<u8>) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.db.get(key)?)
+    }
+
     fn get_many_exact(&self, keys: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
         let mut result = Vec::new();
         for key in keys {